@@ -0,0 +1,307 @@
+//! Agent definition parsing and writing.
+//!
+//! OpenCode defines agents as YAML or JSON documents with the system prompt
+//! under `prompt` and tools as a boolean record. Claude Code, Copilot CLI,
+//! Droid and AMP Code use Markdown files whose YAML frontmatter lists tools
+//! as a comma-separated string and whose body is the prompt. [`Agent`] holds
+//! either in one model.
+
+use serde_yaml::{Mapping, Value};
+
+use crate::skill::{parse_frontmatter, split_frontmatter};
+use crate::types::{FileFormat, HarnessKind};
+use crate::validation::{AgentCapabilities, ToolsFormat};
+use crate::{Error, Result};
+
+/// A harness-neutral agent definition.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Agent {
+    /// The agent name. OpenCode takes it from the file name instead.
+    pub name: Option<String>,
+    /// What the agent does and when to use it.
+    pub description: Option<String>,
+    /// The model identifier, as the source harness wrote it.
+    pub model: Option<String>,
+    /// The tools the agent may use, or `None` for every tool.
+    pub tools: Option<Vec<String>>,
+    /// Tools explicitly denied while every other tool is allowed, as in an
+    /// OpenCode record of `false` entries.
+    pub disabled_tools: Vec<String>,
+    /// A named (`red`) or hex (`#FF0000`) color.
+    pub color: Option<String>,
+    /// The agent mode (`subagent`, `primary` or `all`).
+    pub mode: Option<String>,
+    /// The system prompt.
+    pub prompt: String,
+    /// Other fields, in file order.
+    pub metadata: Mapping,
+}
+
+/// Returns the file format agents of `kind` are written in.
+///
+/// OpenCode agents are YAML documents; other harnesses use Markdown with
+/// YAML frontmatter.
+#[must_use]
+pub fn agent_file_format(kind: HarnessKind) -> FileFormat {
+    match kind {
+        HarnessKind::OpenCode => FileFormat::Yaml,
+        _ => FileFormat::MarkdownWithFrontmatter,
+    }
+}
+
+impl Agent {
+    /// Parses an agent definition written for `kind`.
+    ///
+    /// OpenCode content is read as a YAML or JSON document, unless it has
+    /// Markdown frontmatter. Tools are accepted as a boolean record, a
+    /// comma-separated string or a list, whatever the harness.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::YamlParse` if the document or frontmatter is not
+    /// valid YAML, or `Error::InvalidConfig` if it is not a mapping.
+    pub fn parse(content: &str, kind: HarnessKind) -> Result<Self> {
+        let document =
+            agent_file_format(kind) == FileFormat::Yaml && split_frontmatter(content).0.is_none();
+        let (fields, body) = if document {
+            (serde_yaml::from_str(content)?, None)
+        } else {
+            let frontmatter = parse_frontmatter(content)?;
+            (
+                frontmatter.yaml.unwrap_or(Value::Null),
+                Some(frontmatter.body),
+            )
+        };
+        let mut fields = match fields {
+            Value::Mapping(fields) => fields,
+            Value::Null => Mapping::new(),
+            _ => {
+                return Err(Error::InvalidConfig(
+                    "agent definition is not a mapping".to_string(),
+                ));
+            }
+        };
+
+        let mut agent = Self {
+            name: take_string(&mut fields, "name"),
+            description: take_string(&mut fields, "description"),
+            model: take_string(&mut fields, "model"),
+            color: take_string(&mut fields, "color"),
+            mode: take_string(&mut fields, "mode"),
+            prompt: match body {
+                Some(body) => body.to_string(),
+                None => take_string(&mut fields, "prompt").unwrap_or_default(),
+            },
+            ..Self::default()
+        };
+        match fields.remove("tools") {
+            Some(Value::Mapping(record)) => agent.read_tool_record(&record),
+            Some(Value::String(list)) => {
+                agent.tools = Some(
+                    list.split(',')
+                        .map(str::trim)
+                        .filter(|tool| !tool.is_empty())
+                        .map(String::from)
+                        .collect(),
+                );
+            }
+            Some(Value::Sequence(list)) => {
+                agent.tools = Some(
+                    list.iter()
+                        .filter_map(Value::as_str)
+                        .map(String::from)
+                        .collect(),
+                );
+            }
+            Some(other) => {
+                fields.insert(Value::from("tools"), other);
+            }
+            None => {}
+        }
+        agent.metadata = fields;
+        Ok(agent)
+    }
+
+    /// Reads an OpenCode tools record. With a `"*": false` entry the `true`
+    /// entries are an allowlist; otherwise every tool is allowed except the
+    /// `false` entries.
+    fn read_tool_record(&mut self, record: &Mapping) {
+        let entries = record
+            .iter()
+            .filter_map(|(tool, enabled)| Some((tool.as_str()?, enabled.as_bool()?)));
+        if record.get("*").and_then(Value::as_bool) == Some(false) {
+            self.tools = Some(
+                entries
+                    .filter(|(tool, enabled)| *enabled && *tool != "*")
+                    .map(|(tool, _)| tool.to_string())
+                    .collect(),
+            );
+        } else {
+            self.disabled_tools = entries
+                .filter(|(_, enabled)| !enabled)
+                .map(|(tool, _)| tool.to_string())
+                .collect();
+        }
+    }
+
+    /// Writes the agent in the file format and tools format of `kind`.
+    ///
+    /// Fields are written as they are; use
+    /// [`convert_agent`](crate::convert_agent) to translate values the
+    /// harness does not accept. OpenCode documents omit `name`, which is the
+    /// file name there. Denied tools cannot be written as a comma-separated
+    /// string and are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfig` if `kind` does not support agents.
+    pub fn to_native(&self, kind: HarnessKind) -> Result<String> {
+        let caps = AgentCapabilities::for_kind(kind).ok_or_else(|| {
+            Error::InvalidConfig(format!("{} does not support agents", kind.as_str()))
+        })?;
+        let document = agent_file_format(kind) == FileFormat::Yaml;
+
+        let mut fields = Mapping::new();
+        if !document {
+            insert_string(&mut fields, "name", self.name.as_deref());
+        }
+        insert_string(&mut fields, "description", self.description.as_deref());
+        if let Some(tools) = self.tools_value(caps.tools_format) {
+            fields.insert(Value::from("tools"), tools);
+        }
+        insert_string(&mut fields, "model", self.model.as_deref());
+        insert_string(&mut fields, "color", self.color.as_deref());
+        insert_string(&mut fields, "mode", self.mode.as_deref());
+        for (key, value) in &self.metadata {
+            fields.insert(key.clone(), value.clone());
+        }
+
+        if document {
+            if !self.prompt.is_empty() {
+                fields.insert(Value::from("prompt"), Value::from(self.prompt.as_str()));
+            }
+            return Ok(serde_yaml::to_string(&fields)?);
+        }
+        let yaml = if fields.is_empty() {
+            String::new()
+        } else {
+            serde_yaml::to_string(&fields)?
+        };
+        Ok(format!("---\n{yaml}---\n{}", self.prompt))
+    }
+
+    fn tools_value(&self, format: ToolsFormat) -> Option<Value> {
+        match format {
+            ToolsFormat::CommaSeparatedString => self
+                .tools
+                .as_ref()
+                .map(|tools| Value::from(tools.join(", "))),
+            ToolsFormat::BooleanRecord => {
+                let mut record = Mapping::new();
+                if let Some(tools) = &self.tools {
+                    record.insert(Value::from("*"), Value::from(false));
+                    for tool in tools {
+                        record.insert(Value::from(tool.as_str()), Value::from(true));
+                    }
+                }
+                for tool in &self.disabled_tools {
+                    record.insert(Value::from(tool.as_str()), Value::from(false));
+                }
+                (!record.is_empty()).then_some(Value::Mapping(record))
+            }
+        }
+    }
+}
+
+fn take_string(fields: &mut Mapping, key: &str) -> Option<String> {
+    match fields.remove(key)? {
+        Value::String(value) => Some(value),
+        Value::Null => None,
+        other => {
+            // Keep values of unexpected types as metadata
+            fields.insert(Value::from(key), other);
+            None
+        }
+    }
+}
+
+fn insert_string(fields: &mut Mapping, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        fields.insert(Value::from(key), Value::from(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_markdown_agent() {
+        let content = "---\nname: reviewer\ndescription: Reviews code\ntools: Read, Grep,\nmodel: sonnet\nskills: [pdf]\n---\nReview it.\n";
+
+        let agent = Agent::parse(content, HarnessKind::ClaudeCode).unwrap();
+
+        assert_eq!(agent.name.as_deref(), Some("reviewer"));
+        assert_eq!(agent.description.as_deref(), Some("Reviews code"));
+        assert_eq!(agent.tools, Some(vec!["Read".into(), "Grep".into()]));
+        assert_eq!(agent.model.as_deref(), Some("sonnet"));
+        assert_eq!(agent.prompt, "Review it.\n");
+        assert_eq!(agent.metadata.len(), 1);
+        assert!(agent.metadata.contains_key("skills"));
+    }
+
+    #[test]
+    fn parses_opencode_documents() {
+        let yaml = "description: Plans\nmode: primary\ntemperature: 0.1\ntools:\n  write: false\n  bash: true\nprompt: Plan it.\n";
+        let agent = Agent::parse(yaml, HarnessKind::OpenCode).unwrap();
+        assert_eq!(agent.name, None);
+        assert_eq!(agent.mode.as_deref(), Some("primary"));
+        assert_eq!(agent.tools, None);
+        assert_eq!(agent.disabled_tools, ["write"]);
+        assert_eq!(agent.prompt, "Plan it.");
+        assert!(agent.metadata.contains_key("temperature"));
+
+        let json = r#"{"tools": {"*": false, "read": true}, "prompt": "Read."}"#;
+        let agent = Agent::parse(json, HarnessKind::OpenCode).unwrap();
+        assert_eq!(agent.tools, Some(vec!["read".into()]));
+        assert!(agent.disabled_tools.is_empty());
+
+        let markdown = "---\ndescription: Plans\n---\nPlan it.\n";
+        let agent = Agent::parse(markdown, HarnessKind::OpenCode).unwrap();
+        assert_eq!(agent.prompt, "Plan it.\n");
+    }
+
+    #[test]
+    fn parse_rejects_non_mapping() {
+        assert!(Agent::parse("- a\n- b\n", HarnessKind::OpenCode).is_err());
+    }
+
+    #[test]
+    fn to_native_writes_each_layout() {
+        let agent = Agent {
+            name: Some("reviewer".into()),
+            description: Some("Reviews code".into()),
+            tools: Some(vec!["read".into(), "grep".into()]),
+            prompt: "Review it.\n".into(),
+            ..Agent::default()
+        };
+
+        assert_eq!(
+            agent.to_native(HarnessKind::Droid).unwrap(),
+            "---\nname: reviewer\ndescription: Reviews code\ntools: read, grep\n---\nReview it.\n"
+        );
+
+        let yaml = agent.to_native(HarnessKind::OpenCode).unwrap();
+        let value: Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(value.get("name"), None);
+        assert_eq!(value["tools"]["*"], Value::from(false));
+        assert_eq!(value["tools"]["read"], Value::from(true));
+        assert_eq!(value["prompt"], Value::from("Review it.\n"));
+        assert_eq!(
+            Agent::parse(&yaml, HarnessKind::OpenCode).unwrap().tools,
+            agent.tools
+        );
+
+        assert!(agent.to_native(HarnessKind::Zed).is_err());
+    }
+}
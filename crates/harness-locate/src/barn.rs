@@ -0,0 +1,332 @@
+//! A machine-wide view of what every harness has installed.
+//!
+//! [`Barn::inventory`] lists the skills, commands, agents and rules of every
+//! harness in every scope, and merges what is really the same component:
+//!
+//! - Harnesses that read the same directory, such as Goose and AMP Code
+//!   with `~/.config/agents/skills`, report one [`Location`] with several
+//!   [`Install`]s.
+//! - Entries reached through symlinks are resolved to the file they point
+//!   to, so a skill symlinked into several harnesses is also one location.
+//! - Copies with the same kind, name and content hash are one
+//!   [`Component`] with several locations.
+//!
+//! The result serializes to JSON:
+//!
+//! ```no_run
+//! use harness_locate::Barn;
+//! use std::path::Path;
+//!
+//! let inventory = Barn::new()?.with_project(Path::new(".")).inventory()?;
+//! println!("{}", serde_json::to_string_pretty(&inventory)?);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::harness::Harness;
+use crate::inventory::{ItemKind, ItemMetadata};
+use crate::types::{HarnessKind, Scope};
+
+/// The set of harnesses to take inventory of.
+#[derive(Debug)]
+pub struct Barn {
+    harnesses: Vec<Harness>,
+    project_root: Option<PathBuf>,
+}
+
+impl Barn {
+    /// Creates a barn of every installed harness, built-in or registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the installed harnesses cannot be determined.
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_harnesses(Harness::installed()?))
+    }
+
+    /// Creates a barn of the given harnesses.
+    #[must_use]
+    pub fn with_harnesses(harnesses: Vec<Harness>) -> Self {
+        Self {
+            harnesses,
+            project_root: None,
+        }
+    }
+
+    /// Includes the project and local scopes of the project at `root`.
+    #[must_use]
+    pub fn with_project(mut self, root: impl Into<PathBuf>) -> Self {
+        self.project_root = Some(root.into());
+        self
+    }
+
+    /// Returns the harnesses in the barn.
+    #[must_use]
+    pub fn harnesses(&self) -> &[Harness] {
+        &self.harnesses
+    }
+
+    /// Lists every component installed across the barn's harnesses and
+    /// scopes, merging shared directories, symlinks and identical copies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a harness directory exists but cannot be read.
+    pub fn inventory(&self) -> Result<BarnInventory> {
+        let mut components: BTreeMap<(ItemKind, String, String), Component> = BTreeMap::new();
+        for harness in &self.harnesses {
+            let inventory = harness.inventory(self.project_root.as_deref())?;
+            for scope in inventory.scopes {
+                for item in scope.items {
+                    let resolved =
+                        fs::canonicalize(&item.path).unwrap_or_else(|_| item.path.clone());
+                    // Unreadable items have no hash; keep them apart by path
+                    let identity = if item.hash.is_empty() {
+                        resolved.to_string_lossy().into_owned()
+                    } else {
+                        item.hash.clone()
+                    };
+                    let component = components
+                        .entry((item.kind, item.name.clone(), identity))
+                        .or_insert_with(|| Component {
+                            kind: item.kind,
+                            name: item.name,
+                            hash: item.hash,
+                            metadata: item.metadata,
+                            errors: item.errors,
+                            locations: Vec::new(),
+                        });
+                    let install = Install {
+                        harness: inventory.harness,
+                        scope: scope.scope.clone(),
+                        path: item.path,
+                    };
+                    match component
+                        .locations
+                        .iter_mut()
+                        .find(|location| location.path == resolved)
+                    {
+                        Some(location) => location.installs.push(install),
+                        None => component.locations.push(Location {
+                            path: resolved,
+                            installs: vec![install],
+                        }),
+                    }
+                }
+            }
+        }
+
+        let mut components: Vec<Component> = components.into_values().collect();
+        for component in &mut components {
+            component.locations.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+        Ok(BarnInventory { components })
+    }
+}
+
+/// Everything installed on the machine, as returned by
+/// [`Barn::inventory`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BarnInventory {
+    /// The components, sorted by kind, then name.
+    pub components: Vec<Component>,
+}
+
+impl BarnInventory {
+    /// Returns the components of `kind`.
+    pub fn components_of(&self, kind: ItemKind) -> impl Iterator<Item = &Component> {
+        self.components
+            .iter()
+            .filter(move |component| component.kind == kind)
+    }
+}
+
+/// One logical skill, command, agent or rules file, with every place it is
+/// installed.
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Component {
+    /// What the component is.
+    pub kind: ItemKind,
+    /// The component's name, as in [`InventoryItem::name`](crate::InventoryItem::name).
+    pub name: String,
+    /// Hex-encoded SHA-256 of the content shared by every location.
+    pub hash: String,
+    /// The parsed metadata.
+    pub metadata: ItemMetadata,
+    /// Problems found while reading or parsing the component.
+    pub errors: Vec<String>,
+    /// The physical copies of the component, sorted by path.
+    pub locations: Vec<Location>,
+}
+
+impl Component {
+    /// Returns the harnesses the component is installed in, without
+    /// duplicates.
+    #[must_use]
+    pub fn harnesses(&self) -> Vec<HarnessKind> {
+        let mut harnesses = Vec::new();
+        for install in self.locations.iter().flat_map(|l| &l.installs) {
+            if !harnesses.contains(&install.harness) {
+                harnesses.push(install.harness);
+            }
+        }
+        harnesses
+    }
+}
+
+/// One physical copy of a [`Component`].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Location {
+    /// The file with symlinks resolved (`SKILL.md` for skills).
+    pub path: PathBuf,
+    /// The harnesses and scopes that read this file.
+    pub installs: Vec<Install>,
+}
+
+/// A harness and scope that read a [`Location`].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Install {
+    /// The harness.
+    pub harness: HarnessKind,
+    /// The scope the harness reads the file in.
+    pub scope: Scope,
+    /// The path the harness reads, which differs from
+    /// [`Location::path`] when it goes through a symlink.
+    pub path: PathBuf,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const SKILL: &str = "---\nname: pdf\ndescription: Work with PDFs\n---\nUse it.\n";
+
+    fn write_skill(dir: &Path, content: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("SKILL.md"), content).unwrap();
+    }
+
+    fn project_inventory(root: &Path, kinds: &[HarnessKind]) -> Vec<Component> {
+        let harnesses = kinds.iter().map(|kind| Harness::new(*kind)).collect();
+        let root = fs::canonicalize(root).unwrap();
+        Barn::with_harnesses(harnesses)
+            .with_project(&root)
+            .inventory()
+            .unwrap()
+            .components
+            .into_iter()
+            .filter(|c| c.locations.iter().all(|l| l.path.starts_with(&root)))
+            .collect()
+    }
+
+    #[test]
+    fn shared_directory_is_one_location() {
+        let dir = tempfile::tempdir().unwrap();
+        write_skill(&dir.path().join(".agents/skills/pdf"), SKILL);
+
+        let components = project_inventory(dir.path(), &[HarnessKind::Goose, HarnessKind::AmpCode]);
+
+        assert_eq!(components.len(), 1);
+        let pdf = &components[0];
+        assert_eq!((pdf.kind, pdf.name.as_str()), (ItemKind::Skill, "pdf"));
+        assert_eq!(pdf.locations.len(), 1);
+        assert_eq!(pdf.locations[0].installs.len(), 2);
+        assert_eq!(pdf.harnesses(), [HarnessKind::Goose, HarnessKind::AmpCode]);
+    }
+
+    #[test]
+    fn identical_copies_are_one_component() {
+        let dir = tempfile::tempdir().unwrap();
+        write_skill(&dir.path().join(".claude/skills/pdf"), SKILL);
+        write_skill(&dir.path().join(".opencode/skill/pdf"), SKILL);
+        write_skill(
+            &dir.path().join(".cursor/skills/pdf"),
+            "---\nname: pdf\ndescription: Changed\n---\nUse it.\n",
+        );
+
+        let components = project_inventory(
+            dir.path(),
+            &[
+                HarnessKind::ClaudeCode,
+                HarnessKind::OpenCode,
+                HarnessKind::Cursor,
+            ],
+        );
+        let skills: Vec<_> = components
+            .iter()
+            .filter(|c| c.kind == ItemKind::Skill)
+            .collect();
+
+        assert_eq!(skills.len(), 2);
+        let shared = skills.iter().find(|c| c.locations.len() == 2).unwrap();
+        assert_eq!(
+            shared.harnesses(),
+            [HarnessKind::ClaudeCode, HarnessKind::OpenCode]
+        );
+        let changed = skills.iter().find(|c| c.locations.len() == 1).unwrap();
+        assert_eq!(changed.harnesses(), [HarnessKind::Cursor]);
+        assert_ne!(changed.hash, shared.hash);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_entries_resolve_to_their_target() {
+        let dir = tempfile::tempdir().unwrap();
+        write_skill(&dir.path().join("shared/pdf"), SKILL);
+        fs::create_dir_all(dir.path().join(".claude/skills")).unwrap();
+        fs::create_dir_all(dir.path().join(".opencode/skill")).unwrap();
+        std::os::unix::fs::symlink(
+            dir.path().join("shared/pdf"),
+            dir.path().join(".claude/skills/pdf"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(
+            dir.path().join("shared/pdf"),
+            dir.path().join(".opencode/skill/pdf"),
+        )
+        .unwrap();
+
+        let components = project_inventory(
+            dir.path(),
+            &[HarnessKind::ClaudeCode, HarnessKind::OpenCode],
+        );
+
+        assert_eq!(components.len(), 1);
+        let location = &components[0].locations[0];
+        assert_eq!(components[0].locations.len(), 1);
+        assert!(location.path.ends_with("shared/pdf/SKILL.md"));
+        assert_eq!(location.installs.len(), 2);
+        assert!(
+            location.installs[0]
+                .path
+                .ends_with(".claude/skills/pdf/SKILL.md")
+        );
+    }
+
+    #[test]
+    fn inventory_serializes_to_json() {
+        let dir = tempfile::tempdir().unwrap();
+        write_skill(&dir.path().join(".agents/skills/pdf"), SKILL);
+
+        let components = project_inventory(dir.path(), &[HarnessKind::Goose]);
+        let json = serde_json::to_value(BarnInventory { components }).unwrap();
+
+        let component = &json["components"][0];
+        assert_eq!(component["kind"], "skill");
+        assert_eq!(component["name"], "pdf");
+        assert_eq!(component["metadata"]["type"], "skill");
+        let install = &component["locations"][0]["installs"][0];
+        assert_eq!(install["harness"], "Goose");
+        assert_eq!(install["scope"]["type"], "project");
+    }
+}
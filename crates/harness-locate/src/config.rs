@@ -0,0 +1,498 @@
+//! Reading and editing configuration files by JSON pointer.
+//!
+//! A [`ConfigDocument`] loads the file behind a [`ConfigResource`] and
+//! addresses its content with [RFC 6901] JSON pointers, whatever the file
+//! format. JSON, JSONC, YAML and TOML files are all presented as the same
+//! [`serde_json::Value`] model, so `resource.key_path` can be used directly:
+//!
+//! ```no_run
+//! use harness_locate::{ConfigDocument, Harness, HarnessKind, Scope};
+//! use serde_json::json;
+//!
+//! let harness = Harness::new(HarnessKind::ClaudeCode);
+//! let resource = harness.mcp(&Scope::Global)?.expect("Claude Code has MCP config");
+//!
+//! let mut config = ConfigDocument::load(&resource)?;
+//! let servers = config.get(&resource.key_path)?.cloned();
+//! config.set(&format!("{}/fetch", resource.key_path), json!({"command": "uvx"}))?;
+//! config.save()?;
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+//!
+//! Dots have no special meaning: `/amp.mcpServers` names the single key
+//! `amp.mcpServers`. A `/` or `~` inside a key is written as `~1` or `~0`.
+//!
+//! Edits keep comments, key order and indentation outside the edited entry.
+//! An edit that cannot be made that way, such as one inside a YAML flow
+//! mapping, fails with [`Error::LossyEdit`](crate::Error::LossyEdit) unless
+//! [`allow_reformat`](ConfigDocument::allow_reformat) was called.
+//!
+//! [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::document::{self, Document};
+use crate::error::Result;
+use crate::journal::{Journal, JournalMode};
+use crate::plan::ChangePlan;
+use crate::types::{ConfigResource, FileFormat};
+
+/// A configuration file loaded for reading and editing.
+///
+/// Edits are made in memory; nothing is written until [`save`](Self::save)
+/// is called or the [`plan`](Self::plan) is applied.
+#[derive(Debug, Clone)]
+pub struct ConfigDocument {
+    path: PathBuf,
+    document: Document,
+    /// The text as last read from or written to disk.
+    saved: String,
+    journal: JournalMode,
+}
+
+impl ConfigDocument {
+    /// Loads the file of `resource` in its format.
+    ///
+    /// A missing file loads as an empty object, and is created by the first
+    /// [`save`](Self::save) after an edit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not parse as
+    /// `resource.format`.
+    pub fn load(resource: &ConfigResource) -> Result<Self> {
+        let document = Document::read(&resource.file, resource.format)?;
+        Ok(Self {
+            path: resource.file.clone(),
+            saved: document.as_str().to_string(),
+            document,
+            journal: JournalMode::Default,
+        })
+    }
+
+    /// Records the changes [`save`](Self::save) writes in `journal` instead
+    /// of the default journal.
+    ///
+    /// By default, each save is recorded, with the prior content of the
+    /// file, in [`Journal::open_default`] so it can be rolled back later.
+    #[must_use]
+    pub fn with_journal(mut self, journal: Journal) -> Self {
+        self.journal = JournalMode::Custom(journal);
+        self
+    }
+
+    /// Saves changes without recording them in a journal.
+    #[must_use]
+    pub fn without_journal(mut self) -> Self {
+        self.journal = JournalMode::Disabled;
+        self
+    }
+
+    /// Returns the path of the file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the format of the file.
+    #[must_use]
+    pub fn format(&self) -> FileFormat {
+        self.document.format()
+    }
+
+    /// Returns the whole document.
+    #[must_use]
+    pub fn value(&self) -> &Value {
+        self.document.value()
+    }
+
+    /// Returns the current text of the file, including unsaved edits.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.document.as_str()
+    }
+
+    /// Returns whether the document has edits that have not been saved.
+    #[must_use]
+    pub fn is_modified(&self) -> bool {
+        self.document.as_str() != self.saved
+    }
+
+    /// Returns the value at `pointer`, if present.
+    ///
+    /// The empty pointer returns the whole document. Tokens index into
+    /// arrays as well as objects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pointer` is non-empty and does not start with
+    /// `/`.
+    pub fn get(&self, pointer: &str) -> Result<Option<&Value>> {
+        self.document.get(pointer)
+    }
+
+    /// Returns the 1-based line and column of the value at `pointer`, or of
+    /// its key when it is an object member.
+    ///
+    /// Only JSON and JSONC files track positions; other formats return
+    /// `None`.
+    #[must_use]
+    pub fn position(&self, pointer: &str) -> Option<(usize, usize)> {
+        self.document.position(pointer)
+    }
+
+    /// Sets the value at `pointer`, replacing any existing value in place
+    /// and creating missing parent objects.
+    ///
+    /// When the parent is an array, the last token is an existing index,
+    /// which is replaced, or the array's length or `-`, which append
+    /// (RFC 6901). Array elements along the way must already exist.
+    ///
+    /// Setting a value equal to the current one leaves the text untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pointer` is empty or does not start with `/`,
+    /// if a value along it is neither an object nor an array, or if an
+    /// array index is out of range. Returns
+    /// [`Error::LossyEdit`](crate::Error::LossyEdit) if the edit cannot be
+    /// made without reformatting the file and that was not allowed.
+    pub fn set(&mut self, pointer: &str, value: Value) -> Result<()> {
+        let (parent, key) = document::split_pointer(pointer)?;
+        if self.document.get(pointer)? == Some(&value) {
+            return Ok(());
+        }
+        self.document.insert(parent, &key, value)
+    }
+
+    /// Removes the value at `pointer`.
+    ///
+    /// Removing an array element shifts the elements after it down.
+    ///
+    /// Returns the removed value, or `None` if there was none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pointer` is empty or does not start with `/`,
+    /// or [`Error::LossyEdit`](crate::Error::LossyEdit) as for
+    /// [`set`](Self::set).
+    pub fn remove(&mut self, pointer: &str) -> Result<Option<Value>> {
+        let (parent, key) = document::split_pointer(pointer)?;
+        self.document.remove(parent, &key)
+    }
+
+    /// Allows edits that cannot be spliced into the text to re-serialize the
+    /// whole file instead of failing with
+    /// [`Error::LossyEdit`](crate::Error::LossyEdit).
+    ///
+    /// Re-serializing drops comments and formatting. Empty files, and files
+    /// already in the serializer's layout, are rewritten either way since
+    /// nothing is lost.
+    pub fn allow_reformat(&mut self, allow: bool) {
+        self.document.allow_reformat(allow);
+    }
+
+    /// Returns a [`ChangePlan`] that writes the edits to disk.
+    ///
+    /// The plan is empty if there are no unsaved edits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current content of the file cannot be read.
+    pub fn plan(&self) -> Result<ChangePlan> {
+        let mut plan = ChangePlan::new();
+        if self.is_modified() {
+            plan.set_file(&self.path, Some(self.as_str().to_string()))?;
+        }
+        Ok(plan)
+    }
+
+    /// Writes the edits to disk atomically.
+    ///
+    /// The change is recorded in the document's journal (see
+    /// [`with_journal`](Self::with_journal)) so it can be rolled back. Does
+    /// nothing if there are no unsaved edits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Conflict`](crate::Error::Conflict) if the file
+    /// changed on disk while it was being written, or an error if the file,
+    /// its parent directory or the journal cannot be written.
+    pub fn save(&mut self) -> Result<()> {
+        let plan = self.plan()?;
+        self.journal
+            .apply(&format!("edit {}", self.path.display()), &plan)?;
+        self.saved = self.document.as_str().to_string();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+
+    fn resource(path: &Path, format: FileFormat) -> ConfigResource {
+        ConfigResource {
+            file: path.to_path_buf(),
+            file_exists: path.exists(),
+            key_path: String::new(),
+            format,
+            schema_url: None,
+        }
+    }
+
+    fn load(dir: &Path, name: &str, format: FileFormat, text: &str) -> ConfigDocument {
+        let path = dir.join(name);
+        fs::write(&path, text).unwrap();
+        ConfigDocument::load(&resource(&path, format))
+            .unwrap()
+            .without_journal()
+    }
+
+    #[test]
+    fn get_reads_every_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let cases = [
+            ("a.json", FileFormat::Json, "{\"mcp\": {\"dev\": 1}}"),
+            (
+                "b.jsonc",
+                FileFormat::Jsonc,
+                "{\n  // c\n  \"mcp\": {\"dev\": 1},\n}",
+            ),
+            ("c.yaml", FileFormat::Yaml, "mcp:\n  dev: 1\n"),
+            ("d.toml", FileFormat::Toml, "[mcp]\ndev = 1\n"),
+        ];
+        for (name, format, text) in cases {
+            let config = load(dir.path(), name, format, text);
+            assert_eq!(config.get("/mcp/dev").unwrap(), Some(&json!(1)), "{name}");
+            assert_eq!(config.get("/mcp/missing").unwrap(), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn dotted_keys_are_single_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(
+            dir.path(),
+            "settings.json",
+            FileFormat::Json,
+            "{\"amp.mcpServers\": {}}",
+        );
+
+        config
+            .set("/amp.mcpServers/dev", json!({"command": "node"}))
+            .unwrap();
+
+        assert_eq!(
+            config.value(),
+            &json!({"amp.mcpServers": {"dev": {"command": "node"}}})
+        );
+    }
+
+    #[test]
+    fn pointers_follow_rfc6901_escaping() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(dir.path(), "c.json", FileFormat::Json, "{}");
+
+        config.set("/a~1b/c~0d", json!(1)).unwrap();
+
+        assert_eq!(config.value(), &json!({"a/b": {"c~d": 1}}));
+        assert_eq!(config.get("/a~1b/c~0d").unwrap(), Some(&json!(1)));
+    }
+
+    #[test]
+    fn set_keeps_yaml_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(
+            dir.path(),
+            "config.yaml",
+            FileFormat::Yaml,
+            "# goose\nextensions:\n  dev:\n    cmd: a\n",
+        );
+
+        config.set("/extensions/dev/cmd", json!("b")).unwrap();
+
+        assert_eq!(
+            config.as_str(),
+            "# goose\nextensions:\n  dev:\n    cmd: b\n"
+        );
+    }
+
+    #[test]
+    fn set_and_remove_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(
+            dir.path(),
+            "config.toml",
+            FileFormat::Toml,
+            "model = \"o3\"\n\n[mcp_servers.dev]\ncommand = \"node\"\n",
+        );
+
+        config
+            .set("/mcp_servers/fetch", json!({"command": "uvx"}))
+            .unwrap();
+        assert_eq!(
+            config.remove("/mcp_servers/dev").unwrap(),
+            Some(json!({"command": "node"}))
+        );
+
+        assert_eq!(
+            config.value(),
+            &json!({"model": "o3", "mcp_servers": {"fetch": {"command": "uvx"}}})
+        );
+        assert!(config.as_str().starts_with("model = \"o3\"\n"));
+    }
+
+    #[test]
+    fn set_rejects_root_and_relative_pointers() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(dir.path(), "c.json", FileFormat::Json, "{}");
+
+        assert!(config.set("", json!({})).is_err());
+        assert!(config.set("mcp", json!({})).is_err());
+        assert!(config.remove("").is_err());
+    }
+
+    #[test]
+    fn set_through_non_object_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(dir.path(), "c.json", FileFormat::Json, "{\"mcp\": [1]}");
+
+        assert!(config.set("/mcp/dev", json!(1)).is_err());
+    }
+
+    #[test]
+    fn set_and_remove_array_elements() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(
+            dir.path(),
+            "c.jsonc",
+            FileFormat::Jsonc,
+            "{\n  // dev server\n  \"mcp\": {\"args\": [\"a\", \"b\"]}\n}\n",
+        );
+
+        config.set("/mcp/args/0", json!("x")).unwrap();
+        assert_eq!(
+            config.as_str(),
+            "{\n  // dev server\n  \"mcp\": {\"args\": [\"x\", \"b\"]}\n}\n"
+        );
+
+        config.set("/mcp/args/-", json!("c")).unwrap();
+        config.set("/mcp/args/3", json!("d")).unwrap();
+        assert_eq!(
+            config.as_str(),
+            "{\n  // dev server\n  \"mcp\": {\"args\": [\"x\", \"b\", \"c\", \"d\"]}\n}\n"
+        );
+
+        assert_eq!(config.remove("/mcp/args/1").unwrap(), Some(json!("b")));
+        assert_eq!(config.remove("/mcp/args/9").unwrap(), None);
+        assert_eq!(
+            config.as_str(),
+            "{\n  // dev server\n  \"mcp\": {\"args\": [\"x\", \"c\", \"d\"]}\n}\n"
+        );
+    }
+
+    #[test]
+    fn set_through_array_elements() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(
+            dir.path(),
+            "c.yaml",
+            FileFormat::Yaml,
+            "# servers\nservers:\n  - name: dev\n    cmd: a\n",
+        );
+
+        // YAML sequences are not edited in place
+        assert!(matches!(
+            config.set("/servers/0/cmd", json!("b")),
+            Err(crate::Error::LossyEdit { pointer }) if pointer == "/servers/0/cmd"
+        ));
+        assert!(!config.is_modified());
+
+        config.allow_reformat(true);
+        config.set("/servers/0/cmd", json!("b")).unwrap();
+        assert_eq!(
+            config.value(),
+            &json!({"servers": [{"name": "dev", "cmd": "b"}]})
+        );
+        assert_eq!(
+            config.remove("/servers/0/name").unwrap(),
+            Some(json!("dev"))
+        );
+        assert_eq!(config.value(), &json!({"servers": [{"cmd": "b"}]}));
+    }
+
+    #[test]
+    fn set_rejects_invalid_array_indices() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(dir.path(), "c.json", FileFormat::Json, "{\"a\": [1]}");
+
+        assert!(config.set("/a/2", json!(2)).is_err());
+        assert!(config.set("/a/01", json!(2)).is_err());
+        assert!(config.set("/a/1/b", json!(2)).is_err());
+        assert!(config.set("/a/-/b", json!(2)).is_err());
+        assert!(!config.is_modified());
+    }
+
+    #[test]
+    fn set_equal_value_is_not_an_edit() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = load(dir.path(), "c.json", FileFormat::Json, "{\"a\":   1}");
+
+        config.set("/a", json!(1)).unwrap();
+
+        assert!(!config.is_modified());
+        assert_eq!(config.as_str(), "{\"a\":   1}");
+    }
+
+    #[test]
+    fn save_writes_only_when_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("new/config.json");
+        let mut config = ConfigDocument::load(&resource(&path, FileFormat::Json))
+            .unwrap()
+            .without_journal();
+
+        config.save().unwrap();
+        assert!(!path.exists());
+        assert!(config.plan().unwrap().is_empty());
+
+        config
+            .set("/mcpServers/dev", json!({"command": "node"}))
+            .unwrap();
+        assert_eq!(config.plan().unwrap().changes().len(), 1);
+        config.save().unwrap();
+
+        assert!(!config.is_modified());
+        let reloaded = ConfigDocument::load(&resource(&path, FileFormat::Json)).unwrap();
+        assert_eq!(reloaded.value(), config.value());
+    }
+
+    #[test]
+    fn save_is_journaled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, "{\n  // keep me\n  \"a\": 1\n}\n").unwrap();
+        let journal = Journal::new(dir.path().join("journal"));
+        let mut config = ConfigDocument::load(&resource(&path, FileFormat::Jsonc))
+            .unwrap()
+            .with_journal(journal.clone());
+
+        config.set("/b", json!(2)).unwrap();
+        config.save().unwrap();
+        config.save().unwrap();
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].files().collect::<Vec<_>>(), [path.as_path()]);
+        journal.rollback(&entries[0].id).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "{\n  // keep me\n  \"a\": 1\n}\n"
+        );
+    }
+}
@@ -0,0 +1,657 @@
+//! Converting skills and agents between harnesses.
+//!
+//! Harnesses disagree on what a valid skill is: OpenCode, Copilot CLI and
+//! the other [agentskills.io](https://agentskills.io) harnesses require a
+//! lowercase hyphenated name matching the skill directory and a
+//! description, while Claude Code accepts any name. [`convert_skill`]
+//! rewrites a skill to satisfy the target's [`SkillCapabilities`] and
+//! reports what it changed and what it could not fix.
+//!
+//! Agents differ in file layout, tools format, colors and modes, as
+//! recorded in [`AgentCapabilities`]. [`convert_agent`] translates one
+//! agent definition for each of them.
+//!
+//! Both translate tool and model names with the tables in
+//! [`mapping`](crate::mapping).
+
+use crate::agent::{Agent, agent_file_format};
+use crate::error::{Error, Result};
+use crate::mapping::{ModelRef, split_tool_list, translate_tool};
+use crate::skill::Skill;
+use crate::types::{FileFormat, HarnessKind};
+use crate::validation::{
+    AgentCapabilities, CODE_AGENT_FIELD_DROPPED, CODE_AGENT_NAME_MISSING, CODE_AGENT_TOOL_UNKNOWN,
+    CODE_SKILL_DESCRIPTION_DERIVED, CODE_SKILL_NAME_NORMALIZED, CODE_SKILL_TOOL_UNKNOWN,
+    ColorFormat, NameFormat, SKILL_DESCRIPTION_MAX_LEN, SKILL_NAME_MAX_LEN, SkillCapabilities,
+    ToolsFormat, ValidationIssue, validate_agent_for_harness, validate_skill_for_harness,
+};
+
+/// Hex values of the named colors Claude Code, Copilot CLI and Droid
+/// accept, as defined by CSS.
+const NAMED_COLORS: &[(&str, &str)] = &[
+    ("red", "#FF0000"),
+    ("orange", "#FFA500"),
+    ("yellow", "#FFFF00"),
+    ("green", "#008000"),
+    ("cyan", "#00FFFF"),
+    ("blue", "#0000FF"),
+    ("purple", "#800080"),
+    ("pink", "#FFC0CB"),
+];
+
+/// Converts a skill written for `from` into one that `to` accepts.
+///
+/// - Names are normalized to lowercase hyphenated form (`PDF Tools` and
+///   `pdfTools` become `pdf-tools`) when `to` requires it. The skill's
+///   directory must be named after the returned skill's `name`.
+/// - A missing description is filled from the first paragraph of the
+///   body when `to` requires one.
+/// - `allowed-tools` entries are renamed to `to`'s tool names; tools `to`
+///   has no equivalent for are dropped with a `CODE_SKILL_TOOL_UNKNOWN`
+///   warning.
+///
+/// Each change is reported as a warning, followed by the issues
+/// [`validate_skill_for_harness`] finds in the converted skill, such as a
+/// description that could not be filled. A skill converted to the harness
+/// it was written for is only validated.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::{HarnessKind, convert_skill, parse_skill};
+///
+/// let skill = parse_skill("---\nname: PDF Tools\n---\nFill in PDF forms.\n")?;
+/// let (converted, issues) = convert_skill(&skill, HarnessKind::ClaudeCode, HarnessKind::OpenCode);
+///
+/// assert_eq!(converted.name, "pdf-tools");
+/// assert_eq!(converted.description.as_deref(), Some("Fill in PDF forms."));
+/// assert_eq!(issues.len(), 2);
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+#[must_use]
+pub fn convert_skill(
+    skill: &Skill,
+    from: HarnessKind,
+    to: HarnessKind,
+) -> (Skill, Vec<ValidationIssue>) {
+    if from == to {
+        let issues = validate_skill_for_harness(&skill.to_markdown(), &skill.name, to);
+        return (skill.clone(), issues);
+    }
+    let mut skill = skill.clone();
+    let mut issues = Vec::new();
+    if let Some(tools) = skill.metadata.get_mut("allowed-tools") {
+        let (list, comma) = match tools {
+            serde_yaml::Value::String(list) => (split_tool_list(list), list.contains(',')),
+            serde_yaml::Value::Sequence(list) => {
+                (list.iter().filter_map(|v| v.as_str()).collect(), false)
+            }
+            _ => (Vec::new(), false),
+        };
+        let (kept, dropped) = translate_tools(list, from, to);
+        for tool in dropped {
+            issues.push(ValidationIssue::warning(
+                "allowed-tools",
+                format!("tool '{tool}' has no {} equivalent; dropped", to.as_str()),
+                Some(CODE_SKILL_TOOL_UNKNOWN),
+            ));
+        }
+        match tools {
+            serde_yaml::Value::String(list) => {
+                *list = kept.join(if comma { ", " } else { " " });
+            }
+            serde_yaml::Value::Sequence(list) => {
+                *list = kept.into_iter().map(serde_yaml::Value::from).collect();
+            }
+            _ => {}
+        }
+    }
+    let (converted, adapted) = adapt_skill(&skill, to);
+    issues.extend(adapted);
+    (converted, issues)
+}
+
+/// Translates tool names from `from` to `to`, returning the translated
+/// names without duplicates and the names `to` has no equivalent for.
+fn translate_tools<'a>(
+    tools: impl IntoIterator<Item = &'a str>,
+    from: HarnessKind,
+    to: HarnessKind,
+) -> (Vec<String>, Vec<&'a str>) {
+    let mut kept = Vec::new();
+    let mut dropped = Vec::new();
+    for tool in tools {
+        match translate_tool(tool, from, to) {
+            Some(name) if !kept.contains(&name) => kept.push(name),
+            Some(_) => {}
+            None => dropped.push(tool),
+        }
+    }
+    (kept, dropped)
+}
+
+/// Rewrites `skill` to satisfy the [`SkillCapabilities`] of `to`,
+/// whatever harness it was written for.
+pub(crate) fn adapt_skill(skill: &Skill, to: HarnessKind) -> (Skill, Vec<ValidationIssue>) {
+    let mut converted = skill.clone();
+    let mut issues = Vec::new();
+    let Some(caps) = SkillCapabilities::for_kind(to) else {
+        issues.extend(validate_skill_for_harness(
+            &skill.to_markdown(),
+            &skill.name,
+            to,
+        ));
+        return (converted, issues);
+    };
+
+    if caps.name_format == NameFormat::LowercaseHyphenated {
+        let name = normalize_name(&converted.name);
+        if !name.is_empty() && name != converted.name {
+            issues.push(ValidationIssue::warning(
+                "name",
+                format!(
+                    "skill name '{}' renamed to '{name}' for {}",
+                    converted.name,
+                    to.as_str()
+                ),
+                Some(CODE_SKILL_NAME_NORMALIZED),
+            ));
+            converted.name = name;
+        }
+    }
+
+    let has_description = converted
+        .description
+        .as_deref()
+        .is_some_and(|d| !d.trim().is_empty());
+    if caps.description_required
+        && !has_description
+        && let Some(description) = first_paragraph(&converted.body)
+    {
+        issues.push(ValidationIssue::warning(
+            "description",
+            format!(
+                "{} requires a description; filled from the skill body",
+                to.as_str()
+            ),
+            Some(CODE_SKILL_DESCRIPTION_DERIVED),
+        ));
+        converted.description = Some(description);
+    }
+
+    issues.extend(validate_skill_for_harness(
+        &converted.to_markdown(),
+        &converted.name,
+        to,
+    ));
+    (converted, issues)
+}
+
+/// An agent definition converted for a harness.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ConvertedAgent {
+    /// The converted agent.
+    pub agent: Agent,
+    /// The file to write `content` to in the harness's agents directory
+    /// ([`Harness::agents`](crate::Harness::agents)), or `None` if the
+    /// agent has no name.
+    pub file_name: Option<String>,
+    /// The format of `content`.
+    pub format: FileFormat,
+    /// The agent definition in the harness's native format.
+    pub content: String,
+}
+
+/// Converts an agent definition written for `from` into the native format
+/// of `to`.
+///
+/// - Tool names are translated to `to`'s names (`Bash` becomes `bash` for
+///   OpenCode); tools `to` has no equivalent for are dropped with a
+///   `CODE_AGENT_TOOL_UNKNOWN` warning.
+/// - Tools are written as a boolean record for OpenCode and as a
+///   comma-separated string elsewhere. Denied tools cannot be expressed as
+///   a string and are dropped.
+/// - Models are rewritten as `to` refers to them (`sonnet` becomes
+///   `anthropic/claude-sonnet-4-5` for OpenCode) and dropped if it cannot.
+/// - Named colors become hex colors for harnesses that only accept hex;
+///   unknown names are dropped.
+/// - Modes the target does not support are dropped.
+/// - OpenCode agents are YAML documents named after the agent; other
+///   harnesses get a Markdown file with frontmatter.
+///
+/// Each dropped field is reported as a `CODE_AGENT_FIELD_DROPPED` warning,
+/// followed by the issues [`validate_agent_for_harness`] finds in the
+/// result. Other fields are carried over as they are.
+///
+/// # Errors
+///
+/// Returns an error if `content` cannot be parsed as an agent of `from`,
+/// or `Error::InvalidConfig` if `to` does not support agents.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::{HarnessKind, convert_agent};
+///
+/// let content = "---\nname: reviewer\ntools: Read, Grep\ncolor: red\n---\nReview code.\n";
+/// let (converted, issues) = convert_agent(content, HarnessKind::ClaudeCode, HarnessKind::OpenCode)?;
+///
+/// assert_eq!(converted.file_name.as_deref(), Some("reviewer.yaml"));
+/// assert_eq!(converted.agent.color.as_deref(), Some("#FF0000"));
+/// assert!(converted.content.contains("prompt: |"));
+/// assert!(issues.is_empty());
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+pub fn convert_agent(
+    content: &str,
+    from: HarnessKind,
+    to: HarnessKind,
+) -> Result<(ConvertedAgent, Vec<ValidationIssue>)> {
+    let caps = AgentCapabilities::for_kind(to)
+        .ok_or_else(|| Error::InvalidConfig(format!("{} does not support agents", to.as_str())))?;
+    let mut agent = Agent::parse(content, from)?;
+    let mut issues = Vec::new();
+    if from != to {
+        let mut unknown = Vec::new();
+        if let Some(tools) = agent.tools.take() {
+            let (kept, dropped) = translate_tools(tools.iter().map(String::as_str), from, to);
+            unknown.extend(dropped.into_iter().map(String::from));
+            agent.tools = Some(kept);
+        }
+        let (kept, dropped) =
+            translate_tools(agent.disabled_tools.iter().map(String::as_str), from, to);
+        unknown.extend(dropped.into_iter().map(String::from));
+        agent.disabled_tools = kept;
+        for tool in unknown {
+            issues.push(ValidationIssue::warning(
+                "tools",
+                format!("tool '{tool}' has no {} equivalent; dropped", to.as_str()),
+                Some(CODE_AGENT_TOOL_UNKNOWN),
+            ));
+        }
+
+        if let Some(model) = agent.model.take() {
+            agent.model = ModelRef::parse(&model, from).to_native(to);
+            if agent.model.is_none() {
+                issues.push(ValidationIssue::warning(
+                    "model",
+                    format!("{} cannot refer to model '{model}'; dropped", to.as_str()),
+                    Some(CODE_AGENT_FIELD_DROPPED),
+                ));
+            }
+        }
+    }
+
+    let mut dropped = |field: &str, message: String| {
+        issues.push(ValidationIssue::warning(
+            field,
+            message,
+            Some(CODE_AGENT_FIELD_DROPPED),
+        ));
+    };
+
+    if caps.tools_format == ToolsFormat::CommaSeparatedString && !agent.disabled_tools.is_empty() {
+        dropped(
+            "tools",
+            format!(
+                "{} cannot deny tools; dropped denied tools {}",
+                to.as_str(),
+                agent.disabled_tools.join(", ")
+            ),
+        );
+        agent.disabled_tools.clear();
+    }
+
+    if caps.color_format == ColorFormat::HexOnly
+        && let Some(color) = agent.color.take()
+    {
+        match hex_color(&color) {
+            Some(hex) => agent.color = Some(hex),
+            None => dropped(
+                "color",
+                format!("{} requires a hex color; dropped '{color}'", to.as_str()),
+            ),
+        }
+    }
+
+    if let Some(mode) = agent.mode.take() {
+        if caps.supported_modes.contains(&mode.as_str()) {
+            agent.mode = Some(mode);
+        } else {
+            dropped(
+                "mode",
+                format!("{} does not support mode '{mode}'; dropped", to.as_str()),
+            );
+        }
+    }
+
+    let format = agent_file_format(to);
+    if agent.name.is_none() && format != FileFormat::Yaml {
+        issues.push(ValidationIssue::warning(
+            "name",
+            format!("agent has no name; {} agents need one", to.as_str()),
+            Some(CODE_AGENT_NAME_MISSING),
+        ));
+    }
+
+    let content = agent.to_native(to)?;
+    let frontmatter = match format {
+        FileFormat::Yaml => format!("---\n{content}---\n"),
+        _ => content.clone(),
+    };
+    issues.extend(validate_agent_for_harness(&frontmatter, to));
+
+    let extension = match format {
+        FileFormat::Yaml => "yaml",
+        _ => "md",
+    };
+    let file_name = agent
+        .name
+        .as_ref()
+        .map(|name| format!("{name}.{extension}"));
+    Ok((
+        ConvertedAgent {
+            agent,
+            file_name,
+            format,
+            content,
+        },
+        issues,
+    ))
+}
+
+/// Returns `color` as a `#RRGGBB` hex color, if it is one or names one.
+fn hex_color(color: &str) -> Option<String> {
+    if let Some(hex) = color.strip_prefix('#')
+        && hex.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return match hex.len() {
+            6 => Some(color.to_string()),
+            3 => Some(hex.chars().fold(String::from("#"), |mut expanded, c| {
+                expanded.push(c);
+                expanded.push(c);
+                expanded
+            })),
+            _ => None,
+        };
+    }
+    NAMED_COLORS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(color.trim()))
+        .map(|(_, hex)| (*hex).to_string())
+}
+
+/// Returns `name` in lowercase hyphenated form, splitting camelCase words
+/// and dropping characters other than ASCII letters and digits.
+fn normalize_name(name: &str) -> String {
+    let mut normalized = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            let word_start = c.is_ascii_uppercase()
+                && previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit());
+            if word_start && !normalized.is_empty() {
+                normalized.push('-');
+            }
+            normalized.push(c.to_ascii_lowercase());
+        } else if !normalized.is_empty() && !normalized.ends_with('-') {
+            normalized.push('-');
+        }
+        previous = Some(c);
+    }
+    normalized.truncate(SKILL_NAME_MAX_LEN);
+    normalized.trim_end_matches('-').to_string()
+}
+
+/// Returns the first paragraph of a Markdown body that is not a heading,
+/// joined into one line and cut to the maximum description length.
+fn first_paragraph(body: &str) -> Option<String> {
+    let paragraph: Vec<&str> = body
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty() || line.starts_with('#'))
+        .take_while(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    if paragraph.is_empty() {
+        return None;
+    }
+    let mut description = paragraph.join(" ");
+    if description.len() > SKILL_DESCRIPTION_MAX_LEN {
+        let mut end = SKILL_DESCRIPTION_MAX_LEN;
+        while !description.is_char_boundary(end) {
+            end -= 1;
+        }
+        description.truncate(end);
+    }
+    Some(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::parse_skill;
+    use crate::validation::{CODE_SKILL_DESCRIPTION_MISSING, CODE_SKILL_UNSUPPORTED, Severity};
+
+    fn codes(issues: &[ValidationIssue]) -> Vec<&str> {
+        issues.iter().filter_map(|i| i.code).collect()
+    }
+
+    #[test]
+    fn normalize_name_forms() {
+        assert_eq!(normalize_name("pdf-tools"), "pdf-tools");
+        assert_eq!(normalize_name("PDF Tools"), "pdf-tools");
+        assert_eq!(normalize_name("pdfTools"), "pdf-tools");
+        assert_eq!(normalize_name("  my__skill v2! "), "my-skill-v2");
+        assert_eq!(normalize_name("!!!"), "");
+        assert_eq!(normalize_name(&"a".repeat(80)).len(), SKILL_NAME_MAX_LEN);
+    }
+
+    #[test]
+    fn first_paragraph_skips_headings() {
+        let body = "\n# PDF\n\nFill in\nPDF forms.\n\nMore detail.\n";
+        assert_eq!(first_paragraph(body).as_deref(), Some("Fill in PDF forms."));
+        assert_eq!(first_paragraph("# Only a heading\n"), None);
+    }
+
+    #[test]
+    fn convert_to_permissive_harness_keeps_skill() {
+        let skill = parse_skill("---\nname: PDF Tools\n---\nBody\n").unwrap();
+
+        let (converted, issues) =
+            convert_skill(&skill, HarnessKind::OpenCode, HarnessKind::ClaudeCode);
+
+        assert_eq!(converted, skill);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn convert_to_strict_harness_normalizes_and_fills() {
+        let skill = parse_skill("---\nname: PDF Tools\n---\n# PDF\n\nFill in forms.\n").unwrap();
+
+        let (converted, issues) =
+            convert_skill(&skill, HarnessKind::ClaudeCode, HarnessKind::CopilotCli);
+
+        assert_eq!(converted.name, "pdf-tools");
+        assert_eq!(converted.description.as_deref(), Some("Fill in forms."));
+        assert_eq!(converted.body, skill.body);
+        assert_eq!(
+            codes(&issues),
+            [CODE_SKILL_NAME_NORMALIZED, CODE_SKILL_DESCRIPTION_DERIVED]
+        );
+        assert!(issues.iter().all(|i| i.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn convert_flags_description_it_cannot_fill() {
+        let skill = parse_skill("---\nname: pdf\n---\n# PDF\n").unwrap();
+
+        let (converted, issues) =
+            convert_skill(&skill, HarnessKind::ClaudeCode, HarnessKind::OpenCode);
+
+        assert_eq!(converted.description, None);
+        assert_eq!(codes(&issues), [CODE_SKILL_DESCRIPTION_MISSING]);
+    }
+
+    #[test]
+    fn convert_to_harness_without_skills_fails() {
+        let skill = parse_skill("---\nname: pdf\n---\nBody\n").unwrap();
+
+        let (_, issues) = convert_skill(&skill, HarnessKind::ClaudeCode, HarnessKind::Zed);
+
+        assert_eq!(codes(&issues), [CODE_SKILL_UNSUPPORTED]);
+    }
+
+    #[test]
+    fn hex_color_forms() {
+        assert_eq!(hex_color("#12abEF").as_deref(), Some("#12abEF"));
+        assert_eq!(hex_color("#fa0").as_deref(), Some("#ffaa00"));
+        assert_eq!(hex_color("Red").as_deref(), Some("#FF0000"));
+        assert_eq!(hex_color("#12345"), None);
+        assert_eq!(hex_color("chartreuse"), None);
+    }
+
+    #[test]
+    fn convert_agent_claude_code_to_opencode() {
+        let content = "---\nname: reviewer\ndescription: Reviews code\ntools: Read, Grep\ncolor: blue\nmode: subagent\n---\nReview it.\n";
+
+        let (converted, issues) =
+            convert_agent(content, HarnessKind::ClaudeCode, HarnessKind::OpenCode).unwrap();
+
+        assert!(issues.is_empty(), "{issues:?}");
+        assert_eq!(converted.format, FileFormat::Yaml);
+        assert_eq!(converted.file_name.as_deref(), Some("reviewer.yaml"));
+        let value: serde_yaml::Value = serde_yaml::from_str(&converted.content).unwrap();
+        assert_eq!(value["color"], "#0000FF");
+        assert_eq!(value["mode"], "subagent");
+        assert_eq!(value["tools"]["*"], false);
+        assert_eq!(value["tools"]["read"], true);
+        assert_eq!(value["prompt"], "Review it.\n");
+    }
+
+    #[test]
+    fn convert_agent_maps_tools_and_models() {
+        let content = "---\nname: a\ntools: Bash, Grep, Glob, WebSearch, mcp__github__search\nmodel: sonnet\n---\n";
+
+        let (converted, issues) =
+            convert_agent(content, HarnessKind::ClaudeCode, HarnessKind::CopilotCli).unwrap();
+
+        assert_eq!(
+            converted.agent.tools,
+            Some(vec![
+                "execute".into(),
+                "search".into(),
+                "web".into(),
+                "mcp__github__search".into()
+            ])
+        );
+        assert_eq!(converted.agent.model.as_deref(), Some("claude-sonnet-4-5"));
+        assert!(issues.is_empty(), "{issues:?}");
+
+        let (converted, issues) =
+            convert_agent(content, HarnessKind::ClaudeCode, HarnessKind::OpenCode).unwrap();
+
+        assert_eq!(
+            converted.agent.model.as_deref(),
+            Some("anthropic/claude-sonnet-4-5")
+        );
+        assert_eq!(codes(&issues), [CODE_AGENT_TOOL_UNKNOWN]);
+        assert!(issues[0].message.contains("WebSearch"));
+    }
+
+    #[test]
+    fn convert_agent_drops_model_target_cannot_use() {
+        let content =
+            "description: Plans\nmodel: openai/gpt-5\ntools:\n  bash: false\nprompt: Plan.\n";
+
+        let (converted, issues) =
+            convert_agent(content, HarnessKind::OpenCode, HarnessKind::ClaudeCode).unwrap();
+
+        assert_eq!(converted.agent.model, None);
+        assert_eq!(converted.agent.disabled_tools, Vec::<String>::new());
+        assert!(issues.iter().any(|i| i.field == "model"));
+        assert!(issues.iter().any(|i| i.message.contains("Bash")));
+    }
+
+    #[test]
+    fn convert_skill_maps_allowed_tools() {
+        let skill = parse_skill(
+            "---\nname: git\ndescription: Git\nallowed-tools: Bash(git:*), Read, WebSearch\n---\n",
+        )
+        .unwrap();
+
+        let (converted, issues) =
+            convert_skill(&skill, HarnessKind::ClaudeCode, HarnessKind::OpenCode);
+
+        assert_eq!(
+            converted.metadata["allowed-tools"],
+            serde_yaml::Value::from("bash(git:*), read")
+        );
+        assert_eq!(codes(&issues), [CODE_SKILL_TOOL_UNKNOWN]);
+    }
+
+    #[test]
+    fn convert_agent_opencode_to_markdown_reports_lossy_fields() {
+        let content = "description: Plans\nmode: all\ncolor: \"#FF5733\"\ntemperature: 0.1\ntools:\n  write: false\nprompt: Plan it.\n";
+
+        for to in [
+            HarnessKind::ClaudeCode,
+            HarnessKind::CopilotCli,
+            HarnessKind::Droid,
+        ] {
+            let (converted, issues) = convert_agent(content, HarnessKind::OpenCode, to).unwrap();
+
+            assert_eq!(converted.format, FileFormat::MarkdownWithFrontmatter);
+            assert_eq!(converted.file_name, None);
+            assert_eq!(
+                codes(&issues),
+                [
+                    CODE_AGENT_FIELD_DROPPED,
+                    CODE_AGENT_FIELD_DROPPED,
+                    CODE_AGENT_NAME_MISSING
+                ],
+                "{to}"
+            );
+            assert_eq!(converted.agent.color.as_deref(), Some("#FF5733"));
+            assert!(converted.content.contains("temperature: 0.1"));
+            assert!(converted.content.ends_with("---\nPlan it."));
+            assert!(validate_agent_for_harness(&converted.content, to).is_empty());
+        }
+    }
+
+    #[test]
+    fn convert_agent_drops_unknown_named_color() {
+        let content = "---\nname: a\ncolor: chartreuse\n---\n";
+
+        let (converted, issues) =
+            convert_agent(content, HarnessKind::Droid, HarnessKind::OpenCode).unwrap();
+
+        assert_eq!(converted.agent.color, None);
+        assert_eq!(codes(&issues), [CODE_AGENT_FIELD_DROPPED]);
+        assert_eq!(issues[0].field, "color");
+    }
+
+    #[test]
+    fn convert_agent_round_trips_between_markdown_harnesses() {
+        let content = "---\nname: reviewer\ndescription: Reviews code\ntools: Read, Grep\ncolor: red\n---\nReview it.\n";
+
+        let (converted, issues) =
+            convert_agent(content, HarnessKind::ClaudeCode, HarnessKind::Droid).unwrap();
+
+        assert!(issues.is_empty());
+        assert_eq!(converted.file_name.as_deref(), Some("reviewer.md"));
+        assert_eq!(converted.content, content);
+    }
+
+    #[test]
+    fn convert_agent_to_harness_without_agents_fails() {
+        assert!(matches!(
+            convert_agent(
+                "---\nname: a\n---\n",
+                HarnessKind::ClaudeCode,
+                HarnessKind::Zed
+            ),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
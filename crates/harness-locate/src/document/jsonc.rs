@@ -0,0 +1,727 @@
+//! Lossless editing of JSON and JSONC text.
+//!
+//! The parser records the byte span of every value and object member so
+//! that edits can be spliced into the original source, leaving comments,
+//! trailing commas, key order and indentation untouched. Strict JSON is
+//! a subset of JSONC and goes through the same code path.
+
+use std::ops::Range;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::Style;
+use crate::error::{Error, Result};
+
+/// A parsed JSONC value together with its location in the source.
+#[derive(Debug)]
+enum Node {
+    Object {
+        span: Range<usize>,
+        members: Vec<Member>,
+    },
+    Array {
+        span: Range<usize>,
+        items: Vec<Node>,
+    },
+    Scalar {
+        span: Range<usize>,
+        value: Value,
+    },
+}
+
+/// A `"key": value` pair inside an object.
+#[derive(Debug)]
+struct Member {
+    key: String,
+    key_span: Range<usize>,
+    value: Node,
+}
+
+impl Node {
+    fn span(&self) -> Range<usize> {
+        match self {
+            Self::Object { span, .. } | Self::Array { span, .. } | Self::Scalar { span, .. } => {
+                span.clone()
+            }
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            Self::Object { members, .. } => Value::Object(
+                members
+                    .iter()
+                    .map(|m| (m.key.clone(), m.value.to_value()))
+                    .collect::<Map<_, _>>(),
+            ),
+            Self::Array { items, .. } => Value::Array(items.iter().map(Node::to_value).collect()),
+            Self::Scalar { value, .. } => value.clone(),
+        }
+    }
+
+    /// Returns the member named `key`; the last one wins on duplicates.
+    fn member(&self, key: &str) -> Option<&Member> {
+        match self {
+            Self::Object { members, .. } => members.iter().rev().find(|m| m.key == key),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        let pos = if src.starts_with('\u{feff}') { 3 } else { 0 };
+        Self { src, pos }
+    }
+
+    fn parse(mut self) -> Result<Node> {
+        self.skip_trivia()?;
+        let root = self.value()?;
+        self.skip_trivia()?;
+        if self.pos < self.src.len() {
+            return Err(self.error("unexpected trailing content"));
+        }
+        Ok(root)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> Error {
+        let (line, column) = super::line_column(self.src, self.pos);
+        Error::InvalidConfig(format!(
+            "JSONC parse error at line {line}, column {column}: {message}"
+        ))
+    }
+
+    fn skip_trivia(&mut self) -> Result<()> {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\n' | b'\r') => self.pos += 1,
+                Some(b'/') => match self.src.as_bytes().get(self.pos + 1) {
+                    Some(b'/') => {
+                        while self.peek().is_some_and(|b| b != b'\n') {
+                            self.pos += 1;
+                        }
+                    }
+                    Some(b'*') => {
+                        let len = self.src[self.pos + 2..]
+                            .find("*/")
+                            .ok_or_else(|| self.error("unterminated block comment"))?;
+                        self.pos += len + 4;
+                    }
+                    _ => return Err(self.error("unexpected '/'")),
+                },
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Node> {
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => {
+                let span = self.string()?;
+                let value = self.scalar(span.clone())?;
+                Ok(Node::Scalar { span, value })
+            }
+            Some(_) => self.literal(),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Node> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut members = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Node::Object {
+                        span: start..self.pos,
+                        members,
+                    });
+                }
+                Some(b'"') => {}
+                _ => return Err(self.error("expected string key or '}'")),
+            }
+            let key_span = self.string()?;
+            let key = match self.scalar(key_span.clone())? {
+                Value::String(key) => key,
+                _ => return Err(self.error("expected string key")),
+            };
+            self.skip_trivia()?;
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected ':'"));
+            }
+            self.pos += 1;
+            self.skip_trivia()?;
+            let value = self.value()?;
+            members.push(Member {
+                key,
+                key_span,
+                value,
+            });
+            self.skip_trivia()?;
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {}
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Node> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            if self.peek() == Some(b']') {
+                self.pos += 1;
+                return Ok(Node::Array {
+                    span: start..self.pos,
+                    items,
+                });
+            }
+            items.push(self.value()?);
+            self.skip_trivia()?;
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {}
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<Range<usize>> {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(start..self.pos);
+                }
+                Some(b'\\') => self.pos += 2,
+                Some(b'\n') | None => return Err(self.error("unterminated string")),
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+
+    fn literal(&mut self) -> Result<Node> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| !matches!(b, b',' | b']' | b'}' | b'/') && !b.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+        let span = start..self.pos;
+        let value = self.scalar(span.clone())?;
+        Ok(Node::Scalar { span, value })
+    }
+
+    fn scalar(&self, span: Range<usize>) -> Result<Value> {
+        let text = self.src.get(span.clone()).unwrap_or_default();
+        serde_json::from_str(text).map_err(|_| {
+            let at = Self {
+                src: self.src,
+                pos: span.start,
+            };
+            at.error(&format!("invalid value '{text}'"))
+        })
+    }
+}
+
+/// Parses JSONC text into a JSON value.
+///
+/// Line and block comments and trailing commas are accepted.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidConfig`] with the line and column of the first
+/// syntax error.
+pub(super) fn parse(src: &str) -> Result<Value> {
+    Parser::new(src).parse().map(|root| root.to_value())
+}
+
+/// Returns the byte offset of the value at `path`, or of its key when it
+/// is an object member.
+///
+/// Returns `None` if the source does not parse or there is no such value.
+pub(super) fn offset(src: &str, path: &[String]) -> Option<usize> {
+    let root = Parser::new(src).parse().ok()?;
+    let mut node = &root;
+    let mut offset = root.span().start;
+    for token in path {
+        match node {
+            Node::Object { .. } => {
+                let member = node.member(token)?;
+                offset = member.key_span.start;
+                node = &member.value;
+            }
+            Node::Array { items, .. } => {
+                node = items.get(token.parse::<usize>().ok()?)?;
+                offset = node.span().start;
+            }
+            Node::Scalar { .. } => return None,
+        }
+    }
+    Some(offset)
+}
+
+/// Sets the value at `path`, creating missing parent objects.
+///
+/// Returns `None` if the source does not parse or the edit cannot be
+/// expressed as a splice of the original text.
+pub(super) fn set(src: &str, path: &[String], value: &Value, style: &Style) -> Option<String> {
+    let root = Parser::new(src).parse().ok()?;
+    let mut node = &root;
+    for (i, token) in path.iter().enumerate() {
+        if let Node::Array { span, items } = node {
+            let index = token.parse::<usize>().ok()?;
+            if i + 1 == path.len() && index == items.len() {
+                let entries: Vec<_> = items.iter().map(|item| item.span()).collect();
+                return insert_entry(src, span, &entries, "", value, style);
+            }
+            let item = items.get(index)?;
+            if i + 1 == path.len() {
+                let indent = line_indent(src, item.span().start);
+                let rendered = render(value, indent, style)?;
+                return Some(splice(src, &[(item.span(), rendered)]));
+            }
+            node = item;
+            continue;
+        }
+        if !matches!(node, Node::Object { .. }) {
+            // Only reachable for `null` placeholders the caller already
+            // replaced with an object in its model
+            let indent = line_indent(src, node.span().start);
+            let rendered = render(&nest(&path[i..], value), indent, style)?;
+            return Some(splice(src, &[(node.span(), rendered)]));
+        }
+        match node.member(token) {
+            Some(member) if i + 1 == path.len() => {
+                let indent = line_indent(src, member.key_span.start);
+                let rendered = render(value, indent, style)?;
+                return Some(splice(src, &[(member.value.span(), rendered)]));
+            }
+            Some(member) => node = &member.value,
+            None => return insert_member(src, node, token, &nest(&path[i + 1..], value), style),
+        }
+    }
+    None
+}
+
+/// Removes the member or array element at `path`, along with its line if
+/// it has one.
+///
+/// Returns `None` if the source does not parse or the entry is missing.
+pub(super) fn remove(src: &str, path: &[String]) -> Option<String> {
+    let root = Parser::new(src).parse().ok()?;
+    let (key, parents) = path.split_last()?;
+    let mut node = &root;
+    for token in parents {
+        node = match node {
+            Node::Array { items, .. } => items.get(token.parse::<usize>().ok()?)?,
+            _ => &node.member(token)?.value,
+        };
+    }
+    match node {
+        Node::Object { span, members } => {
+            let index = members.iter().rposition(|m| &m.key == key)?;
+            let entries: Vec<_> = members
+                .iter()
+                .map(|m| m.key_span.start..m.value.span().end)
+                .collect();
+            remove_entry(src, span, &entries, index)
+        }
+        Node::Array { span, items } => {
+            let index = key.parse::<usize>().ok().filter(|&i| i < items.len())?;
+            let entries: Vec<_> = items.iter().map(|item| item.span()).collect();
+            remove_entry(src, span, &entries, index)
+        }
+        Node::Scalar { .. } => None,
+    }
+}
+
+/// Removes `entries[index]` from the object or array spanning `span`.
+///
+/// Each entry runs from the start of its key (or value, in an array) to
+/// the end of its value.
+fn remove_entry(
+    src: &str,
+    span: &Range<usize>,
+    entries: &[Range<usize>],
+    index: usize,
+) -> Option<String> {
+    let entry = &entries[index];
+
+    let bytes = src.as_bytes();
+    let line_start = src[..entry.start].rfind('\n').map_or(0, |i| i + 1);
+    let own_line = src[line_start..entry.start].trim().is_empty();
+
+    // Walk the rest of the entry's line: separator, then an optional
+    // trailing comment that belongs to the removed entry
+    let mut end = skip_blanks(bytes, entry.end);
+    let has_comma = bytes.get(end) == Some(&b',');
+    if has_comma {
+        end = skip_blanks(bytes, end + 1);
+    }
+    let mut start = entry.start;
+    if own_line {
+        if src[end..].starts_with("//") {
+            end += src[end..].find('\n').unwrap_or(src.len() - end);
+        }
+        if src[end..].starts_with("\r\n") {
+            end += 2;
+            start = line_start;
+        } else if src[end..].starts_with('\n') {
+            end += 1;
+            start = line_start;
+        }
+    }
+
+    let mut edits = Vec::new();
+    if !has_comma && index > 0 {
+        // The entry was last, so the one before it loses its separator
+        let prev_end = entries[index - 1].end;
+        let comma = src[prev_end..start].find(',')? + prev_end;
+        if start == line_start {
+            edits.push((comma..comma + 1, String::new()));
+        } else {
+            start = comma;
+        }
+    }
+    edits.push((start..end, String::new()));
+    if entries.len() == 1 {
+        let interior = span.start + 1..span.end - 1;
+        let mut remaining = src[interior.clone()].to_string();
+        remaining.replace_range(start - interior.start..end - interior.start, "");
+        if remaining.trim().is_empty() {
+            return Some(splice(src, &[(interior, String::new())]));
+        }
+    }
+    Some(splice(src, &edits))
+}
+
+/// Inserts `"key": value` as the last member of `object`.
+fn insert_member(
+    src: &str,
+    object: &Node,
+    key: &str,
+    value: &Value,
+    style: &Style,
+) -> Option<String> {
+    let Node::Object { span, members } = object else {
+        return None;
+    };
+    let key = serde_json::to_string(key).ok()?;
+    let entries: Vec<_> = members
+        .iter()
+        .map(|m| m.key_span.start..m.value.span().end)
+        .collect();
+    insert_entry(src, span, &entries, &format!("{key}: "), value, style)
+}
+
+/// Inserts `{prefix}value` after the last of `entries` in the object or
+/// array spanning `span`.
+///
+/// `prefix` is the quoted key and colon of an object member, and empty for
+/// an array element.
+fn insert_entry(
+    src: &str,
+    span: &Range<usize>,
+    entries: &[Range<usize>],
+    prefix: &str,
+    value: &Value,
+    style: &Style,
+) -> Option<String> {
+    let nl = style.newline;
+
+    let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+        let open = span.start + 1;
+        let close = span.end - 1;
+        let outer = line_indent(src, span.start);
+        let inner = format!("{outer}{}", style.indent);
+        let rendered = render(value, &inner, style)?;
+        let entry = format!("{nl}{inner}{prefix}{rendered}");
+        return Some(if src[open..close].trim().is_empty() {
+            splice(src, &[(open..close, format!("{entry}{nl}{outer}"))])
+        } else {
+            splice(src, &[(open..open, entry)])
+        });
+    };
+
+    let bytes = src.as_bytes();
+    let value_end = last.end;
+    let mut at = skip_blanks(bytes, value_end);
+    let has_comma = bytes.get(at) == Some(&b',');
+
+    if !src[span.start..first.start].contains('\n') {
+        // Single-line object or array: keep it on one line
+        let compact = serde_json::to_string(value).ok()?;
+        return Some(if has_comma {
+            splice(src, &[(at + 1..at + 1, format!(" {prefix}{compact},"))])
+        } else {
+            splice(
+                src,
+                &[(value_end..value_end, format!(", {prefix}{compact}"))],
+            )
+        });
+    }
+
+    // Insert after anything trailing the last entry on its line
+    if has_comma {
+        at = skip_blanks(bytes, at + 1);
+    }
+    if src[at..].starts_with("//") {
+        at += src[at..].find('\n').unwrap_or(src.len() - at);
+        if src[..at].ends_with('\r') {
+            at -= 1;
+        }
+    } else if src[at..].starts_with("/*") {
+        let len = src[at..].find("*/")? + 2;
+        if !src[at..at + len].contains('\n') {
+            at = skip_blanks(bytes, at + len);
+        }
+    }
+    let at_break = src[at..].starts_with('\n') || src[at..].starts_with("\r\n");
+    if !at_break && !src[at..].starts_with(['}', ']']) {
+        at = if has_comma {
+            skip_blanks(bytes, value_end) + 1
+        } else {
+            value_end
+        };
+    }
+
+    let indent = line_indent(src, first.start);
+    let rendered = render(value, indent, style)?;
+    let entry = format!("{nl}{indent}{prefix}{rendered}");
+    let edits = if has_comma {
+        vec![(at..at, format!("{entry},"))]
+    } else if at == value_end {
+        vec![(at..at, format!(",{entry}"))]
+    } else {
+        vec![(at..at, entry), (value_end..value_end, ",".to_string())]
+    };
+    Some(splice(src, &edits))
+}
+
+/// Wraps `value` in one object per token, innermost last.
+fn nest(tokens: &[String], value: &Value) -> Value {
+    tokens.iter().rev().fold(value.clone(), |inner, token| {
+        let mut map = Map::new();
+        map.insert(token.clone(), inner);
+        Value::Object(map)
+    })
+}
+
+/// Pretty-prints `value` for placement on a line indented by `base`.
+fn render(value: &Value, base: &str, style: &Style) -> Option<String> {
+    let mut out = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(style.indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+    value.serialize(&mut serializer).ok()?;
+    let out = String::from_utf8(out).ok()?;
+    Some(out.replace('\n', &format!("{}{base}", style.newline)))
+}
+
+/// Returns the leading whitespace of the line containing `pos`.
+fn line_indent(src: &str, pos: usize) -> &str {
+    let start = src[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let line = &src[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Advances past spaces and tabs, stopping at line breaks.
+fn skip_blanks(bytes: &[u8], mut pos: usize) -> usize {
+    while matches!(bytes.get(pos), Some(b' ' | b'\t')) {
+        pos += 1;
+    }
+    pos
+}
+
+/// Applies non-overlapping replacements to `src`.
+fn splice(src: &str, edits: &[(Range<usize>, String)]) -> String {
+    let mut edits: Vec<_> = edits.iter().collect();
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut out = src.to_string();
+    for (range, text) in edits {
+        out.replace_range(range.clone(), text);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn style() -> Style {
+        Style {
+            indent: "  ".to_string(),
+            newline: "\n",
+        }
+    }
+
+    fn path(tokens: &[&str]) -> Vec<String> {
+        tokens.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_accepts_comments_and_trailing_commas() {
+        let src = "{\n  // line\n  \"a\": 1, /* block */\n  \"b\": [true, null,],\n}\n";
+        assert_eq!(parse(src).unwrap(), json!({"a": 1, "b": [true, null]}));
+    }
+
+    #[test]
+    fn parse_reports_position() {
+        let err = parse("{\n  \"a\": 1\n  \"b\": 2\n}")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("line 3, column 3"), "{err}");
+    }
+
+    #[test]
+    fn parse_rejects_invalid_literal() {
+        assert!(parse("{\"a\": nope}").is_err());
+        assert!(parse("{\"a\": 1} extra").is_err());
+    }
+
+    #[test]
+    fn set_appends_member_keeping_comments() {
+        let src =
+            "{\n  // servers\n  \"mcp\": {\n    \"a\": {\"type\": \"local\"} // first\n  }\n}\n";
+        let out = set(src, &path(&["mcp", "b"]), &json!({"x": 1}), &style()).unwrap();
+        assert_eq!(
+            out,
+            "{\n  // servers\n  \"mcp\": {\n    \"a\": {\"type\": \"local\"}, // first\n    \"b\": {\n      \"x\": 1\n    }\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn set_preserves_trailing_comma_style() {
+        let src = "{\n  \"a\": 1,\n}\n";
+        let out = set(src, &path(&["b"]), &json!(2), &style()).unwrap();
+        assert_eq!(out, "{\n  \"a\": 1,\n  \"b\": 2,\n}\n");
+    }
+
+    #[test]
+    fn set_replaces_value_in_place() {
+        let src = "{\n    \"a\": 1, // keep\n    \"b\": 2\n}\n";
+        let out = set(src, &path(&["a"]), &json!({"k": "v"}), &style()).unwrap();
+        assert_eq!(
+            out,
+            "{\n    \"a\": {\n      \"k\": \"v\"\n    }, // keep\n    \"b\": 2\n}\n"
+        );
+    }
+
+    #[test]
+    fn set_creates_missing_parents() {
+        let src = "{\n  \"theme\": \"dark\"\n}\n";
+        let out = set(src, &path(&["mcp", "dev"]), &json!(true), &style()).unwrap();
+        assert_eq!(
+            out,
+            "{\n  \"theme\": \"dark\",\n  \"mcp\": {\n    \"dev\": true\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn set_fills_empty_object() {
+        let out = set(
+            "{\n  \"mcp\": {}\n}\n",
+            &path(&["mcp", "a"]),
+            &json!(1),
+            &style(),
+        );
+        assert_eq!(out.unwrap(), "{\n  \"mcp\": {\n    \"a\": 1\n  }\n}\n");
+    }
+
+    #[test]
+    fn set_keeps_single_line_objects_inline() {
+        let out = set("{\"a\": 1}", &path(&["b"]), &json!({"c": 2}), &style());
+        assert_eq!(out.unwrap(), "{\"a\": 1, \"b\": {\"c\":2}}");
+    }
+
+    #[test]
+    fn remove_drops_member_line() {
+        let src = "{\n  \"a\": 1,\n  // about b\n  \"b\": 2, // trailing\n  \"c\": 3\n}\n";
+        let out = remove(src, &path(&["b"])).unwrap();
+        assert_eq!(out, "{\n  \"a\": 1,\n  // about b\n  \"c\": 3\n}\n");
+    }
+
+    #[test]
+    fn remove_last_member_drops_previous_comma() {
+        let src = "{\n  \"a\": 1,\n  \"b\": 2\n}\n";
+        assert_eq!(remove(src, &path(&["b"])).unwrap(), "{\n  \"a\": 1\n}\n");
+    }
+
+    #[test]
+    fn remove_only_member_collapses_object() {
+        let src = "{\n  \"mcp\": {\n    \"a\": 1\n  }\n}\n";
+        assert_eq!(
+            remove(src, &path(&["mcp", "a"])).unwrap(),
+            "{\n  \"mcp\": {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn remove_inline_member() {
+        let src = "{\"a\": 1, \"b\": 2, \"c\": 3}";
+        assert_eq!(remove(src, &path(&["b"])).unwrap(), "{\"a\": 1, \"c\": 3}");
+        assert_eq!(remove(src, &path(&["c"])).unwrap(), "{\"a\": 1, \"b\": 2}");
+    }
+
+    #[test]
+    fn set_appends_array_element() {
+        let src = "{\n  \"args\": [\n    \"a\", // first\n    \"b\" // second\n  ]\n}\n";
+        let out = set(src, &path(&["args", "2"]), &json!("c"), &style()).unwrap();
+        assert_eq!(
+            out,
+            "{\n  \"args\": [\n    \"a\", // first\n    \"b\", // second\n    \"c\"\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn set_appends_to_inline_and_empty_arrays() {
+        let out = set("{\"a\": [1, 2]}", &path(&["a", "2"]), &json!(3), &style()).unwrap();
+        assert_eq!(out, "{\"a\": [1, 2, 3]}");
+
+        let out = set(
+            "{\n  \"a\": []\n}\n",
+            &path(&["a", "0"]),
+            &json!(1),
+            &style(),
+        )
+        .unwrap();
+        assert_eq!(out, "{\n  \"a\": [\n    1\n  ]\n}\n");
+    }
+
+    #[test]
+    fn remove_array_element_keeps_comments() {
+        let src = "{\n  // tools\n  \"args\": [\n    \"a\", // first\n    \"b\", // second\n    \"c\"\n  ]\n}\n";
+        assert_eq!(
+            remove(src, &path(&["args", "1"])).unwrap(),
+            "{\n  // tools\n  \"args\": [\n    \"a\", // first\n    \"c\"\n  ]\n}\n"
+        );
+        assert_eq!(
+            remove(src, &path(&["args", "2"])).unwrap(),
+            "{\n  // tools\n  \"args\": [\n    \"a\", // first\n    \"b\" // second\n  ]\n}\n"
+        );
+        assert_eq!(remove("[1, 2, 3]", &path(&["1"])).unwrap(), "[1, 3]");
+        assert_eq!(remove("[1, 2, 3]", &path(&["3"])), None);
+    }
+}
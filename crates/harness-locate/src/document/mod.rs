@@ -0,0 +1,831 @@
+//! Reading and writing harness configuration files.
+//!
+//! Harness configuration files are loaded into a [`Document`], which keeps
+//! the original text alongside a [`serde_json::Value`] model of it. Edits
+//! are applied to the model and spliced into the text by a format-specific
+//! editor, so comments, key order and indentation outside the edited entry
+//! survive a round trip. When an edit cannot be made in place, or the
+//! spliced text would not parse back to the edited model, the edit fails
+//! with [`Error::LossyEdit`] rather than re-serializing the document from
+//! the model, unless that loses nothing or the caller allowed it.
+
+mod jsonc;
+mod toml;
+mod yaml;
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::types::FileFormat;
+
+/// Formatting conventions detected from existing text.
+#[derive(Debug, Clone)]
+struct Style {
+    /// One level of indentation.
+    indent: String,
+    newline: &'static str,
+}
+
+impl Style {
+    fn detect(src: &str) -> Self {
+        let indent = src
+            .lines()
+            .find_map(|line| {
+                let rest = line.trim_start_matches([' ', '\t']);
+                let width = line.len() - rest.len();
+                (width > 0 && !rest.trim().is_empty()).then(|| line[..width].to_string())
+            })
+            .map(|ws| {
+                if ws.starts_with('\t') {
+                    "\t".to_string()
+                } else {
+                    ws.replace('\t', "")
+                }
+            })
+            .unwrap_or_else(|| "  ".to_string());
+        let newline = if src.contains("\r\n") { "\r\n" } else { "\n" };
+        Self { indent, newline }
+    }
+}
+
+/// A configuration file held as text plus a value model.
+#[derive(Debug, Clone)]
+pub(crate) struct Document {
+    format: FileFormat,
+    text: String,
+    value: Value,
+    /// Whether an edit that cannot be made in place may re-serialize the
+    /// whole document.
+    allow_reformat: bool,
+}
+
+impl Document {
+    /// Reads a configuration file.
+    ///
+    /// A missing file yields an empty document so that callers can create
+    /// the file on first write.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not parse as
+    /// the given format.
+    pub(crate) fn read(path: &Path, format: FileFormat) -> Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Self::parse(text, format)
+    }
+
+    /// Parses configuration file content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content does not parse as the given format.
+    pub(crate) fn parse(text: String, format: FileFormat) -> Result<Self> {
+        let value = parse_value(&text, format)?;
+        Ok(Self {
+            format,
+            text,
+            value,
+            allow_reformat: false,
+        })
+    }
+
+    /// Returns the value at `pointer`, if present.
+    ///
+    /// Tokens index into arrays as well as objects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pointer` is malformed.
+    pub(crate) fn get(&self, pointer: &str) -> Result<Option<&Value>> {
+        let mut current = &self.value;
+        for token in pointer_tokens(pointer)? {
+            let next = match current {
+                Value::Array(items) => token.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => current.get(&token),
+            };
+            match next {
+                Some(next) => current = next,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(current))
+    }
+
+    /// Returns the value model of the whole document.
+    pub(crate) fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Returns the format of the document.
+    pub(crate) fn format(&self) -> FileFormat {
+        self.format
+    }
+
+    /// Returns the 1-based line and column of the value at `pointer`, or
+    /// of its key when it is an object member.
+    ///
+    /// Only JSON and JSONC documents track positions; other formats
+    /// return `None`.
+    pub(crate) fn position(&self, pointer: &str) -> Option<(usize, usize)> {
+        let offset = match self.format {
+            FileFormat::Json | FileFormat::Jsonc => {
+                jsonc::offset(&self.text, &pointer_tokens(pointer).ok()?)?
+            }
+            _ => return None,
+        };
+        Some(line_column(&self.text, offset))
+    }
+
+    /// Returns the current text of the document.
+    pub(crate) fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Inserts `key` into the object or array at `pointer`, replacing any
+    /// existing entry in place and creating missing parent objects.
+    ///
+    /// In an array, `key` is an existing index, which is replaced, or the
+    /// array's length or `-`, which append (RFC 6901).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value along `pointer` is neither an object nor
+    /// an array, or if an array index is not a valid index into it.
+    pub(crate) fn insert(&mut self, pointer: &str, key: &str, value: Value) -> Result<()> {
+        let mut expected = self.value.clone();
+        let key = match container_at_mut(&mut expected, pointer)? {
+            Value::Object(obj) => {
+                obj.insert(key.to_string(), value.clone());
+                key.to_string()
+            }
+            Value::Array(items) => {
+                let index = array_index(key, items.len())
+                    .filter(|&i| i <= items.len())
+                    .ok_or_else(|| {
+                        Error::InvalidConfig(format!(
+                            "'{key}' is not an index into the array at '{pointer}'"
+                        ))
+                    })?;
+                if index == items.len() {
+                    items.push(value.clone());
+                } else {
+                    items[index] = value.clone();
+                }
+                index.to_string()
+            }
+            _ => {
+                return Err(Error::InvalidConfig(format!(
+                    "value at '{pointer}' is not an object or array"
+                )));
+            }
+        };
+
+        let mut path = pointer_tokens(pointer)?;
+        path.push(key);
+        let style = Style::detect(&self.text);
+        let edited = match self.format {
+            FileFormat::Json | FileFormat::Jsonc => jsonc::set(&self.text, &path, &value, &style),
+            FileFormat::Yaml => yaml::set(&self.text, &path, &value, &style),
+            FileFormat::Toml => toml::set(&self.text, &path, &value),
+            _ => None,
+        };
+        self.commit(edited, expected, &path)
+    }
+
+    /// Removes `key` from the object or array at `pointer`.
+    ///
+    /// Removing an array element shifts the elements after it down.
+    ///
+    /// Returns the removed value, or `None` if there was no such entry, in
+    /// which case the document is unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pointer` is malformed.
+    pub(crate) fn remove(&mut self, pointer: &str, key: &str) -> Result<Option<Value>> {
+        let mut path = pointer_tokens(pointer)?;
+        let mut expected = self.value.clone();
+        let removed = match expected.pointer_mut(pointer) {
+            Some(Value::Object(obj)) => obj.shift_remove(key),
+            Some(Value::Array(items)) => array_index(key, items.len())
+                .filter(|&i| i < items.len())
+                .map(|i| items.remove(i)),
+            _ => None,
+        };
+        let Some(removed) = removed else {
+            return Ok(None);
+        };
+
+        path.push(key.to_string());
+        let style = Style::detect(&self.text);
+        let edited = match self.format {
+            FileFormat::Json | FileFormat::Jsonc => jsonc::remove(&self.text, &path),
+            FileFormat::Yaml => yaml::remove(&self.text, &path, &style),
+            FileFormat::Toml => toml::remove(&self.text, &path),
+            _ => None,
+        };
+        self.commit(edited, expected, &path)?;
+        Ok(Some(removed))
+    }
+
+    /// Allows edits that cannot be made in place to re-serialize the whole
+    /// document, dropping its comments and formatting.
+    pub(crate) fn allow_reformat(&mut self, allow: bool) {
+        self.allow_reformat = allow;
+    }
+
+    /// Adopts `edited` if it parses back to `expected`.
+    ///
+    /// Otherwise `expected` is re-serialized, but only if that loses
+    /// nothing (the text is empty or already in serialized form) or
+    /// reformatting was allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LossyEdit`] if the edit would reformat the
+    /// document without permission.
+    fn commit(&mut self, edited: Option<String>, expected: Value, path: &[String]) -> Result<()> {
+        self.text = match edited {
+            Some(text) if parse_value(&text, self.format).is_ok_and(|v| v == expected) => text,
+            _ if self.allow_reformat
+                || self.text.trim().is_empty()
+                || to_string(&self.value, self.format).is_ok_and(|t| t == self.text) =>
+            {
+                to_string(&expected, self.format)?
+            }
+            _ => {
+                return Err(Error::LossyEdit {
+                    pointer: path
+                        .iter()
+                        .map(|t| format!("/{}", t.replace('~', "~0").replace('/', "~1")))
+                        .collect(),
+                });
+            }
+        };
+        self.value = expected;
+        Ok(())
+    }
+}
+
+/// Returns the 1-based line and byte column of `offset` in `src`.
+fn line_column(src: &str, offset: usize) -> (usize, usize) {
+    let before = &src.as_bytes()[..offset.min(src.len())];
+    let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+    let line_start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    (line, before.len() - line_start + 1)
+}
+
+/// Parses configuration file content into a JSON value.
+///
+/// # Errors
+///
+/// Returns an error if the content does not parse as the given format.
+fn parse_value(content: &str, format: FileFormat) -> Result<Value> {
+    if content.trim().is_empty() {
+        return Ok(Value::Object(Map::new()));
+    }
+    match format {
+        FileFormat::Json | FileFormat::Jsonc => jsonc::parse(content),
+        FileFormat::Yaml => {
+            let value: Value = serde_yaml::from_str(content)?;
+            // An empty YAML document parses as null
+            Ok(if value.is_null() {
+                Value::Object(Map::new())
+            } else {
+                value
+            })
+        }
+        FileFormat::Toml => toml::parse(content),
+        other => Err(Error::InvalidConfig(format!(
+            "{other:?} is not a configuration file format"
+        ))),
+    }
+}
+
+/// Serializes a JSON value in the given configuration file format.
+///
+/// # Errors
+///
+/// Returns an error if the value cannot be represented in the format.
+fn to_string(value: &Value, format: FileFormat) -> Result<String> {
+    match format {
+        FileFormat::Json | FileFormat::Jsonc => {
+            let mut out = serde_json::to_string_pretty(value)?;
+            out.push('\n');
+            Ok(out)
+        }
+        FileFormat::Yaml => Ok(serde_yaml::to_string(value)?),
+        FileFormat::Toml => toml::to_string(value),
+        other => Err(Error::InvalidConfig(format!(
+            "{other:?} is not a configuration file format"
+        ))),
+    }
+}
+
+/// Writes content to a file atomically.
+///
+/// The content is written to a temporary file in the same directory and
+/// renamed over the destination, so readers never observe a partially
+/// written file. Missing parent directories are created and the
+/// permissions of an existing file are preserved; the temporary file has
+/// them before any content is written.
+///
+/// A symbolic link at `path` is followed and its target written, so a
+/// config linked in from a dotfiles repository stays a link.
+///
+/// # Errors
+///
+/// Returns an error if the file or its parent directory cannot be written.
+pub(crate) fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    write_atomic_with(path, content, false)
+}
+
+/// Writes content to a file atomically, as [`write_atomic`] does, creating
+/// a missing file readable by its owner only (`0600`) on Unix.
+///
+/// # Errors
+///
+/// Returns an error if the file or its parent directory cannot be written.
+pub(crate) fn write_private(path: &Path, content: &str) -> Result<()> {
+    write_atomic_with(path, content, true)
+}
+
+fn write_atomic_with(path: &Path, content: &str, private: bool) -> Result<()> {
+    let path = resolve_symlinks(path)?;
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent)?;
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::InvalidPath(path.clone()))?;
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(file_name);
+    tmp_name.push(format!(".tmp-{}", std::process::id()));
+    let tmp_path = parent.join(tmp_name);
+
+    let result = (|| {
+        let mut permissions = fs::metadata(&path).ok().map(|m| m.permissions());
+        #[cfg(unix)]
+        if permissions.is_none() && private {
+            use std::os::unix::fs::PermissionsExt;
+            permissions = Some(fs::Permissions::from_mode(0o600));
+        }
+        #[cfg(not(unix))]
+        let _ = private;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        if let Some(permissions) = &permissions {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(permissions.mode() & 0o777);
+        }
+        let mut file = options.open(&tmp_path)?;
+        if let Some(permissions) = permissions {
+            // The umask may have narrowed the mode `open` applied
+            file.set_permissions(permissions)?;
+        }
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    Ok(result?)
+}
+
+/// Follows symbolic links at `path` to the file they point to, which need
+/// not exist yet.
+///
+/// # Errors
+///
+/// Returns an error if a link cannot be read or the chain is too long to
+/// be anything but a loop.
+fn resolve_symlinks(path: &Path) -> Result<std::path::PathBuf> {
+    let mut path = path.to_path_buf();
+    for _ in 0..40 {
+        match fs::symlink_metadata(&path) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                let target = fs::read_link(&path)?;
+                path = match path.parent() {
+                    Some(parent) => parent.join(target),
+                    None => target,
+                };
+            }
+            _ => return Ok(path),
+        }
+    }
+    Err(Error::InvalidPath(path))
+}
+
+/// Splits a JSON pointer into its unescaped reference tokens.
+///
+/// Follows RFC 6901: `~1` decodes to `/` and `~0` decodes to `~`.
+/// Dots carry no meaning, so `/amp.mcpServers` is a single token.
+///
+/// # Errors
+///
+/// Returns an error if the pointer is non-empty and does not start with `/`.
+fn pointer_tokens(pointer: &str) -> Result<Vec<String>> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rest = pointer.strip_prefix('/').ok_or_else(|| {
+        Error::InvalidConfig(format!("JSON pointer '{pointer}' must start with '/'"))
+    })?;
+    Ok(rest.split('/').map(unescape_token).collect())
+}
+
+/// Splits a JSON pointer into the pointer of its parent and its last,
+/// unescaped reference token.
+///
+/// # Errors
+///
+/// Returns an error if the pointer is empty (the document root has no
+/// parent) or does not start with `/`.
+pub(crate) fn split_pointer(pointer: &str) -> Result<(&str, String)> {
+    if !pointer.starts_with('/') {
+        return Err(Error::InvalidConfig(format!(
+            "JSON pointer '{pointer}' must start with '/' and name a key"
+        )));
+    }
+    let (parent, token) = pointer.rsplit_once('/').unwrap_or_default();
+    Ok((parent, unescape_token(token)))
+}
+
+/// Decodes `~1` to `/` and then `~0` to `~`, in that order.
+fn unescape_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Returns the object or array at `pointer`, creating intermediate objects
+/// as needed.
+///
+/// Array elements are followed but never created.
+///
+/// # Errors
+///
+/// Returns an error if a value along the path exists but is neither an
+/// object nor an array, or if an array index is out of range.
+fn container_at_mut<'a>(root: &'a mut Value, pointer: &str) -> Result<&'a mut Value> {
+    let mut current = root;
+    for token in pointer_tokens(pointer)? {
+        current = match current {
+            Value::Object(obj) => {
+                let next = obj
+                    .entry(token)
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if next.is_null() {
+                    *next = Value::Object(Map::new());
+                }
+                next
+            }
+            Value::Array(items) => {
+                let len = items.len();
+                array_index(&token, len)
+                    .and_then(|i| items.get_mut(i))
+                    .ok_or_else(|| {
+                        Error::InvalidConfig(format!(
+                            "'{token}' in '{pointer}' is not an index into an array of {len}"
+                        ))
+                    })?
+            }
+            _ => {
+                return Err(Error::InvalidConfig(format!(
+                    "parent of '{token}' in '{pointer}' is not an object or array"
+                )));
+            }
+        };
+    }
+    Ok(current)
+}
+
+/// Parses an RFC 6901 array index into an array of `len` elements.
+///
+/// `-` names the element after the last one, `len`. Indices with leading
+/// zeros or signs are rejected; range checks are left to the caller.
+fn array_index(token: &str, len: usize) -> Option<usize> {
+    if token == "-" {
+        Some(len)
+    } else if token == "0" || (!token.starts_with('0') && token.bytes().all(|b| b.is_ascii_digit()))
+    {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pointer_tokens_keeps_dotted_keys_whole() {
+        assert_eq!(
+            pointer_tokens("/amp.mcpServers").unwrap(),
+            vec!["amp.mcpServers"]
+        );
+    }
+
+    #[test]
+    fn pointer_tokens_unescapes_rfc6901() {
+        assert_eq!(pointer_tokens("/a~1b/c~0d").unwrap(), vec!["a/b", "c~d"]);
+        assert_eq!(pointer_tokens("/~01").unwrap(), vec!["~1"]);
+    }
+
+    #[test]
+    fn pointer_tokens_empty_is_root() {
+        assert!(pointer_tokens("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn pointer_tokens_rejects_relative() {
+        assert!(pointer_tokens("mcpServers").is_err());
+    }
+
+    #[test]
+    fn split_pointer_returns_escaped_parent() {
+        assert_eq!(
+            split_pointer("/a~1b/c~0d").unwrap(),
+            ("/a~1b", "c~d".to_string())
+        );
+        assert_eq!(split_pointer("/x").unwrap(), ("", "x".to_string()));
+        assert!(split_pointer("").is_err());
+        assert!(split_pointer("x").is_err());
+    }
+
+    #[test]
+    fn get_indexes_arrays() {
+        let doc = Document::parse("{\"a\": [1, {\"b\": 2}]}".into(), FileFormat::Json).unwrap();
+        assert_eq!(doc.get("/a/1/b").unwrap(), Some(&json!(2)));
+        assert_eq!(doc.get("/a/2").unwrap(), None);
+        assert_eq!(doc.get("/a/x").unwrap(), None);
+    }
+
+    #[test]
+    fn position_points_at_member_key() {
+        let text = "{\n  \"mcp\": {\n    \"dev\": [1, 2]\n  }\n}\n";
+        let doc = Document::parse(text.to_string(), FileFormat::Json).unwrap();
+        assert_eq!(doc.position(""), Some((1, 1)));
+        assert_eq!(doc.position("/mcp/dev"), Some((3, 5)));
+        assert_eq!(doc.position("/mcp/dev/1"), Some((3, 16)));
+        assert_eq!(doc.position("/mcp/missing"), None);
+
+        let yaml = Document::parse("mcp: {}\n".to_string(), FileFormat::Yaml).unwrap();
+        assert_eq!(yaml.position("/mcp"), None);
+    }
+
+    #[test]
+    fn parse_value_empty_is_object() {
+        assert_eq!(parse_value("", FileFormat::Json).unwrap(), json!({}));
+        assert_eq!(parse_value("\n", FileFormat::Yaml).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn parse_value_yaml() {
+        let value =
+            parse_value("extensions:\n  dev:\n    type: stdio\n", FileFormat::Yaml).unwrap();
+        assert_eq!(value, json!({"extensions": {"dev": {"type": "stdio"}}}));
+    }
+
+    #[test]
+    fn container_at_mut_creates_missing_objects() {
+        let mut value = json!({"other": 1});
+        container_at_mut(&mut value, "/a/b")
+            .unwrap()
+            .as_object_mut()
+            .unwrap()
+            .insert("k".into(), json!(true));
+        assert_eq!(value, json!({"other": 1, "a": {"b": {"k": true}}}));
+    }
+
+    #[test]
+    fn container_at_mut_rejects_scalar_parent() {
+        let mut value = json!({"mcp": "nope"});
+        assert!(container_at_mut(&mut value, "/mcp/dev").is_err());
+    }
+
+    #[test]
+    fn container_at_mut_follows_existing_array_elements() {
+        let mut value = json!({"list": [{"a": 1}]});
+        assert_eq!(
+            container_at_mut(&mut value, "/list/0").unwrap(),
+            &json!({"a": 1})
+        );
+        assert!(container_at_mut(&mut value, "/list/1").is_err());
+        assert!(container_at_mut(&mut value, "/list/-").is_err());
+    }
+
+    #[test]
+    fn array_index_follows_rfc6901() {
+        assert_eq!(array_index("0", 2), Some(0));
+        assert_eq!(array_index("10", 2), Some(10));
+        assert_eq!(array_index("-", 2), Some(2));
+        assert_eq!(array_index("01", 2), None);
+        assert_eq!(array_index("+1", 2), None);
+        assert_eq!(array_index("-1", 2), None);
+        assert_eq!(array_index("", 2), None);
+    }
+
+    #[test]
+    fn read_missing_file_is_empty_object() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = Document::read(&dir.path().join("missing.json"), FileFormat::Json).unwrap();
+        assert_eq!(doc.value, json!({}));
+        assert_eq!(doc.as_str(), "");
+    }
+
+    #[test]
+    fn insert_into_empty_document_serializes_model() {
+        let mut doc = Document::parse(String::new(), FileFormat::Json).unwrap();
+        doc.insert("/mcpServers", "dev", json!({"command": "node"}))
+            .unwrap();
+        assert_eq!(
+            doc.as_str(),
+            "{\n  \"mcpServers\": {\n    \"dev\": {\n      \"command\": \"node\"\n    }\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn insert_jsonc_keeps_comments() {
+        let text = "{\n  // my servers\n  \"mcp\": {}, // none yet\n}\n";
+        let mut doc = Document::parse(text.to_string(), FileFormat::Jsonc).unwrap();
+        doc.insert("/mcp", "dev", json!(true)).unwrap();
+        assert_eq!(
+            doc.as_str(),
+            "{\n  // my servers\n  \"mcp\": {\n    \"dev\": true\n  }, // none yet\n}\n"
+        );
+        assert_eq!(doc.value, json!({"mcp": {"dev": true}}));
+    }
+
+    #[test]
+    fn insert_detects_indentation() {
+        let text = "{\n    \"a\": {\n        \"b\": 1\n    }\n}\n";
+        let mut doc = Document::parse(text.to_string(), FileFormat::Json).unwrap();
+        doc.insert("/a", "c", json!([1])).unwrap();
+        assert_eq!(
+            doc.as_str(),
+            "{\n    \"a\": {\n        \"b\": 1,\n        \"c\": [\n            1\n        ]\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn insert_replaces_null_parent() {
+        let mut doc = Document::parse("{\"mcp\": null}".to_string(), FileFormat::Json).unwrap();
+        doc.insert("/mcp", "dev", json!(1)).unwrap();
+        assert_eq!(doc.value, json!({"mcp": {"dev": 1}}));
+        assert_eq!(
+            parse_value(doc.as_str(), FileFormat::Json).unwrap(),
+            doc.value
+        );
+    }
+
+    #[test]
+    fn insert_yaml_flow_mapping_requires_reformat() {
+        let text = "# comment\nextensions: {dev: {cmd: a}}\n";
+        let mut doc = Document::parse(text.to_string(), FileFormat::Yaml).unwrap();
+        assert!(matches!(
+            doc.insert("/extensions", "x", json!({"cmd": "b"})),
+            Err(Error::LossyEdit { .. })
+        ));
+        assert_eq!(doc.as_str(), text);
+
+        doc.allow_reformat(true);
+        doc.insert("/extensions", "x", json!({"cmd": "b"})).unwrap();
+        let value = parse_value(doc.as_str(), FileFormat::Yaml).unwrap();
+        assert_eq!(
+            value,
+            json!({"extensions": {"dev": {"cmd": "a"}, "x": {"cmd": "b"}}})
+        );
+    }
+
+    #[test]
+    fn insert_reformats_text_that_is_already_serialized() {
+        let text = "servers:\n- cmd: a\n";
+        let mut doc = Document::parse(text.to_string(), FileFormat::Yaml).unwrap();
+        doc.insert("/servers/0", "cmd", json!("b")).unwrap();
+        assert_eq!(doc.as_str(), "servers:\n- cmd: b\n");
+    }
+
+    #[test]
+    fn lossy_edit_pointer_is_escaped() {
+        let text = "# comment\n\"a/b\": {x: 1}\n";
+        let mut doc = Document::parse(text.to_string(), FileFormat::Yaml).unwrap();
+        assert!(matches!(
+            doc.insert("/a~1b", "y", json!(2)),
+            Err(Error::LossyEdit { pointer }) if pointer == "/a~1b/y"
+        ));
+    }
+
+    #[test]
+    fn remove_returns_removed_value() {
+        let text = "{\n  \"mcp\": {\n    \"a\": 1,\n    \"b\": 2\n  }\n}\n";
+        let mut doc = Document::parse(text.to_string(), FileFormat::Json).unwrap();
+        assert_eq!(doc.remove("/mcp", "a").unwrap(), Some(json!(1)));
+        assert_eq!(doc.remove("/mcp", "a").unwrap(), None);
+        assert_eq!(doc.as_str(), "{\n  \"mcp\": {\n    \"b\": 2\n  }\n}\n");
+    }
+
+    #[test]
+    fn remove_keeps_crlf_line_endings() {
+        let text = "extensions:\r\n  a: 1\r\n  b: 2\r\n";
+        let mut doc = Document::parse(text.to_string(), FileFormat::Yaml).unwrap();
+        doc.remove("/extensions", "a").unwrap();
+        assert_eq!(doc.as_str(), "extensions:\r\n  b: 2\r\n");
+    }
+
+    #[test]
+    fn write_atomic_creates_parent_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/dir/config.json");
+        write_atomic(&path, "{}\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}\n");
+        let leftovers: Vec<_> = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().contains(".tmp-"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_writes_through_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("dotfiles/config.json");
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        fs::write(&target, "{}\n").unwrap();
+        let link = dir.path().join("config.json");
+        std::os::unix::fs::symlink("dotfiles/config.json", &link).unwrap();
+
+        write_atomic(&link, "{\"a\": 1}\n").unwrap();
+
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&target).unwrap(), "{\"a\": 1}\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_keeps_private_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, "{}\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_atomic(&path, "{\"token\": \"secret\"}\n").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_creates_owner_only_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.yaml");
+        write_private(&path, "token: secret\n").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // An existing file keeps the permissions it has
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+        write_private(&path, "token: other\n").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_rejects_symlink_loops() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::os::unix::fs::symlink("config.json", &path).unwrap();
+
+        assert!(write_atomic(&path, "{}\n").is_err());
+    }
+}
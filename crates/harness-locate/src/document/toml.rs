@@ -0,0 +1,174 @@
+//! Lossless editing of TOML text.
+//!
+//! Edits go through [`toml_edit`], which keeps comments, whitespace and
+//! key order of everything outside the edited entry. Objects inserted
+//! directly under a standard table become `[a.b]` tables; objects nested
+//! deeper are written as inline tables. Paths that run through inline
+//! tables or arrays of tables are not edited in place; the caller falls
+//! back to re-serializing the document.
+
+use serde_json::Value;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table};
+
+use crate::error::{Error, Result};
+
+/// Parses TOML text into a JSON value.
+pub(super) fn parse(src: &str) -> Result<Value> {
+    toml::from_str(src).map_err(|e| Error::InvalidConfig(format!("TOML parse error: {e}")))
+}
+
+/// Serializes a JSON value as TOML.
+pub(super) fn to_string(value: &Value) -> Result<String> {
+    toml::to_string_pretty(value)
+        .map_err(|e| Error::InvalidConfig(format!("cannot serialize as TOML: {e}")))
+}
+
+/// Sets the value at `path`, creating missing parent tables.
+///
+/// Returns `None` if the edit cannot be made in place.
+pub(super) fn set(src: &str, path: &[String], value: &Value) -> Option<String> {
+    let mut doc: DocumentMut = src.parse().ok()?;
+    let (key, parents) = path.split_last()?;
+    let table = table_at(doc.as_table_mut(), parents, true)?;
+    let item = match value {
+        Value::Object(map) => {
+            let mut new = Table::new();
+            for (k, v) in map {
+                new.insert(k, Item::Value(to_value(v)?));
+            }
+            Item::Table(new)
+        }
+        other => Item::Value(to_value(other)?),
+    };
+    table.insert(key, item);
+    Some(doc.to_string())
+}
+
+/// Removes the entry at `path`.
+///
+/// A parent table left without entries keeps its `[header]` so the
+/// document still describes an empty table.
+pub(super) fn remove(src: &str, path: &[String]) -> Option<String> {
+    let mut doc: DocumentMut = src.parse().ok()?;
+    let (key, parents) = path.split_last()?;
+    let table = table_at(doc.as_table_mut(), parents, false)?;
+    table.remove(key)?;
+    if table.is_empty() {
+        table.set_implicit(false);
+    }
+    Some(doc.to_string())
+}
+
+/// Walks `path` through standard tables, optionally creating missing ones.
+fn table_at<'a>(mut table: &'a mut Table, path: &[String], create: bool) -> Option<&'a mut Table> {
+    for token in path {
+        if !table.contains_key(token) {
+            if !create {
+                return None;
+            }
+            let mut new = Table::new();
+            new.set_implicit(true);
+            table.insert(token, Item::Table(new));
+        }
+        table = table.get_mut(token)?.as_table_mut()?;
+    }
+    Some(table)
+}
+
+/// Converts a JSON value to an inline TOML value. TOML has no null, so
+/// nulls cannot be converted.
+fn to_value(value: &Value) -> Option<toml_edit::Value> {
+    Some(match value {
+        Value::Null => return None,
+        Value::Bool(b) => (*b).into(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into(),
+            None => n.as_f64()?.into(),
+        },
+        Value::String(s) => s.as_str().into(),
+        Value::Array(items) => {
+            let mut array = Array::new();
+            for item in items {
+                array.push(to_value(item)?);
+            }
+            array.into()
+        }
+        Value::Object(map) => {
+            let mut table = InlineTable::new();
+            for (k, v) in map {
+                table.insert(k, to_value(v)?);
+            }
+            table.into()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn path(tokens: &[&str]) -> Vec<String> {
+        tokens.iter().map(|t| t.to_string()).collect()
+    }
+
+    const CODEX: &str = "\
+# Codex configuration
+model = \"o3\" # pinned
+
+[mcp_servers.docs]
+command = \"docs-server\"
+args = [\"--port\", \"4000\"]
+";
+
+    #[test]
+    fn set_appends_table() {
+        let out = set(
+            CODEX,
+            &path(&["mcp_servers", "fetch"]),
+            &json!({"command": "uvx", "env": {"A": "1"}}),
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            format!("{CODEX}\n[mcp_servers.fetch]\ncommand = \"uvx\"\nenv = {{ A = \"1\" }}\n")
+        );
+    }
+
+    #[test]
+    fn set_replaces_table_keeping_comments() {
+        let out = set(
+            CODEX,
+            &path(&["mcp_servers", "docs"]),
+            &json!({"command": "docs"}),
+        )
+        .unwrap();
+        assert!(out.starts_with("# Codex configuration\nmodel = \"o3\" # pinned\n"));
+        assert_eq!(
+            parse(&out).unwrap(),
+            json!({"model": "o3", "mcp_servers": {"docs": {"command": "docs"}}})
+        );
+    }
+
+    #[test]
+    fn set_refuses_null_and_inline_parents() {
+        assert!(set("", &path(&["a"]), &json!(null)).is_none());
+        assert!(set("servers = { a = 1 }\n", &path(&["servers", "b"]), &json!(2)).is_none());
+    }
+
+    #[test]
+    fn remove_keeps_empty_parent_header() {
+        let out = remove(CODEX, &path(&["mcp_servers", "docs"])).unwrap();
+        assert_eq!(
+            out,
+            "# Codex configuration\nmodel = \"o3\" # pinned\n\n[mcp_servers]\n"
+        );
+        assert_eq!(parse(&out).unwrap()["mcp_servers"], json!({}));
+    }
+
+    #[test]
+    fn to_string_round_trips() {
+        let value = json!({"model": "o3", "mcp_servers": {"a": {"args": ["x"]}}});
+        assert_eq!(parse(&to_string(&value).unwrap()).unwrap(), value);
+    }
+}
@@ -0,0 +1,388 @@
+//! Lossless editing of block-style YAML text.
+//!
+//! Mapping entries are located by indentation and replaced, inserted or
+//! removed as whole lines, so comments and formatting outside the edited
+//! entry are left untouched. Paths that run through flow collections,
+//! sequences, anchors or multi-document streams are not edited in place;
+//! the caller reports them as lossy edits.
+
+use serde_json::{Map, Value};
+
+use super::Style;
+
+/// A `key: value` entry of a block mapping.
+#[derive(Debug)]
+struct Entry {
+    key: String,
+    /// The key as written, including any quotes.
+    key_text: String,
+    /// Index of the line holding the key.
+    line: usize,
+    /// One past the last content line of the entry's value.
+    end: usize,
+    indent: usize,
+    inline: Inline,
+}
+
+/// What follows the colon on an entry's first line.
+#[derive(Debug, PartialEq)]
+enum Inline {
+    /// Nothing: the value is a nested block or null.
+    None,
+    /// An explicitly empty value such as `{}`, `~` or `null`.
+    Empty,
+    /// Any other inline value.
+    Value,
+}
+
+/// A block mapping: the lines it spans and the indentation of its keys.
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    start: usize,
+    end: usize,
+    indent: Option<usize>,
+}
+
+struct Doc {
+    lines: Vec<String>,
+    style: Style,
+}
+
+impl Doc {
+    fn parse(src: &str, style: &Style) -> Option<Self> {
+        let mut lines: Vec<String> = src.split_inclusive('\n').map(str::to_string).collect();
+        if let Some(last) = lines.last_mut().filter(|l| !l.ends_with('\n')) {
+            last.push_str(style.newline);
+        }
+        let doc = Self {
+            lines,
+            style: style.clone(),
+        };
+        // Directives and document markers mean a multi-document stream
+        let markers = doc
+            .lines
+            .iter()
+            .any(|l| l.starts_with("---") || l.starts_with("...") || l.starts_with('%'));
+        (!markers).then_some(doc)
+    }
+
+    fn content(&self, index: usize) -> Option<&str> {
+        let text = self.lines[index].trim_end_matches(['\r', '\n']);
+        let trimmed = text.trim_start_matches(' ');
+        (!trimmed.is_empty() && !trimmed.starts_with('#')).then_some(trimmed)
+    }
+
+    fn indent(&self, index: usize) -> usize {
+        let line = &self.lines[index];
+        line.len() - line.trim_start_matches(' ').len()
+    }
+
+    fn root(&self) -> Block {
+        let indent = (0..self.lines.len())
+            .find(|&i| self.content(i).is_some())
+            .map(|i| self.indent(i));
+        Block {
+            start: 0,
+            end: self.lines.len(),
+            indent,
+        }
+    }
+
+    /// Splits a block into its entries.
+    fn entries(&self, block: Block) -> Option<Vec<Entry>> {
+        let Some(indent) = block.indent else {
+            return Some(Vec::new());
+        };
+        let mut entries: Vec<Entry> = Vec::new();
+        for i in block.start..block.end {
+            let Some(content) = self.content(i) else {
+                continue;
+            };
+            let line_indent = self.indent(i);
+            let compact_item = line_indent == indent && content.starts_with('-');
+            if line_indent > indent || (compact_item && !entries.is_empty()) {
+                // Nested block, or a sequence written flush with its key
+                entries.last_mut()?.end = i + 1;
+                continue;
+            }
+            if line_indent < indent {
+                return None;
+            }
+            let (key, key_text, rest) = parse_key(content)?;
+            let inline = match rest.split(" #").next().unwrap_or_default().trim() {
+                "" => Inline::None,
+                "{}" | "~" | "null" => Inline::Empty,
+                _ => Inline::Value,
+            };
+            entries.push(Entry {
+                key,
+                key_text,
+                line: i,
+                end: i + 1,
+                indent,
+                inline,
+            });
+        }
+        Some(entries)
+    }
+
+    /// Returns the block mapping that is the value of `entry`.
+    fn child(&mut self, entry: &Entry) -> Option<Block> {
+        match entry.inline {
+            Inline::None => {}
+            Inline::Empty => {
+                let indent = " ".repeat(entry.indent);
+                self.lines[entry.line] =
+                    format!("{indent}{}:{}", entry.key_text, self.style.newline);
+            }
+            Inline::Value => return None,
+        }
+        let start = entry.line + 1;
+        let indent = (start..entry.end)
+            .find(|&i| self.content(i).is_some())
+            .map_or(entry.indent + self.style.indent.len(), |i| self.indent(i));
+        (indent > entry.indent).then_some(Block {
+            start,
+            end: entry.end,
+            indent: Some(indent),
+        })
+    }
+
+    /// Renders `key: value` as lines indented by `indent` spaces.
+    fn render(&self, key: &str, value: &Value, indent: usize) -> Option<Vec<String>> {
+        let mut map = Map::new();
+        map.insert(key.to_string(), value.clone());
+        let yaml = serde_yaml::to_string(&map).ok()?;
+        let prefix = " ".repeat(indent);
+        Some(
+            yaml.lines()
+                .map(|line| format!("{prefix}{line}{}", self.style.newline))
+                .collect(),
+        )
+    }
+
+    fn into_string(self) -> String {
+        self.lines.concat()
+    }
+}
+
+/// Splits `key: rest` into the unquoted key, the key as written and the
+/// text after the colon.
+fn parse_key(content: &str) -> Option<(String, String, &str)> {
+    let key_len = match content.as_bytes().first()? {
+        b'"' => {
+            let bytes = content.as_bytes();
+            let mut i = 1;
+            while *bytes.get(i)? != b'"' {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            i + 1
+        }
+        b'\'' => {
+            let mut i = 1;
+            loop {
+                i += content[i..].find('\'')?;
+                if content[i + 1..].starts_with('\'') {
+                    i += 2;
+                } else {
+                    break i + 1;
+                }
+            }
+        }
+        b'-' | b'?' | b'&' | b'*' | b'!' | b'{' | b'[' | b'|' | b'>' | b'#' => return None,
+        _ => content
+            .find(": ")
+            .or_else(|| content.strip_suffix(':').map(str::len))?,
+    };
+    let key_text = content[..key_len].trim_end();
+    let rest = content[key_len..].trim_start().strip_prefix(':')?;
+    if !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let key = if key_text.starts_with(['"', '\'']) {
+        serde_yaml::from_str(key_text).ok()?
+    } else {
+        key_text.to_string()
+    };
+    Some((key, key_text.to_string(), rest))
+}
+
+/// Sets the value at `path`, creating missing parent mappings.
+///
+/// Returns `None` if the edit cannot be made in place.
+pub(super) fn set(src: &str, path: &[String], value: &Value, style: &Style) -> Option<String> {
+    let mut doc = Doc::parse(src, style)?;
+    let mut block = doc.root();
+    for (i, token) in path.iter().enumerate() {
+        let entries = doc.entries(block)?;
+        match entries.iter().rev().find(|e| &e.key == token) {
+            Some(entry) if i + 1 == path.len() => {
+                let lines = doc.render(token, value, entry.indent)?;
+                doc.lines.splice(entry.line..entry.end, lines);
+                return Some(doc.into_string());
+            }
+            Some(entry) => block = doc.child(entry)?,
+            None => {
+                let nested = path[i + 1..].iter().rev().fold(value.clone(), |inner, t| {
+                    let mut map = Map::new();
+                    map.insert(t.clone(), inner);
+                    Value::Object(map)
+                });
+                let at = entries.last().map_or(block.end, |e| e.end);
+                let lines = doc.render(token, &nested, block.indent.unwrap_or(0))?;
+                doc.lines.splice(at..at, lines);
+                return Some(doc.into_string());
+            }
+        }
+    }
+    None
+}
+
+/// Removes the entry at `path` and every line of its value.
+///
+/// A parent mapping left without entries is rewritten as `key: {}` so the
+/// document still describes an empty mapping rather than null.
+pub(super) fn remove(src: &str, path: &[String], style: &Style) -> Option<String> {
+    let mut doc = Doc::parse(src, style)?;
+    let (key, parents) = path.split_last()?;
+    let mut block = doc.root();
+    let mut parent = None;
+    for token in parents {
+        let entries = doc.entries(block)?;
+        let entry = entries.into_iter().rev().find(|e| &e.key == token)?;
+        block = doc.child(&entry)?;
+        parent = Some(entry);
+    }
+    let entries = doc.entries(block)?;
+    let entry = entries.iter().rev().find(|e| &e.key == key)?;
+    doc.lines.drain(entry.line..entry.end);
+    if let (1, Some(parent)) = (entries.len(), parent) {
+        let indent = " ".repeat(parent.indent);
+        doc.lines[parent.line] = format!("{indent}{}: {{}}{}", parent.key_text, doc.style.newline);
+    }
+    Some(doc.into_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn style() -> Style {
+        Style {
+            indent: "  ".to_string(),
+            newline: "\n",
+        }
+    }
+
+    fn path(tokens: &[&str]) -> Vec<String> {
+        tokens.iter().map(|t| t.to_string()).collect()
+    }
+
+    const GOOSE: &str = "\
+# Goose configuration
+GOOSE_PROVIDER: anthropic
+extensions:
+  # built in
+  developer:
+    enabled: true
+    type: builtin
+  memory:
+    args:
+    - server.js
+    cmd: node
+
+GOOSE_MODEL: claude # pinned
+";
+
+    #[test]
+    fn set_appends_entry_after_last_sibling() {
+        let out = set(
+            GOOSE,
+            &path(&["extensions", "fetch"]),
+            &json!({"cmd": "uvx"}),
+            &style(),
+        );
+        let expected = GOOSE.replace("    cmd: node\n", "    cmd: node\n  fetch:\n    cmd: uvx\n");
+        assert_eq!(out.unwrap(), expected);
+    }
+
+    #[test]
+    fn set_replaces_entry_in_place() {
+        let out = set(
+            GOOSE,
+            &path(&["extensions", "developer"]),
+            &json!({"enabled": false}),
+            &style(),
+        );
+        let expected = GOOSE.replace(
+            "  developer:\n    enabled: true\n    type: builtin\n",
+            "  developer:\n    enabled: false\n",
+        );
+        assert_eq!(out.unwrap(), expected);
+    }
+
+    #[test]
+    fn set_creates_missing_parent() {
+        let src = "GOOSE_PROVIDER: openai\n";
+        let out = set(
+            src,
+            &path(&["extensions", "dev"]),
+            &json!({"cmd": "x"}),
+            &style(),
+        );
+        assert_eq!(
+            out.unwrap(),
+            "GOOSE_PROVIDER: openai\nextensions:\n  dev:\n    cmd: x\n"
+        );
+    }
+
+    #[test]
+    fn set_expands_empty_flow_mapping() {
+        let src = "extensions: {}\nother: 1\n";
+        let out = set(src, &path(&["extensions", "dev"]), &json!(1), &style());
+        assert_eq!(out.unwrap(), "extensions:\n  dev: 1\nother: 1\n");
+    }
+
+    #[test]
+    fn set_handles_quoted_keys() {
+        let src = "\"extensions\":\n  'my ''ext''': 1\n";
+        let out = set(src, &path(&["extensions", "my 'ext'"]), &json!(2), &style());
+        assert_eq!(out.unwrap(), "\"extensions\":\n  my 'ext': 2\n");
+    }
+
+    #[test]
+    fn set_refuses_flow_collections() {
+        let src = "extensions: {dev: 1}\n";
+        assert!(set(src, &path(&["extensions", "x"]), &json!(1), &style()).is_none());
+    }
+
+    #[test]
+    fn remove_drops_entry_lines() {
+        let out = remove(GOOSE, &path(&["extensions", "memory"]), &style()).unwrap();
+        let expected = GOOSE.replace("  memory:\n    args:\n    - server.js\n    cmd: node\n", "");
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn remove_last_child_leaves_empty_mapping() {
+        let src = "extensions:\n  dev:\n    cmd: x\nother: 1\n";
+        let out = remove(src, &path(&["extensions", "dev"]), &style()).unwrap();
+        assert_eq!(out, "extensions: {}\nother: 1\n");
+    }
+
+    #[test]
+    fn parse_key_forms() {
+        assert_eq!(
+            parse_key("name: value"),
+            Some(("name".into(), "name".into(), " value"))
+        );
+        assert_eq!(parse_key("name:"), Some(("name".into(), "name".into(), "")));
+        assert_eq!(
+            parse_key("\"a: b\": 1"),
+            Some(("a: b".into(), "\"a: b\"".into(), " 1"))
+        );
+        assert_eq!(parse_key("- item"), None);
+        assert_eq!(parse_key("url:http"), None);
+    }
+}
@@ -43,10 +43,41 @@ pub enum Error {
     #[error("{harness} does not support {scope} scope")]
     UnsupportedScope { harness: String, scope: String },
 
+    /// The requested scope cannot be written to.
+    #[error("{scope} scope of {harness} is read-only")]
+    ReadOnlyScope { harness: String, scope: String },
+
     /// YAML parsing failed.
     #[error("YAML parse error: {0}")]
     YamlParse(#[from] serde_yaml::Error),
 
+    /// JSON parsing or serialization failed.
+    #[error("JSON parse error: {0}")]
+    JsonParse(#[from] serde_json::Error),
+
+    /// A configuration file does not have the expected structure.
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    /// A file changed between planning and applying a [`ChangePlan`], or
+    /// two merged plans edit the same file from the same content.
+    ///
+    /// [`ChangePlan`]: crate::plan::ChangePlan
+    #[error("file changed since the plan was created: {0}")]
+    Conflict(PathBuf),
+
+    /// An edit could not be spliced into the file text, and making it would
+    /// re-serialize the whole file, dropping its comments and formatting.
+    #[error("editing {pointer} would reformat the whole file")]
+    LossyEdit {
+        /// The JSON pointer of the edited entry.
+        pointer: String,
+    },
+
+    /// A change journal entry is missing or cannot be rolled back.
+    #[error("journal error: {0}")]
+    Journal(String),
+
     /// A required field is missing from the input.
     #[error("missing required field: {0}")]
     MissingField(String),
@@ -0,0 +1,721 @@
+//! The [`HarnessAdapter`] trait behind every [`Harness`].
+//!
+//! An adapter knows where one harness keeps its resources and how it
+//! stores MCP servers. Each built-in harness module provides one (for
+//! example [`ClaudeCodeAdapter`](super::claude_code::ClaudeCodeAdapter)),
+//! harnesses described by a [`HarnessDefinition`] use
+//! [`CustomAdapter`](super::custom::CustomAdapter), and other crates can
+//! implement the trait and register their adapter with
+//! [`HarnessRegistry::register_adapter`].
+//!
+//! [`Harness`]: crate::Harness
+//! [`HarnessDefinition`]: crate::registry::HarnessDefinition
+//! [`HarnessRegistry::register_adapter`]: crate::registry::HarnessRegistry::register_adapter
+//!
+//! # Example
+//!
+//! ```
+//! use std::path::PathBuf;
+//! use std::sync::Arc;
+//!
+//! use harness_locate::harness::HarnessAdapter;
+//! use harness_locate::registry::HarnessRegistry;
+//! use harness_locate::{Harness, HarnessKind, Result, Scope};
+//!
+//! struct Acme;
+//!
+//! impl HarnessAdapter for Acme {
+//!     fn kind(&self) -> HarnessKind {
+//!         HarnessKind::Custom("adapter-doc-acme")
+//!     }
+//!
+//!     fn name(&self) -> &str {
+//!         "Acme Agent"
+//!     }
+//!
+//!     fn is_installed(&self) -> bool {
+//!         false
+//!     }
+//!
+//!     fn config_dir(&self, _scope: &Scope) -> Result<PathBuf> {
+//!         Ok(PathBuf::from("/opt/acme"))
+//!     }
+//! }
+//!
+//! let kind = HarnessRegistry::global().register_adapter(Arc::new(Acme))?;
+//! let harness = Harness::new(kind);
+//! assert_eq!(harness.kind().to_string(), "Acme Agent");
+//! assert_eq!(harness.config(&Scope::Global)?, PathBuf::from("/opt/acme"));
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::mcp::{McpCapabilities, McpServer};
+use crate::registry::HarnessRegistry;
+use crate::types::{
+    DirectoryResource, DirectoryStructure, EnvSyntax, FileFormat, HarnessKind, Scope,
+};
+use crate::validation::{AgentCapabilities, SkillCapabilities, ValidationIssue};
+
+use super::{
+    amp_code, claude_code, cline, codex, copilot_cli, crush, cursor, custom, droid, gemini_cli,
+    goose, opencode, roo_code, vscode, zed,
+};
+
+/// Paths, MCP conversion and capabilities of one harness.
+///
+/// Only [`kind`](Self::kind), [`is_installed`](Self::is_installed) and
+/// [`config_dir`](Self::config_dir) are required. The other methods
+/// default to "not supported": no resource directories, no MCP config and
+/// no extra validation issues. Capabilities and the environment variable
+/// syntax default to the crate's tables for built-in kinds, and to none
+/// (or [`EnvSyntax::Shell`]) for custom ones.
+pub trait HarnessAdapter: Send + Sync {
+    /// Returns the harness this adapter implements.
+    ///
+    /// Adapters registered with
+    /// [`HarnessRegistry::register_adapter`] must return
+    /// [`HarnessKind::Custom`].
+    fn kind(&self) -> HarnessKind;
+
+    /// Returns the display name of the harness.
+    fn name(&self) -> &str {
+        self.kind().as_str()
+    }
+
+    /// Returns the CLI binary names that indicate the harness is
+    /// installed.
+    ///
+    /// Read once, when the adapter is registered.
+    fn binary_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns `true` if the harness is installed on this system.
+    fn is_installed(&self) -> bool;
+
+    /// Returns the base configuration directory for `scope`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedScope`] for scopes the harness does not
+    /// have, or an error if the directory cannot be determined.
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf>;
+
+    /// Returns the skills directory for `scope`, or `None` if the harness
+    /// has no skills.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedScope`] for local and managed scopes the
+    /// harness has no skills directory for, or an error if the directory
+    /// cannot be determined.
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        missing_resource(self.kind(), scope)
+    }
+
+    /// Returns the commands directory for `scope`, or `None` if the harness
+    /// has no commands.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedScope`] for local and managed scopes the
+    /// harness has no commands directory for, or an error if the directory
+    /// cannot be determined.
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        missing_resource(self.kind(), scope)
+    }
+
+    /// Returns the agents directory for `scope`, or `None` if the harness
+    /// has no agents.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedScope`] for local and managed scopes the
+    /// harness has no agents directory for, or an error if the directory
+    /// cannot be determined.
+    fn agents(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        missing_resource(self.kind(), scope)
+    }
+
+    /// Returns the plugins directory for `scope`, or `None` if the harness
+    /// has no plugins.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedScope`] for local and managed scopes the
+    /// harness has no plugins directory for, or an error if the directory
+    /// cannot be determined.
+    fn plugins(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        missing_resource(self.kind(), scope)
+    }
+
+    /// Returns the rules directory for `scope`, or `None` if the harness
+    /// has no rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedScope`] for local and managed scopes the
+    /// harness has no rules directory for, or an error if the directory
+    /// cannot be determined.
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        missing_resource(self.kind(), scope)
+    }
+
+    /// Returns the names of the rules files the harness reads from its
+    /// rules directory for `scope`, or `None` if every file matching the
+    /// rules resource's pattern is a rules file.
+    ///
+    /// Harnesses whose rules directory is shared with other files, such as
+    /// the project root, name the files they read here.
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// Returns the JSON pointer to the object holding MCP servers in the
+    /// config file for `scope`, or `None` if the harness has no MCP
+    /// config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pointer cannot be determined.
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Returns every file the harness reads MCP servers from for `scope`,
+    /// highest precedence first. The first entry is the default file to
+    /// write to when none exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration directory cannot be
+    /// determined.
+    fn mcp_files(&self, _scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns the candidate main settings files for `scope`; an empty
+    /// list means the harness has none. Defaults to
+    /// [`mcp_files`](Self::mcp_files).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration directory cannot be
+    /// determined.
+    fn settings_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        self.mcp_files(scope)
+    }
+
+    /// Returns the URL of the JSON Schema that `file`, one of the
+    /// harness's MCP or settings files, is written against.
+    ///
+    /// See [`schema`](crate::schema) for the schemas bundled with this
+    /// crate.
+    fn schema_url(&self, _file: &Path) -> Option<&'static str> {
+        None
+    }
+
+    /// Parses one native MCP server entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMcpConfig`] if the entry is malformed.
+    fn parse_mcp_server(&self, _value: &Value) -> Result<McpServer> {
+        Err(Error::UnsupportedMcpConfig {
+            harness: self.name().to_string(),
+            reason: "MCP servers are not supported".to_string(),
+        })
+    }
+
+    /// Parses every MCP server of a whole config file.
+    ///
+    /// The default reads the object at the global
+    /// [`mcp_key_path`](Self::mcp_key_path) and parses each entry with
+    /// [`parse_mcp_server`](Self::parse_mcp_server).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMcpConfig`] if the servers object is
+    /// missing or an entry is malformed.
+    fn parse_mcp_servers(&self, config: &Value) -> Result<Vec<(String, McpServer)>> {
+        let servers = self
+            .mcp_key_path(&Scope::Global)?
+            .and_then(|key_path| config.pointer(&key_path))
+            .and_then(Value::as_object)
+            .ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: self.name().to_string(),
+                reason: "Config missing MCP servers object".to_string(),
+            })?;
+        servers
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), self.parse_mcp_server(value)?)))
+            .collect()
+    }
+
+    /// Converts a normalized server to its native entry.
+    ///
+    /// Called by [`McpServer::to_native_value`] after the server has been
+    /// checked against [`mcp_capabilities`](Self::mcp_capabilities).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMcpConfig`] if the server cannot be
+    /// expressed in the harness's format.
+    fn mcp_to_native(&self, _name: &str, _server: &McpServer) -> Result<Value> {
+        Err(Error::UnsupportedMcpConfig {
+            harness: self.name().to_string(),
+            reason: "MCP servers are not supported".to_string(),
+        })
+    }
+
+    /// Adjusts `native` before it replaces the `existing` entry of the
+    /// same name, for example to keep fields [`McpServer`] cannot express.
+    fn merge_mcp_entry(&self, _existing: &Value, _native: &mut Value) {}
+
+    /// Returns top-level keys to write to the config file alongside an
+    /// installed server, given the file's current content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extra keys cannot be built.
+    fn mcp_install_extras(
+        &self,
+        _config: &Value,
+        _server: &McpServer,
+    ) -> Result<Vec<(String, Value)>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns the MCP features of the latest release of the harness.
+    fn mcp_capabilities(&self) -> McpCapabilities {
+        match self.kind() {
+            HarnessKind::Custom(_) => McpCapabilities::default(),
+            kind => McpCapabilities::for_kind(kind),
+        }
+    }
+
+    /// Returns how the harness writes environment variable references.
+    fn env_syntax(&self) -> EnvSyntax {
+        match self.kind() {
+            HarnessKind::Custom(_) => EnvSyntax::default(),
+            kind => EnvSyntax::of(kind),
+        }
+    }
+
+    /// Returns the skill rules of the harness, or `None` without skills.
+    fn skill_capabilities(&self) -> Option<SkillCapabilities> {
+        match self.kind() {
+            HarnessKind::Custom(_) => None,
+            kind => SkillCapabilities::for_kind(kind),
+        }
+    }
+
+    /// Returns the agent rules of the harness, or `None` without agents.
+    fn agent_capabilities(&self) -> Option<AgentCapabilities> {
+        match self.kind() {
+            HarnessKind::Custom(_) => None,
+            kind => AgentCapabilities::for_kind(kind),
+        }
+    }
+
+    /// Returns harness-specific issues with `server`, reported after the
+    /// capability checks of
+    /// [`validate_for_harness`](crate::validation::validate_for_harness).
+    fn validate_mcp_server(&self, _server: &McpServer) -> Vec<ValidationIssue> {
+        Vec::new()
+    }
+
+    /// Returns harness-specific issues with a skill, reported after the
+    /// checks of
+    /// [`validate_skill_for_harness`](crate::validation::validate_skill_for_harness).
+    fn validate_skill(&self, _content: &str, _directory_name: &str) -> Vec<ValidationIssue> {
+        Vec::new()
+    }
+
+    /// Returns harness-specific issues with an agent, reported after the
+    /// checks of
+    /// [`validate_agent_for_harness`](crate::validation::validate_agent_for_harness).
+    fn validate_agent(&self, _content: &str) -> Vec<ValidationIssue> {
+        Vec::new()
+    }
+}
+
+/// Returns the adapter of `kind`.
+///
+/// Custom kinds resolve to their registered adapter. An unregistered
+/// custom kind gets a [`CustomAdapter`](custom::CustomAdapter) whose
+/// lookups fail with [`Error::NotFound`].
+#[must_use]
+pub fn for_kind(kind: HarnessKind) -> Arc<dyn HarnessAdapter> {
+    match kind {
+        HarnessKind::ClaudeCode => Arc::new(claude_code::ClaudeCodeAdapter),
+        HarnessKind::OpenCode => Arc::new(opencode::OpenCodeAdapter),
+        HarnessKind::Goose => Arc::new(goose::GooseAdapter),
+        HarnessKind::AmpCode => Arc::new(amp_code::AmpCodeAdapter),
+        HarnessKind::CopilotCli => Arc::new(copilot_cli::CopilotCliAdapter),
+        HarnessKind::Crush => Arc::new(crush::CrushAdapter),
+        HarnessKind::Droid => Arc::new(droid::DroidAdapter),
+        HarnessKind::Codex => Arc::new(codex::CodexAdapter),
+        HarnessKind::GeminiCli => Arc::new(gemini_cli::GeminiCliAdapter),
+        HarnessKind::Cursor => Arc::new(cursor::CursorAdapter),
+        HarnessKind::VsCode => Arc::new(vscode::VsCodeAdapter),
+        HarnessKind::Cline => Arc::new(cline::ClineAdapter),
+        HarnessKind::RooCode => Arc::new(roo_code::RooCodeAdapter),
+        HarnessKind::Zed => Arc::new(zed::ZedAdapter),
+        HarnessKind::Custom(id) => HarnessRegistry::global()
+            .adapter(id)
+            .unwrap_or_else(|| Arc::new(custom::CustomAdapter::new(id))),
+    }
+}
+
+/// Returns the result for a resource the harness has no directory for in
+/// `scope`.
+///
+/// The local and managed scopes only exist for some harnesses, so they are
+/// an [`Error::UnsupportedScope`]; any other scope is `Ok(None)`.
+pub(crate) fn missing_resource(
+    kind: HarnessKind,
+    scope: &Scope,
+) -> Result<Option<DirectoryResource>> {
+    let scope = match scope {
+        Scope::Local(_) => "local",
+        Scope::Managed => "managed",
+        _ => return Ok(None),
+    };
+    Err(Error::UnsupportedScope {
+        harness: kind.to_string(),
+        scope: scope.to_string(),
+    })
+}
+
+/// Builds a `*/SKILL.md` skills resource.
+///
+/// A missing directory is handled as by [`missing_resource`].
+pub(crate) fn skills_resource(
+    kind: HarnessKind,
+    scope: &Scope,
+    path: Option<PathBuf>,
+    file_format: FileFormat,
+) -> Result<Option<DirectoryResource>> {
+    let Some(path) = path else {
+        return missing_resource(kind, scope);
+    };
+    Ok(Some(DirectoryResource {
+        exists: path.exists(),
+        path,
+        structure: DirectoryStructure::Nested {
+            subdir_pattern: "*".into(),
+            file_name: "SKILL.md".into(),
+        },
+        file_format,
+    }))
+}
+
+/// Builds a resource of files matching `file_pattern` directly in `path`.
+pub(crate) fn flat_resource(
+    path: PathBuf,
+    file_pattern: &str,
+    file_format: FileFormat,
+) -> DirectoryResource {
+    DirectoryResource {
+        exists: path.exists(),
+        path,
+        structure: DirectoryStructure::Flat {
+            file_pattern: file_pattern.into(),
+        },
+        file_format,
+    }
+}
+
+/// Builds a rules resource from a harness's rules directory.
+///
+/// A missing directory is handled as by [`missing_resource`].
+pub(crate) fn rules_resource(
+    kind: HarnessKind,
+    scope: &Scope,
+    path: Option<PathBuf>,
+    file_pattern: &str,
+    file_format: FileFormat,
+) -> Result<Option<DirectoryResource>> {
+    match path {
+        Some(path) => Ok(Some(flat_resource(path, file_pattern, file_format))),
+        None => missing_resource(kind, scope),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Harness;
+    use crate::mcp::StdioMcpServer;
+    use crate::validation::Severity;
+    use serde_json::json;
+
+    /// Stores servers as `{"run": [command, args...]}` under `/tools`.
+    struct Widget;
+
+    impl HarnessAdapter for Widget {
+        fn kind(&self) -> HarnessKind {
+            HarnessKind::Custom("adapter-test-widget")
+        }
+
+        fn name(&self) -> &str {
+            "Widget"
+        }
+
+        fn binary_names(&self) -> Vec<String> {
+            vec!["widget".to_string()]
+        }
+
+        fn is_installed(&self) -> bool {
+            false
+        }
+
+        fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+            match scope {
+                Scope::Custom(path) => Ok(path.clone()),
+                _ => Err(Error::UnsupportedScope {
+                    harness: self.name().to_string(),
+                    scope: "non-custom".to_string(),
+                }),
+            }
+        }
+
+        fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+            Ok(Some("/tools".to_string()))
+        }
+
+        fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+            Ok(vec![(
+                self.config_dir(scope)?.join("widget.json"),
+                FileFormat::Json,
+            )])
+        }
+
+        fn parse_mcp_server(&self, value: &Value) -> Result<McpServer> {
+            let run: Vec<String> = serde_json::from_value(value["run"].clone())?;
+            let (command, args) = run
+                .split_first()
+                .ok_or_else(|| Error::InvalidConfig("empty run".into()))?;
+            Ok(McpServer::Stdio(StdioMcpServer {
+                command: command.clone(),
+                args: args.to_vec(),
+                env: Default::default(),
+                cwd: None,
+                enabled: true,
+                timeout_ms: None,
+                auto_approve: Vec::new(),
+            }))
+        }
+
+        fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<Value> {
+            let McpServer::Stdio(s) = server else {
+                return Err(Error::InvalidConfig("stdio only".into()));
+            };
+            let mut run = vec![s.command.clone()];
+            run.extend(s.args.iter().cloned());
+            Ok(json!({ "run": run }))
+        }
+
+        fn mcp_capabilities(&self) -> McpCapabilities {
+            McpCapabilities {
+                stdio: true,
+                ..McpCapabilities::default()
+            }
+        }
+
+        fn validate_mcp_server(&self, server: &McpServer) -> Vec<ValidationIssue> {
+            match server {
+                McpServer::Stdio(s) if s.command == "sudo" => vec![ValidationIssue::error(
+                    "command",
+                    "Widget refuses to run sudo",
+                    None,
+                )],
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    fn stdio(command: &str) -> McpServer {
+        McpServer::Stdio(StdioMcpServer {
+            command: command.to_string(),
+            args: vec!["serve".to_string()],
+            env: Default::default(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn built_in_adapters_match_their_kind() {
+        for &kind in HarnessKind::ALL {
+            assert_eq!(for_kind(kind).kind(), kind);
+            assert_eq!(for_kind(kind).name(), kind.as_str());
+        }
+    }
+
+    #[test]
+    fn unregistered_custom_kind_gets_custom_adapter() {
+        let adapter = for_kind(HarnessKind::Custom("adapter-test-missing"));
+        assert!(!adapter.is_installed());
+        assert!(adapter.config_dir(&Scope::Global).is_err());
+    }
+
+    #[test]
+    fn registered_adapter_drives_harness() {
+        let registry = HarnessRegistry::global();
+        let kind = registry.register_adapter(Arc::new(Widget)).unwrap();
+        assert_eq!(kind.to_string(), "Widget");
+        assert_eq!(kind.binary_names(), &["widget"]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        let harness = Harness::new(kind).without_journal();
+        assert_eq!(harness.adapter().name(), "Widget");
+        assert!(harness.skills(&scope).unwrap().is_none());
+
+        harness
+            .install_mcp_server(&scope, "files", &stdio("npx"))
+            .unwrap();
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("widget.json")).unwrap())
+                .unwrap();
+        assert_eq!(
+            written,
+            json!({"tools": {"files": {"run": ["npx", "serve"]}}})
+        );
+
+        let servers = harness.parse_mcp_config(&written).unwrap();
+        assert_eq!(servers["files"], stdio("npx"));
+
+        let issues = crate::validation::validate_for_harness(&stdio("sudo"), kind);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.severity == Severity::Error && i.field == "command")
+        );
+
+        assert!(registry.unregister("adapter-test-widget").is_none());
+        assert!(registry.adapter("adapter-test-widget").is_none());
+    }
+
+    type ResourceGetter = fn(&Harness, &Scope) -> Result<Option<DirectoryResource>>;
+
+    /// Asserts that every built-in harness resolves `scope` to a directory
+    /// or rejects it with [`Error::UnsupportedScope`], never `Ok(None)` or
+    /// [`Error::NotFound`].
+    fn assert_resolved_or_unsupported(resource: ResourceGetter, scope: &Scope, name: &str) {
+        for kind in HarnessKind::ALL {
+            match resource(&Harness::new(*kind), scope) {
+                Ok(Some(_)) => {}
+                Err(Error::UnsupportedScope { harness, scope }) => {
+                    assert_eq!(harness, kind.to_string());
+                    assert_eq!(scope, name);
+                }
+                other => panic!("{kind} {name}: {other:?}"),
+            }
+        }
+    }
+
+    /// Asserts that every built-in harness resolves the project and custom
+    /// scopes to a directory, `None` if it lacks the resource, or
+    /// [`Error::UnsupportedScope`], never [`Error::NotFound`].
+    fn assert_resolves_base_scopes(resource: ResourceGetter) {
+        let dir = tempfile::tempdir().unwrap();
+        for kind in HarnessKind::ALL {
+            let harness = Harness::new(*kind);
+            for scope in [
+                Scope::Project(dir.path().to_path_buf()),
+                Scope::Custom(dir.path().to_path_buf()),
+            ] {
+                match resource(&harness, &scope) {
+                    Ok(_) | Err(Error::UnsupportedScope { .. }) => {}
+                    other => panic!("{kind} {scope:?}: {other:?}"),
+                }
+            }
+        }
+    }
+
+    fn local() -> Scope {
+        Scope::Local(PathBuf::from("/project"))
+    }
+
+    #[test]
+    fn skills_resolve_base_scopes() {
+        assert_resolves_base_scopes(Harness::skills);
+    }
+
+    #[test]
+    fn skills_local_scope_is_resolved_or_unsupported() {
+        assert_resolved_or_unsupported(Harness::skills, &local(), "local");
+    }
+
+    #[test]
+    fn skills_managed_scope_is_resolved_or_unsupported() {
+        assert_resolved_or_unsupported(Harness::skills, &Scope::Managed, "managed");
+    }
+
+    #[test]
+    fn commands_resolve_base_scopes() {
+        assert_resolves_base_scopes(Harness::commands);
+    }
+
+    #[test]
+    fn commands_local_scope_is_resolved_or_unsupported() {
+        assert_resolved_or_unsupported(Harness::commands, &local(), "local");
+        assert!(matches!(
+            Harness::new(HarnessKind::Goose).commands(&local()),
+            Err(Error::UnsupportedScope { .. })
+        ));
+    }
+
+    #[test]
+    fn commands_managed_scope_is_resolved_or_unsupported() {
+        assert_resolved_or_unsupported(Harness::commands, &Scope::Managed, "managed");
+    }
+
+    #[test]
+    fn agents_resolve_base_scopes() {
+        assert_resolves_base_scopes(Harness::agents);
+    }
+
+    #[test]
+    fn agents_local_scope_is_resolved_or_unsupported() {
+        assert_resolved_or_unsupported(Harness::agents, &local(), "local");
+    }
+
+    #[test]
+    fn agents_managed_scope_is_resolved_or_unsupported() {
+        assert_resolved_or_unsupported(Harness::agents, &Scope::Managed, "managed");
+        assert!(matches!(
+            Harness::new(HarnessKind::ClaudeCode).agents(&Scope::Managed),
+            Err(Error::UnsupportedScope { .. })
+        ));
+    }
+
+    #[test]
+    fn resources_a_harness_lacks_are_none_for_base_scopes() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = Scope::Project(dir.path().to_path_buf());
+        assert!(
+            Harness::new(HarnessKind::Goose)
+                .commands(&scope)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            Harness::new(HarnessKind::Goose)
+                .agents(&scope)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
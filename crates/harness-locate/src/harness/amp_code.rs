@@ -5,14 +5,42 @@
 //! - **Project**: Not supported (AMP has no project-scoped config directory)
 //!
 //! Note: Skills are shared with Goose at `~/.config/agents/skills/`.
+//!
+//! Settings live in `amp.json` (or the older `settings.json`) as flat,
+//! dotted keys under the `amp.` namespace: `"amp.mcpServers"` is one key,
+//! not an `mcpServers` object inside `amp`. [`AmpSettings`] reads the MCP
+//! servers, disabled tools and permission rules; [`plan_set_setting`]
+//! writes a single setting. Files that nest the settings as
+//! `{"amp": {"mcpServers": ...}}` are read and written in their own shape.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
+use crate::document::Document;
 use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
 use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use crate::plan::ChangePlan;
 use crate::platform;
-use crate::types::{EnvValue, HarnessKind, Scope};
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
+
+/// Namespace of every AMP setting key.
+pub const SETTINGS_NAMESPACE: &str = "amp";
+
+/// Setting holding the MCP servers, keyed by name.
+pub const MCP_SERVERS: &str = "mcpServers";
+
+/// Setting holding the names (or globs) of disabled tools.
+pub const TOOLS_DISABLE: &str = "tools.disable";
+
+/// Setting holding the tool permission rules.
+pub const PERMISSIONS: &str = "permissions";
+
+/// Setting holding the MCP server permission rules.
+pub const MCP_PERMISSIONS: &str = "mcpPermissions";
 
 /// Returns the global AMP Code configuration directory.
 ///
@@ -41,6 +69,14 @@ pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
             scope: "project".to_string(),
         }),
         Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "AMP Code".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "AMP Code".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -53,6 +89,14 @@ pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
         Scope::Global => Ok(global_config_dir()?.join("commands")),
         Scope::Project(root) => Ok(root.join(".agents").join("commands")),
         Scope::Custom(path) => Ok(path.join("commands")),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "AMP Code".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "AMP Code".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -83,6 +127,7 @@ pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
             .map(|p| p.join("agents").join("skills")),
         Scope::Project(root) => Some(root.join(".agents").join("skills")),
         Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -97,6 +142,7 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok(),
         Scope::Project(root) => Some(root.clone()),
         Scope::Custom(path) => Some(path.clone()),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -120,8 +166,7 @@ pub fn is_installed() -> bool {
 /// # Errors
 ///
 /// Returns an error if the JSON is malformed or missing required fields.
-#[allow(dead_code)]
-pub(crate) fn parse_mcp_server(name: &str, value: &serde_json::Value) -> Result<McpServer> {
+pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
     let obj = value
         .as_object()
         .ok_or_else(|| Error::UnsupportedMcpConfig {
@@ -142,10 +187,8 @@ pub(crate) fn parse_mcp_server(name: &str, value: &serde_json::Value) -> Result<
     } else if obj.contains_key("url") && obj.contains_key("command") {
         Err(Error::UnsupportedMcpConfig {
             harness: "AMP Code".to_string(),
-            reason: format!(
-                "Server '{}' has both 'command' and 'url' fields - specify 'type' to disambiguate",
-                name
-            ),
+            reason: "Server has both 'command' and 'url' fields - specify 'type' to disambiguate"
+                .to_string(),
         })
     } else if obj.contains_key("url") {
         parse_http_server(obj)
@@ -154,15 +197,11 @@ pub(crate) fn parse_mcp_server(name: &str, value: &serde_json::Value) -> Result<
     } else {
         Err(Error::UnsupportedMcpConfig {
             harness: "AMP Code".to_string(),
-            reason: format!(
-                "Server '{}' has neither 'command' (stdio) nor 'url' (http) field",
-                name
-            ),
+            reason: "Server has neither 'command' (stdio) nor 'url' (http) field".to_string(),
         })
     }
 }
 
-#[allow(dead_code)]
 fn parse_stdio_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result<McpServer> {
     let command = obj
         .get("command")
@@ -222,10 +261,10 @@ fn parse_stdio_server(obj: &serde_json::Map<String, serde_json::Value>) -> Resul
         cwd: None,
         enabled: true,
         timeout_ms: None,
+        auto_approve: Vec::new(),
     }))
 }
 
-#[allow(dead_code)]
 fn parse_sse_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result<McpServer> {
     let url = obj
         .get("url")
@@ -261,10 +300,10 @@ fn parse_sse_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result<
         headers,
         enabled: true,
         timeout_ms: None,
+        auto_approve: Vec::new(),
     }))
 }
 
-#[allow(dead_code)]
 fn parse_http_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result<McpServer> {
     let url = obj
         .get("url")
@@ -301,6 +340,7 @@ fn parse_http_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result
         oauth: None,
         enabled: true,
         timeout_ms: None,
+        auto_approve: Vec::new(),
     }))
 }
 
@@ -311,13 +351,8 @@ fn parse_http_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result
 ///
 /// # Errors
 /// Returns an error if the JSON is malformed.
-#[allow(dead_code)]
 pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
-    // Try literal dotted key first (actual AmpCode format), then nested fallback
-    let servers_obj = config
-        .get("amp.mcpServers")
-        .or_else(|| config.get("amp").and_then(|v| v.get("mcpServers")))
-        .and_then(|v| v.as_object());
+    let servers_obj = setting(config, MCP_SERVERS).and_then(|v| v.as_object());
 
     let Some(servers_obj) = servers_obj else {
         return Ok(vec![]);
@@ -325,13 +360,309 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
 
     let mut result = Vec::new();
     for (name, value) in servers_obj {
-        let server = parse_mcp_server(name, value)?;
+        let server = parse_mcp_server(value).map_err(|e| Error::UnsupportedMcpConfig {
+            harness: "AMP Code".to_string(),
+            reason: format!("server '{}': {}", name, e),
+        })?;
         result.push((name.clone(), server));
     }
 
     Ok(result)
 }
 
+/// Returns AMP setting `key` (e.g. `"tools.disable"`) from `config`.
+///
+/// The flat `"amp.tools.disable"` key is preferred; the nested
+/// `{"amp": {"tools": {"disable": ...}}}` form is used as a fallback.
+#[must_use]
+pub fn setting<'a>(config: &'a Value, key: &str) -> Option<&'a Value> {
+    config
+        .get(format!("{SETTINGS_NAMESPACE}.{key}"))
+        .or_else(|| config.pointer(&nested_pointer(key)))
+}
+
+/// Returns the JSON pointer to AMP setting `key` in `config`.
+///
+/// This is `/amp.<key>`, a single dotted token, unless `config` keeps its
+/// settings nested under an `amp` object and has no flat entry for `key`.
+#[must_use]
+pub fn setting_pointer(config: &Value, key: &str) -> String {
+    let (parent, leaf) = setting_location(config, key);
+    format!("{parent}/{leaf}")
+}
+
+/// Returns the pointer to the object holding setting `key` and the name
+/// of its entry there.
+fn setting_location(config: &Value, key: &str) -> (String, String) {
+    let flat = format!("{SETTINGS_NAMESPACE}.{key}");
+    let nested =
+        config.get(&flat).is_none() && config.get(SETTINGS_NAMESPACE).is_some_and(Value::is_object);
+    if !nested {
+        return (String::new(), flat);
+    }
+    let pointer = nested_pointer(key);
+    let (parent, leaf) = pointer.rsplit_once('/').unwrap_or_default();
+    (parent.to_string(), leaf.to_string())
+}
+
+fn nested_pointer(key: &str) -> String {
+    format!("/{SETTINGS_NAMESPACE}/{}", key.replace('.', "/"))
+}
+
+/// Plans setting AMP setting `key` in `file` to `value`, or removing it
+/// when `value` is `None`.
+///
+/// The file is edited in place: comments, key order and every other
+/// setting are kept. Use [`Harness::settings`](crate::Harness::settings)
+/// to find the file. The plan is empty if nothing changes.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or parsed.
+pub fn plan_set_setting(file: &Path, key: &str, value: Option<Value>) -> Result<ChangePlan> {
+    let mut config = Document::read(file, FileFormat::Json)?;
+    set_setting(&mut config, key, value)?;
+    let mut plan = ChangePlan::new();
+    plan.set_file(file, Some(config.as_str().to_string()))?;
+    Ok(plan)
+}
+
+fn set_setting(config: &mut Document, key: &str, value: Option<Value>) -> Result<()> {
+    let root = config.get("")?.cloned().unwrap_or_default();
+    let (parent, leaf) = setting_location(&root, key);
+    match value {
+        // Rewriting an equal value would still reformat it
+        Some(value) if config.get(&parent)?.and_then(|o| o.get(&leaf)) == Some(&value) => Ok(()),
+        Some(value) => config.insert(&parent, &leaf, value),
+        None => config.remove(&parent, &leaf).map(drop),
+    }
+}
+
+/// What AMP does when a permission rule matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionAction {
+    /// Run without asking.
+    Allow,
+    /// Refuse to run.
+    Reject,
+    /// Ask the user first.
+    Ask,
+    /// Let the program named in [`Permission::to`] decide.
+    Delegate,
+}
+
+/// A rule in `amp.permissions`.
+///
+/// Rules are checked in order before a tool runs; the first match wins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Permission {
+    /// Tool name or glob, e.g. `"Bash"` or `"mcp__playwright_*"`.
+    pub tool: String,
+    /// Tool arguments to match, each a glob, a list of globs or a
+    /// `/regex/`.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub matches: Map<String, Value>,
+    /// What to do on a match.
+    pub action: PermissionAction,
+    /// Program that decides for [`PermissionAction::Delegate`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Restricts the rule to the main thread (`"thread"`) or to
+    /// subagents (`"subagent"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
+/// A rule in `amp.mcpPermissions`, deciding whether an MCP server may
+/// start.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpPermission {
+    /// Server fields to match (`command`, `args` or `url`), each a glob.
+    pub matches: Map<String, Value>,
+    /// What to do on a match: [`PermissionAction::Allow`] or
+    /// [`PermissionAction::Reject`].
+    pub action: PermissionAction,
+}
+
+/// The `amp.*` settings this crate understands.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::harness::amp_code::{AmpSettings, PermissionAction};
+/// use serde_json::json;
+///
+/// let settings = AmpSettings::from_value(&json!({
+///     "amp.tools.disable": ["browser_navigate"],
+///     "amp.permissions": [{"tool": "Bash", "matches": {"cmd": "git *"}, "action": "allow"}]
+/// }))?;
+/// assert_eq!(settings.disabled_tools, ["browser_navigate"]);
+/// assert_eq!(settings.permissions[0].action, PermissionAction::Allow);
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AmpSettings {
+    /// `amp.mcpServers`, keyed by server name.
+    pub mcp_servers: BTreeMap<String, McpServer>,
+    /// `amp.tools.disable`: names or globs of tools AMP must not use.
+    pub disabled_tools: Vec<String>,
+    /// `amp.permissions`, in evaluation order.
+    pub permissions: Vec<Permission>,
+    /// `amp.mcpPermissions`, in evaluation order.
+    pub mcp_permissions: Vec<McpPermission>,
+}
+
+impl AmpSettings {
+    /// Reads the settings from a parsed AMP config file.
+    ///
+    /// Missing settings are left empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a setting is present but malformed.
+    pub fn from_value(config: &Value) -> Result<Self> {
+        fn list<T: serde::de::DeserializeOwned>(config: &Value, key: &str) -> Result<Vec<T>> {
+            match setting(config, key) {
+                Some(value) => Vec::<T>::deserialize(value)
+                    .map_err(|e| Error::InvalidConfig(format!("{SETTINGS_NAMESPACE}.{key}: {e}"))),
+                None => Ok(Vec::new()),
+            }
+        }
+        Ok(Self {
+            mcp_servers: parse_mcp_servers(config)?.into_iter().collect(),
+            disabled_tools: list(config, TOOLS_DISABLE)?,
+            permissions: list(config, PERMISSIONS)?,
+            mcp_permissions: list(config, MCP_PERMISSIONS)?,
+        })
+    }
+
+    /// Reads the settings from an AMP config file.
+    ///
+    /// A missing file yields empty settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or a setting
+    /// is malformed.
+    pub fn read(file: &Path) -> Result<Self> {
+        let config = Document::read(file, FileFormat::Json)?;
+        Self::from_value(config.get("")?.unwrap_or(&Value::Null))
+    }
+
+    /// Plans writing the disabled tools and permission rules to `file`.
+    ///
+    /// Empty lists remove their setting. MCP servers are not written; use
+    /// [`Harness::plan_install_mcp_server`](crate::Harness::plan_install_mcp_server)
+    /// for them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn plan_write(&self, file: &Path) -> Result<ChangePlan> {
+        let settings = [
+            (TOOLS_DISABLE, serde_json::to_value(&self.disabled_tools)?),
+            (PERMISSIONS, serde_json::to_value(&self.permissions)?),
+            (
+                MCP_PERMISSIONS,
+                serde_json::to_value(&self.mcp_permissions)?,
+            ),
+        ];
+        let mut config = Document::read(file, FileFormat::Json)?;
+        for (key, value) in settings {
+            let value = value
+                .as_array()
+                .is_some_and(|a| !a.is_empty())
+                .then_some(value);
+            set_setting(&mut config, key, value)?;
+        }
+        let mut plan = ChangePlan::new();
+        plan.set_file(file, Some(config.as_str().to_string()))?;
+        Ok(plan)
+    }
+}
+
+/// [`HarnessAdapter`] for AMP Code.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AmpCodeAdapter;
+
+impl HarnessAdapter for AmpCodeAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::AmpCode
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(&["AGENTS.md"])
+    }
+
+    fn mcp_key_path(&self, scope: &Scope) -> Result<Option<String>> {
+        // Follow the shape of the existing file: flat dotted keys or nested
+        let config = self
+            .mcp_files(scope)?
+            .into_iter()
+            .find(|(file, _)| file.is_file())
+            .and_then(|(file, format)| Document::read(&file, format).ok())
+            .and_then(|config| config.get("").ok().flatten().cloned())
+            .unwrap_or_default();
+        Ok(Some(setting_pointer(&config, MCP_SERVERS)))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        let base = config_dir(scope)?;
+        Ok(vec![
+            (base.join("amp.json"), FileFormat::Json),
+            (base.join("settings.json"), FileFormat::Json),
+        ])
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.to_ampcode_value(self.kind())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -453,7 +784,7 @@ mod tests {
             "args": ["-y", "@modelcontextprotocol/server-filesystem"]
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_ok());
 
         if let McpServer::Stdio(server) = result.unwrap() {
@@ -480,7 +811,7 @@ mod tests {
             }
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_ok());
 
         if let McpServer::Stdio(server) = result.unwrap() {
@@ -502,7 +833,7 @@ mod tests {
             "command": "my-server"
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_ok());
 
         if let McpServer::Stdio(server) = result.unwrap() {
@@ -520,7 +851,7 @@ mod tests {
             "url": "https://example.com/sse"
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_ok());
 
         if let McpServer::Sse(server) = result.unwrap() {
@@ -544,7 +875,7 @@ mod tests {
             }
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_ok());
 
         if let McpServer::Sse(server) = result.unwrap() {
@@ -570,7 +901,7 @@ mod tests {
             "url": "https://api.example.com/mcp"
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_ok());
 
         if let McpServer::Http(server) = result.unwrap() {
@@ -594,7 +925,7 @@ mod tests {
             }
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_ok());
 
         if let McpServer::Http(server) = result.unwrap() {
@@ -615,7 +946,7 @@ mod tests {
             "args": ["server.js"]
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
 
@@ -625,7 +956,7 @@ mod tests {
             "type": "sse"
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
 
@@ -635,7 +966,7 @@ mod tests {
             "type": "http"
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
 
@@ -646,7 +977,7 @@ mod tests {
             "url": "https://example.com"
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
 
@@ -654,7 +985,7 @@ mod tests {
     fn parse_mcp_server_not_object_fails() {
         let json = json!("not an object");
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
 
@@ -775,7 +1106,7 @@ mod tests {
             }
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_ok());
 
         if let McpServer::Stdio(server) = result.unwrap() {
@@ -797,7 +1128,7 @@ mod tests {
             "args": ["-y", "server"]
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_ok());
 
         if let McpServer::Stdio(server) = result.unwrap() {
@@ -815,7 +1146,7 @@ mod tests {
             "args": ["-y", 123, "server"]
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
 
@@ -826,7 +1157,7 @@ mod tests {
             "args": "not-an-array"
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
 
@@ -837,7 +1168,7 @@ mod tests {
             "env": "not-an-object"
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
 
@@ -849,7 +1180,7 @@ mod tests {
             "headers": "not-an-object"
         });
 
-        let result = parse_mcp_server("test", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
 
@@ -860,7 +1191,7 @@ mod tests {
             "args": ["-y", "some-server"]
         });
 
-        let result = parse_mcp_server("test-stdio", &json).unwrap();
+        let result = parse_mcp_server(&json).unwrap();
         assert!(matches!(result, McpServer::Stdio(_)));
     }
 
@@ -871,7 +1202,7 @@ mod tests {
             "headers": { "Authorization": "Bearer token" }
         });
 
-        let result = parse_mcp_server("test-http", &json).unwrap();
+        let result = parse_mcp_server(&json).unwrap();
         assert!(matches!(result, McpServer::Http(_)));
     }
 
@@ -882,10 +1213,9 @@ mod tests {
             "url": "https://example.com"
         });
 
-        let result = parse_mcp_server("ambiguous", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
         let err = result.unwrap_err().to_string();
-        assert!(err.contains("ambiguous"));
         assert!(err.contains("both"));
     }
 
@@ -895,10 +1225,288 @@ mod tests {
             "env": { "FOO": "bar" }
         });
 
-        let result = parse_mcp_server("incomplete", &json);
+        let result = parse_mcp_server(&json);
         assert!(result.is_err());
         let err = result.unwrap_err().to_string();
-        assert!(err.contains("incomplete"));
         assert!(err.contains("neither"));
     }
+
+    #[test]
+    fn parse_errors_name_the_server_once() {
+        let config = json!({
+            "amp.mcpServers": { "bad": { "command": "npx", "url": "https://example.com" } }
+        });
+        let err = parse_mcp_servers(&config).unwrap_err().to_string();
+        assert!(err.contains("server 'bad': "), "{err}");
+        assert!(!err.contains("(unnamed)"), "{err}");
+
+        let harness = crate::Harness::new(HarnessKind::AmpCode);
+        let err = harness
+            .parse_mcp_server_config("bad", &config["amp.mcpServers"]["bad"])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err.matches("bad").count(), 1, "{err}");
+    }
+
+    /// A global `amp.json` as AMP writes it: flat dotted keys, two spaces.
+    const AMP_JSON: &str = r#"{
+  "amp.notifications.enabled": false,
+  "amp.dangerouslyAllowAll": false,
+  "amp.mcpServers": {
+    "playwright": {
+      "command": "npx",
+      "args": ["-y", "@playwright/mcp@latest", "--headless"]
+    },
+    "linear": {
+      "url": "https://mcp.linear.app/mcp",
+      "headers": {
+        "Authorization": "Bearer ${LINEAR_API_KEY}"
+      }
+    },
+    "sourcegraph": {
+      "command": "npx",
+      "args": ["-y", "@sourcegraph/mcp-server"],
+      "env": {
+        "SRC_ACCESS_TOKEN": "${SRC_ACCESS_TOKEN}",
+        "SRC_ENDPOINT": "https://sourcegraph.example.com"
+      }
+    }
+  },
+  "amp.tools.disable": ["browser_navigate", "mcp__playwright__browser_install"],
+  "amp.permissions": [
+    { "tool": "Bash", "matches": { "cmd": ["git status", "git diff*"] }, "action": "allow" },
+    { "tool": "Bash", "matches": { "cmd": "*git push*" }, "action": "ask" },
+    { "tool": "Bash", "matches": { "cmd": "/rm -rf/" }, "action": "reject", "context": "subagent" },
+    { "tool": "edit_file", "action": "delegate", "to": "amp-permission-helper" }
+  ],
+  "amp.mcpPermissions": [
+    { "matches": { "command": "npx", "args": "* @playwright/mcp@*" }, "action": "allow" },
+    { "matches": { "url": "https://mcp.linear.app/*" }, "action": "allow" },
+    { "matches": { "command": "*" }, "action": "reject" }
+  ]
+}
+"#;
+
+    fn write_fixture(dir: &Path, content: &str) -> PathBuf {
+        let file = dir.join("amp.json");
+        std::fs::write(&file, content).unwrap();
+        file
+    }
+
+    #[test]
+    fn settings_from_fixture() {
+        let config: Value = serde_json::from_str(AMP_JSON).unwrap();
+        let settings = AmpSettings::from_value(&config).unwrap();
+
+        assert_eq!(settings.mcp_servers.len(), 3);
+        let McpServer::Stdio(sourcegraph) = &settings.mcp_servers["sourcegraph"] else {
+            panic!("expected stdio server");
+        };
+        assert_eq!(
+            sourcegraph.env["SRC_ACCESS_TOKEN"],
+            EnvValue::env("SRC_ACCESS_TOKEN")
+        );
+        assert!(matches!(settings.mcp_servers["linear"], McpServer::Http(_)));
+
+        assert_eq!(
+            settings.disabled_tools,
+            ["browser_navigate", "mcp__playwright__browser_install"]
+        );
+
+        assert_eq!(settings.permissions.len(), 4);
+        assert_eq!(settings.permissions[0].tool, "Bash");
+        assert_eq!(
+            settings.permissions[0].matches["cmd"],
+            json!(["git status", "git diff*"])
+        );
+        assert_eq!(settings.permissions[1].action, PermissionAction::Ask);
+        assert_eq!(settings.permissions[2].context.as_deref(), Some("subagent"));
+        assert_eq!(settings.permissions[3].action, PermissionAction::Delegate);
+        assert_eq!(
+            settings.permissions[3].to.as_deref(),
+            Some("amp-permission-helper")
+        );
+
+        assert_eq!(settings.mcp_permissions.len(), 3);
+        assert_eq!(
+            settings.mcp_permissions[1].matches["url"],
+            "https://mcp.linear.app/*"
+        );
+        assert_eq!(settings.mcp_permissions[2].action, PermissionAction::Reject);
+    }
+
+    #[test]
+    fn settings_read_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = AmpSettings::read(&dir.path().join("amp.json")).unwrap();
+        assert_eq!(settings, AmpSettings::default());
+    }
+
+    #[test]
+    fn settings_reject_unknown_action() {
+        let config = json!({
+            "amp.permissions": [{ "tool": "Bash", "action": "maybe" }]
+        });
+        let err = AmpSettings::from_value(&config).unwrap_err();
+        assert!(err.to_string().contains("amp.permissions"));
+    }
+
+    #[test]
+    fn setting_pointer_follows_file_shape() {
+        let flat: Value = serde_json::from_str(AMP_JSON).unwrap();
+        assert_eq!(setting_pointer(&flat, MCP_SERVERS), "/amp.mcpServers");
+        assert_eq!(
+            setting_pointer(&json!({}), TOOLS_DISABLE),
+            "/amp.tools.disable"
+        );
+
+        let nested = json!({ "amp": { "mcpServers": {} } });
+        assert_eq!(setting_pointer(&nested, MCP_SERVERS), "/amp/mcpServers");
+        assert_eq!(
+            setting_pointer(&nested, TOOLS_DISABLE),
+            "/amp/tools/disable"
+        );
+        assert_eq!(setting(&nested, MCP_SERVERS), Some(&json!({})));
+    }
+
+    #[test]
+    fn plan_set_setting_edits_fixture_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write_fixture(dir.path(), AMP_JSON);
+
+        let plan = plan_set_setting(&file, TOOLS_DISABLE, Some(json!(["Bash"]))).unwrap();
+        plan.apply().unwrap();
+        let written = std::fs::read_to_string(&file).unwrap();
+        // Untouched settings keep their one-line layout
+        assert!(written.contains(
+            r#"{ "tool": "Bash", "matches": { "cmd": "*git push*" }, "action": "ask" },"#
+        ));
+        assert!(written.starts_with("{\n  \"amp.notifications.enabled\": false,\n"));
+        assert_eq!(AmpSettings::read(&file).unwrap().disabled_tools, ["Bash"]);
+
+        plan_set_setting(&file, MCP_PERMISSIONS, None)
+            .unwrap()
+            .apply()
+            .unwrap();
+        let config: Value = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert!(config.get("amp.mcpPermissions").is_none());
+        assert_eq!(config["amp.mcpServers"].as_object().unwrap().len(), 3);
+
+        assert!(
+            plan_set_setting(&file, MCP_PERMISSIONS, None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn plan_write_round_trips_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write_fixture(dir.path(), AMP_JSON);
+        let mut settings = AmpSettings::read(&file).unwrap();
+        assert!(settings.plan_write(&file).unwrap().is_empty());
+
+        settings.permissions.push(Permission {
+            tool: "mcp__linear__*".to_string(),
+            matches: Map::new(),
+            action: PermissionAction::Allow,
+            to: None,
+            context: None,
+        });
+        settings.disabled_tools.clear();
+        settings.plan_write(&file).unwrap().apply().unwrap();
+
+        let config: Value = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert!(config.get("amp.tools.disable").is_none());
+        assert_eq!(
+            config["amp.permissions"][4],
+            json!({ "tool": "mcp__linear__*", "action": "allow" })
+        );
+        assert_eq!(config["amp.dangerouslyAllowAll"], false);
+        assert_eq!(AmpSettings::read(&file).unwrap(), settings);
+    }
+
+    #[test]
+    fn plan_write_keeps_nested_shape() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write_fixture(
+            dir.path(),
+            r#"{ "amp": { "mcpServers": {}, "tools": { "disable": ["a"] } } }"#,
+        );
+        let mut settings = AmpSettings::read(&file).unwrap();
+        assert_eq!(settings.disabled_tools, ["a"]);
+        settings.disabled_tools.push("b".to_string());
+        settings.plan_write(&file).unwrap().apply().unwrap();
+
+        let config: Value = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(config["amp"]["tools"]["disable"], json!(["a", "b"]));
+        assert!(config.get("amp.tools.disable").is_none());
+    }
+
+    #[test]
+    fn harness_reads_fixture_with_amp_parser() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(dir.path(), AMP_JSON);
+        let harness = crate::Harness::new(HarnessKind::AmpCode);
+        let scope = Scope::Custom(dir.path().to_path_buf());
+
+        let resource = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(resource.key_path, "/amp.mcpServers");
+        let config: Value = serde_json::from_str(AMP_JSON).unwrap();
+        let servers = harness.parse_mcp_config(&config).unwrap();
+        assert_eq!(servers.len(), 3);
+
+        // The AMP parser, unlike Claude Code's, rejects ambiguous entries
+        let ambiguous = json!({ "command": "npx", "url": "https://example.com" });
+        assert!(harness.parse_mcp_server_config("both", &ambiguous).is_err());
+    }
+
+    #[test]
+    fn fixture_servers_install_back_unchanged() {
+        let config: Value = serde_json::from_str(AMP_JSON).unwrap();
+        let servers = AmpSettings::from_value(&config).unwrap().mcp_servers;
+        assert!(matches!(servers["linear"], McpServer::Http(_)));
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = write_fixture(dir.path(), "{}\n");
+        let harness = crate::Harness::new(HarnessKind::AmpCode).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        for (name, server) in &servers {
+            harness.install_mcp_server(&scope, name, server).unwrap();
+        }
+
+        let installed: Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(
+            installed["amp.mcpServers"]["linear"],
+            config["amp.mcpServers"]["linear"]
+        );
+        assert_eq!(AmpSettings::read(&file).unwrap().mcp_servers, servers);
+    }
+
+    #[test]
+    fn harness_installs_into_nested_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write_fixture(
+            dir.path(),
+            r#"{ "amp": { "mcpServers": { "old": { "command": "old" } } } }"#,
+        );
+        let harness = crate::Harness::new(HarnessKind::AmpCode).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec!["server.js".to_string()],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+        harness.install_mcp_server(&scope, "dev", &server).unwrap();
+
+        let config: Value = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(config["amp"]["mcpServers"]["dev"]["command"], "node");
+        assert!(config.get("amp.mcpServers").is_none());
+        assert_eq!(AmpSettings::read(&file).unwrap().mcp_servers.len(), 2);
+    }
 }
@@ -3,14 +3,20 @@
 //! Claude Code stores its configuration in:
 //! - **Global**: `$CLAUDE_CONFIG_DIR` or `~/.claude/`
 //! - **Project**: `.claude/` in project root
+//! - **Local**: `.claude/settings.local.json`, `CLAUDE.local.md` and the
+//!   project's entry in `~/.claude.json`
+//! - **Managed**: system-wide policy files, see [`managed_config_dir`]
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
 use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
 use crate::platform;
-use crate::types::{EnvValue, HarnessKind, Scope};
+use crate::types::{
+    DirectoryResource, DirectoryStructure, EnvValue, FileFormat, HarnessKind, Scope,
+};
 
 /// Environment variable for Claude Code config directory override.
 const CLAUDE_CONFIG_DIR_ENV: &str = "CLAUDE_CONFIG_DIR";
@@ -37,6 +43,52 @@ pub fn global_config_dir() -> Result<PathBuf> {
     Ok(platform::home_dir()?.join(".claude"))
 }
 
+/// Returns the directory holding Claude Code's managed policy files
+/// (`managed-settings.json`, `managed-mcp.json` and `CLAUDE.md`).
+///
+/// - **Linux**: `/etc/claude-code/`
+/// - **macOS**: `/Library/Application Support/ClaudeCode/`
+/// - **Windows**: `%ProgramFiles%\ClaudeCode\`
+///
+/// # Errors
+///
+/// Returns [`Error::UnsupportedPlatform`] on other platforms.
+pub fn managed_config_dir() -> Result<PathBuf> {
+    #[cfg(target_os = "linux")]
+    return Ok(PathBuf::from("/etc/claude-code"));
+
+    #[cfg(target_os = "macos")]
+    return Ok(PathBuf::from("/Library/Application Support/ClaudeCode"));
+
+    #[cfg(target_os = "windows")]
+    return Ok(std::env::var_os("ProgramFiles")
+        .map_or_else(|| PathBuf::from(r"C:\Program Files"), PathBuf::from)
+        .join("ClaudeCode"));
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    Err(Error::UnsupportedPlatform)
+}
+
+/// Returns Claude Code's per-user state file, `~/.claude.json`.
+///
+/// Claude Code keeps this file inside `CLAUDE_CONFIG_DIR` when the
+/// variable is set. Local-scoped MCP servers are stored in it, keyed by
+/// project path.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined and
+/// no environment variable is set.
+pub fn user_state_file() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var(CLAUDE_CONFIG_DIR_ENV) {
+        let path = PathBuf::from(dir);
+        if path.is_absolute() {
+            return Ok(path.join(".claude.json"));
+        }
+    }
+    Ok(platform::home_dir()?.join(".claude.json"))
+}
+
 /// Returns the project-local Claude Code configuration directory.
 ///
 /// # Arguments
@@ -56,6 +108,14 @@ pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
         Scope::Global => Ok(global_config_dir()?.join("commands")),
         Scope::Project(root) => Ok(project_config_dir(root).join("commands")),
         Scope::Custom(path) => Ok(path.join("commands")),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Claude Code".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Claude Code".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -65,17 +125,25 @@ pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
 pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
     match scope {
         Scope::Global => global_config_dir(),
-        Scope::Project(root) => Ok(project_config_dir(root)),
+        Scope::Project(root) | Scope::Local(root) => Ok(project_config_dir(root)),
         Scope::Custom(path) => Ok(path.clone()),
+        Scope::Managed => managed_config_dir(),
     }
 }
 
 /// Returns the MCP configuration directory for the given scope.
 ///
 /// Claude Code stores MCP configuration in the base config directory
-/// (settings files like `.mcp.json`).
+/// (settings files like `.mcp.json`). Local servers live in the directory
+/// holding [`user_state_file`].
 pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
-    config_dir(scope)
+    match scope {
+        Scope::Local(_) => Ok(user_state_file()?
+            .parent()
+            .map(PathBuf::from)
+            .unwrap_or_default()),
+        _ => config_dir(scope),
+    }
 }
 
 /// Returns the skills directory for the given scope.
@@ -89,6 +157,7 @@ pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok().map(|p| p.join("skills")),
         Scope::Project(root) => Some(project_config_dir(root).join("skills")),
         Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -97,12 +166,15 @@ pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
 /// Claude Code stores rules files (`CLAUDE.md`, `CLAUDE.local.md`) at:
 /// - **Global**: `~/.claude/` (supports global `CLAUDE.md`)
 /// - **Project**: Project root directory (not `.claude/`)
+/// - **Local**: Project root directory (`CLAUDE.local.md`)
+/// - **Managed**: The managed policy directory
 #[must_use]
 pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
     match scope {
         Scope::Global => global_config_dir().ok(),
-        Scope::Project(root) => Some(root.clone()),
+        Scope::Project(root) | Scope::Local(root) => Some(root.clone()),
         Scope::Custom(path) => Some(path.clone()),
+        Scope::Managed => managed_config_dir().ok(),
     }
 }
 
@@ -117,6 +189,7 @@ pub fn agents_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok().map(|p| p.join("agents")),
         Scope::Project(root) => Some(project_config_dir(root).join("agents")),
         Scope::Custom(path) => Some(path.join("agents")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -134,6 +207,7 @@ pub fn plugins_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok().map(|p| p.join("plugins")),
         Scope::Project(root) => Some(project_config_dir(root).join("plugins")),
         Scope::Custom(path) => Some(path.join("plugins")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -151,7 +225,6 @@ pub fn is_installed() -> bool {
 ///
 /// # Errors
 /// Returns an error if the JSON is malformed or missing required fields.
-#[allow(dead_code)] // Internal utility for future MCP config reading
 pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
     let obj = value
         .as_object()
@@ -201,6 +274,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                     headers,
                     enabled: true,
                     timeout_ms: None,
+                    auto_approve: Vec::new(),
                 }))
             }
             "http" => {
@@ -242,6 +316,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                     oauth: None,
                     enabled: true,
                     timeout_ms: None,
+                    auto_approve: Vec::new(),
                 }))
             }
             "stdio" => parse_stdio_server(obj),
@@ -255,7 +330,6 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
     }
 }
 
-#[allow(dead_code)] // Internal utility for future MCP config reading
 fn parse_stdio_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result<McpServer> {
     let command = obj
         .get("command")
@@ -315,6 +389,7 @@ fn parse_stdio_server(obj: &serde_json::Map<String, serde_json::Value>) -> Resul
         cwd: None,
         enabled: true,
         timeout_ms: None,
+        auto_approve: Vec::new(),
     }))
 }
 
@@ -325,7 +400,6 @@ fn parse_stdio_server(obj: &serde_json::Map<String, serde_json::Value>) -> Resul
 ///
 /// # Errors
 /// Returns an error if the JSON is malformed.
-#[allow(dead_code)] // Internal utility for future MCP config reading
 pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
     let servers_obj = config
         .get("mcpServers")
@@ -344,6 +418,141 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(result)
 }
 
+/// [`HarnessAdapter`] for Claude Code.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClaudeCodeAdapter;
+
+impl HarnessAdapter for ClaudeCodeAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::ClaudeCode
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn agents(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let Some(path) = agents_dir(scope) else {
+            return adapter::missing_resource(self.kind(), scope);
+        };
+        Ok(Some(adapter::flat_resource(
+            path,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn plugins(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let Some(path) = plugins_dir(scope) else {
+            return adapter::missing_resource(self.kind(), scope);
+        };
+        Ok(Some(DirectoryResource {
+            exists: path.exists(),
+            path,
+            structure: DirectoryStructure::Nested {
+                subdir_pattern: "*".into(),
+                file_name: ".claude-plugin".into(),
+            },
+            file_format: FileFormat::Json,
+        }))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(match scope {
+            Scope::Local(_) => &["CLAUDE.local.md"],
+            _ => &["CLAUDE.md"],
+        })
+    }
+
+    fn mcp_key_path(&self, scope: &Scope) -> Result<Option<String>> {
+        // Local servers are stored per project in ~/.claude.json
+        if let Scope::Local(root) = scope {
+            let root = std::path::absolute(root)?;
+            let project = root.to_string_lossy().replace('~', "~0").replace('/', "~1");
+            return Ok(Some(format!("/projects/{project}/mcpServers")));
+        }
+        Ok(Some("/mcpServers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        // Claude Code reads MCP servers from:
+        // - Global: ~/.claude.json, where `claude mcp add --scope user`
+        //   writes, then ~/.claude/.mcp.json
+        // - Project: .mcp.json (in project root)
+        // - Local: the project's entry in ~/.claude.json
+        let files = match scope {
+            Scope::Global => vec![user_state_file()?, global_config_dir()?.join(".mcp.json")],
+            Scope::Project(root) => vec![root.join(".mcp.json")],
+            Scope::Custom(path) => vec![path.join(".mcp.json")],
+            Scope::Local(_) => vec![user_state_file()?],
+            Scope::Managed => vec![managed_config_dir()?.join("managed-mcp.json")],
+        };
+        Ok(files
+            .into_iter()
+            .map(|file| (file, FileFormat::Json))
+            .collect())
+    }
+
+    fn settings_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        let file = match scope {
+            Scope::Local(_) => "settings.local.json",
+            Scope::Managed => "managed-settings.json",
+            _ => "settings.json",
+        };
+        Ok(vec![(config_dir(scope)?.join(file), FileFormat::Json)])
+    }
+
+    fn schema_url(&self, file: &Path) -> Option<&'static str> {
+        // The MCP files (.mcp.json, ~/.claude.json) have no published schema
+        let name = file.file_name()?.to_str()?;
+        (name.ends_with("settings.json") || name == "settings.local.json")
+            .then_some(crate::schema::CLAUDE_CODE_SETTINGS)
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.to_claude_code_value(self.kind())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
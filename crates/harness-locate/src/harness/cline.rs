@@ -0,0 +1,504 @@
+//! Cline harness implementation.
+//!
+//! Cline is a VS Code extension. It stores its configuration in:
+//! - **Global**: the extension's `globalStorage` directory
+//!   (`saoudrizwan.claude-dev/`) in the user data directory of VS Code,
+//!   VS Code Insiders, Cursor or VSCodium, plus `~/Documents/Cline/` for
+//!   rules and workflows
+//! - **Project**: `.clinerules/` in project root
+//!
+//! MCP servers live under `mcpServers` in
+//! `settings/cline_mcp_settings.json` and are global only. The MCP format
+//! is shared with Roo Code, a fork of Cline; the parsing here is reused by
+//! [`roo_code`](super::roo_code).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
+use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use crate::platform;
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
+
+/// Cline's VS Code extension identifier.
+pub const EXTENSION_ID: &str = "saoudrizwan.claude-dev";
+
+/// Returns Cline's `globalStorage` directory in every VS Code-family
+/// editor, in lookup order.
+///
+/// # Errors
+///
+/// Returns an error if the platform directory cannot be determined.
+pub fn storage_dirs() -> Result<Vec<PathBuf>> {
+    platform::extension_storage_dirs(EXTENSION_ID)
+}
+
+/// Returns the global Cline configuration directory.
+///
+/// This is the first editor's `globalStorage/saoudrizwan.claude-dev/`
+/// that exists, or VS Code's if none does.
+///
+/// # Errors
+///
+/// Returns an error if the platform directory cannot be determined.
+pub fn global_config_dir() -> Result<PathBuf> {
+    first_existing(storage_dirs()?)
+}
+
+/// Returns the directory Cline keeps global rules and workflows in.
+///
+/// Returns `~/Documents/Cline/`.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn documents_dir() -> Result<PathBuf> {
+    Ok(platform::home_dir()?.join("Documents").join("Cline"))
+}
+
+/// Returns the project-local Cline configuration directory.
+///
+/// # Arguments
+///
+/// * `project_root` - Path to the project root directory
+#[must_use]
+pub fn project_config_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".clinerules")
+}
+
+/// Returns the config directory for the given scope.
+///
+/// This is the base configuration directory.
+pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir(),
+        Scope::Project(root) => Ok(project_config_dir(root)),
+        Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Cline".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Cline".to_string(),
+            scope: "managed".to_string(),
+        }),
+    }
+}
+
+/// Returns the workflows directory for the given scope.
+///
+/// Workflows are invoked as slash commands:
+/// - **Global**: `~/Documents/Cline/Workflows/`
+/// - **Project**: `.clinerules/workflows/`
+pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Global => Ok(documents_dir()?.join("Workflows")),
+        _ => Ok(config_dir(scope)?.join("workflows")),
+    }
+}
+
+/// Returns the skills directory for the given scope.
+///
+/// - **Global**: `~/.cline/skills/`
+/// - **Project**: `.cline/skills/`
+#[must_use]
+pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => platform::home_dir()
+            .ok()
+            .map(|p| p.join(".cline").join("skills")),
+        Scope::Project(root) => Some(root.join(".cline").join("skills")),
+        Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Returns the candidate MCP settings files for the given scope, in
+/// lookup order.
+///
+/// - **Global**: `settings/cline_mcp_settings.json` in each editor's
+///   storage directory
+/// - **Project**: Returns `UnsupportedScope` error
+///
+/// # Errors
+///
+/// Returns `Error::UnsupportedScope` for project scope.
+pub fn mcp_files(scope: &Scope) -> Result<Vec<PathBuf>> {
+    let dirs = match scope {
+        Scope::Global => storage_dirs()?,
+        Scope::Custom(path) => vec![path.clone()],
+        Scope::Project(_) => {
+            return Err(Error::UnsupportedScope {
+                harness: "Cline".to_string(),
+                scope: "project".to_string(),
+            });
+        }
+        Scope::Local(_) | Scope::Managed => vec![config_dir(scope)?],
+    };
+    Ok(dirs
+        .into_iter()
+        .map(|dir| dir.join("settings").join("cline_mcp_settings.json"))
+        .collect())
+}
+
+/// Returns the rules directory for the given scope.
+///
+/// - **Global**: `~/Documents/Cline/Rules/`
+/// - **Project**: `.clinerules/`
+///
+/// Older projects use a single `.clinerules` file instead of a directory.
+#[must_use]
+pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => documents_dir().ok().map(|p| p.join("Rules")),
+        Scope::Project(root) => Some(project_config_dir(root)),
+        Scope::Custom(path) => Some(path.join("rules")),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Checks if Cline is installed on this system.
+///
+/// Checks if the extension's storage directory exists in any VS
+/// Code-family editor.
+pub fn is_installed() -> bool {
+    storage_dirs()
+        .map(|dirs| dirs.iter().any(|dir| dir.exists()))
+        .unwrap_or(false)
+}
+
+/// Returns the first directory that exists, falling back to the first.
+fn first_existing(dirs: Vec<PathBuf>) -> Result<PathBuf> {
+    let index = dirs.iter().position(|dir| dir.exists()).unwrap_or(0);
+    dirs.into_iter()
+        .nth(index)
+        .ok_or_else(|| Error::NotFound("editor user directory".into()))
+}
+
+/// Reads an optional object of string values.
+fn string_map(
+    kind: HarnessKind,
+    obj: &Map<String, Value>,
+    key: &str,
+) -> Result<HashMap<String, EnvValue>> {
+    let Some(value) = obj.get(key) else {
+        return Ok(HashMap::new());
+    };
+    let map = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: kind.to_string(),
+            reason: format!("'{key}' must be an object"),
+        })?;
+    map.iter()
+        .map(|(k, v)| {
+            let s = v.as_str().ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: kind.to_string(),
+                reason: format!("'{key}.{k}' must be a string"),
+            })?;
+            Ok((k.clone(), EnvValue::from_native(s, kind)))
+        })
+        .collect()
+}
+
+/// Reads an optional array of strings.
+fn string_array(kind: HarnessKind, obj: &Map<String, Value>, key: &str) -> Result<Vec<String>> {
+    match obj.get(key) {
+        None => Ok(Vec::new()),
+        Some(value) => {
+            serde_json::from_value(value.clone()).map_err(|_| Error::UnsupportedMcpConfig {
+                harness: kind.to_string(),
+                reason: format!("'{key}' must be an array of strings"),
+            })
+        }
+    }
+}
+
+/// Parses a single MCP server in the format shared by Cline and Roo Code.
+///
+/// `disabled` maps to `enabled`, `timeout` (in seconds) to `timeout_ms`,
+/// and the tool names in `alwaysAllow` and `autoApprove` to
+/// `auto_approve`. Without a `type`, servers with a `command` are stdio
+/// and servers with a `url` are SSE.
+pub(crate) fn parse_server(kind: HarnessKind, value: &Value) -> Result<McpServer> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: kind.to_string(),
+            reason: "Server configuration must be an object".to_string(),
+        })?;
+
+    let enabled = !obj
+        .get("disabled")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let timeout_ms = obj
+        .get("timeout")
+        .and_then(Value::as_u64)
+        .map(|secs| secs * 1000);
+    let mut auto_approve = string_array(kind, obj, "alwaysAllow")?;
+    for tool in string_array(kind, obj, "autoApprove")? {
+        if !auto_approve.contains(&tool) {
+            auto_approve.push(tool);
+        }
+    }
+
+    let server_type = match obj.get("type").and_then(Value::as_str) {
+        Some(t) => t,
+        None if obj.contains_key("command") => "stdio",
+        None => "sse",
+    };
+
+    match server_type {
+        "stdio" => {
+            let command = obj
+                .get("command")
+                .and_then(Value::as_str)
+                .ok_or_else(|| Error::UnsupportedMcpConfig {
+                    harness: kind.to_string(),
+                    reason: "stdio server missing 'command'".to_string(),
+                })?
+                .to_string();
+            Ok(McpServer::Stdio(StdioMcpServer {
+                command,
+                args: string_array(kind, obj, "args")?,
+                env: string_map(kind, obj, "env")?,
+                cwd: obj.get("cwd").and_then(Value::as_str).map(PathBuf::from),
+                enabled,
+                timeout_ms,
+                auto_approve,
+            }))
+        }
+        "sse" | "streamableHttp" | "streamable-http" => {
+            let url = obj
+                .get("url")
+                .and_then(Value::as_str)
+                .ok_or_else(|| Error::UnsupportedMcpConfig {
+                    harness: kind.to_string(),
+                    reason: format!("{server_type} server missing 'url'"),
+                })?
+                .to_string();
+            let headers = string_map(kind, obj, "headers")?;
+            if server_type == "sse" {
+                Ok(McpServer::Sse(SseMcpServer {
+                    url,
+                    headers,
+                    enabled,
+                    timeout_ms,
+                    auto_approve,
+                }))
+            } else {
+                Ok(McpServer::Http(HttpMcpServer {
+                    url,
+                    headers,
+                    oauth: None,
+                    enabled,
+                    timeout_ms,
+                    auto_approve,
+                }))
+            }
+        }
+        other => Err(Error::UnsupportedMcpConfig {
+            harness: kind.to_string(),
+            reason: format!("Unknown server type: {other}"),
+        }),
+    }
+}
+
+/// Parses all MCP servers in the format shared by Cline and Roo Code.
+pub(crate) fn parse_servers(kind: HarnessKind, config: &Value) -> Result<Vec<(String, McpServer)>> {
+    let servers_obj = config
+        .get("mcpServers")
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: kind.to_string(),
+            reason: "Config missing 'mcpServers' object".to_string(),
+        })?;
+
+    let mut result = Vec::new();
+    for (name, value) in servers_obj {
+        let server = parse_server(kind, value)?;
+        result.push((name.clone(), server));
+    }
+
+    Ok(result)
+}
+
+/// Parses a single MCP server from Cline's native JSON format.
+///
+/// # Arguments
+/// * `value` - The JSON value representing the server config
+///
+/// # Errors
+/// Returns an error if the JSON is malformed or missing required fields.
+pub(crate) fn parse_mcp_server(value: &Value) -> Result<McpServer> {
+    parse_server(HarnessKind::Cline, value)
+}
+
+/// Parses all MCP servers from a Cline `cline_mcp_settings.json`.
+///
+/// # Arguments
+/// * `config` - The full config JSON (expects mcpServers key)
+///
+/// # Errors
+/// Returns an error if the config is malformed or any server fails to parse.
+pub(crate) fn parse_mcp_servers(config: &Value) -> Result<Vec<(String, McpServer)>> {
+    parse_servers(HarnessKind::Cline, config)
+}
+
+/// [`HarnessAdapter`] for Cline.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClineAdapter;
+
+impl HarnessAdapter for ClineAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::Cline
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/mcpServers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(mcp_files(scope)?
+            .into_iter()
+            .map(|file| (file, FileFormat::Json))
+            .collect())
+    }
+
+    fn parse_mcp_server(&self, value: &Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<Value> {
+        server.to_cline_value(self.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn project_paths() {
+        let root = PathBuf::from("/some/project");
+        let scope = Scope::Project(root.clone());
+        assert_eq!(rules_dir(&scope), Some(root.join(".clinerules")));
+        assert_eq!(
+            commands_dir(&scope).unwrap(),
+            root.join(".clinerules/workflows")
+        );
+        assert!(matches!(
+            mcp_files(&scope),
+            Err(Error::UnsupportedScope { .. })
+        ));
+    }
+
+    #[test]
+    fn global_mcp_files_cover_each_editor() {
+        if platform::home_dir().is_err() {
+            return;
+        }
+
+        let files = mcp_files(&Scope::Global).unwrap();
+        assert_eq!(files.len(), platform::VSCODE_FAMILY_APPS.len());
+        for file in files {
+            assert!(
+                file.ends_with(
+                    Path::new(EXTENSION_ID)
+                        .join("settings")
+                        .join("cline_mcp_settings.json")
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn parse_stdio_server_with_approvals() {
+        let value = json!({
+            "command": "node",
+            "args": ["server.js"],
+            "env": {"MODE": "fast"},
+            "disabled": true,
+            "timeout": 30,
+            "alwaysAllow": ["read"],
+            "autoApprove": ["read", "search"]
+        });
+        let McpServer::Stdio(server) = parse_mcp_server(&value).unwrap() else {
+            panic!("expected stdio server");
+        };
+        assert_eq!(server.command, "node");
+        assert_eq!(server.env["MODE"], EnvValue::plain("fast"));
+        assert!(!server.enabled);
+        assert_eq!(server.timeout_ms, Some(30_000));
+        assert_eq!(server.auto_approve, ["read", "search"]);
+    }
+
+    #[test]
+    fn parse_remote_server_types() {
+        let legacy = json!({"url": "https://example.com/sse"});
+        assert!(matches!(
+            parse_mcp_server(&legacy).unwrap(),
+            McpServer::Sse(_)
+        ));
+        let http = json!({"type": "streamableHttp", "url": "https://example.com/mcp"});
+        assert!(matches!(
+            parse_mcp_server(&http).unwrap(),
+            McpServer::Http(_)
+        ));
+        let unknown = json!({"type": "websocket", "url": "wss://example.com"});
+        assert!(parse_mcp_server(&unknown).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_auto_approve() {
+        let value = json!({"command": "x", "autoApprove": "all"});
+        assert!(matches!(
+            parse_mcp_server(&value),
+            Err(Error::UnsupportedMcpConfig { .. })
+        ));
+    }
+}
@@ -0,0 +1,445 @@
+//! OpenAI Codex CLI harness implementation.
+//!
+//! Codex stores its configuration in:
+//! - **Global**: `$CODEX_HOME` or `~/.codex/`
+//! - **Project**: `.codex/` in project root
+//!
+//! MCP servers live in `config.toml` under `[mcp_servers.<name>]`, rules in
+//! `AGENTS.md` and custom prompts in the global `prompts/` directory.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
+use crate::mcp::{HttpMcpServer, McpServer, StdioMcpServer};
+use crate::platform;
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
+
+/// Environment variable for Codex home directory override.
+const CODEX_HOME_ENV: &str = "CODEX_HOME";
+
+/// Returns the global Codex configuration directory.
+///
+/// Checks `CODEX_HOME` environment variable first, then falls back to
+/// `~/.codex/`.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined and
+/// no environment variable is set.
+pub fn global_config_dir() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var(CODEX_HOME_ENV) {
+        let path = PathBuf::from(dir);
+        if path.is_absolute() {
+            return Ok(path);
+        }
+    }
+    Ok(platform::home_dir()?.join(".codex"))
+}
+
+/// Returns the project-local Codex configuration directory.
+///
+/// # Arguments
+///
+/// * `project_root` - Path to the project root directory
+#[must_use]
+pub fn project_config_dir(project_root: &std::path::Path) -> PathBuf {
+    project_root.join(".codex")
+}
+
+/// Returns the config directory for the given scope.
+///
+/// This is the base configuration directory.
+pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir(),
+        Scope::Project(root) => Ok(project_config_dir(root)),
+        Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Codex".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Codex".to_string(),
+            scope: "managed".to_string(),
+        }),
+    }
+}
+
+/// Returns the custom prompts directory for the given scope.
+///
+/// Codex only reads prompts from `~/.codex/prompts/`.
+///
+/// # Errors
+///
+/// Returns [`Error::UnsupportedScope`] for project scope.
+pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Project(_) => Err(Error::UnsupportedScope {
+            harness: "Codex".to_string(),
+            scope: "project".to_string(),
+        }),
+        _ => Ok(config_dir(scope)?.join("prompts")),
+    }
+}
+
+/// Returns the MCP configuration directory for the given scope.
+///
+/// Codex stores MCP configuration in `config.toml` in the base config
+/// directory.
+pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
+    config_dir(scope)
+}
+
+/// Returns the skills directory for the given scope.
+#[must_use]
+pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir().ok().map(|p| p.join("skills")),
+        Scope::Project(root) => Some(project_config_dir(root).join("skills")),
+        Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Returns the rules directory for the given scope.
+///
+/// Codex reads `AGENTS.md` from:
+/// - **Global**: `~/.codex/`
+/// - **Project**: Project root directory
+#[must_use]
+pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir().ok(),
+        Scope::Project(root) => Some(root.clone()),
+        Scope::Custom(path) => Some(path.clone()),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Checks if Codex is installed on this system.
+///
+/// Currently checks if the global config directory exists.
+pub fn is_installed() -> bool {
+    global_config_dir().map(|p| p.exists()).unwrap_or(false)
+}
+
+/// Parses a single MCP server from Codex's native format.
+///
+/// Entries with a `command` are stdio servers, entries with a `url` are
+/// streamable HTTP servers. Variables listed in `env_vars` and headers in
+/// `env_http_headers` become environment variable references.
+///
+/// # Arguments
+/// * `value` - The JSON value representing the server table
+///
+/// # Errors
+/// Returns an error if the table is malformed or missing required fields.
+pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Codex".to_string(),
+            reason: "Server configuration must be a table".to_string(),
+        })?;
+
+    let enabled = obj.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true);
+    let timeout_ms = obj
+        .get("tool_timeout_sec")
+        .and_then(|v| v.as_f64())
+        .map(|secs| (secs * 1000.0).round() as u64);
+
+    if let Some(command) = obj.get("command") {
+        let command = command
+            .as_str()
+            .ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: "Codex".to_string(),
+                reason: "'command' must be a string".to_string(),
+            })?
+            .to_string();
+        let args = string_array(obj, "args")?;
+
+        let mut env: HashMap<String, EnvValue> = string_table(obj, "env")?
+            .into_iter()
+            .map(|(k, v)| (k, EnvValue::Plain(v)))
+            .collect();
+        for name in string_array(obj, "env_vars")? {
+            env.insert(name.clone(), EnvValue::env(name));
+        }
+
+        let cwd = obj.get("cwd").and_then(|v| v.as_str()).map(PathBuf::from);
+
+        return Ok(McpServer::Stdio(StdioMcpServer {
+            command,
+            args,
+            env,
+            cwd,
+            enabled,
+            timeout_ms,
+            auto_approve: Vec::new(),
+        }));
+    }
+
+    let url = obj
+        .get("url")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Codex".to_string(),
+            reason: "Server has neither 'command' nor 'url'".to_string(),
+        })?
+        .to_string();
+
+    let mut headers: HashMap<String, EnvValue> = string_table(obj, "http_headers")?
+        .into_iter()
+        .map(|(k, v)| (k, EnvValue::Plain(v)))
+        .collect();
+    for (header, var) in string_table(obj, "env_http_headers")? {
+        headers.insert(header, EnvValue::env(var));
+    }
+
+    Ok(McpServer::Http(HttpMcpServer {
+        url,
+        headers,
+        oauth: None,
+        enabled,
+        timeout_ms,
+        auto_approve: Vec::new(),
+    }))
+}
+
+/// Reads an optional array of strings.
+fn string_array(
+    obj: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Result<Vec<String>> {
+    let Some(value) = obj.get(key) else {
+        return Ok(Vec::new());
+    };
+    let invalid = || Error::UnsupportedMcpConfig {
+        harness: "Codex".to_string(),
+        reason: format!("'{key}' must be an array of strings"),
+    };
+    value
+        .as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|item| item.as_str().map(str::to_string).ok_or_else(invalid))
+        .collect()
+}
+
+/// Reads an optional table of strings.
+fn string_table(
+    obj: &serde_json::Map<String, serde_json::Value>,
+    key: &str,
+) -> Result<Vec<(String, String)>> {
+    let Some(value) = obj.get(key) else {
+        return Ok(Vec::new());
+    };
+    let invalid = || Error::UnsupportedMcpConfig {
+        harness: "Codex".to_string(),
+        reason: format!("'{key}' must be a table of strings"),
+    };
+    value
+        .as_object()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|(k, v)| Ok((k.clone(), v.as_str().ok_or_else(invalid)?.to_string())))
+        .collect()
+}
+
+/// Parses all MCP servers from a Codex config.
+///
+/// # Arguments
+/// * `config` - The full Codex config (must contain `mcp_servers` table)
+///
+/// # Errors
+/// Returns an error if the config is malformed or any server fails to parse.
+pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+    let servers_obj = config
+        .get("mcp_servers")
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Codex".to_string(),
+            reason: "Config missing 'mcp_servers' table".to_string(),
+        })?;
+
+    let mut result = Vec::new();
+    for (name, value) in servers_obj {
+        let server = parse_mcp_server(value)?;
+        result.push((name.clone(), server));
+    }
+
+    Ok(result)
+}
+
+/// [`HarnessAdapter`] for Codex.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CodexAdapter;
+
+impl HarnessAdapter for CodexAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::Codex
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(&["AGENTS.md"])
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/mcp_servers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(vec![(
+            mcp_dir(scope)?.join("config.toml"),
+            FileFormat::Toml,
+        )])
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.to_codex_value(self.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn global_config_dir_is_absolute() {
+        if platform::home_dir().is_err() {
+            return;
+        }
+
+        let path = global_config_dir().unwrap();
+        assert!(path.is_absolute());
+    }
+
+    #[test]
+    fn project_paths() {
+        let root = PathBuf::from("/some/project");
+        let scope = Scope::Project(root.clone());
+        assert_eq!(config_dir(&scope).unwrap(), root.join(".codex"));
+        assert_eq!(skills_dir(&scope), Some(root.join(".codex/skills")));
+        assert_eq!(rules_dir(&scope), Some(root.clone()));
+        assert!(matches!(
+            commands_dir(&scope),
+            Err(Error::UnsupportedScope { .. })
+        ));
+    }
+
+    #[test]
+    fn commands_dir_global_is_prompts() {
+        if platform::home_dir().is_err() {
+            return;
+        }
+
+        let path = commands_dir(&Scope::Global).unwrap();
+        assert!(path.ends_with("prompts"));
+    }
+
+    #[test]
+    fn parse_stdio_server_with_env_vars() {
+        let value = json!({
+            "command": "npx",
+            "args": ["-y", "server"],
+            "env": {"MODE": "fast"},
+            "env_vars": ["API_KEY"],
+            "cwd": "/tmp",
+            "enabled": false,
+            "tool_timeout_sec": 1.5
+        });
+        let McpServer::Stdio(server) = parse_mcp_server(&value).unwrap() else {
+            panic!("expected stdio server");
+        };
+        assert_eq!(server.command, "npx");
+        assert_eq!(server.args, ["-y", "server"]);
+        assert_eq!(server.env["MODE"], EnvValue::plain("fast"));
+        assert_eq!(server.env["API_KEY"], EnvValue::env("API_KEY"));
+        assert_eq!(server.cwd, Some(PathBuf::from("/tmp")));
+        assert!(!server.enabled);
+        assert_eq!(server.timeout_ms, Some(1500));
+    }
+
+    #[test]
+    fn parse_http_server_with_env_headers() {
+        let value = json!({
+            "url": "https://example.com/mcp",
+            "http_headers": {"X-Team": "core"},
+            "env_http_headers": {"Authorization": "AUTH_HEADER"}
+        });
+        let McpServer::Http(server) = parse_mcp_server(&value).unwrap() else {
+            panic!("expected HTTP server");
+        };
+        assert_eq!(server.url, "https://example.com/mcp");
+        assert_eq!(server.headers["X-Team"], EnvValue::plain("core"));
+        assert_eq!(
+            server.headers["Authorization"],
+            EnvValue::env("AUTH_HEADER")
+        );
+        assert!(server.enabled);
+    }
+
+    #[test]
+    fn parse_server_without_command_or_url_fails() {
+        let result = parse_mcp_server(&json!({"args": []}));
+        assert!(matches!(result, Err(Error::UnsupportedMcpConfig { .. })));
+    }
+
+    #[test]
+    fn parse_stdio_server_args_not_array_fails() {
+        let result = parse_mcp_server(&json!({"command": "x", "args": "y"}));
+        assert!(matches!(result, Err(Error::UnsupportedMcpConfig { .. })));
+    }
+
+    #[test]
+    fn parse_mcp_servers_missing_table() {
+        let result = parse_mcp_servers(&json!({"model": "o3"}));
+        assert!(matches!(result, Err(Error::UnsupportedMcpConfig { .. })));
+    }
+}
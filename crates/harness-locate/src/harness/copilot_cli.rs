@@ -8,9 +8,10 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
 use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
 use crate::platform;
-use crate::types::{EnvValue, HarnessKind, Scope};
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
 
 /// Environment variable for XDG config directory override.
 const XDG_CONFIG_HOME_ENV: &str = "XDG_CONFIG_HOME";
@@ -55,6 +56,14 @@ pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
         Scope::Global => global_config_dir(),
         Scope::Project(root) => Ok(project_config_dir(root)),
         Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Copilot CLI".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Copilot CLI".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -71,6 +80,14 @@ pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
             scope: "project".to_string(),
         }),
         Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Copilot CLI".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Copilot CLI".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -85,6 +102,7 @@ pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok().map(|p| p.join("skills")),
         Scope::Project(_) => None,
         Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -99,6 +117,7 @@ pub fn agents_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok().map(|p| p.join("agents")),
         Scope::Project(root) => Some(project_config_dir(root).join("agents")),
         Scope::Custom(path) => Some(path.join("agents")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -113,6 +132,7 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok(),
         Scope::Project(root) => Some(project_config_dir(root)),
         Scope::Custom(path) => Some(path.clone()),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -209,6 +229,7 @@ fn parse_stdio_server(obj: &serde_json::Map<String, serde_json::Value>) -> Resul
         cwd: None,
         enabled: true,
         timeout_ms: obj.get("timeout").and_then(|v| v.as_u64()),
+        auto_approve: Vec::new(),
     }))
 }
 
@@ -229,6 +250,7 @@ fn parse_sse_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result<
         headers,
         enabled: true,
         timeout_ms: obj.get("timeout").and_then(|v| v.as_u64()),
+        auto_approve: Vec::new(),
     }))
 }
 
@@ -250,6 +272,7 @@ fn parse_http_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result
         oauth: None,
         enabled: true,
         timeout_ms: obj.get("timeout").and_then(|v| v.as_u64()),
+        auto_approve: Vec::new(),
     }))
 }
 
@@ -306,6 +329,93 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(result)
 }
 
+/// [`HarnessAdapter`] for Copilot CLI.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopilotCliAdapter;
+
+impl HarnessAdapter for CopilotCliAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::CopilotCli
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn agents(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let Some(path) = agents_dir(scope) else {
+            return adapter::missing_resource(self.kind(), scope);
+        };
+        // Global and project agents are Markdown
+        Ok(Some(adapter::flat_resource(
+            path,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(&["copilot-instructions.md"])
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/mcpServers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        // Copilot CLI uses mcp-config.json in config directories
+        Ok(vec![(
+            mcp_dir(scope)?.join("mcp-config.json"),
+            FileFormat::Json,
+        )])
+    }
+
+    fn settings_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        match scope {
+            Scope::Project(_) => Ok(Vec::new()),
+            _ => Ok(vec![(
+                config_dir(scope)?.join("config.json"),
+                FileFormat::Json,
+            )]),
+        }
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.to_copilot_cli_value(self.kind())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
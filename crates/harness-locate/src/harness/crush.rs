@@ -5,12 +5,13 @@
 //! - **Project**: `.crush/` in project root (if exists)
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
 use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
 use crate::platform;
-use crate::types::{EnvValue, Scope};
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
 
 /// Returns the global Crush configuration directory.
 ///
@@ -49,6 +50,14 @@ pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
         Scope::Global => global_config_dir(),
         Scope::Project(root) => Ok(project_config_dir(root)),
         Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Crush".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Crush".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -75,6 +84,7 @@ pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
         }
         Scope::Project(root) => Some(root.join(".crush").join("skills")),
         Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -89,6 +99,7 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok(),
         Scope::Project(root) => Some(root.clone()),
         Scope::Custom(path) => Some(path.clone()),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -131,7 +142,24 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
         .unwrap_or(false);
     let enabled = !disabled;
 
-    let timeout_ms = obj.get("timeout_ms").and_then(|v| v.as_u64());
+    let timeout_ms = if let Some(timeout_value) = obj.get("timeout") {
+        let seconds = timeout_value
+            .as_u64()
+            .ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: "Crush".into(),
+                reason: "'timeout' must be a number".into(),
+            })?;
+        Some(
+            seconds
+                .checked_mul(1000)
+                .ok_or_else(|| Error::UnsupportedMcpConfig {
+                    harness: "Crush".into(),
+                    reason: "timeout value too large".into(),
+                })?,
+        )
+    } else {
+        None
+    };
 
     match server_type {
         "stdio" => {
@@ -193,6 +221,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                 cwd: None,
                 enabled,
                 timeout_ms,
+                auto_approve: Vec::new(),
             }))
         }
         "http" => {
@@ -232,6 +261,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                 oauth: None,
                 enabled,
                 timeout_ms,
+                auto_approve: Vec::new(),
             }))
         }
         "sse" => {
@@ -270,6 +300,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                 headers,
                 enabled,
                 timeout_ms,
+                auto_approve: Vec::new(),
             }))
         }
         _ => Err(Error::UnsupportedMcpConfig {
@@ -310,6 +341,78 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(servers)
 }
 
+/// [`HarnessAdapter`] for Crush.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrushAdapter;
+
+impl HarnessAdapter for CrushAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::Crush
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(&["CRUSH.md"])
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/mcp".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(match scope {
+            // Crush reads .crush.json, then crush.json, from the project root
+            Scope::Project(root) => vec![
+                (root.join(".crush.json"), FileFormat::Json),
+                (root.join("crush.json"), FileFormat::Json),
+            ],
+            _ => vec![(config_dir(scope)?.join("crush.json"), FileFormat::Json)],
+        })
+    }
+
+    fn schema_url(&self, _file: &Path) -> Option<&'static str> {
+        Some(crate::schema::CRUSH_CONFIG)
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.to_crush_value(self.kind())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -434,7 +537,7 @@ mod tests {
                 "API_KEY": "secret123",
                 "DEBUG": "true"
             },
-            "timeout_ms": 30000
+            "timeout": 30
         });
 
         let result = parse_mcp_server(&json).unwrap();
@@ -525,7 +628,7 @@ mod tests {
         let json = json!({
             "type": "sse",
             "url": "https://example.com/sse",
-            "timeout_ms": 45000
+            "timeout": 45
         });
 
         let result = parse_mcp_server(&json).unwrap();
@@ -762,4 +865,59 @@ mod tests {
         let result = parse_mcp_server(&json);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn parse_timeout_must_be_seconds() {
+        let json = json!({"type": "http", "url": "https://example.com", "timeout": "30s"});
+        assert!(parse_mcp_server(&json).is_err());
+    }
+
+    #[test]
+    fn mcp_to_native_round_trips() {
+        let mut env = HashMap::new();
+        env.insert("DEBUG".to_string(), EnvValue::plain("1"));
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec!["server.js".to_string()],
+            env,
+            cwd: None,
+            enabled: false,
+            timeout_ms: Some(5000),
+            auto_approve: Vec::new(),
+        });
+
+        let native = CrushAdapter.mcp_to_native("dev", &server).unwrap();
+
+        assert_eq!(
+            native,
+            json!({
+                "type": "stdio",
+                "command": "node",
+                "args": ["server.js"],
+                "env": {"DEBUG": "1"},
+                "disabled": true,
+                "timeout": 5
+            })
+        );
+        assert_eq!(parse_mcp_server(&native).unwrap(), server);
+    }
+
+    #[test]
+    fn mcp_to_native_rounds_timeout_up_to_seconds() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::new(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: Some(1500),
+            auto_approve: Vec::new(),
+        });
+
+        let native = CrushAdapter.mcp_to_native("web", &server).unwrap();
+
+        assert_eq!(
+            native,
+            json!({"type": "http", "url": "https://example.com/mcp", "timeout": 2})
+        );
+    }
 }
@@ -0,0 +1,528 @@
+//! Cursor harness implementation.
+//!
+//! Cursor stores its configuration in:
+//! - **Global**: `~/.cursor/` (on every platform)
+//! - **Project**: `.cursor/` in project root
+//!
+//! MCP servers live under `mcpServers` in `mcp.json`. Project rules are
+//! `.mdc` files in `.cursor/rules/` whose frontmatter controls when they
+//! apply; global rules are kept in the editor's settings, not in files.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
+use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use crate::platform;
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
+
+/// Returns the global Cursor configuration directory.
+///
+/// Returns `~/.cursor/`.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn global_config_dir() -> Result<PathBuf> {
+    Ok(platform::home_dir()?.join(".cursor"))
+}
+
+/// Returns the project-local Cursor configuration directory.
+///
+/// # Arguments
+///
+/// * `project_root` - Path to the project root directory
+#[must_use]
+pub fn project_config_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".cursor")
+}
+
+/// Returns the config directory for the given scope.
+///
+/// This is the base configuration directory.
+pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir(),
+        Scope::Project(root) => Ok(project_config_dir(root)),
+        Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Cursor".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Cursor".to_string(),
+            scope: "managed".to_string(),
+        }),
+    }
+}
+
+/// Returns the commands directory for the given scope.
+///
+/// - **Global**: `~/.cursor/commands/`
+/// - **Project**: `.cursor/commands/`
+pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
+    Ok(config_dir(scope)?.join("commands"))
+}
+
+/// Returns the MCP configuration directory for the given scope.
+///
+/// Cursor stores MCP configuration in `mcp.json` at the base config
+/// directory.
+pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
+    config_dir(scope)
+}
+
+/// Returns the skills directory for the given scope.
+///
+/// - **Global**: `~/.cursor/skills/`
+/// - **Project**: `.cursor/skills/`
+#[must_use]
+pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir().ok().map(|p| p.join("skills")),
+        Scope::Project(root) => Some(project_config_dir(root).join("skills")),
+        Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Returns the rules directory for the given scope.
+///
+/// Cursor reads `.mdc` rules from `.cursor/rules/` in the project. Global
+/// rules are stored in the editor's settings, so `None` is returned for
+/// the global scope.
+#[must_use]
+pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Project(root) => Some(project_config_dir(root).join("rules")),
+        Scope::Custom(path) => Some(path.join("rules")),
+        Scope::Global | Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Checks if Cursor is installed on this system.
+///
+/// Currently checks if the global config directory exists.
+pub fn is_installed() -> bool {
+    global_config_dir().map(|p| p.exists()).unwrap_or(false)
+}
+
+/// A project rule in Cursor's `.mdc` format.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::harness::cursor::{CursorRule, RuleActivation};
+///
+/// let rule = CursorRule::parse("---\nglobs: *.ts, *.tsx\nalwaysApply: false\n---\nUse strict mode.\n")?;
+/// assert_eq!(rule.globs, ["*.ts", "*.tsx"]);
+/// assert_eq!(rule.activation(), RuleActivation::AutoAttached);
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CursorRule {
+    /// Description the agent uses to decide whether to apply the rule.
+    pub description: Option<String>,
+    /// File patterns that attach the rule automatically.
+    pub globs: Vec<String>,
+    /// Whether the rule is included in every request.
+    pub always_apply: bool,
+    /// The rule text after the frontmatter.
+    pub body: String,
+}
+
+/// When Cursor applies a [`CursorRule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleActivation {
+    /// Included in every request (`alwaysApply: true`).
+    Always,
+    /// Included when a matching file is referenced (`globs`).
+    AutoAttached,
+    /// Included when the agent decides it is relevant (`description`).
+    AgentRequested,
+    /// Included only when mentioned with `@`.
+    Manual,
+}
+
+impl CursorRule {
+    /// Parses an `.mdc` rule file.
+    ///
+    /// Cursor's frontmatter is not strict YAML: `globs` is usually written
+    /// as an unquoted, comma-separated list such as `*.ts, *.tsx`, which a
+    /// YAML parser would reject. `description`, `globs` and `alwaysApply`
+    /// are read line by line; YAML lists and quoted values are accepted
+    /// too. Other keys are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if `alwaysApply` is not a boolean.
+    pub fn parse(content: &str) -> Result<Self> {
+        let (frontmatter, body) = crate::skill::split_frontmatter(content);
+        let mut rule = Self {
+            body: body.to_string(),
+            ..Self::default()
+        };
+
+        let mut lines = frontmatter.unwrap_or_default().lines().peekable();
+        while let Some(line) = lines.next() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "description" => {
+                    rule.description = Some(unquote(value).to_string()).filter(|d| !d.is_empty());
+                }
+                "globs" if value.is_empty() => {
+                    while let Some(item) = lines.peek().and_then(|l| l.trim().strip_prefix('-')) {
+                        rule.globs.push(unquote(item.trim()).to_string());
+                        lines.next();
+                    }
+                }
+                "globs" => {
+                    let list = value
+                        .strip_prefix('[')
+                        .and_then(|v| v.strip_suffix(']'))
+                        .unwrap_or(value);
+                    rule.globs = list
+                        .split(',')
+                        .map(|glob| unquote(glob.trim()).to_string())
+                        .filter(|glob| !glob.is_empty())
+                        .collect();
+                }
+                "alwaysApply" => {
+                    rule.always_apply = match value {
+                        "true" => true,
+                        "false" | "" => false,
+                        other => {
+                            return Err(Error::InvalidConfig(format!(
+                                "alwaysApply must be true or false, found '{other}'"
+                            )));
+                        }
+                    };
+                }
+                _ => {}
+            }
+        }
+        Ok(rule)
+    }
+
+    /// Returns when Cursor applies this rule.
+    #[must_use]
+    pub fn activation(&self) -> RuleActivation {
+        if self.always_apply {
+            RuleActivation::Always
+        } else if !self.globs.is_empty() {
+            RuleActivation::AutoAttached
+        } else if self.description.is_some() {
+            RuleActivation::AgentRequested
+        } else {
+            RuleActivation::Manual
+        }
+    }
+
+    /// Serializes the rule in the `.mdc` format Cursor writes.
+    #[must_use]
+    pub fn to_mdc(&self) -> String {
+        format!(
+            "---\ndescription: {}\nglobs: {}\nalwaysApply: {}\n---\n{}",
+            self.description.as_deref().unwrap_or_default(),
+            self.globs.join(","),
+            self.always_apply,
+            self.body
+        )
+    }
+}
+
+/// Strips one pair of matching quotes.
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Reads an optional object of string values.
+fn string_map(obj: &Map<String, Value>, key: &str) -> Result<HashMap<String, EnvValue>> {
+    let Some(value) = obj.get(key) else {
+        return Ok(HashMap::new());
+    };
+    let map = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Cursor".to_string(),
+            reason: format!("'{key}' must be an object"),
+        })?;
+    map.iter()
+        .map(|(k, v)| {
+            let s = v.as_str().ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: "Cursor".to_string(),
+                reason: format!("'{key}.{k}' must be a string"),
+            })?;
+            Ok((k.clone(), EnvValue::from_native(s, HarnessKind::Cursor)))
+        })
+        .collect()
+}
+
+/// Parses a single MCP server from Cursor's native JSON format.
+///
+/// Servers with a `url` are remote; Cursor negotiates the transport, so
+/// they are read as streamable HTTP unless `type` is `"sse"`.
+///
+/// # Arguments
+/// * `value` - The JSON value representing the server config
+///
+/// # Errors
+/// Returns an error if the JSON is malformed or missing required fields.
+pub(crate) fn parse_mcp_server(value: &Value) -> Result<McpServer> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Cursor".to_string(),
+            reason: "Server configuration must be an object".to_string(),
+        })?;
+
+    if let Some(url) = obj.get("url") {
+        let url = url
+            .as_str()
+            .ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: "Cursor".to_string(),
+                reason: "'url' must be a string".to_string(),
+            })?
+            .to_string();
+        let headers = string_map(obj, "headers")?;
+        return if obj.get("type").and_then(Value::as_str) == Some("sse") {
+            Ok(McpServer::Sse(SseMcpServer {
+                url,
+                headers,
+                enabled: true,
+                timeout_ms: None,
+                auto_approve: Vec::new(),
+            }))
+        } else {
+            Ok(McpServer::Http(HttpMcpServer {
+                url,
+                headers,
+                oauth: None,
+                enabled: true,
+                timeout_ms: None,
+                auto_approve: Vec::new(),
+            }))
+        };
+    }
+
+    let command = obj
+        .get("command")
+        .and_then(Value::as_str)
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Cursor".to_string(),
+            reason: "Server has neither 'command' nor 'url'".to_string(),
+        })?
+        .to_string();
+    let args = match obj.get("args") {
+        Some(args) => {
+            serde_json::from_value(args.clone()).map_err(|_| Error::UnsupportedMcpConfig {
+                harness: "Cursor".to_string(),
+                reason: "'args' must be an array of strings".to_string(),
+            })?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(McpServer::Stdio(StdioMcpServer {
+        command,
+        args,
+        env: string_map(obj, "env")?,
+        cwd: None,
+        enabled: true,
+        timeout_ms: None,
+        auto_approve: Vec::new(),
+    }))
+}
+
+/// Parses all MCP servers from a Cursor `mcp.json`.
+///
+/// # Arguments
+/// * `config` - The full config JSON (expects mcpServers key)
+///
+/// # Errors
+/// Returns an error if the config is malformed or any server fails to parse.
+pub(crate) fn parse_mcp_servers(config: &Value) -> Result<Vec<(String, McpServer)>> {
+    let servers_obj = config
+        .get("mcpServers")
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Cursor".to_string(),
+            reason: "Config missing 'mcpServers' object".to_string(),
+        })?;
+
+    let mut result = Vec::new();
+    for (name, value) in servers_obj {
+        let server = parse_mcp_server(value)?;
+        result.push((name.clone(), server));
+    }
+
+    Ok(result)
+}
+
+/// [`HarnessAdapter`] for Cursor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CursorAdapter;
+
+impl HarnessAdapter for CursorAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::Cursor
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        // Rules are .mdc files, parsed with CursorRule
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.mdc",
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/mcpServers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(vec![(mcp_dir(scope)?.join("mcp.json"), FileFormat::Json)])
+    }
+
+    fn parse_mcp_server(&self, value: &Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<Value> {
+        server.to_cursor_value(self.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn project_paths() {
+        let root = PathBuf::from("/some/project");
+        let scope = Scope::Project(root.clone());
+        assert_eq!(config_dir(&scope).unwrap(), root.join(".cursor"));
+        assert_eq!(rules_dir(&scope), Some(root.join(".cursor/rules")));
+        assert_eq!(rules_dir(&Scope::Global), None);
+    }
+
+    #[test]
+    fn parse_stdio_server_with_env_ref() {
+        let value = json!({
+            "command": "npx",
+            "args": ["-y", "server"],
+            "env": {"API_KEY": "${env:API_KEY}", "MODE": "fast"}
+        });
+        let McpServer::Stdio(server) = parse_mcp_server(&value).unwrap() else {
+            panic!("expected stdio server");
+        };
+        assert_eq!(server.env["API_KEY"], EnvValue::env("API_KEY"));
+        assert_eq!(server.env["MODE"], EnvValue::plain("fast"));
+    }
+
+    #[test]
+    fn parse_remote_server_defaults_to_http() {
+        let http = json!({"url": "https://example.com/mcp"});
+        assert!(matches!(
+            parse_mcp_server(&http).unwrap(),
+            McpServer::Http(_)
+        ));
+        let sse = json!({"url": "https://example.com/sse", "type": "sse"});
+        assert!(matches!(parse_mcp_server(&sse).unwrap(), McpServer::Sse(_)));
+    }
+
+    #[test]
+    fn parse_rule_with_comma_separated_globs() {
+        let rule = CursorRule::parse(
+            "---\ndescription: \"TypeScript style\"\nglobs: *.ts, src/**/*.tsx\nalwaysApply: false\n---\nPrefer interfaces.\n",
+        )
+        .unwrap();
+        assert_eq!(rule.description.as_deref(), Some("TypeScript style"));
+        assert_eq!(rule.globs, ["*.ts", "src/**/*.tsx"]);
+        assert!(!rule.always_apply);
+        assert_eq!(rule.body, "Prefer interfaces.\n");
+        assert_eq!(rule.activation(), RuleActivation::AutoAttached);
+    }
+
+    #[test]
+    fn parse_rule_with_yaml_list_globs() {
+        let rule =
+            CursorRule::parse("---\nglobs:\n  - \"*.rs\"\n  - Cargo.toml\n---\nBody").unwrap();
+        assert_eq!(rule.globs, ["*.rs", "Cargo.toml"]);
+    }
+
+    #[test]
+    fn parse_rule_activation() {
+        let always = CursorRule::parse("---\nalwaysApply: true\n---\n").unwrap();
+        assert_eq!(always.activation(), RuleActivation::Always);
+        let requested = CursorRule::parse("---\ndescription: Testing\nglobs:\n---\n").unwrap();
+        assert_eq!(requested.activation(), RuleActivation::AgentRequested);
+        let manual = CursorRule::parse("Just text").unwrap();
+        assert_eq!(manual.activation(), RuleActivation::Manual);
+        assert_eq!(manual.body, "Just text");
+    }
+
+    #[test]
+    fn parse_rule_rejects_invalid_always_apply() {
+        let result = CursorRule::parse("---\nalwaysApply: sometimes\n---\n");
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn rule_round_trips_through_mdc() {
+        let rule = CursorRule {
+            description: Some("Docs".to_string()),
+            globs: vec!["*.md".to_string(), "docs/**".to_string()],
+            always_apply: false,
+            body: "Write plainly.\n".to_string(),
+        };
+        assert_eq!(CursorRule::parse(&rule.to_mdc()).unwrap(), rule);
+    }
+}
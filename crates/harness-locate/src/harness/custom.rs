@@ -0,0 +1,483 @@
+//! Custom harness implementation.
+//!
+//! Resolves paths and converts MCP servers for harnesses described by a
+//! registered [`HarnessDefinition`]:
+//! - **Global**: the definition's `global_dir`
+//! - **Project**: the definition's `project_dir` in the project root
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
+use crate::mcp::{HttpMcpServer, McpCapabilities, McpServer, SseMcpServer, StdioMcpServer};
+use crate::registry::{HarnessDefinition, HarnessRegistry, McpDefinition, ResourceLayout};
+use crate::types::{
+    DirectoryResource, DirectoryStructure, EnvSyntax, EnvValue, FileFormat, HarnessKind, Scope,
+};
+use crate::validation::{
+    AgentCapabilities, ColorFormat, NameFormat, SkillCapabilities, ToolsFormat,
+};
+
+/// Returns the registered definition of harness `id`.
+///
+/// # Errors
+///
+/// Returns [`Error::NotFound`] if no definition with that id is registered.
+pub fn definition(id: &str) -> Result<Arc<HarnessDefinition>> {
+    HarnessRegistry::global()
+        .get(id)
+        .ok_or_else(|| Error::NotFound(format!("custom harness '{id}' is not registered")))
+}
+
+/// Returns the config directory of harness `id` for the given scope.
+///
+/// # Errors
+///
+/// Returns [`Error::UnsupportedScope`] for scopes the definition does not
+/// describe, or an error if the harness is not registered or the global
+/// directory cannot be determined.
+pub fn config_dir(id: &str, scope: &Scope) -> Result<PathBuf> {
+    let definition = definition(id)?;
+    let unsupported = |scope: &str| Error::UnsupportedScope {
+        harness: definition.display_name().to_string(),
+        scope: scope.to_string(),
+    };
+    match scope {
+        Scope::Global => definition.global_config_dir(),
+        Scope::Project(root) => match &definition.project_dir {
+            Some(dir) => Ok(join(root, dir)),
+            None => Err(unsupported("project")),
+        },
+        Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(unsupported("local")),
+        Scope::Managed => Err(unsupported("managed")),
+    }
+}
+
+/// Checks if harness `id` is registered and its global config directory
+/// exists.
+pub fn is_installed(id: &str) -> bool {
+    definition(id)
+        .and_then(|d| d.global_config_dir())
+        .is_ok_and(|p| p.exists())
+}
+
+/// Resolves a resource directory of harness `id`.
+///
+/// `select` picks the resource's layout from the definition; a layout the
+/// definition does not declare is handled as by
+/// [`adapter::missing_resource`]. `structure` and
+/// `file_format` are used when the layout does not specify them.
+pub(crate) fn resource(
+    id: &'static str,
+    scope: &Scope,
+    select: fn(&HarnessDefinition) -> Option<&ResourceLayout>,
+    structure: DirectoryStructure,
+    file_format: FileFormat,
+) -> Result<Option<DirectoryResource>> {
+    let definition = definition(id)?;
+    let Some(layout) = select(&definition) else {
+        return adapter::missing_resource(HarnessKind::Custom(id), scope);
+    };
+    let path = match (scope, &layout.project_dir) {
+        (Scope::Project(root), Some(dir)) => join(root, dir),
+        _ => join(&config_dir(id, scope)?, &layout.dir),
+    };
+    Ok(Some(DirectoryResource {
+        exists: path.exists(),
+        path,
+        structure: layout.structure.clone().unwrap_or(structure),
+        file_format: layout.file_format.unwrap_or(file_format),
+    }))
+}
+
+/// Returns the MCP config file of harness `id` for the given scope, or
+/// `None` if the definition has no MCP configuration.
+pub(crate) fn mcp_file(id: &str, scope: &Scope) -> Result<Option<(PathBuf, FileFormat)>> {
+    let definition = definition(id)?;
+    let Some(mcp) = &definition.mcp else {
+        return Ok(None);
+    };
+    let file = match (scope, &mcp.project_file) {
+        (Scope::Project(root), Some(file)) => join(root, file),
+        _ => join(&config_dir(id, scope)?, &mcp.file),
+    };
+    Ok(Some((file, mcp.format)))
+}
+
+/// Returns the JSON pointer to the MCP servers of harness `id`, or `None`
+/// if the definition has no MCP configuration.
+pub(crate) fn mcp_key_path(id: &str) -> Result<Option<String>> {
+    Ok(definition(id)?.mcp.as_ref().map(|mcp| mcp.key_path.clone()))
+}
+
+/// Returns the display name and MCP configuration of harness `id`.
+fn mcp_definition(id: &str) -> Result<(String, McpDefinition)> {
+    let definition = definition(id)?;
+    let name = definition.display_name().to_string();
+    match &definition.mcp {
+        Some(mcp) => Ok((name, mcp.clone())),
+        None => Err(Error::UnsupportedMcpConfig {
+            harness: name,
+            reason: "harness definition has no MCP configuration".to_string(),
+        }),
+    }
+}
+
+/// Joins a relative path from a definition onto `base`, treating `""` and
+/// `"."` as `base` itself.
+fn join(base: &Path, relative: &str) -> PathBuf {
+    match relative {
+        "" | "." => base.to_path_buf(),
+        _ => base.join(relative),
+    }
+}
+
+/// Converts a normalized server to the native entry of harness `id`.
+///
+/// Capabilities are expected to have been validated by the caller.
+pub(crate) fn to_native_value(id: &'static str, server: &McpServer) -> Result<Value> {
+    let (_, mcp) = mcp_definition(id)?;
+    let kind = HarnessKind::Custom(id);
+    let fields = &mcp.fields;
+    let caps = &mcp.capabilities;
+    let native_map = |values: &HashMap<String, EnvValue>| -> Result<Value> {
+        let map = values
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), Value::String(v.try_to_native(kind)?))))
+            .collect::<Result<Map<_, _>>>()?;
+        Ok(Value::Object(map))
+    };
+
+    let mut obj = Map::new();
+    let (enabled, timeout_ms) = match server {
+        McpServer::Stdio(s) => {
+            if let (Some(field), Some(value)) = (&fields.transport, &fields.stdio) {
+                obj.insert(field.clone(), Value::String(value.clone()));
+            }
+            obj.insert(fields.command.clone(), Value::String(s.command.clone()));
+            obj.insert(fields.args.clone(), serde_json::to_value(&s.args)?);
+            if !s.env.is_empty() {
+                obj.insert(fields.env.clone(), native_map(&s.env)?);
+            }
+            if caps.cwd
+                && let Some(cwd) = &s.cwd
+            {
+                obj.insert(
+                    fields.cwd.clone(),
+                    Value::String(cwd.to_string_lossy().into_owned()),
+                );
+            }
+            (s.enabled, s.timeout_ms)
+        }
+        McpServer::Sse(s) => {
+            if let Some(field) = &fields.transport {
+                obj.insert(field.clone(), Value::String(fields.sse.clone()));
+            }
+            obj.insert(fields.url.clone(), Value::String(s.url.clone()));
+            if !s.headers.is_empty() {
+                obj.insert(fields.headers.clone(), native_map(&s.headers)?);
+            }
+            (s.enabled, s.timeout_ms)
+        }
+        McpServer::Http(h) => {
+            if let Some(field) = &fields.transport {
+                obj.insert(field.clone(), Value::String(fields.http.clone()));
+            }
+            obj.insert(fields.url.clone(), Value::String(h.url.clone()));
+            if !h.headers.is_empty() {
+                obj.insert(fields.headers.clone(), native_map(&h.headers)?);
+            }
+            (h.enabled, h.timeout_ms)
+        }
+    };
+    if caps.toggle {
+        obj.insert(fields.enabled.clone(), Value::Bool(enabled));
+    }
+    if caps.timeout
+        && let Some(timeout_ms) = timeout_ms
+    {
+        obj.insert(fields.timeout.clone(), Value::from(timeout_ms));
+    }
+    Ok(Value::Object(obj))
+}
+
+/// Parses a single MCP server from the native entry of harness `id`.
+///
+/// # Errors
+///
+/// Returns an error if the entry is malformed or missing required fields.
+pub(crate) fn parse_mcp_server(id: &'static str, value: &Value) -> Result<McpServer> {
+    let (harness, mcp) = mcp_definition(id)?;
+    let kind = HarnessKind::Custom(id);
+    let fields = &mcp.fields;
+    let invalid = |reason: String| Error::UnsupportedMcpConfig {
+        harness: harness.clone(),
+        reason,
+    };
+
+    let obj = value
+        .as_object()
+        .ok_or_else(|| invalid("Server configuration must be an object".to_string()))?;
+    let string_map = |field: &str| -> Result<HashMap<String, EnvValue>> {
+        let Some(value) = obj.get(field) else {
+            return Ok(HashMap::new());
+        };
+        let map = value
+            .as_object()
+            .ok_or_else(|| invalid(format!("'{field}' must be an object")))?;
+        map.iter()
+            .map(|(key, value)| {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| invalid(format!("'{field}.{key}' must be a string")))?;
+                Ok((key.clone(), EnvValue::from_native(value, kind)))
+            })
+            .collect()
+    };
+    let enabled = obj
+        .get(&fields.enabled)
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+    let timeout_ms = obj.get(&fields.timeout).and_then(Value::as_u64);
+    let transport = fields
+        .transport
+        .as_ref()
+        .and_then(|field| obj.get(field))
+        .and_then(Value::as_str);
+
+    if let Some(command) = obj.get(&fields.command) {
+        let command = command
+            .as_str()
+            .ok_or_else(|| invalid(format!("'{}' must be a string", fields.command)))?
+            .to_string();
+        let args = match obj.get(&fields.args) {
+            Some(args) => serde_json::from_value(args.clone())
+                .map_err(|_| invalid(format!("'{}' must be an array of strings", fields.args)))?,
+            None => Vec::new(),
+        };
+        return Ok(McpServer::Stdio(StdioMcpServer {
+            command,
+            args,
+            env: string_map(&fields.env)?,
+            cwd: obj
+                .get(&fields.cwd)
+                .and_then(Value::as_str)
+                .map(PathBuf::from),
+            enabled,
+            timeout_ms,
+            auto_approve: Vec::new(),
+        }));
+    }
+
+    let url = obj
+        .get(&fields.url)
+        .and_then(Value::as_str)
+        .ok_or_else(|| {
+            invalid(format!(
+                "Server has neither '{}' nor '{}'",
+                fields.command, fields.url
+            ))
+        })?
+        .to_string();
+    let headers = string_map(&fields.headers)?;
+    if transport == Some(fields.sse.as_str()) {
+        Ok(McpServer::Sse(SseMcpServer {
+            url,
+            headers,
+            enabled,
+            timeout_ms,
+            auto_approve: Vec::new(),
+        }))
+    } else {
+        Ok(McpServer::Http(HttpMcpServer {
+            url,
+            headers,
+            oauth: None,
+            enabled,
+            timeout_ms,
+            auto_approve: Vec::new(),
+        }))
+    }
+}
+
+/// Parses every MCP server under the definition's key path in `config`.
+///
+/// # Errors
+///
+/// Returns an error if the key path does not hold an object or a server
+/// cannot be parsed.
+pub(crate) fn parse_mcp_servers(
+    id: &'static str,
+    config: &Value,
+) -> Result<Vec<(String, McpServer)>> {
+    let (harness, mcp) = mcp_definition(id)?;
+    let servers = config
+        .pointer(&mcp.key_path)
+        .and_then(Value::as_object)
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness,
+            reason: format!("Config missing '{}' object", mcp.key_path),
+        })?;
+    servers
+        .iter()
+        .map(|(name, value)| Ok((name.clone(), parse_mcp_server(id, value)?)))
+        .collect()
+}
+
+/// [`HarnessAdapter`] for a harness described by a registered
+/// [`HarnessDefinition`].
+#[derive(Debug, Clone, Copy)]
+pub struct CustomAdapter {
+    id: &'static str,
+}
+
+impl CustomAdapter {
+    /// Creates an adapter for the definition registered under `id`.
+    #[must_use]
+    pub const fn new(id: &'static str) -> Self {
+        Self { id }
+    }
+}
+
+impl HarnessAdapter for CustomAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::Custom(self.id)
+    }
+
+    fn name(&self) -> &str {
+        self.id
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed(self.id)
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(self.id, scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        resource(
+            self.id,
+            scope,
+            |d| d.skills.as_ref(),
+            DirectoryStructure::Nested {
+                subdir_pattern: "*".into(),
+                file_name: "SKILL.md".into(),
+            },
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        resource(
+            self.id,
+            scope,
+            |d| d.commands.as_ref(),
+            DirectoryStructure::Flat {
+                file_pattern: "*.md".into(),
+            },
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn agents(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        resource(
+            self.id,
+            scope,
+            |d| d.agents.as_ref(),
+            DirectoryStructure::Flat {
+                file_pattern: "*.md".into(),
+            },
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn plugins(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        resource(
+            self.id,
+            scope,
+            |d| d.plugins.as_ref(),
+            DirectoryStructure::Flat {
+                file_pattern: "*".into(),
+            },
+            FileFormat::Json,
+        )
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        resource(
+            self.id,
+            scope,
+            |d| d.rules.as_ref(),
+            DirectoryStructure::Flat {
+                file_pattern: "*.md".into(),
+            },
+            FileFormat::Markdown,
+        )
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        mcp_key_path(self.id)
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(mcp_file(self.id, scope)?.into_iter().collect())
+    }
+
+    fn parse_mcp_server(&self, value: &Value) -> Result<McpServer> {
+        parse_mcp_server(self.id, value)
+    }
+
+    fn parse_mcp_servers(&self, config: &Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(self.id, config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<Value> {
+        to_native_value(self.id, server)
+    }
+
+    fn mcp_capabilities(&self) -> McpCapabilities {
+        definition(self.id)
+            .ok()
+            .and_then(|d| d.mcp.as_ref().map(|mcp| mcp.capabilities))
+            .unwrap_or_default()
+    }
+
+    fn env_syntax(&self) -> EnvSyntax {
+        definition(self.id)
+            .ok()
+            .and_then(|d| d.mcp.as_ref().map(|mcp| mcp.env_syntax))
+            .unwrap_or_default()
+    }
+
+    // Custom harnesses get the most lenient rules
+    fn skill_capabilities(&self) -> Option<SkillCapabilities> {
+        definition(self.id)
+            .ok()?
+            .skills
+            .as_ref()
+            .map(|_| SkillCapabilities {
+                name_format: NameFormat::Any,
+                name_must_match_directory: false,
+                description_required: false,
+            })
+    }
+
+    fn agent_capabilities(&self) -> Option<AgentCapabilities> {
+        definition(self.id)
+            .ok()?
+            .agents
+            .as_ref()
+            .map(|_| AgentCapabilities {
+                tools_format: ToolsFormat::CommaSeparatedString,
+                color_format: ColorFormat::NamedOrHex,
+                supported_modes: &["subagent", "primary"],
+            })
+    }
+}
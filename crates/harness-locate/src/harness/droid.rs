@@ -8,9 +8,10 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
 use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
 use crate::platform;
-use crate::types::{EnvValue, HarnessKind, Scope};
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
 
 /// Returns the global Droid configuration directory.
 ///
@@ -41,6 +42,14 @@ pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
         Scope::Global => global_config_dir(),
         Scope::Project(root) => Ok(project_config_dir(root)),
         Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Droid".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Droid".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -53,6 +62,14 @@ pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
         Scope::Global => Ok(global_config_dir()?.join("commands")),
         Scope::Project(root) => Ok(project_config_dir(root).join("commands")),
         Scope::Custom(path) => Ok(path.join("commands")),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Droid".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Droid".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -74,6 +91,7 @@ pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok().map(|p| p.join("skills")),
         Scope::Project(root) => Some(project_config_dir(root).join("skills")),
         Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -88,6 +106,7 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok(),
         Scope::Project(root) => Some(root.clone()),
         Scope::Custom(path) => Some(path.clone()),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -102,6 +121,7 @@ pub fn agents_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok().map(|p| p.join("droids")),
         Scope::Project(root) => Some(project_config_dir(root).join("droids")),
         Scope::Custom(path) => Some(path.join("droids")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -176,6 +196,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                     oauth: None, // OAuth handled via browser flow
                     enabled,
                     timeout_ms,
+                    auto_approve: Vec::new(),
                 }))
             }
             "stdio" => parse_stdio_server(obj),
@@ -229,6 +250,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
             headers,
             enabled,
             timeout_ms,
+            auto_approve: Vec::new(),
         }))
     } else {
         parse_stdio_server(obj)
@@ -302,6 +324,7 @@ fn parse_stdio_server(obj: &serde_json::Map<String, serde_json::Value>) -> Resul
         cwd: None,
         enabled,
         timeout_ms,
+        auto_approve: Vec::new(),
     }))
 }
 
@@ -330,6 +353,93 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(result)
 }
 
+/// [`HarnessAdapter`] for Droid.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DroidAdapter;
+
+impl HarnessAdapter for DroidAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::Droid
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn agents(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let Some(path) = agents_dir(scope) else {
+            return adapter::missing_resource(self.kind(), scope);
+        };
+        Ok(Some(adapter::flat_resource(
+            path,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(&["AGENTS.md"])
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/mcpServers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(vec![(mcp_dir(scope)?.join("mcp.json"), FileFormat::Json)])
+    }
+
+    fn settings_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(vec![(
+            config_dir(scope)?.join("settings.json"),
+            FileFormat::Json,
+        )])
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.to_droid_value(self.kind())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,572 @@
+//! Gemini CLI harness implementation.
+//!
+//! Gemini CLI stores its configuration in:
+//! - **Global**: `~/.gemini/`
+//! - **Project**: `.gemini/` in project root
+//!
+//! MCP servers live under `mcpServers` in `settings.json`, context files are
+//! named `GEMINI.md`, skills live in `skills/` and custom commands are TOML
+//! files in `commands/`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
+use crate::mcp::{HttpMcpServer, McpServer, OAuthConfig, SseMcpServer, StdioMcpServer};
+use crate::platform;
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
+
+/// Server fields with no counterpart in [`McpServer`].
+///
+/// They are kept from the existing entry when a server is reinstalled.
+pub(crate) const UNMODELED_FIELDS: &[&str] = &["trust", "includeTools", "excludeTools"];
+
+/// Returns the global Gemini CLI configuration directory.
+///
+/// Returns `~/.gemini/`.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn global_config_dir() -> Result<PathBuf> {
+    Ok(platform::home_dir()?.join(".gemini"))
+}
+
+/// Returns the project-local Gemini CLI configuration directory.
+///
+/// # Arguments
+///
+/// * `project_root` - Path to the project root directory
+#[must_use]
+pub fn project_config_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".gemini")
+}
+
+/// Returns the config directory for the given scope.
+///
+/// This is the base configuration directory.
+pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir(),
+        Scope::Project(root) => Ok(project_config_dir(root)),
+        Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Gemini CLI".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Gemini CLI".to_string(),
+            scope: "managed".to_string(),
+        }),
+    }
+}
+
+/// Returns the commands directory for the given scope.
+///
+/// - **Global**: `~/.gemini/commands/`
+/// - **Project**: `.gemini/commands/`
+pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
+    Ok(config_dir(scope)?.join("commands"))
+}
+
+/// Returns the MCP configuration directory for the given scope.
+///
+/// Gemini CLI stores MCP configuration in `settings.json` at the base
+/// config directory.
+pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
+    config_dir(scope)
+}
+
+/// Returns the skills directory for the given scope.
+///
+/// Gemini CLI stores skills in nested directories with `SKILL.md` files:
+/// - **Global**: `~/.gemini/skills/`
+/// - **Project**: `.gemini/skills/`
+#[must_use]
+pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir().ok().map(|p| p.join("skills")),
+        Scope::Project(root) => Some(project_config_dir(root).join("skills")),
+        Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Returns the rules directory for the given scope.
+///
+/// Gemini CLI reads `GEMINI.md` from:
+/// - **Global**: `~/.gemini/`
+/// - **Project**: Project root directory (not `.gemini/`)
+#[must_use]
+pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir().ok(),
+        Scope::Project(root) => Some(root.clone()),
+        Scope::Custom(path) => Some(path.clone()),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Checks if Gemini CLI is installed on this system.
+///
+/// Currently checks if the global config directory exists.
+pub fn is_installed() -> bool {
+    global_config_dir().map(|p| p.exists()).unwrap_or(false)
+}
+
+/// A custom command in Gemini CLI's TOML format.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::harness::gemini_cli::GeminiCommand;
+///
+/// let command = GeminiCommand::parse("description = \"Review\"\nprompt = \"Review {{args}}\"\n")?;
+/// assert_eq!(command.description.as_deref(), Some("Review"));
+/// assert_eq!(command.prompt, "Review {{args}}");
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeminiCommand {
+    /// One-line description shown in `/help`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The prompt sent to the model. `{{args}}` is replaced with the
+    /// command's arguments.
+    pub prompt: String,
+}
+
+impl GeminiCommand {
+    /// Parses a command file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if the content is not valid TOML,
+    /// or [`Error::MissingField`] if it has no `prompt`.
+    pub fn parse(content: &str) -> Result<Self> {
+        let table: toml::Table = toml::from_str(content)
+            .map_err(|e| Error::InvalidConfig(format!("TOML parse error: {e}")))?;
+        if !table.contains_key("prompt") {
+            return Err(Error::MissingField("prompt".to_string()));
+        }
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| Error::InvalidConfig(format!("invalid Gemini command: {e}")))
+    }
+
+    /// Serializes the command as TOML.
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Returns the name a command file is invoked by.
+///
+/// Subdirectories of the commands directory become namespaces separated
+/// by `:`, so `commands/git/commit.toml` is invoked as `/git:commit`.
+/// Returns `None` if `file` is not a `.toml` file inside `commands_dir`.
+#[must_use]
+pub fn command_name(commands_dir: &Path, file: &Path) -> Option<String> {
+    let relative = file.strip_prefix(commands_dir).ok()?;
+    if relative.extension()? != "toml" {
+        return None;
+    }
+    let relative = relative.with_extension("");
+    let parts = relative
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    Some(parts.join(":"))
+}
+
+/// Parses an environment value. Gemini CLI expands both `$VAR` and
+/// `${VAR}`.
+fn env_value(s: &str) -> EnvValue {
+    match s.strip_prefix('$') {
+        Some(var)
+            if !var.is_empty() && var.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+        {
+            EnvValue::env(var)
+        }
+        _ => EnvValue::from_native(s, HarnessKind::GeminiCli),
+    }
+}
+
+/// Reads an optional object of string values.
+fn string_map(obj: &Map<String, Value>, key: &str) -> Result<HashMap<String, EnvValue>> {
+    let Some(value) = obj.get(key) else {
+        return Ok(HashMap::new());
+    };
+    let map = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Gemini CLI".to_string(),
+            reason: format!("'{key}' must be an object"),
+        })?;
+    map.iter()
+        .map(|(k, v)| {
+            let s = v.as_str().ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: "Gemini CLI".to_string(),
+                reason: format!("'{key}.{k}' must be a string"),
+            })?;
+            Ok((k.clone(), env_value(s)))
+        })
+        .collect()
+}
+
+/// Reads an optional array of strings.
+fn string_array(obj: &Map<String, Value>, key: &str) -> Result<Vec<String>> {
+    match obj.get(key) {
+        None => Ok(Vec::new()),
+        Some(value) => {
+            serde_json::from_value(value.clone()).map_err(|_| Error::UnsupportedMcpConfig {
+                harness: "Gemini CLI".to_string(),
+                reason: format!("'{key}' must be an array of strings"),
+            })
+        }
+    }
+}
+
+/// Parses Gemini CLI's `oauth` object.
+///
+/// Returns `None` if OAuth is absent or explicitly disabled.
+fn parse_oauth(obj: &Map<String, Value>) -> Result<Option<OAuthConfig>> {
+    let Some(value) = obj.get("oauth") else {
+        return Ok(None);
+    };
+    let oauth = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Gemini CLI".to_string(),
+            reason: "'oauth' must be an object".to_string(),
+        })?;
+    if oauth.get("enabled").and_then(Value::as_bool) == Some(false) {
+        return Ok(None);
+    }
+    let scopes = string_array(oauth, "scopes")?;
+    Ok(Some(OAuthConfig {
+        client_id: oauth
+            .get("clientId")
+            .and_then(Value::as_str)
+            .map(String::from),
+        client_secret: oauth
+            .get("clientSecret")
+            .and_then(Value::as_str)
+            .map(env_value),
+        scope: (!scopes.is_empty()).then(|| scopes.join(" ")),
+    }))
+}
+
+/// Parses a single MCP server from Gemini CLI's native JSON format.
+///
+/// `command` selects a stdio server, `httpUrl` a streamable HTTP server
+/// and `url` an SSE server. `trust`, `includeTools` and `excludeTools`
+/// are checked but have no counterpart in [`McpServer`].
+///
+/// # Arguments
+/// * `value` - The JSON value representing the server config
+///
+/// # Errors
+/// Returns an error if the JSON is malformed or missing required fields.
+pub(crate) fn parse_mcp_server(value: &Value) -> Result<McpServer> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Gemini CLI".to_string(),
+            reason: "Server configuration must be an object".to_string(),
+        })?;
+
+    if obj.get("trust").is_some_and(|v| !v.is_boolean()) {
+        return Err(Error::UnsupportedMcpConfig {
+            harness: "Gemini CLI".to_string(),
+            reason: "'trust' must be a boolean".to_string(),
+        });
+    }
+    string_array(obj, "includeTools")?;
+    string_array(obj, "excludeTools")?;
+
+    let timeout_ms = obj.get("timeout").and_then(Value::as_u64);
+
+    if let Some(command) = obj.get("command") {
+        let command = command
+            .as_str()
+            .ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: "Gemini CLI".to_string(),
+                reason: "'command' must be a string".to_string(),
+            })?
+            .to_string();
+        return Ok(McpServer::Stdio(StdioMcpServer {
+            command,
+            args: string_array(obj, "args")?,
+            env: string_map(obj, "env")?,
+            cwd: obj.get("cwd").and_then(Value::as_str).map(PathBuf::from),
+            enabled: true,
+            timeout_ms,
+            auto_approve: Vec::new(),
+        }));
+    }
+
+    let headers = string_map(obj, "headers")?;
+    if let Some(url) = obj.get("httpUrl").and_then(Value::as_str) {
+        return Ok(McpServer::Http(HttpMcpServer {
+            url: url.to_string(),
+            headers,
+            oauth: parse_oauth(obj)?,
+            enabled: true,
+            timeout_ms,
+            auto_approve: Vec::new(),
+        }));
+    }
+    if let Some(url) = obj.get("url").and_then(Value::as_str) {
+        return Ok(McpServer::Sse(SseMcpServer {
+            url: url.to_string(),
+            headers,
+            enabled: true,
+            timeout_ms,
+            auto_approve: Vec::new(),
+        }));
+    }
+
+    Err(Error::UnsupportedMcpConfig {
+        harness: "Gemini CLI".to_string(),
+        reason: "Server has none of 'command', 'httpUrl' or 'url'".to_string(),
+    })
+}
+
+/// Parses all MCP servers from a Gemini CLI settings JSON.
+///
+/// # Arguments
+/// * `config` - The full settings JSON (expects mcpServers key)
+///
+/// # Errors
+/// Returns an error if the config is malformed or any server fails to parse.
+pub(crate) fn parse_mcp_servers(config: &Value) -> Result<Vec<(String, McpServer)>> {
+    let servers_obj = config
+        .get("mcpServers")
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Gemini CLI".to_string(),
+            reason: "Config missing 'mcpServers' object".to_string(),
+        })?;
+
+    let mut result = Vec::new();
+    for (name, value) in servers_obj {
+        let server = parse_mcp_server(value)?;
+        result.push((name.clone(), server));
+    }
+
+    Ok(result)
+}
+
+/// Copies the [`UNMODELED_FIELDS`] of an existing entry into a freshly
+/// converted one, so reinstalling a server keeps its trust and tool filters.
+pub(crate) fn carry_over_fields(existing: &Value, native: &mut Value) {
+    let (Some(existing), Some(native)) = (existing.as_object(), native.as_object_mut()) else {
+        return;
+    };
+    for field in UNMODELED_FIELDS {
+        if let Some(value) = existing.get(*field) {
+            native
+                .entry(field.to_string())
+                .or_insert_with(|| value.clone());
+        }
+    }
+}
+
+/// [`HarnessAdapter`] for Gemini CLI.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeminiCliAdapter;
+
+impl HarnessAdapter for GeminiCliAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::GeminiCli
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        // Subdirectories namespace commands, e.g. git/commit.toml is /git:commit
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "**/*.toml",
+            FileFormat::Toml,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(&["GEMINI.md"])
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/mcpServers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(vec![(
+            mcp_dir(scope)?.join("settings.json"),
+            FileFormat::Json,
+        )])
+    }
+
+    fn parse_mcp_server(&self, value: &Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<Value> {
+        server.to_gemini_cli_value(self.kind())
+    }
+
+    fn merge_mcp_entry(&self, existing: &Value, native: &mut Value) {
+        carry_over_fields(existing, native);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn project_paths() {
+        let root = PathBuf::from("/some/project");
+        let scope = Scope::Project(root.clone());
+        assert_eq!(config_dir(&scope).unwrap(), root.join(".gemini"));
+        assert_eq!(commands_dir(&scope).unwrap(), root.join(".gemini/commands"));
+        assert_eq!(skills_dir(&scope), Some(root.join(".gemini/skills")));
+        assert_eq!(rules_dir(&scope), Some(root));
+    }
+
+    #[test]
+    fn parse_stdio_server() {
+        let value = json!({
+            "command": "node",
+            "args": ["server.js"],
+            "env": {"API_KEY": "$API_KEY", "TOKEN": "${TOKEN}", "MODE": "fast"},
+            "cwd": "./server",
+            "timeout": 30000,
+            "trust": true,
+            "includeTools": ["search"]
+        });
+        let McpServer::Stdio(server) = parse_mcp_server(&value).unwrap() else {
+            panic!("expected stdio server");
+        };
+        assert_eq!(server.command, "node");
+        assert_eq!(server.args, ["server.js"]);
+        assert_eq!(server.env["API_KEY"], EnvValue::env("API_KEY"));
+        assert_eq!(server.env["TOKEN"], EnvValue::env("TOKEN"));
+        assert_eq!(server.env["MODE"], EnvValue::plain("fast"));
+        assert_eq!(server.cwd, Some(PathBuf::from("./server")));
+        assert_eq!(server.timeout_ms, Some(30000));
+    }
+
+    #[test]
+    fn parse_http_url_is_streamable_http() {
+        let value = json!({
+            "httpUrl": "https://example.com/mcp",
+            "headers": {"Authorization": "Bearer $TOKEN"},
+            "oauth": {"enabled": true, "clientId": "app", "scopes": ["read", "write"]}
+        });
+        let McpServer::Http(server) = parse_mcp_server(&value).unwrap() else {
+            panic!("expected HTTP server");
+        };
+        assert_eq!(server.url, "https://example.com/mcp");
+        assert_eq!(
+            server.headers["Authorization"],
+            EnvValue::plain("Bearer $TOKEN")
+        );
+        let oauth = server.oauth.unwrap();
+        assert_eq!(oauth.client_id.as_deref(), Some("app"));
+        assert_eq!(oauth.scope.as_deref(), Some("read write"));
+    }
+
+    #[test]
+    fn parse_url_is_sse() {
+        let value = json!({"url": "https://example.com/sse"});
+        assert!(matches!(
+            parse_mcp_server(&value).unwrap(),
+            McpServer::Sse(_)
+        ));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_unmodeled_fields() {
+        let trust = json!({"command": "x", "trust": "yes"});
+        assert!(parse_mcp_server(&trust).is_err());
+        let tools = json!({"command": "x", "excludeTools": "all"});
+        assert!(parse_mcp_server(&tools).is_err());
+    }
+
+    #[test]
+    fn carry_over_keeps_trust_and_filters() {
+        let existing = json!({"command": "old", "trust": true, "excludeTools": ["rm"]});
+        let mut native = json!({"command": "new"});
+        carry_over_fields(&existing, &mut native);
+        assert_eq!(
+            native,
+            json!({"command": "new", "trust": true, "excludeTools": ["rm"]})
+        );
+    }
+
+    #[test]
+    fn command_round_trip() {
+        let content = "description = \"Commit\"\nprompt = \"\"\"\nWrite a commit message for {{args}}\n\"\"\"\n";
+        let command = GeminiCommand::parse(content).unwrap();
+        assert_eq!(command.description.as_deref(), Some("Commit"));
+        assert_eq!(command.prompt, "Write a commit message for {{args}}\n");
+        assert_eq!(GeminiCommand::parse(&command.to_toml()).unwrap(), command);
+    }
+
+    #[test]
+    fn command_without_prompt_is_missing_field() {
+        let result = GeminiCommand::parse("description = \"x\"\n");
+        assert!(matches!(result, Err(Error::MissingField(field)) if field == "prompt"));
+    }
+
+    #[test]
+    fn command_name_uses_namespaces() {
+        let dir = Path::new("/home/u/.gemini/commands");
+        assert_eq!(
+            command_name(dir, &dir.join("git/commit.toml")),
+            Some("git:commit".to_string())
+        );
+        assert_eq!(
+            command_name(dir, &dir.join("test.toml")),
+            Some("test".to_string())
+        );
+        assert_eq!(command_name(dir, &dir.join("notes.md")), None);
+    }
+}
@@ -3,14 +3,26 @@
 //! Goose stores its configuration in:
 //! - **Global**: `~/.config/goose/`
 //! - **Project**: `.goose/` in project root (if exists)
+//!
+//! Environment variable references are written to an extension's
+//! `env_keys`, which Goose resolves from the environment or from its
+//! secret store at startup. [`store_env_secrets`] copies the values into a
+//! [`SecretStore`] for variables that will not be set when Goose runs.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use serde_json::Value;
 
+use crate::document::Document;
 use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
+use crate::journal::JournalMode;
 use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use crate::plan::ChangePlan;
 use crate::platform;
-use crate::types::{EnvValue, Scope};
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
+use crate::validation::{CODE_ENV_KEY_RENAMED, ValidationIssue};
 
 /// Returns the global Goose configuration directory.
 ///
@@ -49,6 +61,14 @@ pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
         Scope::Global => global_config_dir(),
         Scope::Project(root) => Ok(project_config_dir(root)),
         Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Goose".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Goose".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -75,6 +95,7 @@ pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
         }
         Scope::Project(root) => Some(root.join(".agents").join("skills")),
         Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -89,6 +110,7 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok(),
         Scope::Project(root) => Some(root.clone()),
         Scope::Custom(path) => Some(path.clone()),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -106,7 +128,6 @@ pub fn is_installed() -> bool {
 ///
 /// # Errors
 /// Returns an error if the JSON is malformed or missing required fields.
-#[allow(dead_code)] // Internal utility for future MCP config reading
 pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
     let obj = value
         .as_object()
@@ -177,7 +198,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                 Vec::new()
             };
 
-            let env = if let Some(envs_value) = obj.get("envs") {
+            let mut env = if let Some(envs_value) = obj.get("envs") {
                 let env_obj =
                     envs_value
                         .as_object()
@@ -197,6 +218,22 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
             } else {
                 HashMap::new()
             };
+            // Goose reads these from its secret store or the environment
+            if let Some(keys_value) = obj.get("env_keys") {
+                let keys = keys_value
+                    .as_array()
+                    .ok_or_else(|| Error::UnsupportedMcpConfig {
+                        harness: "Goose".into(),
+                        reason: "'env_keys' must be an array".into(),
+                    })?;
+                for (i, key) in keys.iter().enumerate() {
+                    let key = key.as_str().ok_or_else(|| Error::UnsupportedMcpConfig {
+                        harness: "Goose".into(),
+                        reason: format!("env_keys[{}] must be a string", i),
+                    })?;
+                    env.insert(key.to_string(), EnvValue::env(key));
+                }
+            }
 
             Ok(McpServer::Stdio(StdioMcpServer {
                 command,
@@ -205,6 +242,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                 cwd: None,
                 enabled,
                 timeout_ms,
+                auto_approve: Vec::new(),
             }))
         }
         "sse" => {
@@ -222,6 +260,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                 headers: HashMap::new(),
                 enabled,
                 timeout_ms,
+                auto_approve: Vec::new(),
             }))
         }
         "streamable_http" => {
@@ -261,6 +300,7 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                 oauth: None,
                 enabled,
                 timeout_ms,
+                auto_approve: Vec::new(),
             }))
         }
         _ => Err(Error::UnsupportedMcpConfig {
@@ -277,7 +317,6 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
 ///
 /// # Errors
 /// Returns an error if the JSON is malformed.
-#[allow(dead_code)] // Internal utility for future MCP config reading
 pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
     let extensions = config
         .get("extensions")
@@ -300,9 +339,289 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(servers)
 }
 
+/// Returns the file Goose keeps secrets in when `GOOSE_DISABLE_KEYRING`
+/// is set.
+///
+/// Returns `~/.config/goose/secrets.yaml`.
+///
+/// # Errors
+///
+/// Returns an error if the config directory cannot be determined.
+pub fn secrets_file() -> Result<PathBuf> {
+    Ok(global_config_dir()?.join("secrets.yaml"))
+}
+
+/// Where Goose keeps the values of `env_keys`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SecretStore {
+    /// A YAML file of key-value pairs, normally [`secrets_file`].
+    File(PathBuf),
+    /// The system keyring entry Goose uses (service `goose`, user
+    /// `secrets`), holding every secret as one JSON object.
+    #[cfg(feature = "keyring")]
+    Keyring,
+}
+
+impl SecretStore {
+    /// Returns the store Goose reads: [`secrets_file`] if
+    /// `GOOSE_DISABLE_KEYRING` is set, the system keyring otherwise.
+    ///
+    /// Without the `keyring` feature, the secrets file is always returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined.
+    pub fn detect() -> Result<Self> {
+        #[cfg(feature = "keyring")]
+        if std::env::var_os("GOOSE_DISABLE_KEYRING").is_none() {
+            return Ok(Self::Keyring);
+        }
+        Ok(Self::File(secrets_file()?))
+    }
+
+    /// Returns the secret stored under `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read.
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .read()?
+            .get(key)
+            .and_then(Value::as_str)
+            .map(String::from))
+    }
+
+    /// Stores `value` under `key`, replacing any previous value.
+    ///
+    /// A new secrets file is only readable by its owner. Writes to a
+    /// secrets file are recorded in the default
+    /// [`Journal`](crate::Journal); keyring writes are not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read or written.
+    pub fn set(&self, key: &str, value: &str) -> Result<()> {
+        self.update(|secrets| {
+            secrets.insert(key.to_string(), Value::String(value.to_string()));
+        })
+    }
+
+    /// Removes the secret stored under `key`, returning whether it existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read or written.
+    pub fn remove(&self, key: &str) -> Result<bool> {
+        let mut removed = false;
+        self.update(|secrets| removed = secrets.remove(key).is_some())?;
+        Ok(removed)
+    }
+
+    fn read(&self) -> Result<serde_json::Map<String, Value>> {
+        let value = match self {
+            Self::File(path) => Document::read(path, FileFormat::Yaml)?
+                .get("")?
+                .cloned()
+                .unwrap_or_default(),
+            #[cfg(feature = "keyring")]
+            Self::Keyring => match keyring_entry()?.get_password() {
+                Ok(json) => serde_json::from_str(&json)?,
+                Err(keyring::Error::NoEntry) => Value::Null,
+                Err(e) => return Err(keyring_error(e)),
+            },
+        };
+        match value {
+            Value::Object(secrets) => Ok(secrets),
+            Value::Null => Ok(serde_json::Map::new()),
+            _ => Err(Error::InvalidConfig(
+                "Goose secrets must be a map of keys to values".into(),
+            )),
+        }
+    }
+
+    fn update(&self, edit: impl FnOnce(&mut serde_json::Map<String, Value>)) -> Result<()> {
+        let mut secrets = self.read()?;
+        let before = secrets.clone();
+        edit(&mut secrets);
+        if secrets == before {
+            return Ok(());
+        }
+        match self {
+            Self::File(path) => write_secrets_file(path, &secrets),
+            #[cfg(feature = "keyring")]
+            Self::Keyring => keyring_entry()?
+                .set_password(&serde_json::to_string(&secrets)?)
+                .map_err(keyring_error),
+        }
+    }
+}
+
+fn write_secrets_file(path: &Path, secrets: &serde_json::Map<String, Value>) -> Result<()> {
+    let mut config = Document::read(path, FileFormat::Yaml)?;
+    let existing = config.get("")?.cloned().unwrap_or_default();
+    if let Some(old) = existing.as_object() {
+        for key in old.keys().filter(|key| !secrets.contains_key(*key)) {
+            config.remove("", key)?;
+        }
+    }
+    for (key, value) in secrets {
+        if existing.get(key) != Some(value) {
+            config.insert("", key, value.clone())?;
+        }
+    }
+    let mut plan = ChangePlan::new();
+    plan.set_private_file(path, Some(config.as_str().to_string()))?;
+    JournalMode::Default.apply("update Goose secrets", &plan)
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_entry() -> Result<keyring::Entry> {
+    keyring::Entry::new("goose", "secrets").map_err(keyring_error)
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_error(e: keyring::Error) -> Error {
+    Error::Io(std::io::Error::other(format!("keyring: {e}")))
+}
+
+/// Copies the value of every environment variable `server` references into
+/// `store`, under the name Goose looks it up by.
+///
+/// Installing a server only writes the names to `env_keys`; Goose then
+/// needs each variable set when it starts. Call this to keep the values
+/// in the secret store instead. An entry `API_KEY` that references
+/// `GITHUB_TOKEN` stores the value of `GITHUB_TOKEN` as `API_KEY`.
+///
+/// Returns the stored keys, sorted.
+///
+/// # Errors
+///
+/// Returns [`Error::MissingEnvVar`] if a referenced variable is not set,
+/// or an error if the store cannot be written. Nothing is stored unless
+/// every variable is set.
+pub fn store_env_secrets(server: &McpServer, store: &SecretStore) -> Result<Vec<String>> {
+    let McpServer::Stdio(stdio) = server else {
+        return Ok(Vec::new());
+    };
+    let secrets = stdio
+        .env
+        .iter()
+        .filter_map(|(key, value)| match value {
+            EnvValue::EnvRef { env } => Some(
+                std::env::var(env)
+                    .map(|value| (key.clone(), value))
+                    .map_err(|_| Error::MissingEnvVar { name: env.clone() }),
+            ),
+            _ => None,
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+    store.update(|stored| {
+        for (key, value) in &secrets {
+            stored.insert(key.clone(), Value::String(value.clone()));
+        }
+    })?;
+    Ok(secrets.into_keys().collect())
+}
+
+/// [`HarnessAdapter`] for Goose.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GooseAdapter;
+
+impl HarnessAdapter for GooseAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::Goose
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(&[".goosehints", "AGENTS.md"])
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/extensions".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(vec![(
+            config_dir(scope)?.join("config.yaml"),
+            FileFormat::Yaml,
+        )])
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.to_goose_value(self.kind(), name)
+    }
+
+    fn validate_mcp_server(&self, server: &McpServer) -> Vec<ValidationIssue> {
+        let McpServer::Stdio(stdio) = server else {
+            return Vec::new();
+        };
+        let mut renamed: Vec<_> = stdio
+            .env
+            .iter()
+            .filter_map(|(key, value)| match value {
+                EnvValue::EnvRef { env } if env != key => Some((key, env)),
+                _ => None,
+            })
+            .collect();
+        renamed.sort();
+        renamed
+            .into_iter()
+            .map(|(key, env)| {
+                ValidationIssue::warning(
+                    format!("env.{key}"),
+                    format!(
+                        "Goose reads env_keys by name, so {key} will not pick up ${env}; \
+                         store it with goose::store_env_secrets"
+                    ),
+                    Some(CODE_ENV_KEY_RENAMED),
+                )
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    use crate::platform::test_utils::EnvGuard;
     use serde_json::json;
 
     #[test]
@@ -447,6 +766,159 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_stdio_server_with_env_keys() {
+        let json = json!({
+            "type": "stdio",
+            "cmd": "node",
+            "envs": {"DEBUG": "true"},
+            "env_keys": ["GITHUB_TOKEN"]
+        });
+
+        let McpServer::Stdio(server) = parse_mcp_server(&json).unwrap() else {
+            panic!("Expected Stdio variant");
+        };
+        assert_eq!(
+            server.env.get("GITHUB_TOKEN"),
+            Some(&EnvValue::env("GITHUB_TOKEN"))
+        );
+        assert_eq!(server.env.get("DEBUG"), Some(&EnvValue::plain("true")));
+    }
+
+    #[test]
+    fn parse_stdio_server_rejects_non_string_env_keys() {
+        let json = json!({"type": "stdio", "cmd": "node", "env_keys": [1]});
+        assert!(parse_mcp_server(&json).is_err());
+    }
+
+    #[test]
+    fn env_keys_round_trip() {
+        let json = json!({
+            "type": "stdio",
+            "cmd": "node",
+            "env_keys": ["A_TOKEN", "B_TOKEN"]
+        });
+
+        let server = parse_mcp_server(&json).unwrap();
+        let native = GooseAdapter.mcp_to_native("test", &server).unwrap();
+        assert_eq!(native["env_keys"], json!(["A_TOKEN", "B_TOKEN"]));
+        assert!(native.get("envs").is_none_or(|envs| envs == &json!({})));
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    #[test]
+    fn file_secret_store_set_get_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut env = EnvGuard::new();
+        env.set_data_dir(&dir.path().join("data"));
+        let path = dir.path().join("secrets.yaml");
+        let store = SecretStore::File(path.clone());
+
+        assert_eq!(store.get("API_KEY").unwrap(), None);
+        store.set("API_KEY", "one").unwrap();
+        store.set("OTHER", "two").unwrap();
+        assert_eq!(store.get("API_KEY").unwrap().as_deref(), Some("one"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        assert!(store.remove("API_KEY").unwrap());
+        assert!(!store.remove("API_KEY").unwrap());
+        assert_eq!(store.get("API_KEY").unwrap(), None);
+        assert_eq!(store.get("OTHER").unwrap().as_deref(), Some("two"));
+
+        let journal = crate::Journal::open_default().unwrap();
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 3);
+        for entry in entries.iter().rev() {
+            journal.rollback(&entry.id).unwrap();
+        }
+        assert!(!path.exists());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    #[test]
+    fn store_env_secrets_copies_values_under_entry_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut env_guard = EnvGuard::new();
+        env_guard.set_data_dir(&dir.path().join("data"));
+        env_guard.set("TEST_GOOSE_SECRET_VALUE", "s3cret");
+        let store = SecretStore::File(dir.path().join("secrets.yaml"));
+
+        let mut env = HashMap::new();
+        env.insert(
+            "API_KEY".to_string(),
+            EnvValue::env("TEST_GOOSE_SECRET_VALUE"),
+        );
+        env.insert("MODE".to_string(), EnvValue::plain("fast"));
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        let stored = store_env_secrets(&server, &store).unwrap();
+
+        assert_eq!(stored, vec!["API_KEY"]);
+        assert_eq!(store.get("API_KEY").unwrap().as_deref(), Some("s3cret"));
+        assert_eq!(store.get("MODE").unwrap(), None);
+    }
+
+    #[test]
+    fn store_env_secrets_requires_every_variable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.yaml");
+        let store = SecretStore::File(path.clone());
+
+        let mut env = HashMap::new();
+        env.insert(
+            "API_KEY".to_string(),
+            EnvValue::env("TEST_GOOSE_SECRET_UNSET_VAR"),
+        );
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        let err = store_env_secrets(&server, &store).unwrap_err();
+        assert!(matches!(err, Error::MissingEnvVar { .. }));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn validate_warns_on_renamed_env_reference() {
+        let mut env = HashMap::new();
+        env.insert("API_KEY".to_string(), EnvValue::env("GITHUB_TOKEN"));
+        env.insert("HOME_DIR".to_string(), EnvValue::env("HOME_DIR"));
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        let issues = GooseAdapter.validate_mcp_server(&server);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "env.API_KEY");
+        assert_eq!(issues[0].code, Some(CODE_ENV_KEY_RENAMED));
+    }
+
     #[test]
     fn parse_stdio_server_timeout_converts_to_milliseconds() {
         let json = json!({
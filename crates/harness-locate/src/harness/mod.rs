@@ -1,29 +1,60 @@
 //! Harness discovery and path resolution.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+use crate::document::Document;
 use crate::error::{Error, Result};
-use crate::mcp::{McpCapabilities, McpServer};
+use crate::inventory::{self, Inventory, InventoryItem, ItemKind, ScopeInventory};
+use crate::journal::{Journal, JournalMode};
+use crate::mcp::{EffectiveMcpServer, McpCapabilities, McpServer, McpSource};
+use crate::plan::{ChangePlan, DirectoryEntry};
+use crate::registry::HarnessRegistry;
 use crate::types::{
     ConfigResource, DirectoryResource, DirectoryStructure, FileFormat, HarnessKind,
-    InstallationStatus, ResourceKind, Scope,
+    InstallationStatus, ManagedPolicy, ResourceKind, Scope,
 };
+use crate::validation::{Severity, ValidationIssue};
+use crate::version::Version;
 
+pub mod adapter;
 pub mod amp_code;
 pub mod claude_code;
+pub mod cline;
+pub mod codex;
 pub mod copilot_cli;
 pub mod crush;
+pub mod cursor;
+pub mod custom;
 pub mod droid;
+pub mod gemini_cli;
 pub mod goose;
 pub mod opencode;
+pub mod roo_code;
+pub mod vscode;
+pub mod zed;
+
+pub use adapter::HarnessAdapter;
 
 /// A discovered harness with resolved base paths.
 ///
 /// Use [`Harness::locate`] to find a harness on the current system.
-#[derive(Debug)]
 pub struct Harness {
     kind: HarnessKind,
+    version: Option<Version>,
+    adapter: Arc<dyn HarnessAdapter>,
+    journal: JournalMode,
+}
+
+impl std::fmt::Debug for Harness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Harness")
+            .field("kind", &self.kind)
+            .field("version", &self.version)
+            .field("journal", &self.journal)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Harness {
@@ -47,18 +78,10 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn locate(kind: HarnessKind) -> Result<Self> {
-        let is_installed = match kind {
-            HarnessKind::ClaudeCode => claude_code::is_installed(),
-            HarnessKind::OpenCode => opencode::is_installed(),
-            HarnessKind::Goose => goose::is_installed(),
-            HarnessKind::AmpCode => amp_code::is_installed(),
-            HarnessKind::CopilotCli => copilot_cli::is_installed(),
-            HarnessKind::Crush => crush::is_installed(),
-            HarnessKind::Droid => droid::is_installed(),
-        };
+        let is_installed = adapter::for_kind(kind).is_installed();
 
         if is_installed {
-            Ok(Self { kind })
+            Ok(Self::new(kind))
         } else {
             Err(Error::NotFound(kind.to_string()))
         }
@@ -79,6 +102,12 @@ impl Harness {
         self.kind
     }
 
+    /// Returns the [`HarnessAdapter`] this harness dispatches to.
+    #[must_use]
+    pub fn adapter(&self) -> &dyn HarnessAdapter {
+        self.adapter.as_ref()
+    }
+
     /// Returns the expected directory name(s) for a resource kind.
     ///
     /// Delegates to [`HarnessKind::directory_names`].
@@ -106,7 +135,82 @@ impl Harness {
     /// ```
     #[must_use]
     pub fn new(kind: HarnessKind) -> Self {
-        Self { kind }
+        Self {
+            kind,
+            version: None,
+            adapter: adapter::for_kind(kind),
+            journal: JournalMode::Default,
+        }
+    }
+
+    /// Sets the harness version that capability lookups and validation
+    /// should target.
+    ///
+    /// Without a version, the capabilities of the latest known release are
+    /// assumed. Use [`Harness::detect_version`] to find the installed one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::{Harness, HarnessKind};
+    /// use harness_locate::version::Version;
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode).with_version(Version::new(1, 0, 0));
+    /// assert!(!harness.mcp_capabilities().http);
+    /// ```
+    #[must_use]
+    pub fn with_version(mut self, version: Version) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Records the changes this harness writes in `journal` instead of the
+    /// default journal.
+    ///
+    /// By default, [`Harness::install_mcp_server`],
+    /// [`Harness::remove_mcp_server`] and [`Harness::install_skill`] record
+    /// each change, with the prior content of every file, in
+    /// [`Journal::open_default`] so it can be rolled back later.
+    #[must_use]
+    pub fn with_journal(mut self, journal: Journal) -> Self {
+        self.journal = JournalMode::Custom(journal);
+        self
+    }
+
+    /// Writes changes without recording them in a journal.
+    #[must_use]
+    pub fn without_journal(mut self) -> Self {
+        self.journal = JournalMode::Disabled;
+        self
+    }
+
+    /// Applies `plan`, recording it in the harness's journal.
+    fn apply_journaled(&self, description: &str, plan: &ChangePlan) -> Result<()> {
+        self.journal.apply(description, plan)
+    }
+
+    /// Returns the version set with [`Harness::with_version`], if any.
+    #[must_use]
+    pub fn version(&self) -> Option<&Version> {
+        self.version.as_ref()
+    }
+
+    /// Detects the version of the installed harness binary.
+    ///
+    /// Runs the first binary found in PATH with `--version`; see
+    /// [`crate::version::detect_version`] for the timeout and caching.
+    /// Returns `Ok(None)` if no binary is installed or its output contains
+    /// no version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the binary cannot be run or does not exit in
+    /// time.
+    pub fn detect_version(&self) -> Result<Option<Version>> {
+        match self.find_first_binary()? {
+            Some(binary) => crate::version::detect_version(&binary),
+            None => Ok(None),
+        }
     }
 
     /// Returns `true` if this harness is installed on the current system.
@@ -126,51 +230,80 @@ impl Harness {
     /// ```
     #[must_use]
     pub fn is_installed(&self) -> bool {
-        match self.kind {
-            HarnessKind::ClaudeCode => claude_code::is_installed(),
-            HarnessKind::OpenCode => opencode::is_installed(),
-            HarnessKind::Goose => goose::is_installed(),
-            HarnessKind::AmpCode => amp_code::is_installed(),
-            HarnessKind::CopilotCli => copilot_cli::is_installed(),
-            HarnessKind::Crush => crush::is_installed(),
-            HarnessKind::Droid => droid::is_installed(),
-        }
+        self.adapter.is_installed()
     }
 
     /// Returns detailed installation status for this harness.
     ///
-    /// Checks both binary availability in PATH and config directory existence.
+    /// Checks both binary availability in PATH and config directory existence,
+    /// and reports any managed policy deployed for the harness (see
+    /// [`Harness::managed_policy`]).
     ///
     /// # Errors
     ///
     /// Returns an error if binary detection fails due to a system error.
     pub fn installation_status(&self) -> Result<InstallationStatus> {
         let binary_path = self.find_first_binary()?;
+        let managed = self.managed_policy()?;
 
-        let config_path = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::global_config_dir().ok(),
-            HarnessKind::OpenCode => opencode::global_config_dir().ok(),
-            HarnessKind::Goose => goose::global_config_dir().ok(),
-            HarnessKind::AmpCode => amp_code::global_config_dir().ok(),
-            HarnessKind::CopilotCli => copilot_cli::global_config_dir().ok(),
-            HarnessKind::Crush => crush::global_config_dir().ok(),
-            HarnessKind::Droid => droid::global_config_dir().ok(),
-        }
-        .filter(|p| p.exists());
+        let config_path = self
+            .adapter
+            .config_dir(&Scope::Global)
+            .ok()
+            .filter(|p| p.exists());
 
         let status = match (binary_path, config_path) {
             (Some(binary_path), Some(config_path)) => InstallationStatus::FullyInstalled {
                 binary_path,
                 config_path,
+                managed,
+            },
+            (Some(binary_path), None) => InstallationStatus::BinaryOnly {
+                binary_path,
+                managed,
+            },
+            (None, Some(config_path)) => InstallationStatus::ConfigOnly {
+                config_path,
+                managed,
+            },
+            (None, None) => match managed {
+                Some(managed) => InstallationStatus::ManagedOnly { managed },
+                None => InstallationStatus::NotInstalled,
             },
-            (Some(binary_path), None) => InstallationStatus::BinaryOnly { binary_path },
-            (None, Some(config_path)) => InstallationStatus::ConfigOnly { config_path },
-            (None, None) => InstallationStatus::NotInstalled,
         };
 
         Ok(status)
     }
 
+    /// Returns the managed (enterprise policy) configuration deployed for
+    /// this harness, or `None` if there is none.
+    ///
+    /// Managed files live in a system-wide location and override user and
+    /// project configuration; they are resolved with [`Scope::Managed`].
+    /// A managed file that cannot be parsed is still listed in
+    /// [`ManagedPolicy::files`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the managed directory cannot be determined.
+    pub fn managed_policy(&self) -> Result<Option<ManagedPolicy>> {
+        let supported = |resource: Result<Option<ConfigResource>>| match resource {
+            Err(Error::UnsupportedScope { .. }) => Ok(None),
+            other => other,
+        };
+        let settings = supported(self.settings(&Scope::Managed))?;
+        let mcp = supported(self.mcp(&Scope::Managed))?;
+        let rules = match self.rules(&Scope::Managed) {
+            Err(Error::UnsupportedScope { .. }) => None,
+            other => other?,
+        };
+        Ok(read_managed_policy(
+            settings.as_ref(),
+            mcp.as_ref(),
+            rules.as_ref(),
+        ))
+    }
+
     fn find_first_binary(&self) -> Result<Option<PathBuf>> {
         for name in self.kind.binary_names() {
             if let Some(path) = crate::detection::find_binary(name)? {
@@ -182,6 +315,9 @@ impl Harness {
 
     /// Returns all harnesses that are installed on the current system.
     ///
+    /// Custom harnesses registered with the
+    /// [`HarnessRegistry`](crate::registry::HarnessRegistry) are included.
+    ///
     /// # Errors
     ///
     /// Returns an error if the home directory or config directory cannot
@@ -199,7 +335,7 @@ impl Harness {
     /// ```
     pub fn installed() -> Result<Vec<Harness>> {
         let mut result = Vec::new();
-        for &kind in HarnessKind::ALL {
+        for kind in HarnessRegistry::global().kinds() {
             let harness = Self::new(kind);
             if harness.is_installed() {
                 result.push(harness);
@@ -212,7 +348,10 @@ impl Harness {
     ///
     /// # Errors
     ///
-    /// Returns an error if the configuration directory cannot be determined.
+    /// Returns [`Error::UnsupportedScope`] for a scope the harness has no
+    /// skills directory for, such as most harnesses' local and managed
+    /// scopes, or an error if the configuration directory cannot be
+    /// determined.
     ///
     /// # Returns
     ///
@@ -231,106 +370,18 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        match self.kind {
-            HarnessKind::ClaudeCode => {
-                let path = claude_code::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
-            }
-            HarnessKind::OpenCode => {
-                let path = opencode::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::Markdown,
-                }))
-            }
-            HarnessKind::Goose => {
-                let path = goose::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::Markdown,
-                }))
-            }
-            HarnessKind::AmpCode => {
-                let path = amp_code::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::Markdown,
-                }))
-            }
-            HarnessKind::CopilotCli => {
-                let path = copilot_cli::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
-            }
-            HarnessKind::Crush => {
-                let path = crush::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::Markdown,
-                }))
-            }
-            HarnessKind::Droid => {
-                let path = droid::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
-            }
-        }
+        self.adapter.skills(scope)
     }
 
-    /// Returns the commands directory resource for the given scope.
+    /// Returns the commands directory resource for the given scope, or
+    /// `None` if the harness has no commands (Goose, Copilot CLI, Crush).
     ///
     /// # Errors
     ///
-    /// Returns an error if the configuration directory cannot be determined.
+    /// Returns [`Error::UnsupportedScope`] for a scope the harness has no
+    /// commands directory for, such as most harnesses' local and managed
+    /// scopes, or an error if the configuration directory cannot be
+    /// determined.
     ///
     /// # Examples
     ///
@@ -344,28 +395,17 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        let path = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::commands_dir(scope)?,
-            HarnessKind::OpenCode => opencode::commands_dir(scope)?,
-            HarnessKind::Goose | HarnessKind::CopilotCli | HarnessKind::Crush => return Ok(None),
-            HarnessKind::AmpCode => amp_code::commands_dir(scope)?,
-            HarnessKind::Droid => droid::commands_dir(scope)?,
-        };
-        Ok(Some(DirectoryResource {
-            exists: path.exists(),
-            path,
-            structure: DirectoryStructure::Flat {
-                file_pattern: "*.md".into(),
-            },
-            file_format: FileFormat::MarkdownWithFrontmatter,
-        }))
+        self.adapter.commands(scope)
     }
 
     /// Returns the plugins directory resource for the given scope.
     ///
     /// # Errors
     ///
-    /// Returns an error if the configuration directory cannot be determined.
+    /// Returns [`Error::UnsupportedScope`] for a scope the harness has no
+    /// plugins directory for, such as most harnesses' local and managed
+    /// scopes, or an error if the configuration directory cannot be
+    /// determined.
     ///
     /// # Returns
     ///
@@ -384,44 +424,17 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn plugins(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        match self.kind {
-            HarnessKind::ClaudeCode => {
-                let path = claude_code::plugins_dir(scope)
-                    .ok_or_else(|| Error::NotFound("plugins directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: ".claude-plugin".into(),
-                    },
-                    file_format: FileFormat::Json,
-                }))
-            }
-            HarnessKind::OpenCode => {
-                let path = opencode::config_dir(scope)?.join("plugin");
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Flat {
-                        file_pattern: "*.{js,ts}".into(),
-                    },
-                    file_format: FileFormat::Json,
-                }))
-            }
-            HarnessKind::Goose
-            | HarnessKind::AmpCode
-            | HarnessKind::CopilotCli
-            | HarnessKind::Crush
-            | HarnessKind::Droid => Ok(None),
-        }
+        self.adapter.plugins(scope)
     }
 
     /// Returns the agents directory resource for the given scope.
     ///
     /// # Errors
     ///
-    /// Returns an error if the configuration directory cannot be determined.
+    /// Returns [`Error::UnsupportedScope`] for a scope the harness has no
+    /// agents directory for, such as most harnesses' local and managed
+    /// scopes, or an error if the configuration directory cannot be
+    /// determined.
     ///
     /// # Returns
     ///
@@ -440,57 +453,7 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn agents(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        match self.kind {
-            HarnessKind::ClaudeCode => {
-                let path = claude_code::agents_dir(scope)
-                    .ok_or_else(|| Error::NotFound("agents directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Flat {
-                        file_pattern: "*.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
-            }
-            HarnessKind::OpenCode => {
-                let path = opencode::config_dir(scope)?.join("agent");
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Flat {
-                        file_pattern: "*.{yaml,json}".into(),
-                    },
-                    file_format: FileFormat::Yaml,
-                }))
-            }
-            HarnessKind::CopilotCli => {
-                let path = copilot_cli::agents_dir(scope)
-                    .ok_or_else(|| Error::NotFound("agents directory".into()))?;
-                // Global and project agents are Markdown
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Flat {
-                        file_pattern: "*.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
-            }
-            HarnessKind::Droid => {
-                let path = droid::agents_dir(scope)
-                    .ok_or_else(|| Error::NotFound("agents directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Flat {
-                        file_pattern: "*.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
-            }
-            HarnessKind::Goose | HarnessKind::AmpCode | HarnessKind::Crush => Ok(None),
-        }
+        self.adapter.agents(scope)
     }
 
     /// Returns the base configuration directory path for the given scope.
@@ -515,19 +478,18 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn config(&self, scope: &Scope) -> Result<PathBuf> {
-        match self.kind {
-            HarnessKind::ClaudeCode => claude_code::config_dir(scope),
-            HarnessKind::OpenCode => opencode::config_dir(scope),
-            HarnessKind::Goose => goose::config_dir(scope),
-            HarnessKind::AmpCode => amp_code::config_dir(scope),
-            HarnessKind::CopilotCli => copilot_cli::config_dir(scope),
-            HarnessKind::Crush => crush::config_dir(scope),
-            HarnessKind::Droid => droid::config_dir(scope),
-        }
+        self.adapter.config_dir(scope)
     }
 
     /// Returns the MCP configuration resource for the given scope.
     ///
+    /// Harnesses may read their config from one of several files (for
+    /// example OpenCode accepts both `opencode.json` and `opencode.jsonc`).
+    /// The first candidate that exists is returned with its matching
+    /// [`FileFormat`]; if none exist, the harness's default file is returned
+    /// with `file_exists` set to `false`. [`Harness::mcp_sources`] lists
+    /// every existing candidate.
+    ///
     /// # Errors
     ///
     /// Returns an error if the configuration directory cannot be determined.
@@ -545,74 +507,65 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn mcp(&self, scope: &Scope) -> Result<Option<ConfigResource>> {
-        let (file, key_path, format) = match self.kind {
-            HarnessKind::ClaudeCode => {
-                // Claude Code CLI uses .mcp.json in config directories:
-                // - Global: ~/.claude/.mcp.json
-                // - Project: .mcp.json (in project root)
-                // Note: ~/.claude.json is a cache/stats file, NOT MCP config
-                let file = match scope {
-                    Scope::Global => claude_code::config_dir(&Scope::Global)?.join(".mcp.json"),
-                    Scope::Project(root) => root.join(".mcp.json"),
-                    Scope::Custom(path) => path.join(".mcp.json"),
-                };
-                (file, "/mcpServers".into(), FileFormat::Json)
-            }
-            HarnessKind::OpenCode => {
-                let base = opencode::config_dir(scope)?;
-                (base.join("opencode.json"), "/mcp".into(), FileFormat::Json)
-            }
-            HarnessKind::Goose => {
-                let base = goose::config_dir(scope)?;
-                (
-                    base.join("config.yaml"),
-                    "/extensions".into(),
-                    FileFormat::Yaml,
-                )
-            }
-            HarnessKind::AmpCode => {
-                let base = amp_code::config_dir(scope)?;
-                (
-                    base.join("amp.json"),
-                    "/amp.mcpServers".into(),
-                    FileFormat::Json,
-                )
-            }
-            HarnessKind::CopilotCli => {
-                // Copilot CLI uses mcp-config.json in config directories
-                let base = copilot_cli::mcp_dir(scope)?;
-                (
-                    base.join("mcp-config.json"),
-                    "/mcpServers".into(),
-                    FileFormat::Json,
-                )
-            }
-            HarnessKind::Crush => {
-                let base = crush::config_dir(scope)?;
-                (base.join("crush.json"), "/mcp".into(), FileFormat::Json)
-            }
-            HarnessKind::Droid => {
-                let base = droid::mcp_dir(scope)?;
-                (
-                    base.join("mcp.json"),
-                    "/mcpServers".into(),
-                    FileFormat::Json,
-                )
-            }
+        let Some(key_path) = self.adapter.mcp_key_path(scope)? else {
+            return Ok(None);
         };
-        Ok(Some(ConfigResource {
-            file_exists: file.exists(),
-            file,
-            key_path,
-            format,
-            schema_url: None,
-        }))
+        Ok(resolve_config(
+            &*self.adapter,
+            self.adapter.mcp_files(scope)?,
+            &key_path,
+        ))
+    }
+
+    /// Returns every existing MCP configuration file the harness reads for
+    /// the given scope, highest precedence first.
+    ///
+    /// [`Harness::mcp`] returns only the first of these, the file that
+    /// writes go to. Claude Code, for example, reads user servers from both
+    /// `~/.claude.json` and `~/.claude/.mcp.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration directory cannot be determined.
+    pub fn mcp_sources(&self, scope: &Scope) -> Result<Vec<ConfigResource>> {
+        let Some(key_path) = self.adapter.mcp_key_path(scope)? else {
+            return Ok(Vec::new());
+        };
+        Ok(self
+            .adapter
+            .mcp_files(scope)?
+            .into_iter()
+            .filter(|(file, _)| file.is_file())
+            .filter_map(|candidate| resolve_config(&*self.adapter, vec![candidate], &key_path))
+            .collect())
+    }
+
+    /// Returns the main settings file for the given scope.
+    ///
+    /// The resource's `key_path` is empty, addressing the whole file.
+    /// Candidates are resolved as described for [`Harness::mcp`]. For
+    /// Claude Code, [`Scope::Local`] selects `.claude/settings.local.json`
+    /// and [`Scope::Managed`] selects `managed-settings.json`.
+    ///
+    /// Returns `Ok(None)` if the harness has no settings file for `scope`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration directory cannot be determined.
+    pub fn settings(&self, scope: &Scope) -> Result<Option<ConfigResource>> {
+        Ok(resolve_config(
+            &*self.adapter,
+            self.adapter.settings_files(scope)?,
+            "",
+        ))
     }
 
     /// Returns the MCP capabilities for this harness.
     ///
     /// Describes what MCP features this harness supports, such as transport
     /// types (stdio, SSE, HTTP) and configuration options (OAuth, headers, etc.).
+    /// Features newer than the version set with [`Harness::with_version`]
+    /// are reported as unsupported.
     ///
     /// # Example
     ///
@@ -625,7 +578,7 @@ impl Harness {
     /// ```
     #[must_use]
     pub fn mcp_capabilities(&self) -> McpCapabilities {
-        McpCapabilities::for_kind(self.kind)
+        McpCapabilities::for_version(self.kind, self.version.as_ref())
     }
 
     /// Checks if this harness supports a specific MCP server configuration.
@@ -654,6 +607,7 @@ impl Harness {
     ///     }),
     ///     enabled: true,
     ///     timeout_ms: None,
+    ///     auto_approve: Vec::new(),
     /// });
     ///
     /// let opencode = Harness::new(HarnessKind::OpenCode);
@@ -712,18 +666,19 @@ impl Harness {
     /// Validates an MCP server configuration for this harness.
     ///
     /// Combines base validation with harness-specific capability checks.
-    /// Returns detailed issues explaining any incompatibilities.
+    /// Returns detailed issues explaining any incompatibilities, including
+    /// features that need a newer release than [`Harness::version`].
     #[must_use]
     pub fn validate_mcp_server(
         &self,
         server: &McpServer,
     ) -> Vec<crate::validation::ValidationIssue> {
-        crate::validation::validate_for_harness(server, self.kind)
+        crate::validation::validate_for_harness_version(server, self.kind, self.version.as_ref())
     }
 
     #[must_use]
     pub fn skill_capabilities(&self) -> Option<crate::validation::SkillCapabilities> {
-        crate::validation::SkillCapabilities::for_kind(self.kind)
+        crate::validation::SkillCapabilities::for_version(self.kind, self.version.as_ref())
     }
 
     #[must_use]
@@ -732,7 +687,12 @@ impl Harness {
         content: &str,
         directory_name: &str,
     ) -> Vec<crate::validation::ValidationIssue> {
-        crate::validation::validate_skill_for_harness(content, directory_name, self.kind)
+        crate::validation::validate_skill_for_harness_version(
+            content,
+            directory_name,
+            self.kind,
+            self.version.as_ref(),
+        )
     }
 
     /// Returns the rules directory resource for the given scope.
@@ -756,26 +716,245 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        let path = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::rules_dir(scope),
-            HarnessKind::OpenCode => opencode::rules_dir(scope),
-            HarnessKind::Goose => goose::rules_dir(scope),
-            HarnessKind::AmpCode => amp_code::rules_dir(scope),
-            HarnessKind::CopilotCli => copilot_cli::rules_dir(scope),
-            HarnessKind::Crush => crush::rules_dir(scope),
-            HarnessKind::Droid => droid::rules_dir(scope),
+        self.adapter.rules(scope)
+    }
+
+    /// Lists the skills installed in the given scope, sorted by name.
+    ///
+    /// Each skill's `SKILL.md` is parsed with
+    /// [`parse_skill`](crate::parse_skill). A skill that fails to parse is
+    /// still listed, with the failure in its `errors`. A scope whose skills
+    /// directory does not exist has no skills.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the skills directory cannot be determined for
+    /// `scope`, or exists but cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, Scope};
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// for skill in harness.list_skills(&Scope::Global)? {
+    ///     println!("{}: {:?}", skill.name, skill.metadata.description());
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn list_skills(&self, scope: &Scope) -> Result<Vec<InventoryItem>> {
+        inventory::list(self.skills(scope)?, ItemKind::Skill, None)
+    }
+
+    /// Lists the custom commands installed in the given scope, sorted by
+    /// name.
+    ///
+    /// Markdown commands are parsed by their YAML frontmatter, and TOML,
+    /// YAML and JSON commands as documents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the commands directory cannot be determined for
+    /// `scope`, or exists but cannot be read.
+    pub fn list_commands(&self, scope: &Scope) -> Result<Vec<InventoryItem>> {
+        inventory::list(self.commands(scope)?, ItemKind::Command, None)
+    }
+
+    /// Lists the agent definitions installed in the given scope, sorted by
+    /// name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agents directory cannot be determined for
+    /// `scope`, or exists but cannot be read.
+    pub fn list_agents(&self, scope: &Scope) -> Result<Vec<InventoryItem>> {
+        inventory::list(self.agents(scope)?, ItemKind::Agent, None)
+    }
+
+    /// Lists the rules files present in the given scope, sorted by name.
+    ///
+    /// Where the rules directory is shared with other files, as with
+    /// `CLAUDE.md` in a project root, only the files the harness reads are
+    /// listed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rules directory cannot be determined for
+    /// `scope`, or exists but cannot be read.
+    pub fn list_rules(&self, scope: &Scope) -> Result<Vec<InventoryItem>> {
+        inventory::list(
+            self.rules(scope)?,
+            ItemKind::Rule,
+            self.adapter.rules_file_names(scope),
+        )
+    }
+
+    /// Lists everything installed for this harness across the global and
+    /// managed scopes, plus the project and local scopes of `project_root`
+    /// when given.
+    ///
+    /// Scopes the harness does not support, and scopes with nothing
+    /// installed, are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory exists but cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, ItemKind};
+    /// use std::path::Path;
+    ///
+    /// let harness = Harness::new(HarnessKind::OpenCode);
+    /// let inventory = harness.inventory(Some(Path::new(".")))?;
+    /// for (scope, agent) in inventory.items_of(ItemKind::Agent) {
+    ///     println!("{scope:?}: {}", agent.name);
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn inventory(&self, project_root: Option<&Path>) -> Result<Inventory> {
+        let mut scopes = vec![Scope::Global, Scope::Managed];
+        if let Some(root) = project_root {
+            scopes.push(Scope::Project(root.to_path_buf()));
+            scopes.push(Scope::Local(root.to_path_buf()));
+        }
+
+        let mut inventory = Inventory {
+            harness: self.kind,
+            scopes: Vec::new(),
         };
-        match path {
-            Some(p) => Ok(Some(DirectoryResource {
-                exists: p.exists(),
-                path: p,
-                structure: DirectoryStructure::Flat {
-                    file_pattern: "*.md".into(),
-                },
-                file_format: FileFormat::Markdown,
-            })),
-            None => Ok(None),
+        for scope in scopes {
+            let mut items = Vec::new();
+            for kind in [
+                ItemKind::Skill,
+                ItemKind::Command,
+                ItemKind::Agent,
+                ItemKind::Rule,
+            ] {
+                let found = match kind {
+                    ItemKind::Skill => self.list_skills(&scope),
+                    ItemKind::Command => self.list_commands(&scope),
+                    ItemKind::Agent => self.list_agents(&scope),
+                    ItemKind::Rule => self.list_rules(&scope),
+                };
+                match found {
+                    Ok(found) => items.extend(found),
+                    Err(e) if inventory::is_absent(&e) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            if !items.is_empty() {
+                inventory.scopes.push(ScopeInventory { scope, items });
+            }
+        }
+        Ok(inventory)
+    }
+
+    /// Installs the skill in the directory `source` into this harness's
+    /// skills directory for `scope`.
+    ///
+    /// The skill is converted for this harness as by
+    /// [`convert_skill`](crate::convert_skill), and the whole directory,
+    /// including `scripts/` and reference files, is copied to a directory
+    /// named after the converted skill. `SKILL.md` is rewritten only if the
+    /// conversion changed it, and `.git` directories are not copied.
+    /// Symbolic links are copied as links rather than followed, and skipped
+    /// on platforms other than Unix.
+    ///
+    /// An installed skill of the same name is replaced. It is moved aside
+    /// until the new copy is in place, so a failed install leaves it as it
+    /// was. The install, binary files included, is recorded in the
+    /// harness's journal (see [`Harness::with_journal`]) so it can be rolled
+    /// back.
+    ///
+    /// Returns the installed skill directory and the conversion issues.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ReadOnlyScope`] for the managed scope,
+    /// [`Error::UnsupportedScope`] if the harness has no skills directory
+    /// for `scope`, [`Error::InvalidConfig`] if the converted skill has
+    /// errors for this harness, a parse error if `SKILL.md` cannot be
+    /// parsed, [`Error::Conflict`] if the installed skill changes while it
+    /// is being replaced, or an I/O error if the files or the journal cannot
+    /// be read or written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, Scope};
+    /// use std::path::Path;
+    ///
+    /// let harness = Harness::new(HarnessKind::OpenCode);
+    /// let (installed, issues) = harness.install_skill(Path::new("skills/pdf"), &Scope::Global)?;
+    /// for issue in issues {
+    ///     println!("{}: {}", issue.field, issue.message);
+    /// }
+    /// println!("installed to {}", installed.display());
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn install_skill(
+        &self,
+        source: &Path,
+        scope: &Scope,
+    ) -> Result<(PathBuf, Vec<ValidationIssue>)> {
+        if matches!(scope, Scope::Managed) {
+            return Err(Error::ReadOnlyScope {
+                harness: self.kind.to_string(),
+                scope: "managed".to_string(),
+            });
+        }
+        let resource = self.skills(scope)?.ok_or_else(|| Error::UnsupportedScope {
+            harness: self.kind.to_string(),
+            scope: format!("{scope:?}"),
+        })?;
+        let file_name = match &resource.structure {
+            DirectoryStructure::Nested { file_name, .. } => file_name.as_str(),
+            DirectoryStructure::Flat { .. } => "SKILL.md",
+        };
+
+        let content = std::fs::read_to_string(source.join(file_name))?;
+        let skill = crate::skill::parse_skill(&content)?;
+        let (converted, issues) = crate::convert::adapt_skill(&skill, self.kind);
+        if let Some(error) = issues.iter().find(|i| i.severity == Severity::Error) {
+            return Err(Error::InvalidConfig(format!(
+                "skill '{}' cannot be installed for {}: {}",
+                skill.name, self.kind, error.message
+            )));
+        }
+
+        if converted.name.is_empty()
+            || converted.name.starts_with('.')
+            || converted.name.contains(['/', '\\'])
+        {
+            return Err(Error::InvalidConfig(format!(
+                "skill name '{}' cannot be used as a directory name",
+                converted.name
+            )));
         }
+        let mut content = crate::plan::read_directory(source)?
+            .ok_or_else(|| Error::InvalidPath(source.to_path_buf()))?;
+        content.retain(|path, _| !path.components().any(|c| c.as_os_str() == ".git"));
+        if converted != skill
+            && let Some(DirectoryEntry::File { content, .. }) =
+                content.get_mut(Path::new(file_name))
+        {
+            *content = converted.to_markdown().into_bytes();
+        }
+
+        let target = resource.path.join(&converted.name);
+        let mut plan = ChangePlan::new();
+        plan.set_directory(&target, Some(content))?;
+        self.apply_journaled(
+            &format!(
+                "install skill '{}' in {}",
+                converted.name,
+                self.kind.as_str()
+            ),
+            &plan,
+        )?;
+        Ok((target, issues))
     }
 
     /// Converts an MCP server configuration to native harness format.
@@ -804,12 +983,14 @@ impl Harness {
     ///     cwd: None,
     ///     enabled: true,
     ///     timeout_ms: None,
+    ///     auto_approve: Vec::new(),
     /// });
     ///
     /// let native = harness.mcp_to_native("my-server", &server).unwrap();
     /// ```
     pub fn mcp_to_native(&self, name: &str, server: &McpServer) -> Result<serde_json::Value> {
-        server.to_native_value(self.kind, name)
+        server.validate_capabilities(self.kind)?;
+        self.adapter.mcp_to_native(name, server)
     }
 
     /// Parses MCP server configurations from harness-native JSON format.
@@ -850,15 +1031,7 @@ impl Harness {
         &self,
         config: &serde_json::Value,
     ) -> Result<HashMap<String, McpServer>> {
-        let servers = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::parse_mcp_servers(config)?,
-            HarnessKind::OpenCode => opencode::parse_mcp_servers(config)?,
-            HarnessKind::Goose => goose::parse_mcp_servers(config)?,
-            HarnessKind::AmpCode => claude_code::parse_mcp_servers(config)?,
-            HarnessKind::CopilotCli => copilot_cli::parse_mcp_servers(config)?,
-            HarnessKind::Crush => crush::parse_mcp_servers(config)?,
-            HarnessKind::Droid => droid::parse_mcp_servers(config)?,
-        };
+        let servers = self.adapter.parse_mcp_servers(config)?;
         Ok(servers.into_iter().collect())
     }
 
@@ -889,15 +1062,7 @@ impl Harness {
         name: &str,
         value: &serde_json::Value,
     ) -> Result<McpServer> {
-        let result = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::parse_mcp_server(value),
-            HarnessKind::OpenCode => opencode::parse_mcp_server(value),
-            HarnessKind::Goose => goose::parse_mcp_server(value),
-            HarnessKind::AmpCode => claude_code::parse_mcp_server(value),
-            HarnessKind::CopilotCli => copilot_cli::parse_mcp_server(value),
-            HarnessKind::Crush => crush::parse_mcp_server(value),
-            HarnessKind::Droid => droid::parse_mcp_server(value),
-        };
+        let result = self.adapter.parse_mcp_server(value);
 
         result.map_err(|e| match e {
             Error::UnsupportedMcpConfig { harness, reason } => Error::UnsupportedMcpConfig {
@@ -907,38 +1072,341 @@ impl Harness {
             other => other,
         })
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn locate_claude_code_when_installed() {
-        if !claude_code::is_installed() {
-            return;
+    /// Returns the MCP servers in effect for `project_root`, merged across
+    /// this harness's config layers.
+    ///
+    /// Layers are read from lowest to highest precedence: the global config,
+    /// the project config, the per-user local config, then the managed
+    /// (enterprise policy) config. When several layers define a server with
+    /// the same name, the highest one wins and the others are listed in
+    /// [`EffectiveMcpServer::shadowed`], so a managed server overrides any
+    /// server of the same name the user configured. Layers whose file does
+    /// not exist, or that the harness does not support, are skipped.
+    ///
+    /// Each layer reads every file of [`Harness::mcp_sources`]; a server in
+    /// an earlier file of the same layer shadows one in a later file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a config file exists but cannot be read or parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use harness_locate::{Harness, HarnessKind};
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// for (name, entry) in harness.effective_mcp_servers(Path::new("."))? {
+    ///     println!("{name}: {}", entry.source.file.display());
+    ///     for shadowed in &entry.shadowed {
+    ///         println!("  shadows {}", shadowed.file.display());
+    ///     }
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn effective_mcp_servers(
+        &self,
+        project_root: &Path,
+    ) -> Result<BTreeMap<String, EffectiveMcpServer>> {
+        let mut layers = Vec::new();
+        for scope in self.mcp_layers(project_root) {
+            match self.mcp_sources(&scope) {
+                // Within a scope, the first file wins
+                Ok(resources) => layers.extend(
+                    resources
+                        .into_iter()
+                        .rev()
+                        .map(|resource| (scope.clone(), resource)),
+                ),
+                Err(Error::UnsupportedScope { .. }) => {}
+                Err(e) => return Err(e),
+            }
         }
-
-        let result = Harness::locate(HarnessKind::ClaudeCode);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap().kind(), HarnessKind::ClaudeCode);
+        self.merge_mcp_layers(layers)
     }
 
-    #[test]
-    fn config_global_for_claude_code() {
-        if !claude_code::is_installed() {
-            return;
+    /// Merges the MCP servers of `layers`, given lowest precedence first.
+    fn merge_mcp_layers(
+        &self,
+        layers: Vec<(Scope, ConfigResource)>,
+    ) -> Result<BTreeMap<String, EffectiveMcpServer>> {
+        let mut merged: BTreeMap<String, EffectiveMcpServer> = BTreeMap::new();
+        for (scope, resource) in layers {
+            let config = Document::read(&resource.file, resource.format)?;
+            let Some(servers) = config
+                .get(&resource.key_path)?
+                .and_then(serde_json::Value::as_object)
+            else {
+                continue;
+            };
+            for (name, native) in servers {
+                let mut entry = EffectiveMcpServer {
+                    server: self.parse_mcp_server_config(name, native).ok(),
+                    native: native.clone(),
+                    source: McpSource {
+                        scope: scope.clone(),
+                        file: resource.file.clone(),
+                    },
+                    shadowed: Vec::new(),
+                };
+                if let Some(lower) = merged.remove(name) {
+                    entry.shadowed.push(lower.source);
+                    entry.shadowed.extend(lower.shadowed);
+                }
+                merged.insert(name.clone(), entry);
+            }
         }
+        Ok(merged)
+    }
 
-        let harness = Harness::locate(HarnessKind::ClaudeCode).unwrap();
-        let path = harness.config(&Scope::Global).unwrap();
-        assert!(path.is_absolute());
-        assert!(path.ends_with(".claude"));
+    /// Returns the scopes this harness merges MCP servers from, lowest
+    /// precedence first.
+    fn mcp_layers(&self, project_root: &Path) -> Vec<Scope> {
+        vec![
+            Scope::Global,
+            Scope::Project(project_root.to_path_buf()),
+            Scope::Local(project_root.to_path_buf()),
+            Scope::Managed,
+        ]
     }
 
-    #[test]
-    fn config_project_for_claude_code() {
-        if !claude_code::is_installed() {
+    /// Installs an MCP server into this harness's native config file.
+    ///
+    /// Loads the file described by [`Harness::mcp`] for `scope`, inserts the
+    /// server under the resource's key path and writes the file back
+    /// atomically. An existing server with the same name is replaced; every
+    /// other key in the file is kept as-is. The file and any missing parent
+    /// objects are created when absent.
+    ///
+    /// For Gemini CLI, the replaced entry's `trust`, `includeTools` and
+    /// `excludeTools` are kept, since [`McpServer`] cannot express them.
+    ///
+    /// The change is recorded in the harness's journal (see
+    /// [`Harness::with_journal`]) so it can be rolled back.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedScope`] if the harness has no MCP config
+    /// for `scope`, [`Error::UnsupportedMcpConfig`] if the server uses
+    /// features this harness does not support, [`Error::LossyEdit`] if the
+    /// entry cannot be written without reformatting the file (edit it with
+    /// [`ConfigDocument::allow_reformat`](crate::ConfigDocument::allow_reformat)
+    /// instead), or an I/O or parse error if the existing file or the
+    /// journal cannot be read or written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, Scope};
+    /// use harness_locate::mcp::{McpServer, StdioMcpServer};
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// let server = McpServer::Stdio(StdioMcpServer {
+    ///     command: "npx".to_string(),
+    ///     args: vec!["-y".to_string(), "@modelcontextprotocol/server-memory".to_string()],
+    ///     env: Default::default(),
+    ///     cwd: None,
+    ///     enabled: true,
+    ///     timeout_ms: None,
+    ///     auto_approve: Vec::new(),
+    /// });
+    ///
+    /// harness.install_mcp_server(&Scope::Global, "memory", &server)?;
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn install_mcp_server(&self, scope: &Scope, name: &str, server: &McpServer) -> Result<()> {
+        let plan = self.plan_install_mcp_server(scope, name, server)?;
+        self.apply_journaled(
+            &format!("install MCP server '{name}' in {}", self.kind.as_str()),
+            &plan,
+        )
+    }
+
+    /// Plans [`Harness::install_mcp_server`] without writing anything.
+    ///
+    /// The returned [`ChangePlan`] holds the config file's current and new
+    /// content; call [`ChangePlan::apply`] to perform the install. The plan
+    /// is empty if the server is already installed exactly as given.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Harness::install_mcp_server`], except that nothing is
+    /// written.
+    pub fn plan_install_mcp_server(
+        &self,
+        scope: &Scope,
+        name: &str,
+        server: &McpServer,
+    ) -> Result<ChangePlan> {
+        let resource = self.mcp_resource(scope)?;
+        let mut native = self.mcp_to_native(name, server)?;
+
+        let mut config = Document::read(&resource.file, resource.format)?;
+        if let Some(existing) = config.get(&resource.key_path)?.and_then(|s| s.get(name)) {
+            self.adapter.merge_mcp_entry(existing, &mut native);
+        }
+        config.insert(&resource.key_path, name, native)?;
+        let root = config.get("")?.cloned().unwrap_or_default();
+        for (key, value) in self.adapter.mcp_install_extras(&root, server)? {
+            config.insert("", &key, value)?;
+        }
+
+        let mut plan = ChangePlan::new();
+        plan.set_file(&resource.file, Some(config.as_str().to_string()))?;
+        Ok(plan)
+    }
+
+    /// Removes an MCP server from this harness's native config file.
+    ///
+    /// Returns `Ok(true)` if the server was present and has been removed,
+    /// or `Ok(false)` if the file or the server entry did not exist, in
+    /// which case nothing is written. The change is recorded in the
+    /// harness's journal, as for [`Harness::install_mcp_server`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedScope`] if the harness has no MCP config
+    /// for `scope`, [`Error::LossyEdit`] as for
+    /// [`Harness::install_mcp_server`], or an I/O or parse error if the
+    /// existing file or the journal cannot be read or written.
+    pub fn remove_mcp_server(&self, scope: &Scope, name: &str) -> Result<bool> {
+        let plan = self.plan_remove_mcp_server(scope, name)?;
+        self.apply_journaled(
+            &format!("remove MCP server '{name}' from {}", self.kind.as_str()),
+            &plan,
+        )?;
+        Ok(!plan.is_empty())
+    }
+
+    /// Plans [`Harness::remove_mcp_server`] without writing anything.
+    ///
+    /// The plan is empty if the file or the server entry does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Harness::remove_mcp_server`], except that nothing is
+    /// written.
+    pub fn plan_remove_mcp_server(&self, scope: &Scope, name: &str) -> Result<ChangePlan> {
+        let resource = self.mcp_resource(scope)?;
+        let mut plan = ChangePlan::new();
+        if !resource.file_exists {
+            return Ok(plan);
+        }
+
+        let mut config = Document::read(&resource.file, resource.format)?;
+        if config.remove(&resource.key_path, name)?.is_some() {
+            plan.set_file(&resource.file, Some(config.as_str().to_string()))?;
+        }
+        Ok(plan)
+    }
+
+    fn mcp_resource(&self, scope: &Scope) -> Result<ConfigResource> {
+        if matches!(scope, Scope::Managed) {
+            return Err(Error::ReadOnlyScope {
+                harness: self.kind.to_string(),
+                scope: "managed".to_string(),
+            });
+        }
+        self.mcp(scope)?.ok_or_else(|| Error::UnsupportedScope {
+            harness: self.kind.to_string(),
+            scope: format!("{scope:?}"),
+        })
+    }
+}
+
+/// Collects the managed files that exist and what they configure.
+fn read_managed_policy(
+    settings: Option<&ConfigResource>,
+    mcp: Option<&ConfigResource>,
+    rules: Option<&DirectoryResource>,
+) -> Option<ManagedPolicy> {
+    let mut policy = ManagedPolicy {
+        files: Vec::new(),
+        settings: None,
+        mcp_servers: Vec::new(),
+    };
+    if let Some(resource) = settings.filter(|r| r.file_exists) {
+        policy.files.push(resource.file.clone());
+        policy.settings = Document::read(&resource.file, resource.format)
+            .ok()
+            .and_then(|doc| doc.get("").ok().flatten().cloned());
+    }
+    if let Some(resource) = mcp.filter(|r| r.file_exists) {
+        if !policy.files.contains(&resource.file) {
+            policy.files.push(resource.file.clone());
+        }
+        if let Ok(doc) = Document::read(&resource.file, resource.format)
+            && let Ok(Some(serde_json::Value::Object(servers))) = doc.get(&resource.key_path)
+        {
+            policy.mcp_servers = servers.keys().cloned().collect();
+        }
+    }
+    if let Some(rules) = rules.filter(|r| r.exists)
+        && let Ok(entries) = std::fs::read_dir(&rules.path)
+    {
+        let mut files: Vec<_> = entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+            .collect();
+        files.sort();
+        policy.files.extend(files);
+    }
+    (!policy.files.is_empty()).then_some(policy)
+}
+
+/// Builds a [`ConfigResource`] from the first candidate file that exists,
+/// falling back to the first candidate.
+fn resolve_config(
+    adapter: &dyn HarnessAdapter,
+    candidates: Vec<(PathBuf, FileFormat)>,
+    key_path: &str,
+) -> Option<ConfigResource> {
+    let index = candidates
+        .iter()
+        .position(|(file, _)| file.is_file())
+        .unwrap_or(0);
+    let (file, format) = candidates.into_iter().nth(index)?;
+    Some(ConfigResource {
+        file_exists: file.is_file(),
+        schema_url: adapter.schema_url(&file).map(String::from),
+        file,
+        key_path: key_path.to_string(),
+        format,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_claude_code_when_installed() {
+        if !claude_code::is_installed() {
+            return;
+        }
+
+        let result = Harness::locate(HarnessKind::ClaudeCode);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().kind(), HarnessKind::ClaudeCode);
+    }
+
+    #[test]
+    fn config_global_for_claude_code() {
+        if !claude_code::is_installed() {
+            return;
+        }
+
+        let harness = Harness::locate(HarnessKind::ClaudeCode).unwrap();
+        let path = harness.config(&Scope::Global).unwrap();
+        assert!(path.is_absolute());
+        assert!(path.ends_with(".claude"));
+    }
+
+    #[test]
+    fn config_project_for_claude_code() {
+        if !claude_code::is_installed() {
             return;
         }
 
@@ -1198,7 +1666,7 @@ mod tests {
 
     #[test]
     fn harness_kind_all_contains_all_variants() {
-        assert_eq!(HarnessKind::ALL.len(), 7);
+        assert_eq!(HarnessKind::ALL.len(), 14);
         assert!(HarnessKind::ALL.contains(&HarnessKind::ClaudeCode));
         assert!(HarnessKind::ALL.contains(&HarnessKind::OpenCode));
         assert!(HarnessKind::ALL.contains(&HarnessKind::Goose));
@@ -1206,6 +1674,13 @@ mod tests {
         assert!(HarnessKind::ALL.contains(&HarnessKind::CopilotCli));
         assert!(HarnessKind::ALL.contains(&HarnessKind::Crush));
         assert!(HarnessKind::ALL.contains(&HarnessKind::Droid));
+        assert!(HarnessKind::ALL.contains(&HarnessKind::Codex));
+        assert!(HarnessKind::ALL.contains(&HarnessKind::GeminiCli));
+        assert!(HarnessKind::ALL.contains(&HarnessKind::Cursor));
+        assert!(HarnessKind::ALL.contains(&HarnessKind::VsCode));
+        assert!(HarnessKind::ALL.contains(&HarnessKind::Cline));
+        assert!(HarnessKind::ALL.contains(&HarnessKind::RooCode));
+        assert!(HarnessKind::ALL.contains(&HarnessKind::Zed));
     }
 
     #[test]
@@ -1261,6 +1736,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         // All harnesses support basic stdio
@@ -1284,6 +1760,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: Some(30000),
+            auto_approve: Vec::new(),
         });
 
         let claude = Harness::new(HarnessKind::ClaudeCode);
@@ -1307,6 +1784,7 @@ mod tests {
             }),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let claude = Harness::new(HarnessKind::ClaudeCode);
@@ -1325,6 +1803,7 @@ mod tests {
             headers: std::collections::HashMap::new(),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let opencode = Harness::new(HarnessKind::OpenCode);
@@ -1346,6 +1825,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None, // Goose doesn't support timeout
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("test-server", &server).unwrap();
@@ -1370,6 +1850,7 @@ mod tests {
             headers: std::collections::HashMap::new(),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("sse-server", &server);
@@ -1387,6 +1868,7 @@ mod tests {
             oauth: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("http-server", &server).unwrap();
@@ -1413,6 +1895,7 @@ mod tests {
             }),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("test", &server);
@@ -1431,6 +1914,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: Some(30000),
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("test", &server);
@@ -1438,16 +1922,14 @@ mod tests {
     }
 
     #[test]
-    fn mcp_to_native_goose_env_vars_resolved() {
+    fn mcp_to_native_goose_env_refs_use_env_keys() {
         use crate::mcp::StdioMcpServer;
         use crate::types::EnvValue;
 
-        // SAFETY: Test runs single-threaded; no concurrent access to this env var
-        unsafe { std::env::set_var("TEST_GOOSE_ENV_VAR", "resolved_test_value") };
-
         let harness = Harness::new(HarnessKind::Goose);
         let mut env = std::collections::HashMap::new();
-        env.insert("API_KEY".to_string(), EnvValue::env("TEST_GOOSE_ENV_VAR"));
+        env.insert("API_KEY".to_string(), EnvValue::env("API_KEY"));
+        env.insert("MODE".to_string(), EnvValue::plain("fast"));
 
         let server = McpServer::Stdio(StdioMcpServer {
             command: "test".to_string(),
@@ -1456,15 +1938,14 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("test", &server).unwrap();
         let obj = result.as_object().unwrap();
-        let envs = obj.get("envs").unwrap().as_object().unwrap();
-
-        assert_eq!(envs.get("API_KEY").unwrap(), "resolved_test_value");
 
-        unsafe { std::env::remove_var("TEST_GOOSE_ENV_VAR") };
+        assert_eq!(obj["env_keys"], serde_json::json!(["API_KEY"]));
+        assert_eq!(obj["envs"], serde_json::json!({"MODE": "fast"}));
     }
 
     #[test]
@@ -1483,6 +1964,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: Some(30000),
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("test-server", &server).unwrap();
@@ -1520,6 +2002,7 @@ mod tests {
             }),
             enabled: true,
             timeout_ms: Some(60000),
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("api-server", &server).unwrap();
@@ -1549,6 +2032,7 @@ mod tests {
             headers: std::collections::HashMap::new(),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("test", &server);
@@ -1572,6 +2056,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("npx-server", &server).unwrap();
@@ -1595,6 +2080,7 @@ mod tests {
             oauth: None,
             enabled: false,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let result = harness.mcp_to_native("simple", &server).unwrap();
@@ -1703,4 +2189,1309 @@ mod tests {
             "error should include server name"
         );
     }
+
+    #[test]
+    fn mcp_prefers_existing_jsonc_for_opencode() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        let harness = Harness::new(HarnessKind::OpenCode);
+
+        let default = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(default.file, dir.path().join("opencode.json"));
+        assert_eq!(default.format, FileFormat::Json);
+        assert!(!default.file_exists);
+
+        std::fs::write(dir.path().join("opencode.jsonc"), "{}").unwrap();
+        let resolved = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(resolved.file, dir.path().join("opencode.jsonc"));
+        assert_eq!(resolved.format, FileFormat::Jsonc);
+        assert!(resolved.file_exists);
+    }
+
+    #[test]
+    fn mcp_opencode_project_falls_back_to_root_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = Scope::Project(dir.path().to_path_buf());
+        let harness = Harness::new(HarnessKind::OpenCode);
+
+        let default = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(default.file, dir.path().join(".opencode/opencode.json"));
+
+        std::fs::write(dir.path().join("opencode.json"), "{}").unwrap();
+        let resolved = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(resolved.file, dir.path().join("opencode.json"));
+    }
+
+    #[test]
+    fn mcp_crush_project_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = Scope::Project(dir.path().to_path_buf());
+        let harness = Harness::new(HarnessKind::Crush);
+
+        let default = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(default.file, dir.path().join(".crush.json"));
+
+        std::fs::write(dir.path().join("crush.json"), "{}").unwrap();
+        let resolved = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(resolved.file, dir.path().join("crush.json"));
+        assert_eq!(resolved.key_path, "/mcp");
+    }
+
+    #[test]
+    fn config_resources_carry_schema_urls() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = Scope::Project(dir.path().to_path_buf());
+        let schema_url = |kind, settings: bool| {
+            let harness = Harness::new(kind);
+            let resource = if settings {
+                harness.settings(&scope)
+            } else {
+                harness.mcp(&scope)
+            };
+            resource.unwrap().unwrap().schema_url
+        };
+
+        assert_eq!(
+            schema_url(HarnessKind::ClaudeCode, true).as_deref(),
+            Some(crate::schema::CLAUDE_CODE_SETTINGS)
+        );
+        assert_eq!(schema_url(HarnessKind::ClaudeCode, false), None);
+        assert_eq!(
+            schema_url(HarnessKind::OpenCode, false).as_deref(),
+            Some(crate::schema::OPENCODE_CONFIG)
+        );
+        assert_eq!(
+            schema_url(HarnessKind::Crush, false).as_deref(),
+            Some(crate::schema::CRUSH_CONFIG)
+        );
+        assert_eq!(schema_url(HarnessKind::Goose, false), None);
+    }
+
+    #[test]
+    fn settings_claude_code_separates_project_and_local() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+
+        let project = harness
+            .settings(&Scope::Project(dir.path().to_path_buf()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(project.file, dir.path().join(".claude/settings.json"));
+        assert_eq!(project.key_path, "");
+
+        let local = harness
+            .settings(&Scope::Local(dir.path().to_path_buf()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(local.file, dir.path().join(".claude/settings.local.json"));
+    }
+
+    #[test]
+    fn settings_copilot_cli_has_no_project_file() {
+        let harness = Harness::new(HarnessKind::CopilotCli);
+        let scope = Scope::Project(PathBuf::from("/some/project"));
+        assert!(harness.settings(&scope).unwrap().is_none());
+    }
+
+    #[test]
+    fn install_mcp_server_writes_existing_jsonc_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("opencode.jsonc");
+        std::fs::write(&file, "{\n  // keep me\n  \"theme\": \"dark\",\n}\n").unwrap();
+
+        let harness = Harness::new(HarnessKind::OpenCode).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "dev", &stdio_server("node"))
+            .unwrap();
+
+        let content = std::fs::read_to_string(&file).unwrap();
+        assert!(content.starts_with("{\n  // keep me\n  \"theme\": \"dark\",\n  \"mcp\": {"));
+        assert!(!dir.path().join("opencode.json").exists());
+    }
+
+    fn stdio_server(command: &str) -> McpServer {
+        McpServer::Stdio(crate::mcp::StdioMcpServer {
+            command: command.to_string(),
+            args: vec!["server.js".to_string()],
+            env: std::collections::HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn install_mcp_server_preserves_other_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(".mcp.json");
+        std::fs::write(
+            &file,
+            r#"{"theme": "dark", "mcpServers": {"existing": {"command": "a"}}}"#,
+        )
+        .unwrap();
+
+        let harness = Harness::new(HarnessKind::ClaudeCode).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "new", &stdio_server("node"))
+            .unwrap();
+
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(config["theme"], "dark");
+        assert_eq!(config["mcpServers"]["existing"]["command"], "a");
+        assert_eq!(config["mcpServers"]["new"]["command"], "node");
+        let keys: Vec<_> = config.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["theme", "mcpServers"]);
+    }
+
+    #[test]
+    fn install_mcp_server_creates_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::OpenCode).without_journal();
+        let scope = Scope::Custom(dir.path().join("opencode"));
+        harness
+            .install_mcp_server(&scope, "dev", &stdio_server("node"))
+            .unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join("opencode/opencode.json")).unwrap();
+        let config: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(config["mcp"]["dev"]["type"], "local");
+    }
+
+    #[test]
+    fn install_mcp_server_replaces_existing_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::Droid).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "dev", &stdio_server("old"))
+            .unwrap();
+        harness
+            .install_mcp_server(&scope, "dev", &stdio_server("new"))
+            .unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join("mcp.json")).unwrap();
+        let config: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(config["mcpServers"].as_object().unwrap().len(), 1);
+        assert_eq!(config["mcpServers"]["dev"]["command"], "new");
+    }
+
+    #[test]
+    fn install_mcp_server_amp_uses_dotted_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("amp.json");
+        std::fs::write(&file, r#"{"amp.notifications.enabled": false}"#).unwrap();
+
+        let harness = Harness::new(HarnessKind::AmpCode).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "dev", &stdio_server("node"))
+            .unwrap();
+
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(config["amp.notifications.enabled"], false);
+        assert_eq!(config["amp.mcpServers"]["dev"]["command"], "node");
+        assert!(config.get("amp").is_none());
+    }
+
+    #[test]
+    fn install_mcp_server_goose_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.yaml");
+        std::fs::write(
+            &file,
+            "GOOSE_PROVIDER: anthropic\nextensions:\n  developer:\n    type: builtin\n    name: developer\n",
+        )
+        .unwrap();
+
+        let harness = Harness::new(HarnessKind::Goose).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "fetch", &stdio_server("uvx"))
+            .unwrap();
+
+        let config: serde_json::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(config["GOOSE_PROVIDER"], "anthropic");
+        assert_eq!(config["extensions"]["developer"]["type"], "builtin");
+        assert_eq!(config["extensions"]["fetch"]["cmd"], "uvx");
+        assert_eq!(config["extensions"]["fetch"]["name"], "fetch");
+    }
+
+    #[test]
+    fn install_mcp_server_codex_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.toml");
+        let original = "\
+# Codex settings
+model = \"o3\" # pinned
+
+[mcp_servers.docs]
+command = \"docs-server\"
+";
+        std::fs::write(&file, original).unwrap();
+
+        let harness = Harness::new(HarnessKind::Codex).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "fetch", &stdio_server("uvx"))
+            .unwrap();
+
+        let content = std::fs::read_to_string(&file).unwrap();
+        assert!(content.starts_with(original), "{content}");
+        assert!(content.contains("[mcp_servers.fetch]\n"), "{content}");
+
+        let mcp = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(mcp.format, FileFormat::Toml);
+        let config: serde_json::Value = toml::from_str(&content).unwrap();
+        let servers = harness.parse_mcp_config(&config).unwrap();
+        assert_eq!(servers["fetch"], stdio_server("uvx"));
+        assert_eq!(servers.len(), 2);
+    }
+
+    #[test]
+    fn install_mcp_server_gemini_cli_keeps_trust_and_tool_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("settings.json");
+        std::fs::write(
+            &file,
+            r#"{"mcpServers": {"dev": {"command": "old", "trust": true, "includeTools": ["read"]}}}"#,
+        )
+        .unwrap();
+
+        let harness = Harness::new(HarnessKind::GeminiCli).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "dev", &stdio_server("node"))
+            .unwrap();
+
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        let dev = &config["mcpServers"]["dev"];
+        assert_eq!(dev["command"], "node");
+        assert_eq!(dev["trust"], true);
+        assert_eq!(dev["includeTools"], serde_json::json!(["read"]));
+    }
+
+    #[test]
+    fn commands_gemini_cli_are_toml() {
+        let harness = Harness::new(HarnessKind::GeminiCli);
+        let scope = Scope::Project(PathBuf::from("/some/project"));
+        let commands = harness.commands(&scope).unwrap().unwrap();
+        assert_eq!(
+            commands.path,
+            PathBuf::from("/some/project/.gemini/commands")
+        );
+        assert_eq!(commands.file_format, FileFormat::Toml);
+    }
+
+    #[test]
+    fn install_mcp_server_vscode_declares_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("mcp.json");
+        std::fs::write(
+            &file,
+            r#"{"inputs": [{"type": "promptString", "id": "token"}], "servers": {}}"#,
+        )
+        .unwrap();
+
+        let server = McpServer::Stdio(crate::mcp::StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::from([(
+                "API_KEY".to_string(),
+                crate::types::EnvValue::prompt("api-key"),
+            )]),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+        let harness = Harness::new(HarnessKind::VsCode).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness.install_mcp_server(&scope, "dev", &server).unwrap();
+
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(
+            config["servers"]["dev"]["env"]["API_KEY"],
+            "${input:api-key}"
+        );
+        assert_eq!(
+            config["inputs"],
+            serde_json::json!([
+                {"type": "promptString", "id": "token"},
+                {"type": "promptString", "id": "api-key", "password": true}
+            ])
+        );
+        let servers = harness.parse_mcp_config(&config).unwrap();
+        assert_eq!(servers["dev"], server);
+    }
+
+    #[test]
+    fn install_mcp_server_cline_keeps_auto_approve() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::Cline).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        let mut server = stdio_server("node");
+        if let McpServer::Stdio(s) = &mut server {
+            s.auto_approve = vec!["read_file".to_string()];
+        }
+        harness.install_mcp_server(&scope, "dev", &server).unwrap();
+
+        let mcp = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(
+            mcp.file,
+            dir.path().join("settings").join("cline_mcp_settings.json")
+        );
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&mcp.file).unwrap()).unwrap();
+        assert_eq!(
+            config["mcpServers"]["dev"]["autoApprove"],
+            serde_json::json!(["read_file"])
+        );
+        let servers = harness.parse_mcp_config(&config).unwrap();
+        assert_eq!(servers["dev"], server);
+    }
+
+    #[test]
+    fn mcp_cline_project_scope_unsupported() {
+        let harness = Harness::new(HarnessKind::Cline);
+        let result = harness.mcp(&Scope::Project(PathBuf::from("/some/project")));
+        assert!(matches!(result, Err(Error::UnsupportedScope { .. })));
+    }
+
+    #[test]
+    fn mcp_roo_code_project_file() {
+        let harness = Harness::new(HarnessKind::RooCode);
+        let mcp = harness
+            .mcp(&Scope::Project(PathBuf::from("/some/project")))
+            .unwrap()
+            .unwrap();
+        assert_eq!(mcp.file, PathBuf::from("/some/project/.roo/mcp.json"));
+        assert_eq!(mcp.key_path, "/mcpServers");
+    }
+
+    #[test]
+    fn install_mcp_server_zed_keeps_editor_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("settings.json");
+        let original = r#"// Zed settings
+{
+  "theme": "One Dark", // picked by hand
+  "context_servers": {
+    "postgres": { "source": "extension", "settings": {} }
+  }
+}
+"#;
+        std::fs::write(&file, original).unwrap();
+
+        let harness = Harness::new(HarnessKind::Zed).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        let server = stdio_server("uvx");
+        harness
+            .install_mcp_server(&scope, "fetch", &server)
+            .unwrap();
+
+        let written = std::fs::read_to_string(&file).unwrap();
+        assert!(written.starts_with("// Zed settings\n"));
+        assert!(written.contains(r#""theme": "One Dark", // picked by hand"#));
+        let mcp = harness.mcp(&scope).unwrap().unwrap();
+        assert_eq!(mcp.key_path, "/context_servers");
+        let document = crate::document::Document::read(&file, FileFormat::Jsonc).unwrap();
+        let config = document.get("").unwrap().unwrap().clone();
+        assert_eq!(config["context_servers"]["fetch"]["source"], "custom");
+        let servers = harness.parse_mcp_config(&config).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers["fetch"], server);
+    }
+
+    #[test]
+    fn rules_cursor_are_mdc() {
+        let harness = Harness::new(HarnessKind::Cursor);
+        let scope = Scope::Project(PathBuf::from("/some/project"));
+        let rules = harness.rules(&scope).unwrap().unwrap();
+        assert_eq!(rules.path, PathBuf::from("/some/project/.cursor/rules"));
+        assert!(matches!(
+            rules.structure,
+            DirectoryStructure::Flat { ref file_pattern } if file_pattern == "*.mdc"
+        ));
+    }
+
+    #[test]
+    fn install_mcp_server_keeps_comments_and_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.yaml");
+        let original = "\
+# Managed by hand
+GOOSE_PROVIDER: anthropic  # default provider
+extensions:
+  developer:
+    enabled: true
+    type: builtin
+
+GOOSE_MODEL: claude-sonnet-4
+";
+        std::fs::write(&file, original).unwrap();
+
+        let harness = Harness::new(HarnessKind::Goose).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "fetch", &stdio_server("uvx"))
+            .unwrap();
+
+        let content = std::fs::read_to_string(&file).unwrap();
+        let (head, tail) = original.split_at(original.find("\nGOOSE_MODEL").unwrap());
+        assert!(content.starts_with(head), "{content}");
+        assert!(content.ends_with(tail), "{content}");
+        assert!(content.contains("\n  fetch:\n"), "{content}");
+
+        assert!(harness.remove_mcp_server(&scope, "fetch").unwrap());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), original);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    #[test]
+    fn effective_mcp_servers_merges_layers() {
+        use crate::platform::test_utils::EnvGuard;
+
+        let home = tempfile::tempdir().unwrap();
+        let global = home.path().join(".claude");
+        std::fs::create_dir_all(&global).unwrap();
+        std::fs::write(
+            global.join(".mcp.json"),
+            r#"{"mcpServers": {"a": {"command": "global-a"}, "b": {"command": "global-b"}}}"#,
+        )
+        .unwrap();
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join(".mcp.json"),
+            r#"{"mcpServers": {"b": {"command": "project-b"}, "c": {"command": "project-c"}}}"#,
+        )
+        .unwrap();
+
+        let mut env = EnvGuard::new();
+        env.set("CLAUDE_CONFIG_DIR", global.to_str().unwrap());
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let servers = harness.effective_mcp_servers(project.path()).unwrap();
+
+        assert_eq!(servers.keys().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert!(matches!(servers["a"].source.scope, Scope::Global));
+        assert!(servers["a"].shadowed.is_empty());
+
+        let b = &servers["b"];
+        assert!(matches!(b.source.scope, Scope::Project(_)));
+        assert_eq!(b.source.file, project.path().join(".mcp.json"));
+        assert_eq!(b.native["command"], "project-b");
+        assert_eq!(b.shadowed.len(), 1);
+        assert_eq!(b.shadowed[0].file, global.join(".mcp.json"));
+        match &b.server {
+            Some(McpServer::Stdio(stdio)) => assert_eq!(stdio.command, "project-b"),
+            other => panic!("unexpected server: {other:?}"),
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    #[test]
+    fn claude_code_global_mcp_reads_user_state_and_mcp_json() {
+        use crate::platform::test_utils::EnvGuard;
+
+        let global = tempfile::tempdir().unwrap();
+        let state = global.path().join(".claude.json");
+        let legacy = global.path().join(".mcp.json");
+        std::fs::write(
+            &state,
+            r#"{"mcpServers": {"a": {"command": "state-a"}, "b": {"command": "state-b"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &legacy,
+            r#"{"mcpServers": {"b": {"command": "legacy-b"}, "c": {"command": "legacy-c"}}}"#,
+        )
+        .unwrap();
+        let project = tempfile::tempdir().unwrap();
+
+        let mut env = EnvGuard::new();
+        env.set("CLAUDE_CONFIG_DIR", global.path().to_str().unwrap());
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+
+        assert_eq!(harness.mcp(&Scope::Global).unwrap().unwrap().file, state);
+        let sources: Vec<_> = harness
+            .mcp_sources(&Scope::Global)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.file)
+            .collect();
+        assert_eq!(sources, [state.clone(), legacy.clone()]);
+
+        let servers = harness.effective_mcp_servers(project.path()).unwrap();
+        assert_eq!(servers.keys().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(servers["b"].source.file, state);
+        assert_eq!(servers["b"].native["command"], "state-b");
+        assert_eq!(servers["b"].shadowed[0].file, legacy);
+        assert_eq!(servers["c"].source.file, legacy);
+    }
+
+    #[test]
+    fn effective_mcp_servers_managed_layer_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".mcp.json"),
+            r#"{"mcpServers": {"dev": {"command": "project-dev"}}}"#,
+        )
+        .unwrap();
+        let managed_file = dir.path().join("managed-mcp.json");
+        std::fs::write(
+            &managed_file,
+            r#"{"mcpServers": {"dev": {"command": "managed-dev"}}}"#,
+        )
+        .unwrap();
+
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let project_scope = Scope::Project(dir.path().to_path_buf());
+        let project = harness.mcp(&project_scope).unwrap().unwrap();
+        let mut managed = harness.mcp(&Scope::Managed).unwrap().unwrap();
+        managed.file = managed_file.clone();
+        let servers = harness
+            .merge_mcp_layers(vec![(project_scope, project), (Scope::Managed, managed)])
+            .unwrap();
+
+        let dev = &servers["dev"];
+        assert!(matches!(dev.source.scope, Scope::Managed));
+        assert_eq!(dev.source.file, managed_file);
+        assert_eq!(dev.native["command"], "managed-dev");
+        assert_eq!(dev.shadowed.len(), 1);
+        assert!(matches!(dev.shadowed[0].scope, Scope::Project(_)));
+        assert!(matches!(
+            harness.mcp_layers(dir.path()).last(),
+            Some(Scope::Managed)
+        ));
+    }
+
+    #[test]
+    fn effective_mcp_servers_keeps_unparseable_entries() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(project.path().join(".goose")).unwrap();
+        std::fs::write(
+            project.path().join(".goose/config.yaml"),
+            "extensions:\n  developer:\n    type: builtin\n    name: developer\n",
+        )
+        .unwrap();
+
+        let harness = Harness::new(HarnessKind::Goose);
+        let servers = harness.effective_mcp_servers(project.path()).unwrap();
+        let developer = &servers["developer"];
+        assert!(developer.server.is_none());
+        assert_eq!(developer.native["type"], "builtin");
+    }
+
+    #[test]
+    fn effective_mcp_servers_skips_unsupported_layers() {
+        let project = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::AmpCode);
+        assert!(harness.effective_mcp_servers(project.path()).is_ok());
+    }
+
+    #[test]
+    fn plan_install_mcp_server_does_not_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(".mcp.json");
+        std::fs::write(&file, "{\n  \"mcpServers\": {}\n}\n").unwrap();
+
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        let plan = harness
+            .plan_install_mcp_server(&scope, "dev", &stdio_server("node"))
+            .unwrap();
+
+        assert_eq!(plan.changes().len(), 1);
+        assert_eq!(plan.changes()[0].kind, crate::plan::ChangeKind::Modify);
+        assert!(plan.diff().contains("+    \"dev\": {"));
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "{\n  \"mcpServers\": {}\n}\n"
+        );
+
+        plan.apply().unwrap();
+        assert!(std::fs::read_to_string(&file).unwrap().contains("\"dev\""));
+        plan.revert().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "{\n  \"mcpServers\": {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn plan_install_mcp_server_is_empty_when_up_to_date() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::Droid).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "dev", &stdio_server("node"))
+            .unwrap();
+
+        let plan = harness
+            .plan_install_mcp_server(&scope, "dev", &stdio_server("node"))
+            .unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn plan_remove_mcp_server_creates_nothing_for_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::Goose);
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        let plan = harness.plan_remove_mcp_server(&scope, "dev").unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn install_mcp_server_unsupported_server_leaves_file_untouched() {
+        use crate::mcp::SseMcpServer;
+
+        let dir = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::Goose).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        let server = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".to_string(),
+            headers: std::collections::HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        assert!(harness.install_mcp_server(&scope, "sse", &server).is_err());
+        assert!(!dir.path().join("config.yaml").exists());
+    }
+
+    #[test]
+    fn install_mcp_server_rejects_non_object_key_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".mcp.json"), r#"{"mcpServers": []}"#).unwrap();
+
+        let harness = Harness::new(HarnessKind::ClaudeCode).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        let result = harness.install_mcp_server(&scope, "dev", &stdio_server("node"));
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn remove_mcp_server_removes_only_named_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(".mcp.json");
+        std::fs::write(
+            &file,
+            r#"{"mcpServers": {"a": {"command": "a"}, "b": {"command": "b"}, "c": {"command": "c"}}}"#,
+        )
+        .unwrap();
+
+        let harness = Harness::new(HarnessKind::ClaudeCode).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        assert!(harness.remove_mcp_server(&scope, "b").unwrap());
+        assert!(!harness.remove_mcp_server(&scope, "b").unwrap());
+
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        let keys: Vec<_> = config["mcpServers"].as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["a", "c"]);
+    }
+
+    #[test]
+    fn install_and_remove_mcp_server_are_journaled() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config/.mcp.json");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "{}\n").unwrap();
+        let journal = Journal::new(dir.path().join("journal"));
+
+        let harness = Harness::new(HarnessKind::ClaudeCode).with_journal(journal.clone());
+        let scope = Scope::Custom(dir.path().join("config"));
+        harness
+            .install_mcp_server(&scope, "dev", &stdio_server("node"))
+            .unwrap();
+        assert!(harness.remove_mcp_server(&scope, "dev").unwrap());
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].description.contains("install MCP server 'dev'"));
+        journal.rollback(&entries[1].id).unwrap();
+        let config = std::fs::read_to_string(&file).unwrap();
+        assert!(config.contains("\"dev\""));
+        journal.rollback(&entries[0].id).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "{}\n");
+    }
+
+    #[test]
+    fn remove_mcp_server_missing_file_is_noop() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::ClaudeCode).without_journal();
+        let scope = Scope::Custom(dir.path().to_path_buf());
+        assert!(!harness.remove_mcp_server(&scope, "dev").unwrap());
+        assert!(!dir.path().join(".mcp.json").exists());
+    }
+
+    #[test]
+    fn install_mcp_server_project_scope_unsupported_for_amp() {
+        let harness = Harness::new(HarnessKind::AmpCode).without_journal();
+        let result = harness.install_mcp_server(
+            &Scope::Project(PathBuf::from("/some/project")),
+            "dev",
+            &stdio_server("node"),
+        );
+        assert!(matches!(result, Err(Error::UnsupportedScope { .. })));
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    #[test]
+    fn install_mcp_server_local_scope_writes_user_state_project_entry() {
+        use crate::platform::test_utils::EnvGuard;
+
+        let home = tempfile::tempdir().unwrap();
+        let global = home.path().join(".claude");
+        std::fs::create_dir_all(&global).unwrap();
+        let state = global.join(".claude.json");
+        std::fs::write(&state, r#"{"numStartups": 3}"#).unwrap();
+        let project = tempfile::tempdir().unwrap();
+
+        let mut env = EnvGuard::new();
+        env.set("CLAUDE_CONFIG_DIR", global.to_str().unwrap());
+        let harness = Harness::new(HarnessKind::ClaudeCode).without_journal();
+        let scope = Scope::Local(project.path().to_path_buf());
+        harness
+            .install_mcp_server(&scope, "secret", &stdio_server("node"))
+            .unwrap();
+
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&state).unwrap()).unwrap();
+        assert_eq!(config["numStartups"], 3);
+        let key = project.path().to_string_lossy();
+        assert_eq!(
+            config["projects"][key.as_ref()]["mcpServers"]["secret"]["command"],
+            "node"
+        );
+        assert!(!project.path().join(".mcp.json").exists());
+
+        let servers = harness.effective_mcp_servers(project.path()).unwrap();
+        assert!(matches!(servers["secret"].source.scope, Scope::Local(_)));
+    }
+
+    #[test]
+    fn local_scope_unsupported_without_private_config() {
+        let scope = Scope::Local(PathBuf::from("/some/project"));
+        for kind in HarnessKind::ALL
+            .iter()
+            .filter(|kind| **kind != HarnessKind::ClaudeCode)
+        {
+            let harness = Harness::new(*kind).without_journal();
+            assert!(matches!(
+                harness.config(&scope),
+                Err(Error::UnsupportedScope { .. })
+            ));
+            assert!(matches!(
+                harness.rules(&scope),
+                Err(Error::UnsupportedScope { .. })
+            ));
+            assert!(matches!(
+                harness.install_mcp_server(&scope, "dev", &stdio_server("node")),
+                Err(Error::UnsupportedScope { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn read_managed_policy_reports_files_and_servers() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = dir.path().join("managed-settings.json");
+        let mcp = dir.path().join("managed-mcp.json");
+        std::fs::write(&settings, r#"{"permissions": {"deny": ["WebFetch"]}}"#).unwrap();
+        std::fs::write(&mcp, r#"{"mcpServers": {"corp": {"command": "corp-mcp"}}}"#).unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "Be careful.\n").unwrap();
+        let resource = |file: PathBuf, key_path: &str| ConfigResource {
+            file_exists: file.is_file(),
+            file,
+            key_path: key_path.to_string(),
+            format: FileFormat::Json,
+            schema_url: None,
+        };
+        let rules = DirectoryResource {
+            path: dir.path().to_path_buf(),
+            exists: true,
+            structure: DirectoryStructure::Flat {
+                file_pattern: "*.md".into(),
+            },
+            file_format: FileFormat::Markdown,
+        };
+
+        let policy = read_managed_policy(
+            Some(&resource(settings.clone(), "")),
+            Some(&resource(mcp.clone(), "/mcpServers")),
+            Some(&rules),
+        )
+        .unwrap();
+        assert_eq!(policy.files, [settings, mcp, dir.path().join("CLAUDE.md")]);
+        assert_eq!(
+            policy.settings.unwrap()["permissions"]["deny"][0],
+            "WebFetch"
+        );
+        assert_eq!(policy.mcp_servers, ["corp"]);
+    }
+
+    #[test]
+    fn read_managed_policy_none_without_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = ConfigResource {
+            file: dir.path().join("managed-settings.json"),
+            file_exists: false,
+            key_path: String::new(),
+            format: FileFormat::Json,
+            schema_url: None,
+        };
+        assert!(read_managed_policy(Some(&missing), None, None).is_none());
+    }
+
+    #[test]
+    fn managed_scope_resolves_claude_code_policy_files() {
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let mcp = harness.mcp(&Scope::Managed).unwrap().unwrap();
+        assert!(mcp.file.ends_with("managed-mcp.json"));
+        assert_eq!(mcp.key_path, "/mcpServers");
+        let settings = harness.settings(&Scope::Managed).unwrap().unwrap();
+        assert!(settings.file.ends_with("managed-settings.json"));
+    }
+
+    #[test]
+    fn managed_scope_is_read_only() {
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        assert!(matches!(
+            harness.install_mcp_server(&Scope::Managed, "dev", &stdio_server("node")),
+            Err(Error::ReadOnlyScope { .. })
+        ));
+        assert!(matches!(
+            harness.plan_remove_mcp_server(&Scope::Managed, "dev"),
+            Err(Error::ReadOnlyScope { .. })
+        ));
+    }
+
+    #[test]
+    fn managed_scope_unsupported_without_policy_files() {
+        let harness = Harness::new(HarnessKind::Goose);
+        assert!(matches!(
+            harness.mcp(&Scope::Managed),
+            Err(Error::UnsupportedScope { .. })
+        ));
+        assert!(harness.managed_policy().unwrap().is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn detect_version_uses_binary_on_path() {
+        use crate::platform::test_utils::EnvGuard;
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = tempfile::tempdir().unwrap();
+        let goose = bin.path().join("goose");
+        std::fs::write(&goose, "#!/bin/sh\necho 'goose 1.0.3'\n").unwrap();
+        std::fs::set_permissions(&goose, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut env = EnvGuard::new();
+        let path = std::env::var("PATH").unwrap_or_default();
+        env.set("PATH", &format!("{}:{path}", bin.path().display()));
+        let harness = Harness::new(HarnessKind::Goose);
+        let version = harness.detect_version().unwrap().unwrap();
+        assert_eq!(version, Version::new(1, 0, 3));
+
+        let harness = harness.with_version(version);
+        assert!(!harness.mcp_capabilities().http);
+        let server = McpServer::Http(crate::mcp::HttpMcpServer {
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::new(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+        assert!(!harness.supports_mcp_server(&server));
+        assert!(
+            harness
+                .validate_mcp_server(&server)
+                .iter()
+                .any(|issue| { issue.code == Some(crate::validation::CODE_VERSION_UNSUPPORTED) })
+        );
+    }
+
+    fn register_custom(id: &str, global: &Path) -> HarnessKind {
+        let definition = crate::registry::HarnessDefinition::from_toml(&format!(
+            r#"
+            id = "{id}"
+            global_dir = '{}'
+            project_dir = ".custom"
+
+            [commands]
+            dir = "prompts"
+
+            [rules]
+            dir = "."
+            project_dir = "."
+
+            [mcp]
+            file = "config.json"
+            project_file = ".custom.json"
+            key_path = "/tools/mcp"
+            capabilities = {{ stdio = true, http = true, headers = true, toggle = true }}
+
+            [mcp.fields]
+            command = "cmd"
+            args = "argv"
+            transport = "kind"
+            stdio = "local"
+            http = "remote"
+            "#,
+            global.display()
+        ))
+        .unwrap();
+        HarnessRegistry::global().register(definition).unwrap()
+    }
+
+    #[test]
+    fn custom_harness_resolves_resources() {
+        let dir = tempfile::tempdir().unwrap();
+        let global = dir.path().join("global");
+        let project = dir.path().join("project");
+        let harness = Harness::new(register_custom("test-custom-paths", &global));
+
+        assert!(!harness.is_installed());
+        std::fs::create_dir(&global).unwrap();
+        assert!(harness.is_installed());
+        assert!(
+            Harness::installed()
+                .unwrap()
+                .iter()
+                .any(|h| h.kind() == harness.kind())
+        );
+
+        assert_eq!(harness.config(&Scope::Global).unwrap(), global);
+        assert_eq!(
+            harness.config(&Scope::Project(project.clone())).unwrap(),
+            project.join(".custom")
+        );
+        let commands = harness.commands(&Scope::Global).unwrap().unwrap();
+        assert_eq!(commands.path, global.join("prompts"));
+        assert_eq!(
+            commands.structure,
+            DirectoryStructure::Flat {
+                file_pattern: "*.md".into()
+            }
+        );
+        assert!(harness.skills(&Scope::Global).unwrap().is_none());
+        assert_eq!(
+            harness
+                .rules(&Scope::Project(project.clone()))
+                .unwrap()
+                .unwrap()
+                .path,
+            project
+        );
+        assert_eq!(
+            harness
+                .mcp(&Scope::Project(project.clone()))
+                .unwrap()
+                .unwrap()
+                .file,
+            project.join(".custom.json")
+        );
+        assert!(matches!(
+            harness.config(&Scope::Local(project)),
+            Err(Error::UnsupportedScope { .. })
+        ));
+    }
+
+    #[test]
+    fn custom_harness_mcp_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness =
+            Harness::new(register_custom("test-custom-mcp", dir.path())).without_journal();
+        let mut server = stdio_server("node");
+        if let McpServer::Stdio(s) = &mut server {
+            s.env
+                .insert("TOKEN".into(), crate::types::EnvValue::env("TOKEN"));
+        }
+
+        harness
+            .install_mcp_server(&Scope::Global, "dev", &server)
+            .unwrap();
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("config.json")).unwrap())
+                .unwrap();
+        assert_eq!(
+            written["tools"]["mcp"]["dev"],
+            serde_json::json!({
+                "kind": "local",
+                "cmd": "node",
+                "argv": ["server.js"],
+                "env": {"TOKEN": "${TOKEN}"},
+                "enabled": true,
+            })
+        );
+
+        let servers = harness.parse_mcp_config(&written).unwrap();
+        assert_eq!(servers["dev"], server);
+
+        let sse = McpServer::Sse(crate::mcp::SseMcpServer {
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+        assert!(!harness.supports_mcp_server(&sse));
+    }
+
+    #[test]
+    fn unregistered_custom_harness_is_not_found() {
+        let harness = Harness::new(HarnessKind::Custom("test-custom-missing"));
+        assert!(!harness.is_installed());
+        assert!(matches!(
+            harness.config(&Scope::Global),
+            Err(Error::NotFound(_))
+        ));
+        assert_eq!(harness.kind().to_string(), "test-custom-missing");
+    }
+
+    #[test]
+    fn list_project_resources_for_claude_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".claude/skills/pdf")).unwrap();
+        std::fs::create_dir_all(root.join(".claude/commands")).unwrap();
+        std::fs::create_dir_all(root.join(".claude/agents")).unwrap();
+        std::fs::write(
+            root.join(".claude/skills/pdf/SKILL.md"),
+            "---\nname: pdf\ndescription: Work with PDFs\n---\nUse it.\n",
+        )
+        .unwrap();
+        std::fs::write(root.join(".claude/commands/review.md"), "Review.\n").unwrap();
+        std::fs::write(
+            root.join(".claude/agents/planner.md"),
+            "---\nname: planner\ndescription: Plans\n---\nPlan.\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("CLAUDE.md"), "# Project\n").unwrap();
+        std::fs::write(root.join("CLAUDE.local.md"), "# Mine\n").unwrap();
+        std::fs::write(root.join("README.md"), "# Readme\n").unwrap();
+
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let project = Scope::Project(root.to_path_buf());
+        let names = |items: Vec<InventoryItem>| -> Vec<String> {
+            items.into_iter().map(|item| item.name).collect()
+        };
+        assert_eq!(names(harness.list_skills(&project).unwrap()), ["pdf"]);
+        assert_eq!(names(harness.list_commands(&project).unwrap()), ["review"]);
+        assert_eq!(names(harness.list_agents(&project).unwrap()), ["planner"]);
+        assert_eq!(names(harness.list_rules(&project).unwrap()), ["CLAUDE.md"]);
+        assert_eq!(
+            names(
+                harness
+                    .list_rules(&Scope::Local(root.to_path_buf()))
+                    .unwrap()
+            ),
+            ["CLAUDE.local.md"]
+        );
+
+        let inventory = harness.inventory(Some(root)).unwrap();
+        let project_items: Vec<_> = inventory
+            .scopes
+            .iter()
+            .find(|s| matches!(&s.scope, Scope::Project(p) if p == root))
+            .unwrap()
+            .items
+            .iter()
+            .map(|item| (item.kind, item.name.as_str()))
+            .collect();
+        assert_eq!(
+            project_items,
+            [
+                (ItemKind::Skill, "pdf"),
+                (ItemKind::Command, "review"),
+                (ItemKind::Agent, "planner"),
+                (ItemKind::Rule, "CLAUDE.md"),
+            ]
+        );
+        assert!(
+            inventory
+                .items_of(ItemKind::Agent)
+                .any(|(scope, item)| matches!(scope, Scope::Project(_)) && item.name == "planner")
+        );
+    }
+
+    #[test]
+    fn inventory_skips_empty_project_scopes() {
+        let dir = tempfile::tempdir().unwrap();
+        let harness = Harness::new(HarnessKind::OpenCode);
+
+        let inventory = harness.inventory(Some(dir.path())).unwrap();
+
+        assert_eq!(inventory.harness, HarnessKind::OpenCode);
+        assert!(
+            inventory
+                .scopes
+                .iter()
+                .all(|s| !matches!(s.scope, Scope::Project(_) | Scope::Local(_)))
+        );
+    }
+
+    fn write_source_skill(dir: &Path, skill_md: &str) {
+        std::fs::create_dir_all(dir.join("scripts")).unwrap();
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join("SKILL.md"), skill_md).unwrap();
+        std::fs::write(dir.join("scripts/fill.py"), "print('fill')\n").unwrap();
+        std::fs::write(dir.join("reference.md"), "# Reference\n").unwrap();
+        std::fs::write(dir.join(".git/HEAD"), "ref: main\n").unwrap();
+    }
+
+    #[test]
+    fn install_skill_converts_and_copies_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source/PDF Tools");
+        write_source_skill(&source, "---\nname: PDF Tools\n---\nFill in PDF forms.\n");
+        let project = dir.path().join("project");
+        let harness = Harness::new(HarnessKind::OpenCode).without_journal();
+
+        let (installed, issues) = harness
+            .install_skill(&source, &Scope::Project(project.clone()))
+            .unwrap();
+
+        assert_eq!(installed, project.join(".opencode/skill/pdf-tools"));
+        assert_eq!(issues.len(), 2);
+        let skill =
+            crate::parse_skill(&std::fs::read_to_string(installed.join("SKILL.md")).unwrap())
+                .unwrap();
+        assert_eq!(skill.name, "pdf-tools");
+        assert_eq!(skill.description.as_deref(), Some("Fill in PDF forms."));
+        assert!(installed.join("scripts/fill.py").is_file());
+        assert!(installed.join("reference.md").is_file());
+        assert!(!installed.join(".git").exists());
+        assert!(
+            crate::validation::validate_skill_for_harness(
+                &std::fs::read_to_string(installed.join("SKILL.md")).unwrap(),
+                "pdf-tools",
+                HarnessKind::OpenCode,
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn install_skill_keeps_unchanged_skill_file_and_replaces_existing() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("pdf");
+        let skill_md = "---\nname: pdf\ndescription: PDFs  # kept as written\n---\nBody\n";
+        write_source_skill(&source, skill_md);
+        let project = dir.path().join("project");
+        let scope = Scope::Project(project.clone());
+        let harness = Harness::new(HarnessKind::ClaudeCode).without_journal();
+        let stale = project.join(".claude/skills/pdf/stale.md");
+        std::fs::create_dir_all(stale.parent().unwrap()).unwrap();
+        std::fs::write(&stale, "old").unwrap();
+
+        let (installed, issues) = harness.install_skill(&source, &scope).unwrap();
+
+        assert!(issues.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(installed.join("SKILL.md")).unwrap(),
+            skill_md
+        );
+        assert!(!stale.exists());
+        assert!(!project.join(".claude/skills/.pdf.installing").exists());
+        assert!(!project.join(".claude/skills/.pdf.replaced").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn install_skill_copies_symlinks_without_following_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("pdf");
+        write_source_skill(&source, "---\nname: pdf\ndescription: PDFs\n---\n");
+        std::fs::write(dir.path().join("secret.txt"), "outside").unwrap();
+        std::os::unix::fs::symlink("..", source.join("loop")).unwrap();
+        std::os::unix::fs::symlink("../secret.txt", source.join("outside.txt")).unwrap();
+        std::os::unix::fs::symlink("reference.md", source.join("ref.md")).unwrap();
+        let project = dir.path().join("project");
+
+        let (installed, _) = Harness::new(HarnessKind::ClaudeCode)
+            .without_journal()
+            .install_skill(&source, &Scope::Project(project))
+            .unwrap();
+
+        let link = |name: &str| std::fs::read_link(installed.join(name)).unwrap();
+        assert_eq!(link("loop"), Path::new(".."));
+        assert_eq!(link("outside.txt"), Path::new("../secret.txt"));
+        assert_eq!(
+            std::fs::read_to_string(installed.join("ref.md")).unwrap(),
+            "# Reference\n"
+        );
+    }
+
+    #[test]
+    fn install_skill_rejects_read_only_and_unsupported_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("pdf");
+        write_source_skill(&source, "---\nname: pdf\ndescription: PDFs\n---\n");
+
+        assert!(matches!(
+            Harness::new(HarnessKind::ClaudeCode).install_skill(&source, &Scope::Managed),
+            Err(Error::ReadOnlyScope { .. })
+        ));
+        assert!(
+            Harness::new(HarnessKind::Zed)
+                .without_journal()
+                .install_skill(&source, &Scope::Project(dir.path().to_path_buf()))
+                .is_err()
+        );
+
+        write_source_skill(&source, "---\nname: ../escape\n---\n");
+        assert!(matches!(
+            Harness::new(HarnessKind::ClaudeCode)
+                .without_journal()
+                .install_skill(&source, &Scope::Project(dir.path().to_path_buf())),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn install_skill_is_journaled() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("pdf");
+        write_source_skill(&source, "---\nname: pdf\ndescription: PDFs\n---\n");
+        std::fs::write(source.join("form.pdf"), [0x25, 0x50, 0xff, 0x00]).unwrap();
+        let project = dir.path().join("project");
+        let scope = Scope::Project(project.clone());
+        let installed_dir = project.join(".claude/skills/pdf");
+        std::fs::create_dir_all(&installed_dir).unwrap();
+        std::fs::write(installed_dir.join("SKILL.md"), "old\n").unwrap();
+        let journal = Journal::new(dir.path().join("journal"));
+        let harness = Harness::new(HarnessKind::ClaudeCode).with_journal(journal.clone());
+
+        let (installed, _) = harness.install_skill(&source, &scope).unwrap();
+        assert_eq!(
+            std::fs::read(installed.join("form.pdf")).unwrap(),
+            [0x25, 0x50, 0xff, 0x00]
+        );
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].files().collect::<Vec<_>>(),
+            [installed_dir.as_path()]
+        );
+        journal.rollback(&entries[0].id).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(installed_dir.join("SKILL.md")).unwrap(),
+            "old\n"
+        );
+        assert!(!installed_dir.join("form.pdf").exists());
+    }
 }
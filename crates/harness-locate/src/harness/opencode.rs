@@ -5,12 +5,13 @@
 //! - **Project**: `.opencode/` in project root
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
 use crate::mcp::{HttpMcpServer, McpServer, OAuthConfig, StdioMcpServer};
 use crate::platform;
-use crate::types::{EnvValue, HarnessKind, Scope};
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
 
 /// Returns the global OpenCode configuration directory.
 ///
@@ -42,6 +43,14 @@ pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
         Scope::Global => Ok(global_config_dir()?.join("command")),
         Scope::Project(root) => Ok(project_config_dir(root).join("command")),
         Scope::Custom(path) => Ok(path.join("command")),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "OpenCode".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "OpenCode".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -53,6 +62,14 @@ pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
         Scope::Global => global_config_dir(),
         Scope::Project(root) => Ok(project_config_dir(root)),
         Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "OpenCode".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "OpenCode".to_string(),
+            scope: "managed".to_string(),
+        }),
     }
 }
 
@@ -74,6 +91,7 @@ pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => global_config_dir().ok().map(|p| p.join("skill")),
         Scope::Project(root) => Some(project_config_dir(root).join("skill")),
         Scope::Custom(path) => Some(path.join("skill")),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -88,6 +106,7 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
         Scope::Global => None,
         Scope::Project(root) => Some(root.clone()),
         Scope::Custom(path) => Some(path.clone()),
+        Scope::Local(_) | Scope::Managed => None,
     }
 }
 
@@ -107,7 +126,6 @@ pub fn is_installed() -> bool {
 /// # Errors
 ///
 /// Returns an error if the JSON is malformed or missing required fields.
-#[allow(dead_code)] // Internal utility for future MCP config reading
 pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
     let obj = value
         .as_object()
@@ -143,7 +161,6 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
 /// # Errors
 ///
 /// Returns an error if the JSON is malformed.
-#[allow(dead_code)] // Internal utility for future MCP config reading
 pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
     let mcp = config
         .get("mcp")
@@ -170,7 +187,6 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(servers)
 }
 
-#[allow(dead_code)] // Internal utility for future MCP config reading
 fn parse_local_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result<McpServer> {
     // Parse command array: first element is command, rest are args
     let command_array = obj
@@ -243,10 +259,10 @@ fn parse_local_server(obj: &serde_json::Map<String, serde_json::Value>) -> Resul
         cwd: None,
         enabled,
         timeout_ms,
+        auto_approve: Vec::new(),
     }))
 }
 
-#[allow(dead_code)] // Internal utility for future MCP config reading
 fn parse_remote_server(obj: &serde_json::Map<String, serde_json::Value>) -> Result<McpServer> {
     // Parse URL
     let url = obj
@@ -347,9 +363,120 @@ fn parse_remote_server(obj: &serde_json::Map<String, serde_json::Value>) -> Resu
         oauth,
         enabled,
         timeout_ms,
+        auto_approve: Vec::new(),
     }))
 }
 
+/// [`HarnessAdapter`] for OpenCode.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OpenCodeAdapter;
+
+impl HarnessAdapter for OpenCodeAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::OpenCode
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn agents(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            config_dir(scope)?.join("agent"),
+            "*.{yaml,json}",
+            FileFormat::Yaml,
+        )))
+    }
+
+    fn plugins(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            config_dir(scope)?.join("plugin"),
+            "*.{js,ts}",
+            FileFormat::Json,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(&["AGENTS.md"])
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/mcp".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        let opencode_files = |dir: PathBuf| {
+            [
+                (dir.join("opencode.json"), FileFormat::Json),
+                (dir.join("opencode.jsonc"), FileFormat::Jsonc),
+            ]
+        };
+        Ok(match scope {
+            Scope::Global => {
+                let base = global_config_dir()?;
+                let mut files = opencode_files(base.clone()).to_vec();
+                files.push((base.join("config.json"), FileFormat::Json));
+                files
+            }
+            // OpenCode also picks up opencode.json(c) in the project root
+            Scope::Project(root) => opencode_files(project_config_dir(root))
+                .into_iter()
+                .chain(opencode_files(root.clone()))
+                .collect(),
+            Scope::Custom(_) | Scope::Local(_) | Scope::Managed => {
+                opencode_files(config_dir(scope)?).to_vec()
+            }
+        })
+    }
+
+    fn schema_url(&self, _file: &Path) -> Option<&'static str> {
+        Some(crate::schema::OPENCODE_CONFIG)
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.to_opencode_value(self.kind())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
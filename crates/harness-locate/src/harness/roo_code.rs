@@ -0,0 +1,262 @@
+//! Roo Code harness implementation.
+//!
+//! Roo Code is a VS Code extension forked from Cline. It stores its
+//! configuration in:
+//! - **Global**: `~/.roo/` for rules and commands, and the extension's
+//!   `globalStorage` directory (`rooveterinaryinc.roo-cline/`) in the user
+//!   data directory of VS Code, VS Code Insiders, Cursor or VSCodium for
+//!   MCP servers
+//! - **Project**: `.roo/` in project root
+//!
+//! MCP servers live under `mcpServers` in `settings/mcp_settings.json`
+//! (global) or `.roo/mcp.json` (project), in the format shared with
+//! [`cline`](super::cline).
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
+use crate::harness::cline;
+use crate::mcp::McpServer;
+use crate::platform;
+use crate::types::{DirectoryResource, FileFormat, HarnessKind, Scope};
+
+/// Roo Code's VS Code extension identifier.
+pub const EXTENSION_ID: &str = "rooveterinaryinc.roo-cline";
+
+/// Returns Roo Code's `globalStorage` directory in every VS Code-family
+/// editor, in lookup order.
+///
+/// # Errors
+///
+/// Returns an error if the platform directory cannot be determined.
+pub fn storage_dirs() -> Result<Vec<PathBuf>> {
+    platform::extension_storage_dirs(EXTENSION_ID)
+}
+
+/// Returns the global Roo Code configuration directory.
+///
+/// Returns `~/.roo/`.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn global_config_dir() -> Result<PathBuf> {
+    Ok(platform::home_dir()?.join(".roo"))
+}
+
+/// Returns the project-local Roo Code configuration directory.
+///
+/// # Arguments
+///
+/// * `project_root` - Path to the project root directory
+#[must_use]
+pub fn project_config_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".roo")
+}
+
+/// Returns the config directory for the given scope.
+///
+/// This is the base configuration directory.
+pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir(),
+        Scope::Project(root) => Ok(project_config_dir(root)),
+        Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Roo Code".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Roo Code".to_string(),
+            scope: "managed".to_string(),
+        }),
+    }
+}
+
+/// Returns the commands directory for the given scope.
+///
+/// - **Global**: `~/.roo/commands/`
+/// - **Project**: `.roo/commands/`
+pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
+    Ok(config_dir(scope)?.join("commands"))
+}
+
+/// Returns the skills directory for the given scope.
+///
+/// - **Global**: `~/.roo/skills/`
+/// - **Project**: `.roo/skills/`
+#[must_use]
+pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir().ok().map(|p| p.join("skills")),
+        Scope::Project(root) => Some(project_config_dir(root).join("skills")),
+        Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Returns the candidate MCP settings files for the given scope, in
+/// lookup order.
+///
+/// - **Global**: `settings/mcp_settings.json` in each editor's storage
+///   directory
+/// - **Project**: `.roo/mcp.json`
+pub fn mcp_files(scope: &Scope) -> Result<Vec<PathBuf>> {
+    match scope {
+        Scope::Global => Ok(storage_dirs()?
+            .into_iter()
+            .map(|dir| dir.join("settings").join("mcp_settings.json"))
+            .collect()),
+        _ => Ok(vec![config_dir(scope)?.join("mcp.json")]),
+    }
+}
+
+/// Returns the rules directory for the given scope.
+///
+/// - **Global**: `~/.roo/rules/`
+/// - **Project**: `.roo/rules/`
+///
+/// Older projects use a single `.roorules` file in the project root.
+#[must_use]
+pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir().ok().map(|p| p.join("rules")),
+        Scope::Project(root) => Some(project_config_dir(root).join("rules")),
+        Scope::Custom(path) => Some(path.join("rules")),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Checks if Roo Code is installed on this system.
+///
+/// Checks if the extension's storage directory exists in any VS
+/// Code-family editor.
+pub fn is_installed() -> bool {
+    storage_dirs()
+        .map(|dirs| dirs.iter().any(|dir| dir.exists()))
+        .unwrap_or(false)
+}
+
+/// Parses a single MCP server from Roo Code's native JSON format.
+///
+/// # Arguments
+/// * `value` - The JSON value representing the server config
+///
+/// # Errors
+/// Returns an error if the JSON is malformed or missing required fields.
+pub(crate) fn parse_mcp_server(value: &Value) -> Result<McpServer> {
+    cline::parse_server(HarnessKind::RooCode, value)
+}
+
+/// Parses all MCP servers from a Roo Code MCP settings file.
+///
+/// # Arguments
+/// * `config` - The full config JSON (expects mcpServers key)
+///
+/// # Errors
+/// Returns an error if the config is malformed or any server fails to parse.
+pub(crate) fn parse_mcp_servers(config: &Value) -> Result<Vec<(String, McpServer)>> {
+    cline::parse_servers(HarnessKind::RooCode, config)
+}
+
+/// [`HarnessAdapter`] for Roo Code.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RooCodeAdapter;
+
+impl HarnessAdapter for RooCodeAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::RooCode
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "*.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/mcpServers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(mcp_files(scope)?
+            .into_iter()
+            .map(|file| (file, FileFormat::Json))
+            .collect())
+    }
+
+    fn parse_mcp_server(&self, value: &Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<Value> {
+        server.to_cline_value(self.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EnvValue;
+    use serde_json::json;
+
+    #[test]
+    fn project_paths() {
+        let root = PathBuf::from("/some/project");
+        let scope = Scope::Project(root.clone());
+        assert_eq!(config_dir(&scope).unwrap(), root.join(".roo"));
+        assert_eq!(mcp_files(&scope).unwrap(), [root.join(".roo/mcp.json")]);
+        assert_eq!(rules_dir(&scope), Some(root.join(".roo/rules")));
+    }
+
+    #[test]
+    fn parse_streamable_http_with_env_ref() {
+        let value = json!({
+            "type": "streamable-http",
+            "url": "https://example.com/mcp",
+            "headers": {"Authorization": "${env:TOKEN}"},
+            "alwaysAllow": ["search"]
+        });
+        let McpServer::Http(server) = parse_mcp_server(&value).unwrap() else {
+            panic!("expected HTTP server");
+        };
+        assert_eq!(server.headers["Authorization"], EnvValue::env("TOKEN"));
+        assert_eq!(server.auto_approve, ["search"]);
+    }
+}
@@ -0,0 +1,543 @@
+//! Visual Studio Code (agent mode) harness implementation.
+//!
+//! VS Code stores its configuration in:
+//! - **Global**: the user data directory (`Code/User/`), see
+//!   [`global_config_dir`]
+//! - **Project**: `.vscode/` in project root
+//!
+//! MCP servers live under `servers` in `mcp.json`. Secrets the user is
+//! prompted for are declared in the top-level `inputs` array and referenced
+//! as `${input:ID}`; they map to [`SecretPrompt`]. Prompt files, skills and
+//! instructions follow GitHub Copilot's layout under `.github/`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value, json};
+
+use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
+use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use crate::platform;
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope, SecretPrompt};
+
+/// Returns the global VS Code user directory.
+///
+/// - **Linux**: `~/.config/Code/User/`
+/// - **macOS**: `~/Library/Application Support/Code/User/`
+/// - **Windows**: `%APPDATA%\Code\User\`
+///
+/// # Errors
+///
+/// Returns an error if the platform directory cannot be determined.
+pub fn global_config_dir() -> Result<PathBuf> {
+    platform::editor_user_dir("Code")
+}
+
+/// Returns the project-local VS Code configuration directory.
+///
+/// # Arguments
+///
+/// * `project_root` - Path to the project root directory
+#[must_use]
+pub fn project_config_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".vscode")
+}
+
+/// Returns the config directory for the given scope.
+///
+/// This is the base configuration directory.
+pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir(),
+        Scope::Project(root) => Ok(project_config_dir(root)),
+        Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "VS Code".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "VS Code".to_string(),
+            scope: "managed".to_string(),
+        }),
+    }
+}
+
+/// Returns the prompt files directory for the given scope.
+///
+/// - **Global**: `Code/User/prompts/`
+/// - **Project**: `.github/prompts/`
+pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Project(root) => Ok(root.join(".github").join("prompts")),
+        _ => Ok(config_dir(scope)?.join("prompts")),
+    }
+}
+
+/// Returns the MCP configuration directory for the given scope.
+///
+/// VS Code stores MCP configuration in `mcp.json` at the base config
+/// directory.
+pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
+    config_dir(scope)
+}
+
+/// Returns the skills directory for the given scope.
+///
+/// - **Global**: `~/.copilot/skills/`
+/// - **Project**: `.github/skills/`
+#[must_use]
+pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => platform::home_dir()
+            .ok()
+            .map(|p| p.join(".copilot").join("skills")),
+        Scope::Project(root) => Some(root.join(".github").join("skills")),
+        Scope::Custom(path) => Some(path.join("skills")),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Returns the rules directory for the given scope.
+///
+/// - **Global**: `Code/User/prompts/` (`*.instructions.md`)
+/// - **Project**: `.github/` (`copilot-instructions.md`)
+#[must_use]
+pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir().ok().map(|p| p.join("prompts")),
+        Scope::Project(root) => Some(root.join(".github")),
+        Scope::Custom(path) => Some(path.clone()),
+        Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Checks if VS Code is installed on this system.
+///
+/// Currently checks if the global user directory exists.
+pub fn is_installed() -> bool {
+    global_config_dir().map(|p| p.exists()).unwrap_or(false)
+}
+
+/// Reads an optional object of string values.
+fn string_map(obj: &Map<String, Value>, key: &str) -> Result<HashMap<String, EnvValue>> {
+    let Some(value) = obj.get(key) else {
+        return Ok(HashMap::new());
+    };
+    let map = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "VS Code".to_string(),
+            reason: format!("'{key}' must be an object"),
+        })?;
+    map.iter()
+        .map(|(k, v)| {
+            let s = v.as_str().ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: "VS Code".to_string(),
+                reason: format!("'{key}.{k}' must be a string"),
+            })?;
+            Ok((k.clone(), EnvValue::from_native(s, HarnessKind::VsCode)))
+        })
+        .collect()
+}
+
+/// Parses a single MCP server from VS Code's native JSON format.
+///
+/// `${input:ID}` values become bare [`SecretPrompt`]s; use
+/// [`parse_mcp_servers`] to fill in their description from `inputs`.
+///
+/// # Arguments
+/// * `value` - The JSON value representing the server config
+///
+/// # Errors
+/// Returns an error if the JSON is malformed or missing required fields.
+pub(crate) fn parse_mcp_server(value: &Value) -> Result<McpServer> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "VS Code".to_string(),
+            reason: "Server configuration must be an object".to_string(),
+        })?;
+
+    let server_type = match obj.get("type").and_then(Value::as_str) {
+        Some(t) => t,
+        None if obj.contains_key("command") => "stdio",
+        None => "http",
+    };
+
+    match server_type {
+        "stdio" => {
+            let command = obj
+                .get("command")
+                .and_then(Value::as_str)
+                .ok_or_else(|| Error::UnsupportedMcpConfig {
+                    harness: "VS Code".to_string(),
+                    reason: "stdio server missing 'command'".to_string(),
+                })?
+                .to_string();
+            let args = match obj.get("args") {
+                Some(args) => serde_json::from_value(args.clone()).map_err(|_| {
+                    Error::UnsupportedMcpConfig {
+                        harness: "VS Code".to_string(),
+                        reason: "'args' must be an array of strings".to_string(),
+                    }
+                })?,
+                None => Vec::new(),
+            };
+            Ok(McpServer::Stdio(StdioMcpServer {
+                command,
+                args,
+                env: string_map(obj, "env")?,
+                cwd: obj.get("cwd").and_then(Value::as_str).map(PathBuf::from),
+                enabled: true,
+                timeout_ms: None,
+                auto_approve: Vec::new(),
+            }))
+        }
+        "http" | "sse" => {
+            let url = obj
+                .get("url")
+                .and_then(Value::as_str)
+                .ok_or_else(|| Error::UnsupportedMcpConfig {
+                    harness: "VS Code".to_string(),
+                    reason: format!("{server_type} server missing 'url'"),
+                })?
+                .to_string();
+            let headers = string_map(obj, "headers")?;
+            if server_type == "sse" {
+                Ok(McpServer::Sse(SseMcpServer {
+                    url,
+                    headers,
+                    enabled: true,
+                    timeout_ms: None,
+                    auto_approve: Vec::new(),
+                }))
+            } else {
+                Ok(McpServer::Http(HttpMcpServer {
+                    url,
+                    headers,
+                    oauth: None,
+                    enabled: true,
+                    timeout_ms: None,
+                    auto_approve: Vec::new(),
+                }))
+            }
+        }
+        other => Err(Error::UnsupportedMcpConfig {
+            harness: "VS Code".to_string(),
+            reason: format!("Unknown server type: {other}"),
+        }),
+    }
+}
+
+/// Parses the top-level `inputs` array into prompts keyed by id.
+///
+/// Only `promptString` inputs are secrets; pick lists and commands are
+/// skipped.
+fn parse_inputs(config: &Value) -> Result<HashMap<String, SecretPrompt>> {
+    let Some(inputs) = config.get("inputs") else {
+        return Ok(HashMap::new());
+    };
+    let inputs = inputs
+        .as_array()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "VS Code".to_string(),
+            reason: "'inputs' must be an array".to_string(),
+        })?;
+
+    let mut result = HashMap::new();
+    for input in inputs {
+        if input.get("type").and_then(Value::as_str) != Some("promptString") {
+            continue;
+        }
+        let id =
+            input
+                .get("id")
+                .and_then(Value::as_str)
+                .ok_or_else(|| Error::UnsupportedMcpConfig {
+                    harness: "VS Code".to_string(),
+                    reason: "input missing 'id'".to_string(),
+                })?;
+        result.insert(
+            id.to_string(),
+            SecretPrompt {
+                id: id.to_string(),
+                description: input
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+                password: input
+                    .get("password")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+            },
+        );
+    }
+    Ok(result)
+}
+
+/// Replaces bare prompts with their declaration from `inputs`.
+fn fill_prompts(server: &mut McpServer, inputs: &HashMap<String, SecretPrompt>) {
+    let values = match server {
+        McpServer::Stdio(s) => s.env.values_mut(),
+        McpServer::Sse(s) => s.headers.values_mut(),
+        McpServer::Http(h) => h.headers.values_mut(),
+    };
+    for value in values {
+        if let EnvValue::Prompt { prompt } = value
+            && let Some(input) = inputs.get(&prompt.id)
+        {
+            *prompt = input.clone();
+        }
+    }
+}
+
+/// Parses all MCP servers from a VS Code `mcp.json`.
+///
+/// # Arguments
+/// * `config` - The full config JSON (expects servers key)
+///
+/// # Errors
+/// Returns an error if the config is malformed or any server fails to parse.
+pub(crate) fn parse_mcp_servers(config: &Value) -> Result<Vec<(String, McpServer)>> {
+    let servers_obj = config
+        .get("servers")
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "VS Code".to_string(),
+            reason: "Config missing 'servers' object".to_string(),
+        })?;
+    let inputs = parse_inputs(config)?;
+
+    let mut result = Vec::new();
+    for (name, value) in servers_obj {
+        let mut server = parse_mcp_server(value)?;
+        fill_prompts(&mut server, &inputs);
+        result.push((name.clone(), server));
+    }
+
+    Ok(result)
+}
+
+/// Returns `existing` inputs with a `promptString` entry appended for each
+/// prompt that is not declared yet.
+pub(crate) fn merge_inputs(existing: Option<&Value>, prompts: &[&SecretPrompt]) -> Value {
+    let mut inputs = existing
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for prompt in prompts {
+        let declared = inputs
+            .iter()
+            .any(|input| input.get("id").and_then(Value::as_str) == Some(prompt.id.as_str()));
+        if declared {
+            continue;
+        }
+        let mut input = json!({"type": "promptString", "id": prompt.id});
+        if let Some(description) = &prompt.description {
+            input["description"] = json!(description);
+        }
+        if prompt.password {
+            input["password"] = json!(true);
+        }
+        inputs.push(input);
+    }
+    Value::Array(inputs)
+}
+
+/// [`HarnessAdapter`] for VS Code.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VsCodeAdapter;
+
+impl HarnessAdapter for VsCodeAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::VsCode
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::skills_resource(
+            self.kind(),
+            scope,
+            skills_dir(scope),
+            FileFormat::MarkdownWithFrontmatter,
+        )
+    }
+
+    fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        Ok(Some(adapter::flat_resource(
+            commands_dir(scope)?,
+            "*.prompt.md",
+            FileFormat::MarkdownWithFrontmatter,
+        )))
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            "*.md",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, scope: &Scope) -> Option<&'static [&'static str]> {
+        // The global prompts directory holds only instructions files
+        matches!(scope, Scope::Project(_)).then_some(&["copilot-instructions.md"][..])
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/servers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(vec![(mcp_dir(scope)?.join("mcp.json"), FileFormat::Json)])
+    }
+
+    fn parse_mcp_server(&self, value: &Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<Value> {
+        server.to_vscode_value(self.kind())
+    }
+
+    fn mcp_install_extras(
+        &self,
+        config: &Value,
+        server: &McpServer,
+    ) -> Result<Vec<(String, Value)>> {
+        let prompts = server.secret_prompts();
+        if prompts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let inputs = merge_inputs(config.get("inputs"), &prompts);
+        Ok(vec![("inputs".to_string(), inputs)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_paths() {
+        let root = PathBuf::from("/some/project");
+        let scope = Scope::Project(root.clone());
+        assert_eq!(config_dir(&scope).unwrap(), root.join(".vscode"));
+        assert_eq!(commands_dir(&scope).unwrap(), root.join(".github/prompts"));
+        assert_eq!(skills_dir(&scope), Some(root.join(".github/skills")));
+        assert_eq!(rules_dir(&scope), Some(root.join(".github")));
+    }
+
+    #[test]
+    fn global_config_dir_ends_with_code_user() {
+        if platform::home_dir().is_err() {
+            return;
+        }
+
+        let path = global_config_dir().unwrap();
+        assert!(path.ends_with(Path::new("Code").join("User")));
+    }
+
+    #[test]
+    fn parse_servers_resolves_inputs() {
+        let config = json!({
+            "inputs": [
+                {"type": "promptString", "id": "api-key", "description": "API key", "password": true},
+                {"type": "pickString", "id": "region", "options": ["eu", "us"]}
+            ],
+            "servers": {
+                "github": {
+                    "type": "http",
+                    "url": "https://api.example.com/mcp",
+                    "headers": {"Authorization": "Bearer ${input:api-key}"}
+                },
+                "local": {
+                    "type": "stdio",
+                    "command": "node",
+                    "args": ["server.js"],
+                    "env": {"API_KEY": "${input:api-key}", "HOME_DIR": "${env:HOME}"}
+                }
+            }
+        });
+        let servers: HashMap<_, _> = parse_mcp_servers(&config).unwrap().into_iter().collect();
+
+        let McpServer::Stdio(local) = &servers["local"] else {
+            panic!("expected stdio server");
+        };
+        assert_eq!(
+            local.env["API_KEY"],
+            EnvValue::Prompt {
+                prompt: SecretPrompt {
+                    id: "api-key".to_string(),
+                    description: Some("API key".to_string()),
+                    password: true,
+                }
+            }
+        );
+        assert_eq!(local.env["HOME_DIR"], EnvValue::env("HOME"));
+
+        let McpServer::Http(github) = &servers["github"] else {
+            panic!("expected HTTP server");
+        };
+        assert_eq!(
+            github.headers["Authorization"],
+            EnvValue::plain("Bearer ${input:api-key}")
+        );
+    }
+
+    #[test]
+    fn parse_server_infers_type() {
+        let stdio = json!({"command": "npx"});
+        assert!(matches!(
+            parse_mcp_server(&stdio).unwrap(),
+            McpServer::Stdio(_)
+        ));
+        let sse = json!({"type": "sse", "url": "https://example.com/sse"});
+        assert!(matches!(parse_mcp_server(&sse).unwrap(), McpServer::Sse(_)));
+        let unknown = json!({"type": "websocket", "url": "wss://example.com"});
+        assert!(parse_mcp_server(&unknown).is_err());
+    }
+
+    #[test]
+    fn parse_servers_missing_key() {
+        let result = parse_mcp_servers(&json!({"mcpServers": {}}));
+        assert!(matches!(result, Err(Error::UnsupportedMcpConfig { .. })));
+    }
+
+    #[test]
+    fn merge_inputs_appends_undeclared_prompts() {
+        let existing = json!([{"type": "promptString", "id": "token"}]);
+        let token = SecretPrompt {
+            id: "token".to_string(),
+            description: Some("ignored".to_string()),
+            password: true,
+        };
+        let key = SecretPrompt {
+            id: "api-key".to_string(),
+            description: Some("API key".to_string()),
+            password: true,
+        };
+        let merged = merge_inputs(Some(&existing), &[&token, &key]);
+        assert_eq!(
+            merged,
+            json!([
+                {"type": "promptString", "id": "token"},
+                {"type": "promptString", "id": "api-key", "description": "API key", "password": true}
+            ])
+        );
+    }
+}
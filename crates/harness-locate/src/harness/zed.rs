@@ -0,0 +1,436 @@
+//! Zed editor harness implementation.
+//!
+//! Zed stores its configuration in:
+//! - **Global**: `~/.config/zed/` (`%APPDATA%\Zed\` on Windows)
+//! - **Project**: `.zed/` in project root
+//!
+//! MCP servers are the `context_servers` object of `settings.json`, a JSONC
+//! file that also holds every other editor setting. Servers are either
+//! custom (a command or a URL) or provided by a Zed extension
+//! (`"source": "extension"`), which this crate cannot represent as an
+//! [`McpServer`]; see [`extension_servers`]. Zed reads the first rules file
+//! of [`RULES_FILES`] it finds in the project root.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::harness::adapter::{self, HarnessAdapter};
+use crate::mcp::{HttpMcpServer, McpServer, StdioMcpServer};
+use crate::platform;
+use crate::types::{DirectoryResource, EnvValue, FileFormat, HarnessKind, Scope};
+
+/// Project rules files Zed reads, in priority order. Only the first one
+/// found is used.
+pub const RULES_FILES: &[&str] = &[
+    ".rules",
+    ".cursorrules",
+    ".windsurfrules",
+    ".clinerules",
+    ".github/copilot-instructions.md",
+    "AGENT.md",
+    "AGENTS.md",
+    "CLAUDE.md",
+    "GEMINI.md",
+];
+
+/// Returns the global Zed configuration directory.
+///
+/// - **Linux/macOS**: `~/.config/zed/` (respecting `XDG_CONFIG_HOME` on
+///   Linux)
+/// - **Windows**: `%APPDATA%\Zed\`
+///
+/// # Errors
+///
+/// Returns an error if the config directory cannot be determined.
+pub fn global_config_dir() -> Result<PathBuf> {
+    #[cfg(target_os = "windows")]
+    return Ok(platform::config_dir()?.join("Zed"));
+
+    #[cfg(not(target_os = "windows"))]
+    Ok(platform::config_dir()?.join("zed"))
+}
+
+/// Returns the project-local Zed configuration directory.
+///
+/// # Arguments
+///
+/// * `project_root` - Path to the project root directory
+#[must_use]
+pub fn project_config_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".zed")
+}
+
+/// Returns the config directory for the given scope.
+///
+/// This is the base configuration directory.
+pub fn config_dir(scope: &Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir(),
+        Scope::Project(root) => Ok(project_config_dir(root)),
+        Scope::Custom(path) => Ok(path.clone()),
+        Scope::Local(_) => Err(Error::UnsupportedScope {
+            harness: "Zed".to_string(),
+            scope: "local".to_string(),
+        }),
+        Scope::Managed => Err(Error::UnsupportedScope {
+            harness: "Zed".to_string(),
+            scope: "managed".to_string(),
+        }),
+    }
+}
+
+/// Returns the MCP configuration directory for the given scope.
+///
+/// Zed stores context servers in `settings.json` at the base config
+/// directory.
+pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
+    config_dir(scope)
+}
+
+/// Returns the rules directory for the given scope.
+///
+/// Zed reads rules files from the project root. Global rules live in
+/// Zed's rules library, a database rather than files, so `None` is
+/// returned for the global scope.
+#[must_use]
+pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Project(root) => Some(root.clone()),
+        Scope::Custom(path) => Some(path.clone()),
+        Scope::Global | Scope::Local(_) | Scope::Managed => None,
+    }
+}
+
+/// Returns the rules files present in `project_root`, in the order Zed
+/// considers them.
+///
+/// Zed only applies the first entry.
+#[must_use]
+pub fn rules_files(project_root: &Path) -> Vec<PathBuf> {
+    RULES_FILES
+        .iter()
+        .map(|name| project_root.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Checks if Zed is installed on this system.
+///
+/// Currently checks if the global config directory exists.
+pub fn is_installed() -> bool {
+    global_config_dir().map(|p| p.exists()).unwrap_or(false)
+}
+
+/// A context server provided by a Zed extension.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionServer {
+    /// The server name, which is also the extension's server id.
+    pub name: String,
+    /// The extension-specific `settings` object, if any.
+    pub settings: Option<Value>,
+}
+
+/// Returns the extension-provided context servers in a Zed settings file.
+///
+/// These entries are skipped by [`Harness::parse_mcp_config`].
+///
+/// [`Harness::parse_mcp_config`]: crate::Harness::parse_mcp_config
+#[must_use]
+pub fn extension_servers(config: &Value) -> Vec<ExtensionServer> {
+    let Some(servers) = config.get("context_servers").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    servers
+        .iter()
+        .filter(|(_, value)| is_extension(value))
+        .map(|(name, value)| ExtensionServer {
+            name: name.clone(),
+            settings: value.get("settings").cloned(),
+        })
+        .collect()
+}
+
+fn is_extension(value: &Value) -> bool {
+    value.get("source").and_then(Value::as_str) == Some("extension")
+}
+
+/// Reads an optional object of string values.
+fn string_map(obj: &Map<String, Value>, key: &str) -> Result<HashMap<String, EnvValue>> {
+    let Some(value) = obj.get(key) else {
+        return Ok(HashMap::new());
+    };
+    if value.is_null() {
+        return Ok(HashMap::new());
+    }
+    let map = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Zed".to_string(),
+            reason: format!("'{key}' must be an object"),
+        })?;
+    map.iter()
+        .map(|(k, v)| {
+            let s = v.as_str().ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: "Zed".to_string(),
+                reason: format!("'{key}.{k}' must be a string"),
+            })?;
+            Ok((k.clone(), EnvValue::from_native(s, HarnessKind::Zed)))
+        })
+        .collect()
+}
+
+/// Reads an optional array of strings.
+fn string_array(obj: &Map<String, Value>, key: &str) -> Result<Vec<String>> {
+    match obj.get(key) {
+        None => Ok(Vec::new()),
+        Some(value) => {
+            serde_json::from_value(value.clone()).map_err(|_| Error::UnsupportedMcpConfig {
+                harness: "Zed".to_string(),
+                reason: format!("'{key}' must be an array of strings"),
+            })
+        }
+    }
+}
+
+/// Parses a single context server from Zed's native JSON format.
+///
+/// Accepts both the current shape, with `command`, `args` and `env` on the
+/// entry, and the older one that nests them as
+/// `{"command": {"path", "args", "env"}}`. Entries with a `url` are
+/// streamable HTTP servers.
+///
+/// # Arguments
+/// * `value` - The JSON value representing the server config
+///
+/// # Errors
+/// Returns an error if the JSON is malformed or missing required fields,
+/// or if the server is provided by an extension.
+pub(crate) fn parse_mcp_server(value: &Value) -> Result<McpServer> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Zed".to_string(),
+            reason: "Server configuration must be an object".to_string(),
+        })?;
+
+    if is_extension(value) {
+        return Err(Error::UnsupportedMcpConfig {
+            harness: "Zed".to_string(),
+            reason: "Server is provided by an extension".to_string(),
+        });
+    }
+
+    if let Some(url) = obj.get("url") {
+        let url = url
+            .as_str()
+            .ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: "Zed".to_string(),
+                reason: "'url' must be a string".to_string(),
+            })?
+            .to_string();
+        return Ok(McpServer::Http(HttpMcpServer {
+            url,
+            headers: string_map(obj, "headers")?,
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        }));
+    }
+
+    let (command, fields) = match obj.get("command") {
+        Some(Value::String(command)) => (command.clone(), obj),
+        Some(Value::Object(nested)) => {
+            let path = nested.get("path").and_then(Value::as_str).ok_or_else(|| {
+                Error::UnsupportedMcpConfig {
+                    harness: "Zed".to_string(),
+                    reason: "'command.path' must be a string".to_string(),
+                }
+            })?;
+            (path.to_string(), nested)
+        }
+        _ => {
+            return Err(Error::UnsupportedMcpConfig {
+                harness: "Zed".to_string(),
+                reason: "Server has neither 'command' nor 'url'".to_string(),
+            });
+        }
+    };
+
+    Ok(McpServer::Stdio(StdioMcpServer {
+        command,
+        args: string_array(fields, "args")?,
+        env: string_map(fields, "env")?,
+        cwd: None,
+        enabled: true,
+        timeout_ms: None,
+        auto_approve: Vec::new(),
+    }))
+}
+
+/// Parses all custom context servers from a Zed `settings.json`.
+///
+/// Extension-provided servers are skipped; use [`extension_servers`] to
+/// list them.
+///
+/// # Arguments
+/// * `config` - The full settings JSON (expects context_servers key)
+///
+/// # Errors
+/// Returns an error if the config is malformed or any server fails to parse.
+pub(crate) fn parse_mcp_servers(config: &Value) -> Result<Vec<(String, McpServer)>> {
+    let servers_obj = config
+        .get("context_servers")
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| Error::UnsupportedMcpConfig {
+            harness: "Zed".to_string(),
+            reason: "Config missing 'context_servers' object".to_string(),
+        })?;
+
+    let mut result = Vec::new();
+    for (name, value) in servers_obj {
+        if is_extension(value) {
+            continue;
+        }
+        let server = parse_mcp_server(value)?;
+        result.push((name.clone(), server));
+    }
+
+    Ok(result)
+}
+
+/// [`HarnessAdapter`] for Zed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZedAdapter;
+
+impl HarnessAdapter for ZedAdapter {
+    fn kind(&self) -> HarnessKind {
+        HarnessKind::Zed
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        // Zed reads one file from the project root; see rules_files
+        adapter::rules_resource(
+            self.kind(),
+            scope,
+            rules_dir(scope),
+            ".rules",
+            FileFormat::Markdown,
+        )
+    }
+
+    fn rules_file_names(&self, _scope: &Scope) -> Option<&'static [&'static str]> {
+        Some(RULES_FILES)
+    }
+
+    fn mcp_key_path(&self, _scope: &Scope) -> Result<Option<String>> {
+        Ok(Some("/context_servers".to_string()))
+    }
+
+    fn mcp_files(&self, scope: &Scope) -> Result<Vec<(PathBuf, FileFormat)>> {
+        Ok(vec![(
+            mcp_dir(scope)?.join("settings.json"),
+            FileFormat::Jsonc,
+        )])
+    }
+
+    fn parse_mcp_server(&self, value: &Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn parse_mcp_servers(&self, config: &Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<Value> {
+        server.to_zed_value(self.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn project_paths() {
+        let root = PathBuf::from("/some/project");
+        let scope = Scope::Project(root.clone());
+        assert_eq!(config_dir(&scope).unwrap(), root.join(".zed"));
+        assert_eq!(rules_dir(&scope), Some(root));
+        assert_eq!(rules_dir(&Scope::Global), None);
+    }
+
+    #[test]
+    fn rules_files_in_priority_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("AGENTS.md"), "agents").unwrap();
+        std::fs::write(dir.path().join(".rules"), "rules").unwrap();
+        assert_eq!(
+            rules_files(dir.path()),
+            [dir.path().join(".rules"), dir.path().join("AGENTS.md")]
+        );
+    }
+
+    #[test]
+    fn parse_flat_and_nested_commands() {
+        let flat = json!({
+            "source": "custom",
+            "command": "npx",
+            "args": ["-y", "server"],
+            "env": {"MODE": "fast"}
+        });
+        let nested = json!({
+            "command": {"path": "npx", "args": ["-y", "server"], "env": {"MODE": "fast"}},
+            "settings": {}
+        });
+        let flat = parse_mcp_server(&flat).unwrap();
+        assert_eq!(parse_mcp_server(&nested).unwrap(), flat);
+        let McpServer::Stdio(server) = flat else {
+            panic!("expected stdio server");
+        };
+        assert_eq!(server.command, "npx");
+        assert_eq!(server.env["MODE"], EnvValue::plain("fast"));
+    }
+
+    #[test]
+    fn parse_remote_server() {
+        let value = json!({"url": "https://example.com/mcp", "headers": {"X-Key": "abc"}});
+        let McpServer::Http(server) = parse_mcp_server(&value).unwrap() else {
+            panic!("expected HTTP server");
+        };
+        assert_eq!(server.headers["X-Key"], EnvValue::plain("abc"));
+    }
+
+    #[test]
+    fn extension_servers_are_listed_separately() {
+        let config = json!({
+            "context_servers": {
+                "postgres": {"source": "extension", "settings": {"database_url": "pg://"}},
+                "fetch": {"command": "uvx", "args": ["mcp-server-fetch"]}
+            }
+        });
+        let servers = parse_mcp_servers(&config).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].0, "fetch");
+        assert_eq!(
+            extension_servers(&config),
+            [ExtensionServer {
+                name: "postgres".to_string(),
+                settings: Some(json!({"database_url": "pg://"})),
+            }]
+        );
+        assert!(parse_mcp_server(&config["context_servers"]["postgres"]).is_err());
+    }
+}
@@ -0,0 +1,528 @@
+//! Enumerating the skills, commands, agents and rules a harness has
+//! installed.
+//!
+//! [`Harness::list_skills`](crate::Harness::list_skills) and its siblings
+//! walk a resource directory according to its [`DirectoryStructure`] and
+//! parse every entry: skills with [`parse_skill`], Markdown files by their
+//! YAML frontmatter, and TOML, YAML and JSON files as documents. An entry
+//! that fails to parse is still listed, with the failure in its `errors`.
+//! [`Harness::inventory`](crate::Harness::inventory) collects all four
+//! across every scope.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, Result};
+use crate::skill::{Skill, parse_frontmatter, parse_skill};
+use crate::types::{DirectoryResource, DirectoryStructure, HarnessKind, Scope};
+
+/// The kind of an [`InventoryItem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    /// A skill directory.
+    Skill,
+    /// A custom command.
+    Command,
+    /// An agent definition.
+    Agent,
+    /// A rules file.
+    Rule,
+}
+
+/// Metadata parsed from an [`InventoryItem`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ItemMetadata {
+    /// A skill parsed with [`parse_skill`].
+    Skill(Skill),
+    /// The YAML frontmatter of a Markdown file, or the top-level fields of
+    /// a TOML, YAML or JSON file.
+    Fields(Map<String, Value>),
+    /// The file has no metadata, or it failed to parse.
+    None,
+}
+
+impl ItemMetadata {
+    /// Returns the `description` the item declares, if any.
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            Self::Skill(skill) => skill.description.as_deref(),
+            Self::Fields(fields) => fields.get("description").and_then(Value::as_str),
+            Self::None => None,
+        }
+    }
+}
+
+/// One installed skill, command, agent or rules file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct InventoryItem {
+    /// What the item is.
+    pub kind: ItemKind,
+    /// The item's name: the skill directory name, or the file's path
+    /// relative to its directory without the extension matched by the
+    /// resource's pattern (`review` for `review.md`, `git/commit` for
+    /// `git/commit.toml`). Rules files the harness reads by name keep
+    /// their full file name (`CLAUDE.md`).
+    pub name: String,
+    /// The file the item was parsed from (`SKILL.md` for skills).
+    pub path: PathBuf,
+    /// The parsed metadata.
+    pub metadata: ItemMetadata,
+    /// Hex-encoded SHA-256 of the item's content. For skills this covers
+    /// every file in the skill directory, so supporting files count too.
+    pub hash: String,
+    /// Problems found while reading or parsing the item.
+    pub errors: Vec<String>,
+}
+
+/// The items installed in one scope.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScopeInventory {
+    /// The scope the items were found in.
+    pub scope: Scope,
+    /// The items, grouped by kind in the order skills, commands, agents,
+    /// rules, and sorted by name within each kind.
+    pub items: Vec<InventoryItem>,
+}
+
+/// Everything one harness has installed, across scopes.
+#[derive(Debug, Clone, Serialize)]
+pub struct Inventory {
+    /// The harness the items belong to.
+    pub harness: HarnessKind,
+    /// The scopes that have items, in the order global, managed, project,
+    /// local.
+    pub scopes: Vec<ScopeInventory>,
+}
+
+impl Inventory {
+    /// Returns every item along with its scope.
+    pub fn items(&self) -> impl Iterator<Item = (&Scope, &InventoryItem)> {
+        self.scopes
+            .iter()
+            .flat_map(|s| s.items.iter().map(move |item| (&s.scope, item)))
+    }
+
+    /// Returns every item of `kind` along with its scope.
+    pub fn items_of(&self, kind: ItemKind) -> impl Iterator<Item = (&Scope, &InventoryItem)> {
+        self.items().filter(move |(_, item)| item.kind == kind)
+    }
+}
+
+/// Lists the items of `kind` in `resource`.
+///
+/// When `names` is given, only those files directly in the resource
+/// directory are listed, for rules that share a directory with other files.
+///
+/// # Errors
+///
+/// Returns an error if the directory exists but cannot be read.
+pub(crate) fn list(
+    resource: Option<DirectoryResource>,
+    kind: ItemKind,
+    names: Option<&[&str]>,
+) -> Result<Vec<InventoryItem>> {
+    let Some(resource) = resource.filter(|r| r.path.is_dir()) else {
+        return Ok(Vec::new());
+    };
+    let mut items = match (&resource.structure, names) {
+        (_, Some(names)) => names
+            .iter()
+            .map(|name| resource.path.join(name))
+            .filter(|path| path.is_file())
+            .map(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                read_file(kind, name.into_owned(), &path)
+            })
+            .collect(),
+        (DirectoryStructure::Flat { file_pattern }, None) => {
+            let glob = Glob::new(file_pattern)?;
+            let mut files = Vec::new();
+            walk(&resource.path, "", glob.recursive, &mut files)?;
+            files
+                .into_iter()
+                .filter_map(|relative| {
+                    let name = glob.name(&relative)?;
+                    Some(read_file(kind, name, &resource.path.join(&relative)))
+                })
+                .collect()
+        }
+        (
+            DirectoryStructure::Nested {
+                subdir_pattern,
+                file_name,
+            },
+            None,
+        ) => {
+            let glob = Glob::new(subdir_pattern)?;
+            let mut items = Vec::new();
+            for entry in fs::read_dir(&resource.path)? {
+                let dir = entry?.path();
+                let name = dir.file_name().unwrap_or_default().to_string_lossy();
+                if dir.is_dir() && glob.name(&name).is_some() && dir.join(file_name).is_file() {
+                    items.push(read_nested(kind, name.into_owned(), &dir, file_name));
+                }
+            }
+            items
+        }
+    };
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(items)
+}
+
+/// Returns whether `error` means a scope has no such resource, rather than
+/// that it could not be read.
+pub(crate) fn is_absent(error: &Error) -> bool {
+    matches!(error, Error::NotFound(_) | Error::UnsupportedScope { .. })
+}
+
+fn read_file(kind: ItemKind, name: String, path: &Path) -> InventoryItem {
+    let mut errors = Vec::new();
+    let (metadata, hash) = match fs::read(path) {
+        Ok(bytes) => {
+            let metadata = match std::str::from_utf8(&bytes) {
+                Ok(content) => parse(kind, path, content, &mut errors),
+                Err(_) => {
+                    errors.push("file is not valid UTF-8".to_string());
+                    ItemMetadata::None
+                }
+            };
+            (metadata, hex(&Sha256::digest(&bytes)))
+        }
+        Err(e) => {
+            errors.push(e.to_string());
+            (ItemMetadata::None, String::new())
+        }
+    };
+    InventoryItem {
+        kind,
+        name,
+        path: path.to_path_buf(),
+        metadata,
+        hash,
+        errors,
+    }
+}
+
+fn read_nested(kind: ItemKind, name: String, dir: &Path, file_name: &str) -> InventoryItem {
+    let mut item = read_file(kind, name, &dir.join(file_name));
+    let mut files = Vec::new();
+    match walk(dir, "", true, &mut files) {
+        Ok(()) => {
+            files.sort();
+            let mut hasher = Sha256::new();
+            for relative in &files {
+                match fs::read(dir.join(relative)) {
+                    Ok(content) => {
+                        // Length-prefix both parts so that no two trees
+                        // produce the same byte stream
+                        hasher.update((relative.len() as u64).to_le_bytes());
+                        hasher.update(relative.as_bytes());
+                        hasher.update((content.len() as u64).to_le_bytes());
+                        hasher.update(&content);
+                    }
+                    Err(e) => item.errors.push(format!("{relative}: {e}")),
+                }
+            }
+            item.hash = hex(&hasher.finalize());
+        }
+        Err(e) => item.errors.push(e.to_string()),
+    }
+    item
+}
+
+fn parse(kind: ItemKind, path: &Path, content: &str, errors: &mut Vec<String>) -> ItemMetadata {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let fields = match (kind, extension) {
+        (ItemKind::Skill, _) => {
+            return match parse_skill(content) {
+                Ok(skill) => ItemMetadata::Skill(skill),
+                Err(e) => {
+                    errors.push(e.to_string());
+                    ItemMetadata::None
+                }
+            };
+        }
+        (_, "md" | "mdc") => parse_frontmatter(content)
+            .and_then(|fm| Ok(fm.yaml.map(serde_json::to_value).transpose()?)),
+        (_, "toml") => toml::from_str::<Value>(content)
+            .map(Some)
+            .map_err(|e| Error::InvalidConfig(e.to_string())),
+        (_, "yaml" | "yml") => serde_yaml::from_str(content).map(Some).map_err(Error::from),
+        (_, "json") => serde_json::from_str(content).map(Some).map_err(Error::from),
+        _ => Ok(None),
+    };
+    match fields {
+        Ok(Some(Value::Object(fields))) => ItemMetadata::Fields(fields),
+        Ok(None | Some(Value::Null)) => ItemMetadata::None,
+        Ok(Some(_)) => {
+            errors.push("metadata is not a map".to_string());
+            ItemMetadata::None
+        }
+        Err(e) => {
+            errors.push(e.to_string());
+            ItemMetadata::None
+        }
+    }
+}
+
+/// Collects the paths of the files under `dir`, relative to the directory
+/// the walk started in and separated by `/`. Hidden entries are skipped
+/// unless they are files directly in `dir`, and symlinked directories are
+/// not followed.
+fn walk(dir: &Path, prefix: &str, recursive: bool, files: &mut Vec<String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let relative = format!("{prefix}{name}");
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if recursive && !name.starts_with('.') {
+                walk(&entry.path(), &format!("{relative}/"), true, files)?;
+            }
+        } else if entry.path().is_file() && (prefix.is_empty() || !name.starts_with('.')) {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// A compiled [`DirectoryStructure`] pattern.
+///
+/// Supports `*` and `?` within a path segment, `{a,b}` alternatives and
+/// `**/` for any number of directories. As in shells, wildcards do not
+/// match names starting with a dot.
+struct Glob {
+    regex: Regex,
+    /// What follows the last wildcard of the final segment, one entry per
+    /// alternative; stripped from matches to form item names.
+    suffixes: Vec<String>,
+    /// Whether the final segment itself starts with a dot.
+    dotted: bool,
+    /// Whether the pattern reaches into subdirectories.
+    recursive: bool,
+}
+
+impl Glob {
+    fn new(pattern: &str) -> Result<Self> {
+        let mut regex = String::from("^");
+        let mut rest = pattern;
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("**/") {
+                regex.push_str("(?:[^/.][^/]*/)*");
+                rest = after;
+                continue;
+            }
+            let c = rest.chars().next().unwrap_or_default();
+            match c {
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                '{' => regex.push_str("(?:"),
+                ',' => regex.push('|'),
+                '}' => regex.push(')'),
+                c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        regex.push('$');
+        let regex = Regex::new(&regex)
+            .map_err(|e| Error::InvalidConfig(format!("invalid pattern '{pattern}': {e}")))?;
+
+        let segment = pattern.rsplit('/').next().unwrap_or(pattern);
+        let suffixes = match segment.rfind(['*', '?']) {
+            Some(i) => expand_braces(&segment[i + 1..]),
+            None => Vec::new(),
+        };
+        Ok(Self {
+            regex,
+            suffixes,
+            dotted: segment.starts_with('.'),
+            recursive: pattern.contains('/'),
+        })
+    }
+
+    /// Returns the item name for `relative` if it matches the pattern.
+    fn name(&self, relative: &str) -> Option<String> {
+        let file_name = relative.rsplit('/').next().unwrap_or(relative);
+        if !self.regex.is_match(relative) || (file_name.starts_with('.') && !self.dotted) {
+            return None;
+        }
+        let suffix = self
+            .suffixes
+            .iter()
+            .filter(|suffix| relative.ends_with(suffix.as_str()))
+            .max_by_key(|suffix| suffix.len());
+        Some(match suffix {
+            Some(suffix) => relative[..relative.len() - suffix.len()].to_string(),
+            None => relative.to_string(),
+        })
+    }
+}
+
+/// Expands the first `{a,b}` group in `text` into one string per
+/// alternative.
+fn expand_braces(text: &str) -> Vec<String> {
+    let (Some(open), Some(close)) = (text.find('{'), text.find('}')) else {
+        return vec![text.to_string()];
+    };
+    text[open + 1..close]
+        .split(',')
+        .map(|alternative| format!("{}{alternative}{}", &text[..open], &text[close + 1..]))
+        .collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(path: &Path, pattern: &str) -> Option<DirectoryResource> {
+        Some(DirectoryResource {
+            exists: path.exists(),
+            path: path.to_path_buf(),
+            structure: DirectoryStructure::Flat {
+                file_pattern: pattern.to_string(),
+            },
+            file_format: crate::types::FileFormat::Markdown,
+        })
+    }
+
+    fn names(items: &[InventoryItem]) -> Vec<&str> {
+        items.iter().map(|item| item.name.as_str()).collect()
+    }
+
+    #[test]
+    fn glob_names_strip_matched_extension() {
+        let glob = Glob::new("*.{yaml,json}").unwrap();
+        assert_eq!(glob.name("dev.yaml").as_deref(), Some("dev"));
+        assert_eq!(glob.name("dev.json").as_deref(), Some("dev"));
+        assert_eq!(glob.name("dev.md"), None);
+        assert_eq!(glob.name(".hidden.json"), None);
+
+        let glob = Glob::new("*.prompt.md").unwrap();
+        assert_eq!(glob.name("review.prompt.md").as_deref(), Some("review"));
+        assert_eq!(glob.name("review.md"), None);
+
+        let glob = Glob::new("**/*.toml").unwrap();
+        assert_eq!(glob.name("deploy.toml").as_deref(), Some("deploy"));
+        assert_eq!(glob.name("git/commit.toml").as_deref(), Some("git/commit"));
+
+        let glob = Glob::new(".rules").unwrap();
+        assert_eq!(glob.name(".rules").as_deref(), Some(".rules"));
+    }
+
+    #[test]
+    fn list_flat_parses_frontmatter_and_reports_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("review.md"),
+            "---\ndescription: Review code\n---\nReview it.\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("plain.md"), "No frontmatter.\n").unwrap();
+        fs::write(dir.path().join("broken.md"), "---\n: [\n---\nBody\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let items = list(flat(dir.path(), "*.md"), ItemKind::Command, None).unwrap();
+
+        assert_eq!(names(&items), ["broken", "plain", "review"]);
+        assert!(!items[0].errors.is_empty());
+        assert_eq!(items[1].metadata, ItemMetadata::None);
+        assert!(items[1].errors.is_empty());
+        assert_eq!(items[2].metadata.description(), Some("Review code"));
+        assert_eq!(items[2].path, dir.path().join("review.md"));
+        assert_eq!(items[2].hash.len(), 64);
+    }
+
+    #[test]
+    fn list_recursive_toml_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("git")).unwrap();
+        fs::write(
+            dir.path().join("git/commit.toml"),
+            "description = \"Commit\"\nprompt = \"Commit it\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("deploy.toml"), "prompt = [").unwrap();
+
+        let items = list(flat(dir.path(), "**/*.toml"), ItemKind::Command, None).unwrap();
+
+        assert_eq!(names(&items), ["deploy", "git/commit"]);
+        assert!(!items[0].errors.is_empty());
+        assert_eq!(items[1].metadata.description(), Some("Commit"));
+    }
+
+    #[test]
+    fn list_nested_skills_hash_supporting_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("pdf");
+        fs::create_dir_all(skill.join("scripts")).unwrap();
+        fs::write(
+            skill.join("SKILL.md"),
+            "---\nname: pdf\ndescription: Work with PDFs\n---\nUse it.\n",
+        )
+        .unwrap();
+        fs::write(skill.join("scripts/run.py"), "print(1)").unwrap();
+        fs::create_dir_all(dir.path().join("empty")).unwrap();
+        let resource = Some(DirectoryResource {
+            exists: true,
+            path: dir.path().to_path_buf(),
+            structure: DirectoryStructure::Nested {
+                subdir_pattern: "*".to_string(),
+                file_name: "SKILL.md".to_string(),
+            },
+            file_format: crate::types::FileFormat::MarkdownWithFrontmatter,
+        });
+
+        let items = list(resource.clone(), ItemKind::Skill, None).unwrap();
+        assert_eq!(names(&items), ["pdf"]);
+        assert_eq!(items[0].path, skill.join("SKILL.md"));
+        assert_eq!(items[0].metadata.description(), Some("Work with PDFs"));
+        assert!(matches!(items[0].metadata, ItemMetadata::Skill(_)));
+
+        fs::write(skill.join("scripts/run.py"), "print(2)").unwrap();
+        let changed = list(resource, ItemKind::Skill, None).unwrap();
+        assert_ne!(changed[0].hash, items[0].hash);
+    }
+
+    #[test]
+    fn list_named_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("AGENTS.md"), "# Agents\n").unwrap();
+        fs::write(dir.path().join("README.md"), "# Readme\n").unwrap();
+
+        let items = list(
+            flat(dir.path(), "*.md"),
+            ItemKind::Rule,
+            Some(&["AGENTS.md", "CLAUDE.md"]),
+        )
+        .unwrap();
+
+        assert_eq!(names(&items), ["AGENTS.md"]);
+    }
+
+    #[test]
+    fn list_missing_directory_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing");
+
+        assert!(
+            list(flat(&missing, "*.md"), ItemKind::Agent, None)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(list(None, ItemKind::Agent, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,433 @@
+//! A restorable log of applied configuration changes.
+//!
+//! [`Journal::apply`] records a [`ChangePlan`] — including the content every
+//! file had before the change — before applying it, so that any past
+//! operation can be listed and rolled back later.
+//!
+//! Every write this crate makes is recorded in the default journal unless
+//! told otherwise: MCP server installs and removals and skill installs
+//! through [`Harness`] (see [`Harness::with_journal`] and
+//! [`Harness::without_journal`]), [`ConfigDocument::save`] (see
+//! [`ConfigDocument::with_journal`]), and Goose secrets files written by
+//! [`SecretStore`]. Any other [`ChangePlan`], such as several merged
+//! installs, can be recorded with [`Journal::apply`].
+//!
+//! Entries are stored as one JSON file each under
+//! `<data dir>/harness-locate/journal/` by default. They hold the full
+//! content of config files, secrets included, so on Unix the directory is
+//! created readable by the owner only (`0700`) and entries are written with
+//! mode `0600`.
+//!
+//! [`Harness`]: crate::Harness
+//! [`Harness::with_journal`]: crate::Harness::with_journal
+//! [`Harness::without_journal`]: crate::Harness::without_journal
+//! [`ConfigDocument::save`]: crate::ConfigDocument::save
+//! [`ConfigDocument::with_journal`]: crate::ConfigDocument::with_journal
+//! [`SecretStore`]: crate::harness::goose::SecretStore
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::plan::ChangePlan;
+use crate::platform;
+
+/// A recorded operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Unique identifier, prefixed with the creation time in milliseconds.
+    pub id: String,
+    /// When the operation was applied.
+    pub created_at: SystemTime,
+    /// Human-readable description supplied by the caller.
+    pub description: String,
+    /// Whether the operation has been rolled back.
+    pub rolled_back: bool,
+    /// The applied changes, including each file's prior content.
+    pub plan: ChangePlan,
+}
+
+impl JournalEntry {
+    /// Returns the paths of the files and directories this operation
+    /// touched.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.plan
+            .changes()
+            .iter()
+            .map(|change| change.path.as_path())
+            .chain(
+                self.plan
+                    .directory_changes()
+                    .iter()
+                    .map(|change| change.path.as_path()),
+            )
+    }
+}
+
+/// Where an operation records the changes it writes.
+#[derive(Debug, Clone, Default)]
+pub(crate) enum JournalMode {
+    /// The journal in the platform data directory.
+    #[default]
+    Default,
+    /// A journal chosen by the caller.
+    Custom(Journal),
+    /// No journal.
+    Disabled,
+}
+
+impl JournalMode {
+    /// Applies `plan`, recording it in the chosen journal.
+    pub(crate) fn apply(&self, description: &str, plan: &ChangePlan) -> Result<()> {
+        let journal = match self {
+            Self::Default => Journal::open_default()?,
+            Self::Custom(journal) => journal.clone(),
+            Self::Disabled => return plan.apply(),
+        };
+        journal.apply(description, plan)?;
+        Ok(())
+    }
+}
+
+/// A directory of [`JournalEntry`] records.
+///
+/// # Example
+///
+/// ```no_run
+/// use harness_locate::{Harness, HarnessKind, Journal, McpServer, Scope};
+///
+/// # fn example(server: &McpServer) -> harness_locate::Result<()> {
+/// let journal = Journal::open_default()?;
+/// let mut plan = harness_locate::ChangePlan::new();
+/// for kind in HarnessKind::ALL {
+///     let harness = Harness::new(*kind);
+///     if harness.supports_mcp_server(server) {
+///         plan.merge(harness.plan_install_mcp_server(&Scope::Global, "fetch", server)?)?;
+///     }
+/// }
+/// let entry = journal.apply("install fetch everywhere", &plan)?;
+///
+/// // Later, undo the whole bulk install
+/// if let Some(entry) = entry {
+///     journal.rollback(&entry.id)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Journal {
+    dir: PathBuf,
+}
+
+impl Journal {
+    /// Creates a journal stored in `dir`.
+    ///
+    /// The directory is created on first write.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Opens the journal in the platform data directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data directory cannot be determined.
+    pub fn open_default() -> Result<Self> {
+        Ok(Self::new(
+            platform::data_dir()?.join("harness-locate").join("journal"),
+        ))
+    }
+
+    /// Returns the directory entries are stored in.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Records `plan` and then applies it.
+    ///
+    /// Returns `Ok(None)` without recording anything if the plan is empty.
+    /// If applying fails, the entry is discarded and the error returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written or the plan cannot
+    /// be applied.
+    pub fn apply(&self, description: &str, plan: &ChangePlan) -> Result<Option<JournalEntry>> {
+        if plan.is_empty() {
+            return Ok(None);
+        }
+        let entry = JournalEntry {
+            id: next_id(),
+            created_at: SystemTime::now(),
+            description: description.to_string(),
+            rolled_back: false,
+            plan: plan.clone(),
+        };
+        self.save(&entry)?;
+        if let Err(e) = plan.apply() {
+            let _ = fs::remove_file(self.path(&entry.id));
+            return Err(e);
+        }
+        Ok(Some(entry))
+    }
+
+    /// Returns every recorded entry, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal directory or an entry cannot be read.
+    pub fn entries(&self) -> Result<Vec<JournalEntry>> {
+        let read_dir = match fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries = Vec::new();
+        for dir_entry in read_dir {
+            let path = dir_entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                entries.push(serde_json::from_str::<JournalEntry>(&fs::read_to_string(
+                    &path,
+                )?)?);
+            }
+        }
+        entries.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        Ok(entries)
+    }
+
+    /// Returns the entry with the given id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Journal`] if there is no such entry.
+    pub fn entry(&self, id: &str) -> Result<JournalEntry> {
+        match fs::read_to_string(self.path(id)) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(Error::Journal(format!("no journal entry '{id}'")))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Restores every file touched by entry `id` to its prior content.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Journal`] if the entry does not exist or was already
+    /// rolled back, and [`Error::Conflict`] if a file was modified after the
+    /// operation; in that case no file is changed.
+    pub fn rollback(&self, id: &str) -> Result<JournalEntry> {
+        let mut entry = self.entry(id)?;
+        if entry.rolled_back {
+            return Err(Error::Journal(format!(
+                "journal entry '{id}' has already been rolled back"
+            )));
+        }
+        entry.plan.revert()?;
+        entry.rolled_back = true;
+        self.save(&entry)?;
+        Ok(entry)
+    }
+
+    fn save(&self, entry: &JournalEntry) -> Result<()> {
+        let content = serde_json::to_string_pretty(entry)?;
+        create_private_dir(&self.dir)?;
+
+        // Write a private temporary file, then rename it into place
+        let path = self.path(&entry.id);
+        let tmp_path = self.dir.join(format!(".{}.json.tmp", entry.id));
+        let result = (|| {
+            let mut file = private_file_options().open(&tmp_path)?;
+            file.write_all(content.as_bytes())?;
+            file.sync_all()?;
+            fs::rename(&tmp_path, &path)
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        Ok(result?)
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+}
+
+/// Creates `dir` and its parents, making `dir` itself accessible to the
+/// owner only on Unix.
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+        if let Some(parent) = dir.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        match fs::DirBuilder::new().mode(0o700).create(dir) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                // Tighten journals created by earlier releases
+                fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+            }
+            Err(e) => Err(e),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        fs::create_dir_all(dir)
+    }
+}
+
+/// Options creating a new file readable by the owner only on Unix.
+fn private_file_options() -> fs::OpenOptions {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+}
+
+/// Returns an id that is unique across processes.
+fn next_id() -> String {
+    static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    format!("{millis:013}-{:06}-{sequence:06}", std::process::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan_writing(path: &Path, content: &str) -> ChangePlan {
+        let mut plan = ChangePlan::new();
+        plan.set_file(path, Some(content.to_string())).unwrap();
+        plan
+    }
+
+    #[test]
+    fn apply_records_backup_and_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.json");
+        fs::write(&config, "before\n").unwrap();
+        let journal = Journal::new(dir.path().join("journal"));
+
+        let entry = journal
+            .apply("edit config", &plan_writing(&config, "after\n"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(&config).unwrap(), "after\n");
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries, std::slice::from_ref(&entry));
+        assert_eq!(
+            entries[0].plan.changes()[0].before.as_deref(),
+            Some("before\n")
+        );
+        assert_eq!(entry.files().collect::<Vec<_>>(), [config.as_path()]);
+    }
+
+    #[test]
+    fn apply_skips_empty_plans() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::new(dir.path().join("journal"));
+        assert!(journal.apply("noop", &ChangePlan::new()).unwrap().is_none());
+        assert!(journal.entries().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rollback_restores_files_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.json");
+        let journal = Journal::new(dir.path().join("journal"));
+
+        let entry = journal
+            .apply("create config", &plan_writing(&config, "{}\n"))
+            .unwrap()
+            .unwrap();
+        assert!(config.exists());
+
+        let rolled_back = journal.rollback(&entry.id).unwrap();
+        assert!(rolled_back.rolled_back);
+        assert!(!config.exists());
+        assert!(journal.entry(&entry.id).unwrap().rolled_back);
+        assert!(matches!(
+            journal.rollback(&entry.id),
+            Err(Error::Journal(_))
+        ));
+    }
+
+    #[test]
+    fn entries_are_ordered_oldest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::new(dir.path().join("journal"));
+        for i in 0..3 {
+            let path = dir.path().join(format!("{i}.json"));
+            journal
+                .apply(&format!("op {i}"), &plan_writing(&path, "{}\n"))
+                .unwrap();
+        }
+        let descriptions: Vec<_> = journal
+            .entries()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.description)
+            .collect();
+        assert_eq!(descriptions, ["op 0", "op 1", "op 2"]);
+    }
+
+    #[test]
+    fn failed_apply_leaves_no_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.json");
+        let journal = Journal::new(dir.path().join("journal"));
+        let plan = plan_writing(&config, "{}\n");
+        fs::write(&config, "raced\n").unwrap();
+
+        assert!(matches!(
+            journal.apply("create", &plan),
+            Err(Error::Conflict(_))
+        ));
+        assert!(journal.entries().unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn entries_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::new(dir.path().join("journal"));
+        let entry = journal
+            .apply(
+                "create",
+                &plan_writing(&dir.path().join("config.json"), "{}\n"),
+            )
+            .unwrap()
+            .unwrap();
+        journal.rollback(&entry.id).unwrap();
+
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(journal.dir()), 0o700);
+        assert_eq!(mode(&journal.path(&entry.id)), 0o600);
+    }
+
+    #[test]
+    fn missing_entry_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::new(dir.path());
+        assert!(matches!(journal.entry("nope"), Err(Error::Journal(_))));
+    }
+}
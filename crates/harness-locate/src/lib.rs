@@ -4,39 +4,69 @@
 //!
 //! - [`detection`] - Binary detection utilities
 //! - [`error`] - Error types
-//! - [`harness`] - Harness discovery and path resolution
+//! - [`harness`] - Harness discovery, path resolution and per-harness adapters
+//! - [`journal`] - Backups and rollback of applied changes
 //! - [`mcp`] - MCP server type definitions
+//! - [`plan`] - Previewable, reversible file changes
+//! - [`registry`] - Data-driven custom harness definitions
 //! - [`types`] - Core type definitions
 //! - [`skill`] - Skill file parsing utilities
 //! - [`validation`] - MCP server validation utilities
+//! - [`version`] - Harness version detection and version-gated features
 
+pub mod agent;
+pub mod barn;
+pub mod config;
+pub mod convert;
 pub mod detection;
+pub(crate) mod document;
 pub mod error;
 pub mod harness;
+pub mod inventory;
+pub mod journal;
+pub mod mapping;
 pub mod mcp;
+pub mod plan;
 pub mod platform;
+pub mod registry;
+pub mod schema;
 pub mod skill;
 pub mod types;
 pub mod validation;
+pub mod version;
 
+pub use agent::Agent;
+pub use barn::{Barn, BarnInventory, Component, Install, Location};
+pub use config::ConfigDocument;
+pub use convert::{ConvertedAgent, convert_agent, convert_skill};
 pub use detection::find_binary;
 pub use error::{Error, Result};
-pub use harness::Harness;
+pub use harness::{Harness, HarnessAdapter};
+pub use inventory::{Inventory, InventoryItem, ItemKind, ItemMetadata, ScopeInventory};
+pub use journal::{Journal, JournalEntry};
+pub use mapping::{Mappings, ModelRef, ToolName};
 pub use mcp::{
-    HttpMcpServer, McpCapabilities, McpServer, OAuthConfig, SseMcpServer, StdioMcpServer,
+    EffectiveMcpServer, HttpMcpServer, McpCapabilities, McpServer, McpSource, OAuthConfig,
+    SseMcpServer, StdioMcpServer,
 };
+pub use plan::{
+    ChangeKind, ChangePlan, DirectoryChange, DirectoryContent, DirectoryEntry, FileChange,
+};
+pub use registry::{HarnessDefinition, HarnessRegistry};
 pub use skill::{Frontmatter, Skill, parse_frontmatter, parse_skill};
 pub use types::{
-    ConfigResource, DirectoryResource, DirectoryStructure, EnvValue, FileFormat, HarnessKind,
-    InstallationStatus, PathType, ResourceKind, Scope,
+    ConfigResource, DirectoryResource, DirectoryStructure, EnvSyntax, EnvValue, FileFormat,
+    HarnessKind, InstallationStatus, ManagedPolicy, PathType, ResourceKind, Scope,
 };
 pub use validation::{
-    AgentCapabilities, CODE_AGENT_COLOR_FORMAT, CODE_AGENT_MODE_UNSUPPORTED,
-    CODE_AGENT_PARSE_ERROR, CODE_AGENT_TOOLS_FORMAT, CODE_AGENT_UNSUPPORTED,
-    CODE_SKILL_DESCRIPTION_LENGTH, CODE_SKILL_DESCRIPTION_MISSING,
+    AgentCapabilities, CODE_AGENT_COLOR_FORMAT, CODE_AGENT_FIELD_DROPPED,
+    CODE_AGENT_MODE_UNSUPPORTED, CODE_AGENT_NAME_MISSING, CODE_AGENT_PARSE_ERROR,
+    CODE_AGENT_TOOL_UNKNOWN, CODE_AGENT_TOOLS_FORMAT, CODE_AGENT_UNSUPPORTED,
+    CODE_SKILL_DESCRIPTION_DERIVED, CODE_SKILL_DESCRIPTION_LENGTH, CODE_SKILL_DESCRIPTION_MISSING,
     CODE_SKILL_NAME_DIRECTORY_MISMATCH, CODE_SKILL_NAME_FORMAT, CODE_SKILL_NAME_LENGTH,
-    CODE_SKILL_PARSE_ERROR, CODE_SKILL_UNSUPPORTED, ColorFormat, NameFormat,
-    SKILL_DESCRIPTION_MAX_LEN, SKILL_NAME_MAX_LEN, SKILL_NAME_REGEX, Severity, SkillCapabilities,
-    ToolsFormat, ValidationIssue, validate_agent_for_harness, validate_mcp_server,
-    validate_skill_for_harness,
+    CODE_SKILL_NAME_NORMALIZED, CODE_SKILL_PARSE_ERROR, CODE_SKILL_TOOL_UNKNOWN,
+    CODE_SKILL_UNSUPPORTED, ColorFormat, NameFormat, SKILL_DESCRIPTION_MAX_LEN, SKILL_NAME_MAX_LEN,
+    SKILL_NAME_REGEX, Severity, SkillCapabilities, ToolsFormat, ValidationIssue,
+    validate_agent_for_harness, validate_agent_for_harness_version, validate_mcp_server,
+    validate_skill_for_harness, validate_skill_for_harness_version,
 };
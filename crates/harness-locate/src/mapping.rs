@@ -0,0 +1,572 @@
+//! Tool and model names across harnesses.
+//!
+//! Claude Code calls its tools `Read`, `Grep` and `Bash`, OpenCode calls
+//! them `read`, `grep` and `bash`, and Droid runs commands with `Execute`.
+//! [`ToolName`] names a tool independently of any harness and translates
+//! to and from each harness's native name.
+//!
+//! Model references vary the same way: Claude Code accepts aliases such as
+//! `sonnet`, while OpenCode wants `anthropic/claude-sonnet-4-5`.
+//! [`ModelRef`] parses either form and writes the one a harness expects.
+//!
+//! Built-in tables cover Claude Code, OpenCode, Copilot CLI and Droid.
+//! [`Mappings::global`] extends or overrides them, including for custom
+//! harnesses:
+//!
+//! ```
+//! use harness_locate::mapping::{Mappings, ModelRef, ToolName};
+//! use harness_locate::HarnessKind;
+//!
+//! assert_eq!(ToolName::from_native("Bash", HarnessKind::ClaudeCode), Some(ToolName::Bash));
+//! assert_eq!(ToolName::Bash.to_native(HarnessKind::Droid).as_deref(), Some("Execute"));
+//!
+//! let model = ModelRef::parse("sonnet", HarnessKind::ClaudeCode);
+//! assert_eq!(model.to_native(HarnessKind::OpenCode).as_deref(), Some("anthropic/claude-sonnet-4-5"));
+//!
+//! Mappings::global().register_model_alias(
+//!     HarnessKind::ClaudeCode,
+//!     "fast",
+//!     ModelRef::new(Some("anthropic"), "claude-haiku-4-5"),
+//! );
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+use crate::types::HarnessKind;
+
+/// A tool, named independently of any harness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ToolName {
+    /// Read a file.
+    Read,
+    /// Create or overwrite a file.
+    Write,
+    /// Edit part of a file.
+    Edit,
+    /// Apply a patch to files.
+    Patch,
+    /// Find files by glob pattern.
+    Glob,
+    /// Search file contents.
+    Grep,
+    /// List a directory.
+    List,
+    /// Run a shell command.
+    Bash,
+    /// Fetch a web page.
+    WebFetch,
+    /// Search the web.
+    WebSearch,
+    /// Delegate to a subagent.
+    Task,
+    /// Update the todo list.
+    TodoWrite,
+    /// Read the todo list.
+    TodoRead,
+    /// Edit a Jupyter notebook.
+    NotebookEdit,
+}
+
+/// A tool table: native names with their [`ToolName`], or `None` for
+/// native tools without a counterpart elsewhere. The first entry for a
+/// tool is the name it is written as.
+type ToolTable = &'static [(Option<ToolName>, &'static str)];
+
+const CLAUDE_CODE_TOOLS: ToolTable = &[
+    (Some(ToolName::Read), "Read"),
+    (Some(ToolName::Write), "Write"),
+    (Some(ToolName::Edit), "Edit"),
+    (Some(ToolName::Edit), "MultiEdit"),
+    (Some(ToolName::Glob), "Glob"),
+    (Some(ToolName::Grep), "Grep"),
+    (Some(ToolName::List), "LS"),
+    (Some(ToolName::Bash), "Bash"),
+    (Some(ToolName::WebFetch), "WebFetch"),
+    (Some(ToolName::WebSearch), "WebSearch"),
+    (Some(ToolName::Task), "Task"),
+    (Some(ToolName::TodoWrite), "TodoWrite"),
+    (Some(ToolName::NotebookEdit), "NotebookEdit"),
+    (None, "BashOutput"),
+    (None, "KillShell"),
+    (None, "SlashCommand"),
+    (None, "Skill"),
+];
+
+const OPENCODE_TOOLS: ToolTable = &[
+    (Some(ToolName::Read), "read"),
+    (Some(ToolName::Write), "write"),
+    (Some(ToolName::Edit), "edit"),
+    (Some(ToolName::Patch), "patch"),
+    (Some(ToolName::Glob), "glob"),
+    (Some(ToolName::Grep), "grep"),
+    (Some(ToolName::List), "list"),
+    (Some(ToolName::Bash), "bash"),
+    (Some(ToolName::WebFetch), "webfetch"),
+    (Some(ToolName::Task), "task"),
+    (Some(ToolName::TodoWrite), "todowrite"),
+    (Some(ToolName::TodoRead), "todoread"),
+];
+
+// Copilot CLI groups tools under aliases and also accepts Claude Code's
+// names
+const COPILOT_CLI_TOOLS: ToolTable = &[
+    (Some(ToolName::Read), "read"),
+    (Some(ToolName::Edit), "edit"),
+    (Some(ToolName::Write), "edit"),
+    (Some(ToolName::NotebookEdit), "edit"),
+    (Some(ToolName::Grep), "search"),
+    (Some(ToolName::Glob), "search"),
+    (Some(ToolName::Bash), "execute"),
+    (Some(ToolName::Bash), "shell"),
+    (Some(ToolName::WebFetch), "web"),
+    (Some(ToolName::WebSearch), "web"),
+    (Some(ToolName::Task), "agent"),
+    (Some(ToolName::TodoWrite), "todo"),
+    (Some(ToolName::Read), "Read"),
+    (Some(ToolName::Edit), "Edit"),
+    (Some(ToolName::Edit), "MultiEdit"),
+    (Some(ToolName::Write), "Write"),
+    (Some(ToolName::NotebookEdit), "NotebookEdit"),
+    (Some(ToolName::Grep), "Grep"),
+    (Some(ToolName::Glob), "Glob"),
+    (Some(ToolName::Bash), "Bash"),
+    (Some(ToolName::WebFetch), "WebFetch"),
+    (Some(ToolName::WebSearch), "WebSearch"),
+    (Some(ToolName::Task), "Task"),
+];
+
+const DROID_TOOLS: ToolTable = &[
+    (Some(ToolName::Read), "Read"),
+    (Some(ToolName::Write), "Create"),
+    (Some(ToolName::Edit), "Edit"),
+    (Some(ToolName::Edit), "MultiEdit"),
+    (Some(ToolName::Patch), "ApplyPatch"),
+    (Some(ToolName::Glob), "Glob"),
+    (Some(ToolName::Grep), "Grep"),
+    (Some(ToolName::List), "LS"),
+    (Some(ToolName::Bash), "Execute"),
+    (Some(ToolName::WebFetch), "FetchUrl"),
+    (Some(ToolName::WebSearch), "WebSearch"),
+    (Some(ToolName::TodoWrite), "TodoWrite"),
+];
+
+fn builtin_tools(kind: HarnessKind) -> ToolTable {
+    match kind {
+        HarnessKind::ClaudeCode => CLAUDE_CODE_TOOLS,
+        HarnessKind::OpenCode => OPENCODE_TOOLS,
+        HarnessKind::CopilotCli => COPILOT_CLI_TOOLS,
+        HarnessKind::Droid => DROID_TOOLS,
+        _ => &[],
+    }
+}
+
+/// Claude Code's model aliases.
+const CLAUDE_CODE_ALIASES: &[(&str, &str)] = &[
+    ("sonnet", "claude-sonnet-4-5"),
+    ("opus", "claude-opus-4-1"),
+    ("haiku", "claude-haiku-4-5"),
+];
+
+impl ToolName {
+    /// Returns the tool that `native` names in `kind`, or `None` if it is
+    /// not a tool the harness is known to have, or has no counterpart
+    /// elsewhere.
+    ///
+    /// A Claude Code permission suffix such as `Bash(git status:*)` is
+    /// ignored.
+    #[must_use]
+    pub fn from_native(native: &str, kind: HarnessKind) -> Option<Self> {
+        let native = base_name(native);
+        if let Some(tool) = Mappings::global().tool_for(kind, native) {
+            return Some(tool);
+        }
+        builtin_tools(kind)
+            .iter()
+            .find(|(_, name)| *name == native)
+            .and_then(|(tool, _)| *tool)
+    }
+
+    /// Returns the name `kind` uses for this tool, or `None` if the harness
+    /// has no such tool or no known table.
+    #[must_use]
+    pub fn to_native(self, kind: HarnessKind) -> Option<String> {
+        if let Some(native) = Mappings::global().native_for(kind, self) {
+            return Some(native);
+        }
+        builtin_tools(kind)
+            .iter()
+            .find(|(tool, _)| *tool == Some(self))
+            .map(|(_, name)| (*name).to_string())
+    }
+}
+
+/// Returns whether `native` names a tool of `kind`, or `None` if there is
+/// no tool table for the harness.
+///
+/// Wildcard patterns (`mymcp_*`) and Claude Code MCP tools (`mcp__…`) are
+/// always accepted.
+#[must_use]
+pub fn is_known_tool(native: &str, kind: HarnessKind) -> Option<bool> {
+    let overrides = Mappings::global().has_tools(kind);
+    let builtin = builtin_tools(kind);
+    if builtin.is_empty() && !overrides {
+        return None;
+    }
+    let native = base_name(native);
+    Some(
+        native.contains('*')
+            || native.starts_with("mcp__")
+            || builtin.iter().any(|(_, name)| *name == native)
+            || Mappings::global().tool_for(kind, native).is_some(),
+    )
+}
+
+/// Translates the tool `native` from `from` to `to`.
+///
+/// Returns the name unchanged if `from` does not map it to a [`ToolName`]
+/// (an MCP tool, for instance), and `None` if it maps to a tool `to` does
+/// not have. A permission suffix is kept.
+#[must_use]
+pub fn translate_tool(native: &str, from: HarnessKind, to: HarnessKind) -> Option<String> {
+    if from == to {
+        return Some(native.to_string());
+    }
+    let Some(tool) = ToolName::from_native(native, from) else {
+        return Some(native.to_string());
+    };
+    let suffix = &native[base_name(native).len()..];
+    tool.to_native(to).map(|name| format!("{name}{suffix}"))
+}
+
+/// Strips a `(…)` permission suffix from a tool name.
+fn base_name(native: &str) -> &str {
+    native
+        .split_once('(')
+        .map_or(native, |(base, _)| base)
+        .trim()
+}
+
+/// Splits a tool list written as a string, separated by commas (Claude
+/// Code) or by spaces (the agentskills.io `allowed-tools` field).
+/// Separators inside a permission suffix such as `Bash(git status:*)` do
+/// not split.
+pub(crate) fn split_tool_list(list: &str) -> Vec<&str> {
+    let by_comma = list.contains(',');
+    let mut tools = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                tools.push(&list[start..i]);
+                start = i + 1;
+            }
+            c if c.is_whitespace() && depth == 0 && !by_comma => {
+                tools.push(&list[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    tools.push(&list[start..]);
+    tools
+        .into_iter()
+        .map(str::trim)
+        .filter(|tool| !tool.is_empty())
+        .collect()
+}
+
+/// A reference to a model, independent of how a harness spells it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ModelRef {
+    /// Use the model of the parent agent or session (`inherit`).
+    Inherit,
+    /// A specific model.
+    Model {
+        /// The provider (`anthropic`, `openai`, `google`), if known.
+        provider: Option<String>,
+        /// The model id, without the provider (`claude-sonnet-4-5`).
+        id: String,
+    },
+}
+
+impl ModelRef {
+    /// Creates a reference to the model `id` of `provider`.
+    #[must_use]
+    pub fn new(provider: Option<&str>, id: impl Into<String>) -> Self {
+        Self::Model {
+            provider: provider.map(String::from),
+            id: id.into(),
+        }
+    }
+
+    /// Parses a model as written for `kind`.
+    ///
+    /// Accepts `inherit`, `provider/id`, the harness's aliases (`sonnet`
+    /// in Claude Code) and bare ids, whose provider is inferred from
+    /// well-known prefixes (`claude-`, `gpt-`, `gemini-`).
+    #[must_use]
+    pub fn parse(model: &str, kind: HarnessKind) -> Self {
+        let model = model.trim();
+        if model == "inherit" {
+            return Self::Inherit;
+        }
+        if let Some(alias) = Mappings::global().model_alias(kind, model) {
+            return alias;
+        }
+        if kind == HarnessKind::ClaudeCode
+            && let Some((_, id)) = CLAUDE_CODE_ALIASES.iter().find(|(a, _)| *a == model)
+        {
+            return Self::new(Some("anthropic"), *id);
+        }
+        if let Some((provider, id)) = model.split_once('/') {
+            return Self::new(Some(provider), id);
+        }
+        Self::new(infer_provider(model), model)
+    }
+
+    /// Returns the model as `kind` writes it, or `None` if the harness
+    /// cannot refer to it: OpenCode has no `inherit` and needs a provider,
+    /// and Claude Code only runs Anthropic models.
+    #[must_use]
+    pub fn to_native(&self, kind: HarnessKind) -> Option<String> {
+        let Self::Model { provider, id } = self else {
+            return matches!(kind, HarnessKind::ClaudeCode | HarnessKind::Droid)
+                .then(|| "inherit".to_string());
+        };
+        match kind {
+            HarnessKind::OpenCode => provider.as_ref().map(|p| format!("{p}/{id}")),
+            HarnessKind::ClaudeCode => {
+                matches!(provider.as_deref(), None | Some("anthropic")).then(|| id.clone())
+            }
+            _ => Some(id.clone()),
+        }
+    }
+}
+
+fn infer_provider(id: &str) -> Option<&'static str> {
+    const PREFIXES: &[(&str, &str)] = &[
+        ("claude-", "anthropic"),
+        ("gpt-", "openai"),
+        ("o1", "openai"),
+        ("o3", "openai"),
+        ("o4", "openai"),
+        ("gemini-", "google"),
+    ];
+    PREFIXES
+        .iter()
+        .find(|(prefix, _)| id.starts_with(prefix))
+        .map(|(_, provider)| *provider)
+}
+
+/// Process-wide tool names and model aliases that extend or override the
+/// built-in tables.
+///
+/// Entries registered later take precedence over earlier ones and over the
+/// built-in tables.
+pub struct Mappings {
+    tools: RwLock<HashMap<HarnessKind, Vec<(ToolName, String)>>>,
+    models: RwLock<HashMap<HarnessKind, Vec<(String, ModelRef)>>>,
+}
+
+static GLOBAL: LazyLock<Mappings> = LazyLock::new(|| Mappings {
+    tools: RwLock::new(HashMap::new()),
+    models: RwLock::new(HashMap::new()),
+});
+
+impl Mappings {
+    /// Returns the mappings consulted by [`ToolName`], [`ModelRef`] and
+    /// conversion.
+    #[must_use]
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Registers `native` as the name of `tool` in `kind`.
+    pub fn register_tool(&self, kind: HarnessKind, tool: ToolName, native: impl Into<String>) {
+        let mut tools = self.tools.write().unwrap_or_else(PoisonError::into_inner);
+        tools.entry(kind).or_default().push((tool, native.into()));
+    }
+
+    /// Registers `alias` as a model name in `kind` for `model`.
+    pub fn register_model_alias(
+        &self,
+        kind: HarnessKind,
+        alias: impl Into<String>,
+        model: ModelRef,
+    ) {
+        let mut models = self.models.write().unwrap_or_else(PoisonError::into_inner);
+        models.entry(kind).or_default().push((alias.into(), model));
+    }
+
+    /// Removes every tool name and model alias registered for `kind`.
+    pub fn clear(&self, kind: HarnessKind) {
+        let mut tools = self.tools.write().unwrap_or_else(PoisonError::into_inner);
+        tools.remove(&kind);
+        let mut models = self.models.write().unwrap_or_else(PoisonError::into_inner);
+        models.remove(&kind);
+    }
+
+    fn has_tools(&self, kind: HarnessKind) -> bool {
+        let tools = self.tools.read().unwrap_or_else(PoisonError::into_inner);
+        tools.get(&kind).is_some_and(|t| !t.is_empty())
+    }
+
+    fn tool_for(&self, kind: HarnessKind, native: &str) -> Option<ToolName> {
+        let tools = self.tools.read().unwrap_or_else(PoisonError::into_inner);
+        tools
+            .get(&kind)?
+            .iter()
+            .rev()
+            .find(|(_, name)| name == native)
+            .map(|(tool, _)| *tool)
+    }
+
+    fn native_for(&self, kind: HarnessKind, tool: ToolName) -> Option<String> {
+        let tools = self.tools.read().unwrap_or_else(PoisonError::into_inner);
+        tools
+            .get(&kind)?
+            .iter()
+            .rev()
+            .find(|(t, _)| *t == tool)
+            .map(|(_, name)| name.clone())
+    }
+
+    fn model_alias(&self, kind: HarnessKind, alias: &str) -> Option<ModelRef> {
+        let models = self.models.read().unwrap_or_else(PoisonError::into_inner);
+        models
+            .get(&kind)?
+            .iter()
+            .rev()
+            .find(|(a, _)| a == alias)
+            .map(|(_, model)| model.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_names_round_trip_through_tables() {
+        assert_eq!(
+            ToolName::from_native("MultiEdit", HarnessKind::ClaudeCode),
+            Some(ToolName::Edit)
+        );
+        assert_eq!(
+            ToolName::from_native("Bash(git status:*)", HarnessKind::ClaudeCode),
+            Some(ToolName::Bash)
+        );
+        assert_eq!(
+            ToolName::from_native("KillShell", HarnessKind::ClaudeCode),
+            None
+        );
+        assert_eq!(
+            ToolName::Write.to_native(HarnessKind::Droid).as_deref(),
+            Some("Create")
+        );
+        assert_eq!(
+            ToolName::Glob.to_native(HarnessKind::CopilotCli).as_deref(),
+            Some("search")
+        );
+        assert_eq!(ToolName::WebSearch.to_native(HarnessKind::OpenCode), None);
+        assert_eq!(ToolName::Read.to_native(HarnessKind::Zed), None);
+    }
+
+    #[test]
+    fn translate_tool_keeps_unmapped_names() {
+        let translate = |tool| translate_tool(tool, HarnessKind::ClaudeCode, HarnessKind::OpenCode);
+        assert_eq!(translate("Grep").as_deref(), Some("grep"));
+        assert_eq!(translate("Bash(git:*)").as_deref(), Some("bash(git:*)"));
+        assert_eq!(
+            translate("mcp__github__search").as_deref(),
+            Some("mcp__github__search")
+        );
+        assert_eq!(translate("WebSearch"), None);
+        assert_eq!(
+            translate_tool("Read", HarnessKind::ClaudeCode, HarnessKind::AmpCode).as_deref(),
+            None
+        );
+    }
+
+    #[test]
+    fn is_known_tool_per_harness() {
+        assert_eq!(is_known_tool("Read", HarnessKind::ClaudeCode), Some(true));
+        assert_eq!(is_known_tool("read", HarnessKind::ClaudeCode), Some(false));
+        assert_eq!(
+            is_known_tool("mcp__x__y", HarnessKind::ClaudeCode),
+            Some(true)
+        );
+        assert_eq!(is_known_tool("github_*", HarnessKind::OpenCode), Some(true));
+        assert_eq!(is_known_tool("anything", HarnessKind::AmpCode), None);
+    }
+
+    #[test]
+    fn split_tool_list_forms() {
+        assert_eq!(split_tool_list("Read, Grep,"), ["Read", "Grep"]);
+        assert_eq!(
+            split_tool_list("Bash(git status:*) Read"),
+            ["Bash(git status:*)", "Read"]
+        );
+        assert_eq!(
+            split_tool_list("Bash(git add, git commit), Read"),
+            ["Bash(git add, git commit)", "Read"]
+        );
+    }
+
+    #[test]
+    fn model_refs_per_harness() {
+        let sonnet = ModelRef::parse("sonnet", HarnessKind::ClaudeCode);
+        assert_eq!(
+            sonnet,
+            ModelRef::new(Some("anthropic"), "claude-sonnet-4-5")
+        );
+        assert_eq!(
+            sonnet.to_native(HarnessKind::ClaudeCode).as_deref(),
+            Some("claude-sonnet-4-5")
+        );
+        assert_eq!(
+            sonnet.to_native(HarnessKind::Droid).as_deref(),
+            Some("claude-sonnet-4-5")
+        );
+
+        let gpt = ModelRef::parse("openai/gpt-5", HarnessKind::OpenCode);
+        assert_eq!(gpt.to_native(HarnessKind::ClaudeCode), None);
+        assert_eq!(ModelRef::parse("gpt-5", HarnessKind::Droid), gpt);
+
+        let unknown = ModelRef::parse("local-model", HarnessKind::Droid);
+        assert_eq!(unknown.to_native(HarnessKind::OpenCode), None);
+
+        let inherit = ModelRef::parse("inherit", HarnessKind::ClaudeCode);
+        assert_eq!(inherit, ModelRef::Inherit);
+        assert_eq!(
+            inherit.to_native(HarnessKind::Droid).as_deref(),
+            Some("inherit")
+        );
+        assert_eq!(inherit.to_native(HarnessKind::OpenCode), None);
+    }
+
+    #[test]
+    fn overrides_take_precedence() {
+        let kind = HarnessKind::Custom("mapping-test");
+        assert_eq!(is_known_tool("run", kind), None);
+
+        Mappings::global().register_tool(kind, ToolName::Bash, "run");
+        Mappings::global().register_model_alias(kind, "fast", ModelRef::new(None, "m-1"));
+        assert_eq!(ToolName::from_native("run", kind), Some(ToolName::Bash));
+        assert_eq!(ToolName::Bash.to_native(kind).as_deref(), Some("run"));
+        assert_eq!(is_known_tool("shell", kind), Some(false));
+        assert_eq!(
+            translate_tool("Bash", HarnessKind::ClaudeCode, kind).as_deref(),
+            Some("run")
+        );
+        assert_eq!(ModelRef::parse("fast", kind), ModelRef::new(None, "m-1"));
+
+        Mappings::global().clear(kind);
+        assert_eq!(ToolName::from_native("run", kind), None);
+    }
+}
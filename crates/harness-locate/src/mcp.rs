@@ -9,7 +9,8 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::Error;
-use crate::types::{EnvValue, HarnessKind};
+use crate::types::{EnvValue, HarnessKind, Scope, SecretPrompt};
+use crate::version::{Feature, Version};
 
 /// Returns `true` for serde default.
 fn default_true() -> bool {
@@ -73,6 +74,30 @@ impl McpServer {
         names
     }
 
+    /// Returns the secret prompts referenced by this server's values.
+    #[must_use]
+    pub fn secret_prompts(&self) -> Vec<&SecretPrompt> {
+        let values: Vec<&EnvValue> = match self {
+            Self::Stdio(s) => s.env.values().collect(),
+            Self::Sse(s) => s.headers.values().collect(),
+            Self::Http(h) => h
+                .headers
+                .values()
+                .chain(h.oauth.as_ref().and_then(|o| o.client_secret.as_ref()))
+                .collect(),
+        };
+        let mut prompts: Vec<&SecretPrompt> = values
+            .into_iter()
+            .filter_map(|value| match value {
+                EnvValue::Prompt { prompt } => Some(prompt),
+                _ => None,
+            })
+            .collect();
+        prompts.sort_by(|a, b| a.id.cmp(&b.id));
+        prompts.dedup_by(|a, b| a.id == b.id);
+        prompts
+    }
+
     pub fn missing_env_vars(&self) -> Vec<&str> {
         self.env_var_names()
             .into_iter()
@@ -117,6 +142,15 @@ impl McpServer {
             });
         }
 
+        if !caps.prompts
+            && let Some(prompt) = self.secret_prompts().first()
+        {
+            return Err(Error::UnsupportedMcpConfig {
+                harness: format!("{kind:?}"),
+                reason: format!("secret prompt '{}' not supported", prompt.id),
+            });
+        }
+
         let has_toggle = match self {
             Self::Stdio(s) => !s.enabled,
             Self::Sse(s) => !s.enabled,
@@ -139,17 +173,13 @@ impl McpServer {
     ) -> Result<serde_json::Value, Error> {
         self.validate_capabilities(kind)?;
 
-        match kind {
-            HarnessKind::ClaudeCode => self.to_claude_code_value(kind),
-            HarnessKind::CopilotCli => self.to_copilot_cli_value(kind),
-            HarnessKind::OpenCode | HarnessKind::Crush => self.to_opencode_value(kind),
-            HarnessKind::Goose => self.to_goose_value(kind, name),
-            HarnessKind::AmpCode => self.to_ampcode_value(kind),
-            HarnessKind::Droid => self.to_droid_value(kind),
-        }
+        crate::harness::adapter::for_kind(kind).mcp_to_native(name, self)
     }
 
-    fn to_claude_code_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+    pub(crate) fn to_claude_code_value(
+        &self,
+        kind: HarnessKind,
+    ) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let mut obj = serde_json::json!({
@@ -198,7 +228,10 @@ impl McpServer {
         }
     }
 
-    fn to_copilot_cli_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+    pub(crate) fn to_copilot_cli_value(
+        &self,
+        kind: HarnessKind,
+    ) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let mut obj = serde_json::json!({
@@ -257,7 +290,7 @@ impl McpServer {
         }
     }
 
-    fn to_opencode_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+    pub(crate) fn to_opencode_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let mut command = vec![s.command.clone()];
@@ -338,7 +371,59 @@ impl McpServer {
         }
     }
 
-    fn to_goose_value(&self, kind: HarnessKind, name: &str) -> Result<serde_json::Value, Error> {
+    /// Crush: `type` plus `command`/`args`/`env` or `url`/`headers`, with
+    /// `disabled` and a `timeout` in whole seconds.
+    pub(crate) fn to_crush_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+        let native = |values: &HashMap<String, EnvValue>| {
+            values
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
+                .collect::<Result<std::collections::BTreeMap<_, _>, Error>>()
+        };
+        let (mut obj, enabled, timeout_ms) = match self {
+            Self::Stdio(s) => {
+                let mut obj = serde_json::json!({
+                    "type": "stdio",
+                    "command": s.command,
+                });
+                if !s.args.is_empty() {
+                    obj["args"] = serde_json::json!(s.args);
+                }
+                if !s.env.is_empty() {
+                    obj["env"] = serde_json::to_value(native(&s.env)?).unwrap();
+                }
+                (obj, s.enabled, s.timeout_ms)
+            }
+            Self::Sse(s) => {
+                let mut obj = serde_json::json!({"type": "sse", "url": s.url});
+                if !s.headers.is_empty() {
+                    obj["headers"] = serde_json::to_value(native(&s.headers)?).unwrap();
+                }
+                (obj, s.enabled, s.timeout_ms)
+            }
+            Self::Http(h) => {
+                let mut obj = serde_json::json!({"type": "http", "url": h.url});
+                if !h.headers.is_empty() {
+                    obj["headers"] = serde_json::to_value(native(&h.headers)?).unwrap();
+                }
+                (obj, h.enabled, h.timeout_ms)
+            }
+        };
+        if !enabled {
+            obj["disabled"] = serde_json::json!(true);
+        }
+        if let Some(timeout_ms) = timeout_ms {
+            // Rounded up so that a sub-second timeout does not become 0
+            obj["timeout"] = serde_json::json!(timeout_ms.div_ceil(1000));
+        }
+        Ok(obj)
+    }
+
+    pub(crate) fn to_goose_value(
+        &self,
+        kind: HarnessKind,
+        name: &str,
+    ) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let args: Vec<String> = s.args.to_vec();
@@ -350,14 +435,25 @@ impl McpServer {
                     "cmd": s.command,
                     "args": args,
                 });
-                if !s.env.is_empty() {
-                    let envs: std::collections::HashMap<String, String> = s
-                        .env
-                        .iter()
-                        .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
-                        .collect::<Result<_, Error>>()?;
+                // References go to env_keys, which Goose looks up in its
+                // secret store, so their values never land in config.yaml
+                let mut envs = std::collections::BTreeMap::new();
+                let mut env_keys = Vec::new();
+                for (key, value) in &s.env {
+                    match value {
+                        EnvValue::EnvRef { .. } => env_keys.push(key.clone()),
+                        _ => {
+                            envs.insert(key.clone(), value.try_to_native(kind)?);
+                        }
+                    }
+                }
+                if !envs.is_empty() {
                     obj["envs"] = serde_json::to_value(envs).unwrap();
                 }
+                if !env_keys.is_empty() {
+                    env_keys.sort();
+                    obj["env_keys"] = serde_json::json!(env_keys);
+                }
                 if let Some(timeout_ms) = s.timeout_ms {
                     obj["timeout"] = serde_json::json!(timeout_ms / 1000);
                 }
@@ -383,7 +479,7 @@ impl McpServer {
         }
     }
 
-    fn to_ampcode_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+    pub(crate) fn to_ampcode_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let mut obj = serde_json::json!({
@@ -404,14 +500,376 @@ impl McpServer {
                 harness: kind.to_string(),
                 reason: "SSE transport not supported".into(),
             }),
-            Self::Http(_) => Err(Error::UnsupportedMcpConfig {
-                harness: kind.to_string(),
-                reason: "HTTP transport not supported".into(),
+            // AMP reads an entry with a `url` and no `type` as HTTP
+            Self::Http(h) => {
+                let mut obj = serde_json::json!({ "url": h.url });
+                if !h.headers.is_empty() {
+                    let headers: std::collections::BTreeMap<String, String> = h
+                        .headers
+                        .iter()
+                        .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
+                        .collect::<Result<_, Error>>()?;
+                    obj["headers"] = serde_json::to_value(headers).unwrap();
+                }
+                Ok(obj)
+            }
+        }
+    }
+
+    /// Codex cannot template environment variables into values. References
+    /// to a variable of the same name are forwarded through `env_vars`, and
+    /// header references through `env_http_headers`; a reference to a
+    /// differently named variable has no Codex form and is rejected rather
+    /// than resolved into the file.
+    pub(crate) fn to_codex_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+        let mut obj = match self {
+            Self::Stdio(s) => {
+                let mut obj = serde_json::json!({
+                    "command": s.command,
+                    "args": s.args,
+                });
+                let mut env = std::collections::BTreeMap::new();
+                let mut env_vars = Vec::new();
+                for (key, value) in &s.env {
+                    match value {
+                        EnvValue::EnvRef { env: var } if var == key => env_vars.push(key.clone()),
+                        EnvValue::EnvRef { env: var } => {
+                            return Err(Error::UnsupportedMcpConfig {
+                                harness: kind.to_string(),
+                                reason: format!(
+                                    "env.{key} references ${var}, but Codex only passes \
+                                     variables through under their own name"
+                                ),
+                            });
+                        }
+                        _ => {
+                            env.insert(key.clone(), value.try_to_native(kind)?);
+                        }
+                    }
+                }
+                if !env.is_empty() {
+                    obj["env"] = serde_json::to_value(env).unwrap();
+                }
+                if !env_vars.is_empty() {
+                    env_vars.sort();
+                    obj["env_vars"] = serde_json::to_value(env_vars).unwrap();
+                }
+                if let Some(cwd) = &s.cwd {
+                    obj["cwd"] = serde_json::json!(cwd.to_string_lossy());
+                }
+                obj
+            }
+            Self::Http(h) => {
+                let mut obj = serde_json::json!({
+                    "url": h.url,
+                });
+                let mut headers = std::collections::BTreeMap::new();
+                let mut env_headers = std::collections::BTreeMap::new();
+                for (key, value) in &h.headers {
+                    match value {
+                        EnvValue::EnvRef { env } => {
+                            env_headers.insert(key.clone(), env.clone());
+                        }
+                        _ => {
+                            headers.insert(key.clone(), value.try_to_native(kind)?);
+                        }
+                    }
+                }
+                if !headers.is_empty() {
+                    obj["http_headers"] = serde_json::to_value(headers).unwrap();
+                }
+                if !env_headers.is_empty() {
+                    obj["env_http_headers"] = serde_json::to_value(env_headers).unwrap();
+                }
+                obj
+            }
+            Self::Sse(_) => {
+                return Err(Error::UnsupportedMcpConfig {
+                    harness: kind.to_string(),
+                    reason: "SSE transport not supported".into(),
+                });
+            }
+        };
+        let (enabled, timeout_ms) = match self {
+            Self::Stdio(s) => (s.enabled, s.timeout_ms),
+            Self::Sse(s) => (s.enabled, s.timeout_ms),
+            Self::Http(h) => (h.enabled, h.timeout_ms),
+        };
+        if !enabled {
+            obj["enabled"] = serde_json::json!(false);
+        }
+        if let Some(timeout_ms) = timeout_ms {
+            obj["tool_timeout_sec"] = if timeout_ms % 1000 == 0 {
+                serde_json::json!(timeout_ms / 1000)
+            } else {
+                serde_json::json!(timeout_ms as f64 / 1000.0)
+            };
+        }
+        Ok(obj)
+    }
+
+    pub(crate) fn to_gemini_cli_value(
+        &self,
+        kind: HarnessKind,
+    ) -> Result<serde_json::Value, Error> {
+        let native_map = |values: &HashMap<String, EnvValue>| -> Result<serde_json::Value, Error> {
+            let map: std::collections::BTreeMap<String, String> = values
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
+                .collect::<Result<_, Error>>()?;
+            Ok(serde_json::to_value(map).unwrap())
+        };
+        let (mut obj, timeout_ms) = match self {
+            Self::Stdio(s) => {
+                let mut obj = serde_json::json!({
+                    "command": s.command,
+                    "args": s.args,
+                });
+                if !s.env.is_empty() {
+                    obj["env"] = native_map(&s.env)?;
+                }
+                if let Some(cwd) = &s.cwd {
+                    obj["cwd"] = serde_json::json!(cwd.to_string_lossy());
+                }
+                (obj, s.timeout_ms)
+            }
+            Self::Sse(s) => {
+                let mut obj = serde_json::json!({
+                    "url": s.url,
+                });
+                if !s.headers.is_empty() {
+                    obj["headers"] = native_map(&s.headers)?;
+                }
+                (obj, s.timeout_ms)
+            }
+            Self::Http(h) => {
+                let mut obj = serde_json::json!({
+                    "httpUrl": h.url,
+                });
+                if !h.headers.is_empty() {
+                    obj["headers"] = native_map(&h.headers)?;
+                }
+                if let Some(oauth) = &h.oauth {
+                    let mut oauth_obj = serde_json::json!({ "enabled": true });
+                    if let Some(client_id) = &oauth.client_id {
+                        oauth_obj["clientId"] = serde_json::json!(client_id);
+                    }
+                    if let Some(secret) = &oauth.client_secret {
+                        oauth_obj["clientSecret"] = serde_json::json!(secret.try_to_native(kind)?);
+                    }
+                    if let Some(scope) = &oauth.scope {
+                        let scopes: Vec<&str> = scope.split_whitespace().collect();
+                        oauth_obj["scopes"] = serde_json::json!(scopes);
+                    }
+                    obj["oauth"] = oauth_obj;
+                }
+                (obj, h.timeout_ms)
+            }
+        };
+        if let Some(timeout_ms) = timeout_ms {
+            obj["timeout"] = serde_json::json!(timeout_ms);
+        }
+        Ok(obj)
+    }
+
+    pub(crate) fn to_cursor_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+        let native_map = |values: &HashMap<String, EnvValue>| -> Result<serde_json::Value, Error> {
+            let map: std::collections::BTreeMap<String, String> = values
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
+                .collect::<Result<_, Error>>()?;
+            Ok(serde_json::to_value(map).unwrap())
+        };
+        match self {
+            Self::Stdio(s) => {
+                let mut obj = serde_json::json!({
+                    "command": s.command,
+                    "args": s.args,
+                });
+                if !s.env.is_empty() {
+                    obj["env"] = native_map(&s.env)?;
+                }
+                Ok(obj)
+            }
+            Self::Sse(s) => {
+                // Remote servers without a type are read as streamable HTTP
+                let mut obj = serde_json::json!({
+                    "url": s.url,
+                    "type": "sse",
+                });
+                if !s.headers.is_empty() {
+                    obj["headers"] = native_map(&s.headers)?;
+                }
+                Ok(obj)
+            }
+            Self::Http(h) => {
+                let mut obj = serde_json::json!({
+                    "url": h.url,
+                });
+                if !h.headers.is_empty() {
+                    obj["headers"] = native_map(&h.headers)?;
+                }
+                Ok(obj)
+            }
+        }
+    }
+
+    pub(crate) fn to_vscode_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+        let native_map = |values: &HashMap<String, EnvValue>| -> Result<serde_json::Value, Error> {
+            let map: std::collections::BTreeMap<String, String> = values
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
+                .collect::<Result<_, Error>>()?;
+            Ok(serde_json::to_value(map).unwrap())
+        };
+        match self {
+            Self::Stdio(s) => {
+                let mut obj = serde_json::json!({
+                    "type": "stdio",
+                    "command": s.command,
+                    "args": s.args,
+                });
+                if !s.env.is_empty() {
+                    obj["env"] = native_map(&s.env)?;
+                }
+                if let Some(cwd) = &s.cwd {
+                    obj["cwd"] = serde_json::json!(cwd.to_string_lossy());
+                }
+                Ok(obj)
+            }
+            Self::Sse(s) => {
+                let mut obj = serde_json::json!({
+                    "type": "sse",
+                    "url": s.url,
+                });
+                if !s.headers.is_empty() {
+                    obj["headers"] = native_map(&s.headers)?;
+                }
+                Ok(obj)
+            }
+            Self::Http(h) => {
+                let mut obj = serde_json::json!({
+                    "type": "http",
+                    "url": h.url,
+                });
+                if !h.headers.is_empty() {
+                    obj["headers"] = native_map(&h.headers)?;
+                }
+                Ok(obj)
+            }
+        }
+    }
+
+    /// Converts to the format shared by Cline and Roo Code.
+    ///
+    /// Cline writes auto-approved tools as `autoApprove` and streamable
+    /// HTTP as `streamableHttp`; Roo Code uses `alwaysAllow` and
+    /// `streamable-http`.
+    pub(crate) fn to_cline_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+        let native_map = |values: &HashMap<String, EnvValue>| -> Result<serde_json::Value, Error> {
+            let map: std::collections::BTreeMap<String, String> = values
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
+                .collect::<Result<_, Error>>()?;
+            Ok(serde_json::to_value(map).unwrap())
+        };
+        let (mut obj, enabled, timeout_ms, auto_approve) = match self {
+            Self::Stdio(s) => {
+                let mut obj = serde_json::json!({
+                    "command": s.command,
+                    "args": s.args,
+                });
+                if !s.env.is_empty() {
+                    obj["env"] = native_map(&s.env)?;
+                }
+                if let Some(cwd) = &s.cwd {
+                    obj["cwd"] = serde_json::json!(cwd.to_string_lossy());
+                }
+                (obj, s.enabled, s.timeout_ms, &s.auto_approve)
+            }
+            Self::Sse(s) => {
+                let mut obj = serde_json::json!({
+                    "type": "sse",
+                    "url": s.url,
+                });
+                if !s.headers.is_empty() {
+                    obj["headers"] = native_map(&s.headers)?;
+                }
+                (obj, s.enabled, s.timeout_ms, &s.auto_approve)
+            }
+            Self::Http(h) => {
+                let transport = if kind == HarnessKind::Cline {
+                    "streamableHttp"
+                } else {
+                    "streamable-http"
+                };
+                let mut obj = serde_json::json!({
+                    "type": transport,
+                    "url": h.url,
+                });
+                if !h.headers.is_empty() {
+                    obj["headers"] = native_map(&h.headers)?;
+                }
+                (obj, h.enabled, h.timeout_ms, &h.auto_approve)
+            }
+        };
+        if !enabled {
+            obj["disabled"] = serde_json::json!(true);
+        }
+        if let Some(timeout_ms) = timeout_ms {
+            // Timeouts are in whole seconds
+            obj["timeout"] = serde_json::json!(timeout_ms.div_ceil(1000));
+        }
+        if !auto_approve.is_empty() {
+            let key = if kind == HarnessKind::Cline {
+                "autoApprove"
+            } else {
+                "alwaysAllow"
+            };
+            obj[key] = serde_json::json!(auto_approve);
+        }
+        Ok(obj)
+    }
+
+    /// Converts to a Zed custom context server.
+    pub(crate) fn to_zed_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+        let native_map = |values: &HashMap<String, EnvValue>| -> Result<serde_json::Value, Error> {
+            let map: std::collections::BTreeMap<String, String> = values
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
+                .collect::<Result<_, Error>>()?;
+            Ok(serde_json::to_value(map).unwrap())
+        };
+        match self {
+            Self::Stdio(s) => {
+                let mut obj = serde_json::json!({
+                    "source": "custom",
+                    "command": s.command,
+                    "args": s.args,
+                });
+                if !s.env.is_empty() {
+                    obj["env"] = native_map(&s.env)?;
+                }
+                Ok(obj)
+            }
+            Self::Sse(_) => Err(Error::UnsupportedMcpConfig {
+                harness: format!("{kind:?}"),
+                reason: "SSE transport not supported".into(),
             }),
+            Self::Http(h) => {
+                let mut obj = serde_json::json!({
+                    "source": "custom",
+                    "url": h.url,
+                });
+                if !h.headers.is_empty() {
+                    obj["headers"] = native_map(&h.headers)?;
+                }
+                Ok(obj)
+            }
         }
     }
 
-    fn to_droid_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+    pub(crate) fn to_droid_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let mut obj = serde_json::json!({
@@ -496,6 +954,7 @@ impl McpServer {
 ///     cwd: None,
 ///     enabled: true,
 ///     timeout_ms: None,
+///     auto_approve: Vec::new(),
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -528,6 +987,12 @@ pub struct StdioMcpServer {
     /// If not specified, harness-specific defaults apply.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub timeout_ms: Option<u64>,
+
+    /// Tools the harness runs without asking for approval.
+    ///
+    /// Only written by harnesses with [`McpCapabilities::auto_approve`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_approve: Vec<String>,
 }
 
 /// Configuration for an SSE (Server-Sent Events) MCP server.
@@ -544,6 +1009,7 @@ pub struct StdioMcpServer {
 ///     headers: Default::default(),
 ///     enabled: true,
 ///     timeout_ms: Some(30000),
+///     auto_approve: Vec::new(),
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -569,6 +1035,12 @@ pub struct SseMcpServer {
     /// If not specified, harness-specific defaults apply.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub timeout_ms: Option<u64>,
+
+    /// Tools the harness runs without asking for approval.
+    ///
+    /// Only written by harnesses with [`McpCapabilities::auto_approve`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_approve: Vec<String>,
 }
 
 /// Configuration for an HTTP/Streamable HTTP MCP server.
@@ -592,6 +1064,7 @@ pub struct SseMcpServer {
 ///     }),
 ///     enabled: true,
 ///     timeout_ms: None,
+///     auto_approve: Vec::new(),
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -622,6 +1095,12 @@ pub struct HttpMcpServer {
     /// If not specified, harness-specific defaults apply.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub timeout_ms: Option<u64>,
+
+    /// Tools the harness runs without asking for approval.
+    ///
+    /// Only written by harnesses with [`McpCapabilities::auto_approve`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_approve: Vec<String>,
 }
 
 /// OAuth configuration for HTTP MCP servers.
@@ -644,6 +1123,33 @@ pub struct OAuthConfig {
     pub scope: Option<String>,
 }
 
+/// A config file that contributes MCP servers to a harness.
+#[derive(Debug, Clone)]
+pub struct McpSource {
+    /// The scope the file was resolved for.
+    pub scope: Scope,
+    /// Path to the config file.
+    pub file: PathBuf,
+}
+
+/// An MCP server entry after merging a harness's config layers.
+///
+/// Returned by [`Harness::effective_mcp_servers`].
+///
+/// [`Harness::effective_mcp_servers`]: crate::Harness::effective_mcp_servers
+#[derive(Debug, Clone)]
+pub struct EffectiveMcpServer {
+    /// The parsed server, or `None` if the entry is not a server this crate
+    /// can represent (for example a Goose `builtin` extension).
+    pub server: Option<McpServer>,
+    /// The entry exactly as written in its config file.
+    pub native: serde_json::Value,
+    /// The layer whose entry is in effect.
+    pub source: McpSource,
+    /// Lower-precedence layers that define the same name, highest first.
+    pub shadowed: Vec<McpSource>,
+}
+
 /// Describes what MCP features a harness supports.
 ///
 /// Different harnesses support different subsets of MCP configuration options.
@@ -667,7 +1173,8 @@ pub struct OAuthConfig {
 /// assert!(caps.stdio);
 /// assert!(caps.oauth);  // OpenCode supports OAuth
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
 #[non_exhaustive]
 pub struct McpCapabilities {
     /// Supports local stdio servers.
@@ -693,6 +1200,13 @@ pub struct McpCapabilities {
 
     /// Supports working directory (cwd) for stdio servers.
     pub cwd: bool,
+
+    /// Supports prompting the user for secret values
+    /// ([`EnvValue::Prompt`]).
+    pub prompts: bool,
+
+    /// Supports a per-server list of auto-approved tools.
+    pub auto_approve: bool,
 }
 
 impl McpCapabilities {
@@ -710,6 +1224,40 @@ impl McpCapabilities {
     /// ```
     #[must_use]
     pub fn for_kind(kind: HarnessKind) -> Self {
+        Self::for_version(kind, None)
+    }
+
+    /// Returns the MCP capabilities of a specific release of a harness.
+    ///
+    /// Features introduced after `version` (see
+    /// [`Feature::min_version`](crate::version::Feature::min_version)) are
+    /// reported as unsupported. With `None`, the latest known release is
+    /// assumed, as in [`McpCapabilities::for_kind`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::mcp::McpCapabilities;
+    /// use harness_locate::types::HarnessKind;
+    /// use harness_locate::version::Version;
+    ///
+    /// let old = Version::new(1, 0, 0);
+    /// let caps = McpCapabilities::for_version(HarnessKind::ClaudeCode, Some(&old));
+    /// assert!(caps.stdio);
+    /// assert!(!caps.http);
+    /// ```
+    #[must_use]
+    pub fn for_version(kind: HarnessKind, version: Option<&Version>) -> Self {
+        let mut caps = Self::latest(kind);
+        if let Some(version) = version {
+            caps.sse &= Feature::SseTransport.is_available(kind, version);
+            caps.http &= Feature::HttpTransport.is_available(kind, version);
+            caps.oauth &= Feature::OAuth.is_available(kind, version);
+        }
+        caps
+    }
+
+    fn latest(kind: HarnessKind) -> Self {
         match kind {
             HarnessKind::ClaudeCode => Self {
                 stdio: true,
@@ -720,6 +1268,8 @@ impl McpCapabilities {
                 toggle: false,
                 headers: true,
                 cwd: false,
+                prompts: false,
+                auto_approve: false,
             },
             HarnessKind::OpenCode => Self {
                 stdio: true,
@@ -730,6 +1280,8 @@ impl McpCapabilities {
                 toggle: true,
                 headers: true,
                 cwd: false,
+                prompts: false,
+                auto_approve: false,
             },
             HarnessKind::Goose => Self {
                 stdio: true,
@@ -740,16 +1292,20 @@ impl McpCapabilities {
                 toggle: false,
                 headers: false,
                 cwd: false,
+                prompts: false,
+                auto_approve: false,
             },
             HarnessKind::AmpCode => Self {
                 stdio: true,
                 sse: false,
-                http: false,
+                http: true,
                 oauth: false,
                 timeout: false,
                 toggle: false,
-                headers: false,
+                headers: true,
                 cwd: false,
+                prompts: false,
+                auto_approve: false,
             },
             HarnessKind::CopilotCli => Self {
                 stdio: true,
@@ -760,6 +1316,8 @@ impl McpCapabilities {
                 toggle: false,
                 headers: true,
                 cwd: false,
+                prompts: false,
+                auto_approve: false,
             },
             HarnessKind::Crush => Self {
                 stdio: true,
@@ -770,6 +1328,8 @@ impl McpCapabilities {
                 toggle: true,
                 headers: true,
                 cwd: false,
+                prompts: false,
+                auto_approve: false,
             },
             HarnessKind::Droid => Self {
                 stdio: true,
@@ -780,7 +1340,94 @@ impl McpCapabilities {
                 toggle: true,
                 headers: true,
                 cwd: false,
+                prompts: false,
+                auto_approve: false,
+            },
+            HarnessKind::Codex => Self {
+                stdio: true,
+                sse: false,
+                http: true,
+                oauth: false,
+                timeout: true,
+                toggle: true,
+                headers: true,
+                cwd: true,
+                prompts: false,
+                auto_approve: false,
+            },
+            HarnessKind::GeminiCli => Self {
+                stdio: true,
+                sse: true,
+                http: true,
+                oauth: true,
+                timeout: true,
+                toggle: false,
+                headers: true,
+                cwd: true,
+                prompts: false,
+                auto_approve: false,
+            },
+            HarnessKind::Cursor => Self {
+                stdio: true,
+                sse: true,
+                http: true,
+                oauth: false,
+                timeout: false,
+                toggle: false,
+                headers: true,
+                cwd: false,
+                prompts: false,
+                auto_approve: false,
+            },
+            HarnessKind::VsCode => Self {
+                stdio: true,
+                sse: true,
+                http: true,
+                oauth: false,
+                timeout: false,
+                toggle: false,
+                headers: true,
+                cwd: true,
+                prompts: true,
+                auto_approve: false,
+            },
+            HarnessKind::Cline => Self {
+                stdio: true,
+                sse: true,
+                http: true,
+                oauth: false,
+                timeout: true,
+                toggle: true,
+                headers: true,
+                cwd: false,
+                prompts: false,
+                auto_approve: true,
+            },
+            HarnessKind::RooCode => Self {
+                stdio: true,
+                sse: true,
+                http: true,
+                oauth: false,
+                timeout: true,
+                toggle: true,
+                headers: true,
+                cwd: true,
+                prompts: false,
+                auto_approve: true,
+            },
+            HarnessKind::Zed => Self {
+                stdio: true,
+                sse: false,
+                http: true,
+                oauth: false,
+                timeout: false,
+                toggle: false,
+                headers: true,
+                cwd: false,
+                prompts: false,
+                auto_approve: false,
             },
+            HarnessKind::Custom(_) => crate::harness::adapter::for_kind(kind).mcp_capabilities(),
         }
     }
 }
@@ -798,6 +1445,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let json = serde_json::to_string(&server).unwrap();
@@ -819,6 +1467,7 @@ mod tests {
             headers: HashMap::new(),
             enabled: true,
             timeout_ms: Some(30000),
+            auto_approve: Vec::new(),
         });
 
         let json = serde_json::to_string(&server).unwrap();
@@ -844,6 +1493,7 @@ mod tests {
             }),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let json = serde_json::to_string(&server).unwrap();
@@ -898,6 +1548,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         };
 
         let json = serde_json::to_string(&server).unwrap();
@@ -922,6 +1573,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         };
 
         let json = serde_json::to_string(&server).unwrap();
@@ -943,6 +1595,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let sse = McpServer::Sse(SseMcpServer {
@@ -950,6 +1603,7 @@ mod tests {
             headers: HashMap::new(),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let http = McpServer::Http(HttpMcpServer {
@@ -958,6 +1612,7 @@ mod tests {
             oauth: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let stdio_json = serde_json::to_string(&stdio).unwrap();
@@ -1043,6 +1698,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let mut names = server.env_var_names();
@@ -1059,6 +1715,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         assert!(server.env_var_names().is_empty());
@@ -1079,6 +1736,7 @@ mod tests {
             }),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let mut names = server.env_var_names();
@@ -1102,6 +1760,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let missing = server.missing_env_vars();
@@ -1117,6 +1776,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         assert!(
@@ -1136,6 +1796,7 @@ mod tests {
             headers: HashMap::new(),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         assert!(
@@ -1149,13 +1810,14 @@ mod tests {
     }
 
     #[test]
-    fn validate_capabilities_http_rejected_by_ampcode() {
+    fn validate_capabilities_http_accepted_by_ampcode() {
         let server = McpServer::Http(HttpMcpServer {
             url: "http://localhost".to_string(),
             headers: HashMap::new(),
             oauth: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         assert!(
@@ -1165,7 +1827,7 @@ mod tests {
         );
         assert!(server.validate_capabilities(HarnessKind::OpenCode).is_ok());
         assert!(server.validate_capabilities(HarnessKind::Goose).is_ok());
-        assert!(server.validate_capabilities(HarnessKind::AmpCode).is_err());
+        assert!(server.validate_capabilities(HarnessKind::AmpCode).is_ok());
     }
 
     #[test]
@@ -1180,6 +1842,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let value = server
@@ -1199,6 +1862,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: Some(5000),
+            auto_approve: Vec::new(),
         });
 
         let value = server
@@ -1218,6 +1882,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let value = server
@@ -1237,6 +1902,7 @@ mod tests {
             oauth: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let value = server
@@ -1245,4 +1911,266 @@ mod tests {
         assert_eq!(value["type"], "http");
         assert_eq!(value["url"], "http://localhost:8080");
     }
+
+    #[test]
+    fn to_native_value_stdio_codex() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "npx".to_string(),
+            args: vec!["server".to_string()],
+            env: HashMap::from([
+                ("MODE".to_string(), EnvValue::plain("fast")),
+                ("API_KEY".to_string(), EnvValue::env("API_KEY")),
+            ]),
+            cwd: Some(PathBuf::from("/srv")),
+            enabled: false,
+            timeout_ms: Some(1500),
+            auto_approve: Vec::new(),
+        });
+
+        let value = server
+            .to_native_value(HarnessKind::Codex, "test-server")
+            .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "command": "npx",
+                "args": ["server"],
+                "env": {"MODE": "fast"},
+                "env_vars": ["API_KEY"],
+                "cwd": "/srv",
+                "enabled": false,
+                "tool_timeout_sec": 1.5
+            })
+        );
+    }
+
+    #[test]
+    fn to_native_value_codex_rejects_renamed_env_refs() {
+        // SAFETY: Test environment only, no concurrent access
+        unsafe { std::env::set_var("CODEX_RENAMED_SECRET", "s3cret") };
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "npx".to_string(),
+            args: vec![],
+            env: HashMap::from([("API_KEY".to_string(), EnvValue::env("CODEX_RENAMED_SECRET"))]),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        let result = server.to_native_value(HarnessKind::Codex, "test-server");
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedMcpConfig { ref reason, .. })
+                if reason.contains("CODEX_RENAMED_SECRET")
+        ));
+    }
+
+    #[test]
+    fn to_native_value_http_codex() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::from([
+                ("X-Team".to_string(), EnvValue::plain("core")),
+                ("Authorization".to_string(), EnvValue::env("AUTH_HEADER")),
+            ]),
+            oauth: None,
+            enabled: true,
+            timeout_ms: Some(30_000),
+            auto_approve: Vec::new(),
+        });
+
+        let value = server
+            .to_native_value(HarnessKind::Codex, "test-server")
+            .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "url": "https://example.com/mcp",
+                "http_headers": {"X-Team": "core"},
+                "env_http_headers": {"Authorization": "AUTH_HEADER"},
+                "tool_timeout_sec": 30
+            })
+        );
+    }
+
+    #[test]
+    fn to_native_value_http_gemini_cli() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::from([("Authorization".to_string(), EnvValue::env("TOKEN"))]),
+            oauth: Some(OAuthConfig {
+                client_id: Some("app".to_string()),
+                client_secret: None,
+                scope: Some("read write".to_string()),
+            }),
+            enabled: true,
+            timeout_ms: Some(5000),
+            auto_approve: Vec::new(),
+        });
+
+        let value = server
+            .to_native_value(HarnessKind::GeminiCli, "test-server")
+            .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "httpUrl": "https://example.com/mcp",
+                "headers": {"Authorization": "${TOKEN}"},
+                "oauth": {"enabled": true, "clientId": "app", "scopes": ["read", "write"]},
+                "timeout": 5000
+            })
+        );
+    }
+
+    #[test]
+    fn to_native_value_sse_gemini_cli_uses_url() {
+        let server = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        let value = server
+            .to_native_value(HarnessKind::GeminiCli, "test")
+            .unwrap();
+        assert_eq!(value, serde_json::json!({"url": "https://example.com/sse"}));
+    }
+
+    #[test]
+    fn to_native_value_stdio_vscode_with_prompt() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec!["server.js".to_string()],
+            env: HashMap::from([
+                ("API_KEY".to_string(), EnvValue::prompt("api-key")),
+                ("HOME_DIR".to_string(), EnvValue::env("HOME")),
+            ]),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        let value = server.to_native_value(HarnessKind::VsCode, "test").unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "type": "stdio",
+                "command": "node",
+                "args": ["server.js"],
+                "env": {"API_KEY": "${input:api-key}", "HOME_DIR": "${env:HOME}"}
+            })
+        );
+        assert!(server.to_native_value(HarnessKind::Cursor, "test").is_err());
+    }
+
+    #[test]
+    fn to_native_value_sse_cursor_sets_type() {
+        let server = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        let value = server.to_native_value(HarnessKind::Cursor, "test").unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"url": "https://example.com/sse", "type": "sse"})
+        );
+    }
+
+    #[test]
+    fn to_native_value_cline_and_roo_code_approvals() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::new(),
+            oauth: None,
+            enabled: false,
+            timeout_ms: Some(1500),
+            auto_approve: vec!["search".to_string()],
+        });
+
+        let cline = server.to_native_value(HarnessKind::Cline, "test").unwrap();
+        assert_eq!(
+            cline,
+            serde_json::json!({
+                "type": "streamableHttp",
+                "url": "https://example.com/mcp",
+                "disabled": true,
+                "timeout": 2,
+                "autoApprove": ["search"]
+            })
+        );
+        let roo = server
+            .to_native_value(HarnessKind::RooCode, "test")
+            .unwrap();
+        assert_eq!(roo["type"], "streamable-http");
+        assert_eq!(roo["alwaysAllow"], serde_json::json!(["search"]));
+    }
+
+    #[test]
+    fn to_native_value_cline_rejects_env_refs() {
+        unsafe { std::env::set_var("CLINE_TEST_SECRET", "s3cret") };
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "npx".to_string(),
+            args: vec![],
+            env: HashMap::from([(
+                "CLINE_TEST_SECRET".to_string(),
+                EnvValue::env("CLINE_TEST_SECRET"),
+            )]),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        assert!(matches!(
+            server.to_native_value(HarnessKind::Cline, "test"),
+            Err(Error::UnsupportedMcpConfig { .. })
+        ));
+        // Roo Code reads `${env:VAR}` itself
+        let roo = server
+            .to_native_value(HarnessKind::RooCode, "test")
+            .unwrap();
+        assert_eq!(roo["env"]["CLINE_TEST_SECRET"], "${env:CLINE_TEST_SECRET}");
+    }
+
+    #[test]
+    fn to_native_value_zed_rejects_env_refs() {
+        unsafe { std::env::set_var("ZED_TEST_TOKEN", "s3cret") };
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::from([(
+                "Authorization".to_string(),
+                EnvValue::env("ZED_TEST_TOKEN"),
+            )]),
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        assert!(matches!(
+            server.to_native_value(HarnessKind::Zed, "test"),
+            Err(Error::UnsupportedMcpConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn to_native_value_sse_codex_unsupported() {
+        let server = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+
+        assert!(server.to_native_value(HarnessKind::Codex, "test").is_err());
+    }
 }
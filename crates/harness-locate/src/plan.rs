@@ -0,0 +1,996 @@
+//! Previewable, reversible sets of file changes.
+//!
+//! Mutating operations such as [`Harness::plan_install_mcp_server`] return a
+//! [`ChangePlan`] instead of touching the filesystem. The plan records the
+//! content of every affected file before and after the change, can render a
+//! unified diff for review, and is only written to disk by
+//! [`ChangePlan::apply`].
+//!
+//! Whole directories, such as installed skills, are planned as
+//! [`DirectoryChange`]s holding every file below them, binary files
+//! included.
+//!
+//! [`Harness::plan_install_mcp_server`]: crate::Harness::plan_install_mcp_server
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::document;
+use crate::error::{Error, Result};
+
+/// What a [`FileChange`] or [`DirectoryChange`] does to its path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ChangeKind {
+    /// The file does not exist and will be created.
+    Create,
+    /// The file exists and its content will be replaced.
+    Modify,
+    /// The file exists and will be deleted.
+    Delete,
+}
+
+impl ChangeKind {
+    /// Classifies a change from `before` to `after`, or returns `None` if
+    /// nothing changes.
+    fn of<T: PartialEq>(before: Option<&T>, after: Option<&T>) -> Option<Self> {
+        match (before, after) {
+            (before, after) if before == after => None,
+            (None, _) => Some(Self::Create),
+            (Some(_), Some(_)) => Some(Self::Modify),
+            (Some(_), None) => Some(Self::Delete),
+        }
+    }
+}
+
+/// A planned change to a single file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    /// Path of the affected file.
+    pub path: PathBuf,
+    /// Whether the file is created, modified or deleted.
+    pub kind: ChangeKind,
+    /// Content before the change, or `None` if the file does not exist.
+    pub before: Option<String>,
+    /// Content after the change, or `None` if the file will be deleted.
+    pub after: Option<String>,
+    /// Whether the file is created readable by its owner only (Unix), as
+    /// for files holding secrets.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub private: bool,
+}
+
+impl FileChange {
+    /// Returns a unified diff of this change.
+    ///
+    /// The missing side of a created or deleted file is shown as `/dev/null`.
+    #[must_use]
+    pub fn diff(&self) -> String {
+        text_diff(&self.path, self.before.as_deref(), self.after.as_deref())
+    }
+
+    /// Writes `content` to the file, or deletes it when `None`, provided the
+    /// file currently holds `expected`.
+    fn transition(&self, expected: Option<&str>, content: Option<&str>) -> Result<()> {
+        if read_optional(&self.path)?.as_deref() != expected {
+            return Err(Error::Conflict(self.path.clone()));
+        }
+        match content {
+            Some(content) if self.private => document::write_private(&self.path, content),
+            Some(content) => document::write_atomic(&self.path, content),
+            None => match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            },
+        }
+    }
+}
+
+/// An entry below a directory captured by a [`DirectoryChange`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum DirectoryEntry {
+    /// A subdirectory.
+    Directory,
+    /// A regular file.
+    File {
+        /// The file's bytes.
+        content: Vec<u8>,
+        /// Whether the file is executable. Always `false` outside Unix.
+        executable: bool,
+    },
+    /// A symbolic link and its target. Links are only captured on Unix.
+    Symlink(PathBuf),
+}
+
+/// Every entry below a directory, keyed by its path relative to the
+/// directory.
+pub type DirectoryContent = BTreeMap<PathBuf, DirectoryEntry>;
+
+/// A planned replacement of a whole directory, such as an installed skill.
+///
+/// Like a [`FileChange`], it holds the complete content before and after
+/// the change, so it can be applied and reverted without the source it
+/// was planned from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectoryChange {
+    /// Path of the affected directory.
+    pub path: PathBuf,
+    /// Whether the directory is created, replaced or deleted.
+    pub kind: ChangeKind,
+    /// Content before the change, or `None` if the directory does not exist.
+    pub before: Option<DirectoryContent>,
+    /// Content after the change, or `None` if the directory will be deleted.
+    pub after: Option<DirectoryContent>,
+}
+
+impl DirectoryChange {
+    /// Returns a unified diff of every file that differs.
+    ///
+    /// Symbolic links are shown as their target. Files that are not UTF-8
+    /// are reported as `Binary files … differ`, as `git diff` does.
+    #[must_use]
+    pub fn diff(&self) -> String {
+        let empty = DirectoryContent::new();
+        let before = self.before.as_ref().unwrap_or(&empty);
+        let after = self.after.as_ref().unwrap_or(&empty);
+        let mut paths: Vec<&PathBuf> = before.keys().chain(after.keys()).collect();
+        paths.sort();
+        paths.dedup();
+
+        let mut diff = String::new();
+        for relative in paths {
+            let old = before.get(relative).and_then(entry_bytes);
+            let new = after.get(relative).and_then(entry_bytes);
+            if old == new {
+                continue;
+            }
+            let path = self.path.join(relative);
+            match (
+                old.map(std::str::from_utf8).transpose(),
+                new.map(std::str::from_utf8).transpose(),
+            ) {
+                (Ok(old), Ok(new)) => diff.push_str(&text_diff(&path, old, new)),
+                _ => {
+                    let side = |bytes: Option<&[u8]>, prefix: &str| match bytes {
+                        Some(_) => format!("{prefix}/{}", path.display()),
+                        None => "/dev/null".to_string(),
+                    };
+                    diff.push_str(&format!(
+                        "Binary files {} and {} differ\n",
+                        side(old, "a"),
+                        side(new, "b")
+                    ));
+                }
+            }
+        }
+        diff
+    }
+
+    /// Replaces the directory with `content`, or deletes it when `None`,
+    /// provided it currently holds `expected`.
+    fn transition(
+        &self,
+        expected: Option<&DirectoryContent>,
+        content: Option<&DirectoryContent>,
+    ) -> Result<()> {
+        if read_directory(&self.path)?.as_ref() != expected {
+            return Err(Error::Conflict(self.path.clone()));
+        }
+        match content {
+            Some(content) => replace_directory(&self.path, content),
+            None => match fs::remove_dir_all(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            },
+        }
+    }
+}
+
+/// An ordered set of file changes that can be previewed, applied and reverted.
+///
+/// File changes are applied first, in order, then directory changes.
+///
+/// # Example
+///
+/// ```no_run
+/// use harness_locate::{Harness, HarnessKind, Scope};
+/// use harness_locate::mcp::{McpServer, StdioMcpServer};
+///
+/// let harness = Harness::new(HarnessKind::Goose);
+/// let server = McpServer::Stdio(StdioMcpServer {
+///     command: "uvx".to_string(),
+///     args: vec!["mcp-server-fetch".to_string()],
+///     env: Default::default(),
+///     cwd: None,
+///     enabled: true,
+///     timeout_ms: None,
+///     auto_approve: Vec::new(),
+/// });
+///
+/// let plan = harness.plan_install_mcp_server(&Scope::Global, "fetch", &server)?;
+/// print!("{}", plan.diff());
+/// plan.apply()?;
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangePlan {
+    changes: Vec<FileChange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    directories: Vec<DirectoryChange>,
+}
+
+/// One step of [`ChangePlan::apply`], which [`ChangePlan::revert`] undoes.
+enum Step<'a> {
+    File(&'a FileChange),
+    Directory(&'a DirectoryChange),
+}
+
+impl Step<'_> {
+    fn apply(&self) -> Result<()> {
+        match self {
+            Self::File(c) => c.transition(c.before.as_deref(), c.after.as_deref()),
+            Self::Directory(c) => c.transition(c.before.as_ref(), c.after.as_ref()),
+        }
+    }
+
+    fn revert(&self) -> Result<()> {
+        match self {
+            Self::File(c) => c.transition(c.after.as_deref(), c.before.as_deref()),
+            Self::Directory(c) => c.transition(c.after.as_ref(), c.before.as_ref()),
+        }
+    }
+}
+
+impl ChangePlan {
+    /// Creates an empty plan.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a change that sets `path` to `after`, or deletes it when `None`.
+    ///
+    /// The current content of `path` is recorded as the `before` state.
+    /// Nothing is recorded if the file already has the requested content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current content of `path` cannot be read.
+    pub fn set_file(&mut self, path: impl Into<PathBuf>, after: Option<String>) -> Result<()> {
+        self.push_file(path.into(), after, false)
+    }
+
+    /// Adds a change like [`set_file`](Self::set_file), but a file it
+    /// creates is readable by its owner only (`0600`) on Unix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current content of `path` cannot be read.
+    pub fn set_private_file(
+        &mut self,
+        path: impl Into<PathBuf>,
+        after: Option<String>,
+    ) -> Result<()> {
+        self.push_file(path.into(), after, true)
+    }
+
+    fn push_file(&mut self, path: PathBuf, after: Option<String>, private: bool) -> Result<()> {
+        let before = read_optional(&path)?;
+        let Some(kind) = ChangeKind::of(before.as_ref(), after.as_ref()) else {
+            return Ok(());
+        };
+        self.changes.retain(|change| change.path != path);
+        self.changes.push(FileChange {
+            path,
+            kind,
+            before,
+            after,
+            private,
+        });
+        Ok(())
+    }
+
+    /// Adds a change that replaces the directory `path` with `after`, or
+    /// deletes it when `None`.
+    ///
+    /// The current content of `path` is recorded as the `before` state.
+    /// Nothing is recorded if the directory already has the requested
+    /// content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current content of `path` cannot be read.
+    pub fn set_directory(
+        &mut self,
+        path: impl Into<PathBuf>,
+        after: Option<DirectoryContent>,
+    ) -> Result<()> {
+        let path = path.into();
+        let before = read_directory(&path)?;
+        let Some(kind) = ChangeKind::of(before.as_ref(), after.as_ref()) else {
+            return Ok(());
+        };
+        self.directories.retain(|change| change.path != path);
+        self.directories.push(DirectoryChange {
+            path,
+            kind,
+            before,
+            after,
+        });
+        Ok(())
+    }
+
+    /// Appends the changes of `other`, so several operations can be applied
+    /// (and reverted) together.
+    ///
+    /// A change in `other` to a path `self` already changes must have been
+    /// planned on top of it: its `before` content has to be the `after`
+    /// content in `self`. The two are then combined into one change from the
+    /// original `before` to the final `after`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Conflict`] if `other` changes a path `self` changes
+    /// but was planned against different content, in which case applying
+    /// both would lose one of the edits. `self` is left unchanged.
+    pub fn merge(&mut self, other: ChangePlan) -> Result<()> {
+        let conflict = other
+            .changes
+            .iter()
+            .find(|change| {
+                self.changes
+                    .iter()
+                    .any(|c| c.path == change.path && c.after != change.before)
+            })
+            .map(|change| &change.path)
+            .or_else(|| {
+                other
+                    .directories
+                    .iter()
+                    .find(|change| {
+                        self.directories
+                            .iter()
+                            .any(|c| c.path == change.path && c.after != change.before)
+                    })
+                    .map(|change| &change.path)
+            });
+        if let Some(path) = conflict {
+            return Err(Error::Conflict(path.clone()));
+        }
+
+        for mut change in other.changes {
+            if let Some(index) = self.changes.iter().position(|c| c.path == change.path) {
+                let previous = self.changes.remove(index);
+                change.before = previous.before;
+                change.private |= previous.private;
+                match ChangeKind::of(change.before.as_ref(), change.after.as_ref()) {
+                    Some(kind) => change.kind = kind,
+                    None => continue,
+                }
+            }
+            self.changes.push(change);
+        }
+        for mut change in other.directories {
+            if let Some(index) = self.directories.iter().position(|c| c.path == change.path) {
+                let previous = self.directories.remove(index);
+                change.before = previous.before;
+                match ChangeKind::of(change.before.as_ref(), change.after.as_ref()) {
+                    Some(kind) => change.kind = kind,
+                    None => continue,
+                }
+            }
+            self.directories.push(change);
+        }
+        Ok(())
+    }
+
+    /// Returns the planned file changes in the order they will be applied.
+    #[must_use]
+    pub fn changes(&self) -> &[FileChange] {
+        &self.changes
+    }
+
+    /// Returns the planned directory changes in the order they will be
+    /// applied, after every file change.
+    #[must_use]
+    pub fn directory_changes(&self) -> &[DirectoryChange] {
+        &self.directories
+    }
+
+    /// Returns `true` if the plan changes nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.directories.is_empty()
+    }
+
+    /// Returns the unified diff of every change, concatenated.
+    #[must_use]
+    pub fn diff(&self) -> String {
+        self.changes
+            .iter()
+            .map(FileChange::diff)
+            .chain(self.directories.iter().map(DirectoryChange::diff))
+            .collect()
+    }
+
+    /// Applies every change in order.
+    ///
+    /// Each file must still hold its recorded `before` content. If any
+    /// change fails, the changes already applied are reverted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Conflict`] if a file was modified after the plan was
+    /// created, or an I/O error if a file cannot be written.
+    pub fn apply(&self) -> Result<()> {
+        let steps = self.steps();
+        for (index, step) in steps.iter().enumerate() {
+            if let Err(e) = step.apply() {
+                for done in steps[..index].iter().rev() {
+                    let _ = done.revert();
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Restores every file to its recorded `before` content, in reverse order.
+    ///
+    /// Each file must still hold the content [`ChangePlan::apply`] wrote. If
+    /// any file cannot be restored, the files already restored are
+    /// re-applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Conflict`] if a file was modified after the plan was
+    /// applied, or an I/O error if a file cannot be written.
+    pub fn revert(&self) -> Result<()> {
+        let steps = self.steps();
+        for (index, step) in steps.iter().enumerate().rev() {
+            if let Err(e) = step.revert() {
+                for done in &steps[index + 1..] {
+                    let _ = done.apply();
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn steps(&self) -> Vec<Step<'_>> {
+        self.changes
+            .iter()
+            .map(Step::File)
+            .chain(self.directories.iter().map(Step::Directory))
+            .collect()
+    }
+}
+
+impl fmt::Display for ChangePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.diff())
+    }
+}
+
+/// Returns a unified diff of `path` from `before` to `after`, with a
+/// missing side shown as `/dev/null`.
+fn text_diff(path: &Path, before: Option<&str>, after: Option<&str>) -> String {
+    let path = path.display().to_string();
+    let old_header = match before {
+        None => "/dev/null".to_string(),
+        Some(_) => format!("a/{path}"),
+    };
+    let new_header = match after {
+        None => "/dev/null".to_string(),
+        Some(_) => format!("b/{path}"),
+    };
+    similar::TextDiff::from_lines(before.unwrap_or_default(), after.unwrap_or_default())
+        .unified_diff()
+        .context_radius(3)
+        .header(&old_header, &new_header)
+        .to_string()
+}
+
+/// Returns the bytes a directory entry is diffed by: a file's content or a
+/// link's target.
+fn entry_bytes(entry: &DirectoryEntry) -> Option<&[u8]> {
+    match entry {
+        DirectoryEntry::Directory => None,
+        DirectoryEntry::File { content, .. } => Some(content),
+        DirectoryEntry::Symlink(target) => Some(target.as_os_str().as_encoded_bytes()),
+    }
+}
+
+/// Reads a file, returning `None` if it does not exist.
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Reads every entry below the directory `path`, returning `None` if it
+/// does not exist.
+///
+/// Symbolic links are read as links, not followed. Where links cannot be
+/// created (non-Unix platforms), and for entries that are neither files
+/// nor directories, the entry is skipped.
+///
+/// # Errors
+///
+/// Returns an error if `path` is not a directory or cannot be read.
+pub(crate) fn read_directory(path: &Path) -> Result<Option<DirectoryContent>> {
+    if let Err(e) = fs::metadata(path) {
+        return match e.kind() {
+            std::io::ErrorKind::NotFound => Ok(None),
+            _ => Err(e.into()),
+        };
+    }
+    let mut content = DirectoryContent::new();
+    read_entries(path, Path::new(""), &mut content)?;
+    Ok(Some(content))
+}
+
+fn read_entries(dir: &Path, relative: &Path, content: &mut DirectoryContent) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        let relative = relative.join(entry.file_name());
+        if file_type.is_dir() {
+            content.insert(relative.clone(), DirectoryEntry::Directory);
+            read_entries(&path, &relative, content)?;
+        } else if file_type.is_file() {
+            #[cfg(unix)]
+            let executable = {
+                use std::os::unix::fs::PermissionsExt;
+                entry.metadata()?.permissions().mode() & 0o111 != 0
+            };
+            #[cfg(not(unix))]
+            let executable = false;
+            content.insert(
+                relative,
+                DirectoryEntry::File {
+                    content: fs::read(&path)?,
+                    executable,
+                },
+            );
+        } else if file_type.is_symlink() && cfg!(unix) {
+            content.insert(relative, DirectoryEntry::Symlink(fs::read_link(&path)?));
+        }
+    }
+    Ok(())
+}
+
+/// Writes `content` to a new directory at `target`.
+fn write_directory(target: &Path, content: &DirectoryContent) -> Result<()> {
+    fs::create_dir_all(target)?;
+    for (relative, entry) in content {
+        // Keys come from journal files too; keep them inside `target`
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(Error::InvalidPath(relative.clone()));
+        }
+        let path = target.join(relative);
+        match entry {
+            DirectoryEntry::Directory => fs::create_dir_all(&path)?,
+            DirectoryEntry::File {
+                content,
+                executable,
+            } => {
+                fs::write(&path, content)?;
+                #[cfg(unix)]
+                if *executable {
+                    use std::os::unix::fs::PermissionsExt;
+                    let mode = fs::metadata(&path)?.permissions().mode();
+                    fs::set_permissions(&path, fs::Permissions::from_mode(mode | 0o111))?;
+                }
+                #[cfg(not(unix))]
+                let _ = executable;
+            }
+            DirectoryEntry::Symlink(link) => {
+                #[cfg(unix)]
+                std::os::unix::fs::symlink(link, &path)?;
+                #[cfg(not(unix))]
+                let _ = link;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the directory `path` with `content`.
+///
+/// The new directory is written next to `path` first. The old one is moved
+/// aside, not deleted, until the new one is in place, so a failure leaves
+/// it as it was.
+fn replace_directory(path: &Path, content: &DirectoryContent) -> Result<()> {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return Err(Error::InvalidPath(path.to_path_buf()));
+    };
+    let sibling = |suffix: &str| {
+        let mut sibling = std::ffi::OsString::from(".");
+        sibling.push(name);
+        sibling.push(suffix);
+        parent.join(sibling)
+    };
+    let staging = sibling(".installing");
+    if staging.symlink_metadata().is_ok() {
+        remove_path(&staging)?;
+    }
+    if let Err(e) = write_directory(&staging, content) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    let replaced = sibling(".replaced");
+    let had_target = path.symlink_metadata().is_ok();
+    if had_target {
+        if replaced.symlink_metadata().is_ok() {
+            remove_path(&replaced)?;
+        }
+        fs::rename(path, &replaced)?;
+    }
+    if let Err(e) = fs::rename(&staging, path) {
+        if had_target {
+            let _ = fs::rename(&replaced, path);
+        }
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e.into());
+    }
+    if had_target {
+        remove_path(&replaced)?;
+    }
+    Ok(())
+}
+
+/// Removes `path`, whether it is a directory or a file or link.
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.symlink_metadata()?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_file_classifies_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("existing.json");
+        fs::write(&existing, "{}\n").unwrap();
+
+        let mut plan = ChangePlan::new();
+        plan.set_file(dir.path().join("new.json"), Some("{}\n".into()))
+            .unwrap();
+        plan.set_file(&existing, Some("{\"a\": 1}\n".into()))
+            .unwrap();
+        plan.set_file(dir.path().join("missing.json"), None)
+            .unwrap();
+
+        let kinds: Vec<_> = plan.changes().iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, [ChangeKind::Create, ChangeKind::Modify]);
+
+        let mut delete = ChangePlan::new();
+        delete.set_file(&existing, None).unwrap();
+        assert_eq!(delete.changes()[0].kind, ChangeKind::Delete);
+    }
+
+    #[test]
+    fn set_file_skips_unchanged_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, "{}\n").unwrap();
+
+        let mut plan = ChangePlan::new();
+        plan.set_file(&path, Some("{}\n".into())).unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn merge_combines_stacked_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, "0\n").unwrap();
+
+        let mut plan = ChangePlan::new();
+        plan.set_file(&path, Some("1\n".into())).unwrap();
+        let mut later = ChangePlan::new();
+        later.set_file(&path, Some("2\n".into())).unwrap();
+        later.changes[0].before = Some("1\n".into());
+        later
+            .set_file(dir.path().join("other.json"), Some("{}\n".into()))
+            .unwrap();
+        plan.merge(later).unwrap();
+
+        assert_eq!(plan.changes().len(), 2);
+        assert_eq!(plan.changes()[0].before.as_deref(), Some("0\n"));
+        assert_eq!(plan.changes()[0].after.as_deref(), Some("2\n"));
+        assert_eq!(plan.changes()[1].kind, ChangeKind::Create);
+    }
+
+    #[test]
+    fn merge_rejects_edits_planned_against_the_same_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, "{}\n").unwrap();
+
+        let mut plan = ChangePlan::new();
+        plan.set_file(&path, Some("{\"a\": 1}\n".into())).unwrap();
+        let mut other = ChangePlan::new();
+        other.set_file(&path, Some("{\"b\": 2}\n".into())).unwrap();
+
+        assert!(matches!(plan.merge(other), Err(Error::Conflict(p)) if p == path));
+        assert_eq!(plan.changes().len(), 1);
+        assert_eq!(plan.changes()[0].after.as_deref(), Some("{\"a\": 1}\n"));
+    }
+
+    #[test]
+    fn merge_recomputes_kind() {
+        let dir = tempfile::tempdir().unwrap();
+        let created = dir.path().join("created.json");
+        let existing = dir.path().join("existing.json");
+        fs::write(&existing, "{}\n").unwrap();
+
+        let mut plan = ChangePlan::new();
+        plan.set_file(&created, Some("{}\n".into())).unwrap();
+        plan.set_file(&existing, None).unwrap();
+        let mut later = ChangePlan::new();
+        later.changes.push(FileChange {
+            path: created.clone(),
+            kind: ChangeKind::Delete,
+            before: Some("{}\n".into()),
+            after: None,
+            private: false,
+        });
+        later.changes.push(FileChange {
+            path: existing.clone(),
+            kind: ChangeKind::Create,
+            before: None,
+            after: Some("[]\n".into()),
+            private: false,
+        });
+        plan.merge(later).unwrap();
+
+        assert_eq!(plan.changes().len(), 1);
+        assert_eq!(plan.changes()[0].path, existing);
+        assert_eq!(plan.changes()[0].kind, ChangeKind::Modify);
+    }
+
+    #[test]
+    fn diff_is_unified() {
+        let change = FileChange {
+            path: PathBuf::from("config.json"),
+            kind: ChangeKind::Modify,
+            before: Some("a\nb\n".into()),
+            after: Some("a\nc\n".into()),
+            private: false,
+        };
+        assert_eq!(
+            change.diff(),
+            "--- a/config.json\n+++ b/config.json\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n"
+        );
+    }
+
+    #[test]
+    fn diff_of_created_file_uses_dev_null() {
+        let change = FileChange {
+            path: PathBuf::from("new.json"),
+            kind: ChangeKind::Create,
+            before: None,
+            after: Some("{}\n".into()),
+            private: false,
+        };
+        assert!(change.diff().starts_with("--- /dev/null\n+++ b/new.json\n"));
+    }
+
+    #[test]
+    fn apply_and_revert_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let modified = dir.path().join("modified.json");
+        let created = dir.path().join("sub/created.json");
+        let deleted = dir.path().join("deleted.json");
+        fs::write(&modified, "old\n").unwrap();
+        fs::write(&deleted, "gone\n").unwrap();
+
+        let mut plan = ChangePlan::new();
+        plan.set_file(&modified, Some("new\n".into())).unwrap();
+        plan.set_file(&created, Some("created\n".into())).unwrap();
+        plan.set_file(&deleted, None).unwrap();
+
+        plan.apply().unwrap();
+        assert_eq!(fs::read_to_string(&modified).unwrap(), "new\n");
+        assert_eq!(fs::read_to_string(&created).unwrap(), "created\n");
+        assert!(!deleted.exists());
+
+        plan.revert().unwrap();
+        assert_eq!(fs::read_to_string(&modified).unwrap(), "old\n");
+        assert!(!created.exists());
+        assert_eq!(fs::read_to_string(&deleted).unwrap(), "gone\n");
+    }
+
+    #[test]
+    fn apply_detects_conflicts_and_rolls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.json");
+        let second = dir.path().join("second.json");
+        fs::write(&first, "1\n").unwrap();
+        fs::write(&second, "2\n").unwrap();
+
+        let mut plan = ChangePlan::new();
+        plan.set_file(&first, Some("one\n".into())).unwrap();
+        plan.set_file(&second, Some("two\n".into())).unwrap();
+        fs::write(&second, "edited elsewhere\n").unwrap();
+
+        assert!(matches!(plan.apply(), Err(Error::Conflict(path)) if path == second));
+        assert_eq!(fs::read_to_string(&first).unwrap(), "1\n");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "edited elsewhere\n");
+    }
+
+    #[test]
+    fn revert_detects_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        let mut plan = ChangePlan::new();
+        plan.set_file(&path, Some("{}\n".into())).unwrap();
+        plan.apply().unwrap();
+        fs::write(&path, "{\"edited\": true}\n").unwrap();
+
+        assert!(matches!(plan.revert(), Err(Error::Conflict(_))));
+        assert!(path.exists());
+    }
+
+    fn skill(text: &str) -> DirectoryContent {
+        let mut content = DirectoryContent::new();
+        content.insert(
+            PathBuf::from("SKILL.md"),
+            DirectoryEntry::File {
+                content: text.as_bytes().to_vec(),
+                executable: false,
+            },
+        );
+        content.insert(PathBuf::from("scripts"), DirectoryEntry::Directory);
+        content.insert(
+            PathBuf::from("scripts/run.sh"),
+            DirectoryEntry::File {
+                content: b"#!/bin/sh\n".to_vec(),
+                executable: cfg!(unix),
+            },
+        );
+        content.insert(
+            PathBuf::from("logo.png"),
+            DirectoryEntry::File {
+                content: vec![0x89, b'P', b'N', b'G', 0xff, 0x00],
+                executable: false,
+            },
+        );
+        content
+    }
+
+    #[test]
+    fn directory_changes_apply_and_revert() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("skills/pdf");
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("SKILL.md"), "old\n").unwrap();
+        fs::write(path.join("stale.txt"), "stale\n").unwrap();
+
+        let mut plan = ChangePlan::new();
+        plan.set_directory(&path, Some(skill("new\n"))).unwrap();
+        assert_eq!(plan.directory_changes()[0].kind, ChangeKind::Modify);
+
+        plan.apply().unwrap();
+        assert_eq!(read_directory(&path).unwrap(), Some(skill("new\n")));
+        assert!(!path.join("stale.txt").exists());
+        let leftovers: Vec<_> = fs::read_dir(dir.path().join("skills"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(leftovers, ["pdf"]);
+
+        plan.revert().unwrap();
+        assert_eq!(fs::read_to_string(path.join("SKILL.md")).unwrap(), "old\n");
+        assert_eq!(
+            fs::read_to_string(path.join("stale.txt")).unwrap(),
+            "stale\n"
+        );
+        assert!(!path.join("logo.png").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn directory_changes_keep_links_and_executable_bits() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut content = skill("new\n");
+        content.insert(
+            PathBuf::from("docs"),
+            DirectoryEntry::Symlink(PathBuf::from("../shared/docs")),
+        );
+        let path = dir.path().join("pdf");
+
+        let mut plan = ChangePlan::new();
+        plan.set_directory(&path, Some(content.clone())).unwrap();
+        plan.apply().unwrap();
+
+        let mode = fs::metadata(path.join("scripts/run.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_ne!(mode & 0o111, 0);
+        assert_eq!(
+            fs::read_link(path.join("docs")).unwrap(),
+            PathBuf::from("../shared/docs")
+        );
+        assert_eq!(read_directory(&path).unwrap(), Some(content));
+
+        plan.revert().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn directory_changes_detect_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pdf");
+
+        let mut plan = ChangePlan::new();
+        plan.set_directory(&path, Some(skill("new\n"))).unwrap();
+        plan.apply().unwrap();
+        fs::write(path.join("SKILL.md"), "edited\n").unwrap();
+
+        assert!(matches!(plan.revert(), Err(Error::Conflict(p)) if p == path));
+        assert_eq!(
+            fs::read_to_string(path.join("SKILL.md")).unwrap(),
+            "edited\n"
+        );
+    }
+
+    #[test]
+    fn directory_diff_shows_text_and_binary_files() {
+        let change = DirectoryChange {
+            path: PathBuf::from("pdf"),
+            kind: ChangeKind::Create,
+            before: None,
+            after: Some(skill("new\n")),
+        };
+        let diff = change.diff();
+        assert!(diff.contains("--- /dev/null\n+++ b/pdf/SKILL.md\n"));
+        assert!(diff.contains("Binary files /dev/null and b/pdf/logo.png differ\n"));
+    }
+
+    #[test]
+    fn directory_content_stays_inside_the_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut content = DirectoryContent::new();
+        content.insert(
+            PathBuf::from("../escaped"),
+            DirectoryEntry::File {
+                content: Vec::new(),
+                executable: false,
+            },
+        );
+
+        let mut plan = ChangePlan::new();
+        plan.set_directory(dir.path().join("pdf"), Some(content))
+            .unwrap();
+        assert!(matches!(plan.apply(), Err(Error::InvalidPath(_))));
+        assert!(!dir.path().join("escaped").exists());
+        assert!(!dir.path().join("pdf").exists());
+    }
+}
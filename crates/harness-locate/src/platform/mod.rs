@@ -72,6 +72,50 @@ pub fn data_dir() -> Result<PathBuf> {
     Err(Error::UnsupportedPlatform)
 }
 
+/// Application folder names of VS Code-family editors, in lookup order:
+/// VS Code, VS Code Insiders, Cursor and VSCodium.
+pub const VSCODE_FAMILY_APPS: &[&str] = &["Code", "Code - Insiders", "Cursor", "VSCodium"];
+
+/// Returns the user data directory of a VS Code-family editor.
+///
+/// `app` is the editor's application folder name, one of
+/// [`VSCODE_FAMILY_APPS`].
+///
+/// Platform-specific behavior:
+/// - **macOS**: `~/Library/Application Support/<app>/User/`
+/// - **Linux**: `$XDG_CONFIG_HOME/<app>/User/` or `~/.config/<app>/User/`
+/// - **Windows**: `%APPDATA%\<app>\User\`
+///
+/// # Errors
+///
+/// Returns an error if the platform directory cannot be determined.
+pub fn editor_user_dir(app: &str) -> Result<PathBuf> {
+    #[cfg(target_os = "macos")]
+    let base = application_support_dir()?;
+    #[cfg(not(target_os = "macos"))]
+    let base = config_dir()?;
+    Ok(base.join(app).join("User"))
+}
+
+/// Returns the `globalStorage` directories of a VS Code extension in every
+/// VS Code-family editor, in [`VSCODE_FAMILY_APPS`] order.
+///
+/// Directories are returned whether or not they exist.
+///
+/// # Errors
+///
+/// Returns an error if the platform directory cannot be determined.
+pub fn extension_storage_dirs(extension_id: &str) -> Result<Vec<PathBuf>> {
+    VSCODE_FAMILY_APPS
+        .iter()
+        .map(|app| {
+            Ok(editor_user_dir(app)?
+                .join("globalStorage")
+                .join(extension_id))
+        })
+        .collect()
+}
+
 #[cfg(all(test, any(target_os = "linux", target_os = "windows")))]
 pub(crate) mod test_utils {
     use std::sync::Mutex;
@@ -100,6 +144,17 @@ pub(crate) mod test_utils {
             unsafe { std::env::set_var(key, value) };
         }
 
+        /// Points the platform data directory, and with it the default
+        /// journal, at `dir`.
+        pub fn set_data_dir(&mut self, dir: &std::path::Path) {
+            let key = if cfg!(target_os = "windows") {
+                "LOCALAPPDATA"
+            } else {
+                "XDG_DATA_HOME"
+            };
+            self.set(key, &dir.to_string_lossy());
+        }
+
         pub fn remove(&mut self, key: &str) {
             let original = std::env::var(key).ok();
             if !self.vars.iter().any(|(k, _)| k == key) {
@@ -167,4 +222,23 @@ mod tests {
             "data_dir should return an absolute path"
         );
     }
+
+    #[test]
+    fn extension_storage_dirs_cover_each_editor() {
+        #[cfg(any(target_os = "linux", target_os = "windows"))]
+        let _env = EnvGuard::new();
+
+        let dirs = extension_storage_dirs("publisher.extension").unwrap();
+        assert_eq!(dirs.len(), VSCODE_FAMILY_APPS.len());
+        for (dir, app) in dirs.iter().zip(VSCODE_FAMILY_APPS) {
+            assert!(
+                dir.ends_with(
+                    std::path::Path::new(app)
+                        .join("User")
+                        .join("globalStorage")
+                        .join("publisher.extension")
+                )
+            );
+        }
+    }
 }
@@ -0,0 +1,715 @@
+//! Data-driven definitions of harnesses this crate does not know about.
+//!
+//! A [`HarnessDefinition`] describes a harness in a TOML or JSON file: its
+//! binaries, where its global and project configuration lives, how its
+//! resource directories are laid out and how it stores MCP servers. Once a
+//! definition is registered with the [`HarnessRegistry`], the harness is
+//! addressed as [`HarnessKind::Custom`] and every [`Harness`] API works
+//! for it, including [`Harness::installed`].
+//!
+//! [`Harness`]: crate::Harness
+//! [`Harness::installed`]: crate::Harness::installed
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::registry::{HarnessDefinition, HarnessRegistry};
+//! use harness_locate::{Harness, Scope};
+//!
+//! let definition = HarnessDefinition::from_toml(r#"
+//!     id = "acme"
+//!     name = "Acme Agent"
+//!     binaries = ["acme"]
+//!     global_dir = "~/.acme"
+//!     project_dir = ".acme"
+//!
+//!     [skills]
+//!     dir = "skills"
+//!
+//!     [mcp]
+//!     file = "settings.json"
+//!     key_path = "/mcpServers"
+//!     capabilities = { stdio = true, http = true, headers = true }
+//! "#)?;
+//!
+//! let kind = HarnessRegistry::global().register(definition)?;
+//! let harness = Harness::new(kind);
+//! assert_eq!(harness.kind().to_string(), "Acme Agent");
+//! assert!(harness.skills(&Scope::Global)?.is_some());
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::harness::HarnessAdapter;
+use crate::harness::custom::CustomAdapter;
+use crate::mcp::McpCapabilities;
+use crate::platform;
+use crate::types::{DirectoryStructure, EnvSyntax, FileFormat, HarnessKind};
+
+/// Describes a harness: where it keeps its configuration and resources.
+///
+/// Directory templates may start with `~` (the home directory) or with
+/// one of the placeholders `{home}`, `{config}` (the platform config
+/// directory) and `{data}` (the platform data directory).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HarnessDefinition {
+    /// Unique identifier: ASCII letters, digits, `-` and `_`.
+    pub id: String,
+    /// Display name. Defaults to the id.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// CLI binary names that indicate the harness is installed.
+    #[serde(default)]
+    pub binaries: Vec<String>,
+    /// Global configuration directory template (e.g. `"~/.acme"`).
+    pub global_dir: String,
+    /// Project configuration directory, relative to the project root.
+    ///
+    /// Without it, the harness does not support [`Scope::Project`].
+    ///
+    /// [`Scope::Project`]: crate::Scope::Project
+    #[serde(default)]
+    pub project_dir: Option<String>,
+    /// Skills directory layout.
+    #[serde(default)]
+    pub skills: Option<ResourceLayout>,
+    /// Commands directory layout.
+    #[serde(default)]
+    pub commands: Option<ResourceLayout>,
+    /// Agents directory layout.
+    #[serde(default)]
+    pub agents: Option<ResourceLayout>,
+    /// Plugins directory layout.
+    #[serde(default)]
+    pub plugins: Option<ResourceLayout>,
+    /// Rules directory layout.
+    #[serde(default)]
+    pub rules: Option<ResourceLayout>,
+    /// Where and how MCP servers are configured.
+    #[serde(default)]
+    pub mcp: Option<McpDefinition>,
+}
+
+/// Location and layout of one kind of resource.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceLayout {
+    /// Directory relative to the scope's configuration directory. An empty
+    /// string or `"."` is the configuration directory itself.
+    pub dir: String,
+    /// Directory relative to the project root, used instead of `dir` for
+    /// project scope (e.g. `"."` for rules kept in the project root).
+    #[serde(default)]
+    pub project_dir: Option<String>,
+    /// Layout of the directory. Defaults to the usual layout of the
+    /// resource: `*/SKILL.md` for skills, `*.md` otherwise.
+    #[serde(default)]
+    pub structure: Option<DirectoryStructure>,
+    /// Format of the resource files. Defaults to Markdown with frontmatter
+    /// (plain Markdown for rules).
+    #[serde(default)]
+    pub file_format: Option<FileFormat>,
+}
+
+/// Where a harness stores MCP servers and how each entry looks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct McpDefinition {
+    /// Config file relative to the scope's configuration directory.
+    pub file: String,
+    /// Config file relative to the project root, used instead of `file`
+    /// for project scope (e.g. `".mcp.json"`).
+    #[serde(default)]
+    pub project_file: Option<String>,
+    /// JSON pointer to the object holding the servers (e.g. `"/mcpServers"`).
+    pub key_path: String,
+    /// Format of the config file.
+    #[serde(default = "default_format")]
+    pub format: FileFormat,
+    /// How environment variable references are written.
+    #[serde(default)]
+    pub env_syntax: EnvSyntax,
+    /// Names of the fields of a server entry.
+    #[serde(default)]
+    pub fields: McpFieldMap,
+    /// Supported MCP features. Features that are not listed are
+    /// unsupported; by default only stdio servers are.
+    #[serde(default = "default_capabilities")]
+    pub capabilities: McpCapabilities,
+}
+
+fn default_format() -> FileFormat {
+    FileFormat::Json
+}
+
+fn default_capabilities() -> McpCapabilities {
+    McpCapabilities {
+        stdio: true,
+        ..McpCapabilities::default()
+    }
+}
+
+/// Maps the fields of a normalized [`McpServer`] to a harness's native
+/// server entry.
+///
+/// The defaults describe the common Claude Code-style entry:
+/// `{"command", "args", "env"}` for stdio servers and
+/// `{"type", "url", "headers"}` for remote ones.
+///
+/// [`McpServer`]: crate::mcp::McpServer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct McpFieldMap {
+    /// Command of a stdio server.
+    pub command: String,
+    /// Arguments of a stdio server.
+    pub args: String,
+    /// Environment of a stdio server.
+    pub env: String,
+    /// Working directory of a stdio server.
+    pub cwd: String,
+    /// URL of a remote server.
+    pub url: String,
+    /// HTTP headers of a remote server.
+    pub headers: String,
+    /// Enabled flag, written only if the harness supports toggling.
+    pub enabled: String,
+    /// Timeout in milliseconds, written only if the harness supports it.
+    pub timeout: String,
+    /// Field holding the transport type. Without it, servers with a
+    /// command are stdio servers and servers with a URL are HTTP servers.
+    pub transport: Option<String>,
+    /// Transport value of stdio servers. Without it, the transport field
+    /// is omitted for stdio servers.
+    pub stdio: Option<String>,
+    /// Transport value of SSE servers.
+    pub sse: String,
+    /// Transport value of HTTP servers.
+    pub http: String,
+}
+
+impl Default for McpFieldMap {
+    fn default() -> Self {
+        Self {
+            command: "command".to_string(),
+            args: "args".to_string(),
+            env: "env".to_string(),
+            cwd: "cwd".to_string(),
+            url: "url".to_string(),
+            headers: "headers".to_string(),
+            enabled: "enabled".to_string(),
+            timeout: "timeout".to_string(),
+            transport: Some("type".to_string()),
+            stdio: None,
+            sse: "sse".to_string(),
+            http: "http".to_string(),
+        }
+    }
+}
+
+impl HarnessDefinition {
+    /// Parses a definition from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if the text is not a valid definition.
+    pub fn from_toml(text: &str) -> Result<Self> {
+        toml::from_str(text).map_err(|e| Error::InvalidConfig(format!("harness definition: {e}")))
+    }
+
+    /// Parses a definition from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid definition.
+    pub fn from_json(text: &str) -> Result<Self> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    /// Reads a definition from a `.toml` or `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or
+    /// [`Error::InvalidConfig`] if it has another extension.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&text),
+            Some("json") => Self::from_json(&text),
+            _ => Err(Error::InvalidConfig(format!(
+                "{}: harness definitions must be .toml or .json files",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Returns the display name, falling back to the id.
+    #[must_use]
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.id)
+    }
+
+    /// Returns the global configuration directory with its template expanded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the home, config or data directory cannot be
+    /// determined.
+    pub fn global_config_dir(&self) -> Result<PathBuf> {
+        expand(&self.global_dir)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let invalid = |reason: String| {
+            Err(Error::InvalidConfig(format!(
+                "harness definition '{}': {reason}",
+                self.id
+            )))
+        };
+        if !is_valid_id(&self.id) {
+            return invalid("id must be non-empty ASCII letters, digits, '-' or '_'".into());
+        }
+        let layouts = [
+            &self.skills,
+            &self.commands,
+            &self.agents,
+            &self.plugins,
+            &self.rules,
+        ];
+        let relative =
+            self.project_dir
+                .iter()
+                .chain(layouts.into_iter().flatten().flat_map(|layout| {
+                    std::iter::once(&layout.dir).chain(layout.project_dir.as_ref())
+                }))
+                .chain(
+                    self.mcp.iter().flat_map(|mcp| {
+                        std::iter::once(&mcp.file).chain(mcp.project_file.as_ref())
+                    }),
+                );
+        for path in relative {
+            if Path::new(path).is_absolute() {
+                return invalid(format!("'{path}' must be a relative path"));
+            }
+        }
+        if let Some(mcp) = &self.mcp
+            && !mcp.key_path.is_empty()
+            && !mcp.key_path.starts_with('/')
+        {
+            return invalid(format!(
+                "MCP key path '{}' must be a JSON pointer",
+                mcp.key_path
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Checks that `id` is non-empty ASCII letters, digits, `-` and `_`.
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Expands a leading `~`, `{home}`, `{config}` or `{data}` in `template`.
+fn expand(template: &str) -> Result<PathBuf> {
+    let (base, rest) = if let Some(rest) = template.strip_prefix('~') {
+        (platform::home_dir()?, rest)
+    } else if let Some(rest) = template.strip_prefix("{home}") {
+        (platform::home_dir()?, rest)
+    } else if let Some(rest) = template.strip_prefix("{config}") {
+        (platform::config_dir()?, rest)
+    } else if let Some(rest) = template.strip_prefix("{data}") {
+        (platform::data_dir()?, rest)
+    } else {
+        return Ok(PathBuf::from(template));
+    };
+    let rest = rest.trim_start_matches(['/', '\\']);
+    Ok(if rest.is_empty() {
+        base
+    } else {
+        base.join(rest)
+    })
+}
+
+struct Entry {
+    name: String,
+    definition: Option<Arc<HarnessDefinition>>,
+    adapter: Arc<dyn HarnessAdapter>,
+    binaries: &'static [&'static str],
+}
+
+/// The process-wide set of registered [`HarnessDefinition`]s and
+/// [`HarnessAdapter`]s.
+///
+/// Registering a harness leaks its id and binary names so that
+/// [`HarnessKind`] can stay `Copy`; registries are meant to be filled
+/// once at startup.
+pub struct HarnessRegistry {
+    entries: RwLock<BTreeMap<&'static str, Entry>>,
+}
+
+static GLOBAL: LazyLock<HarnessRegistry> = LazyLock::new(|| HarnessRegistry {
+    entries: RwLock::new(BTreeMap::new()),
+});
+
+impl HarnessRegistry {
+    /// Returns the registry consulted by [`Harness`](crate::Harness).
+    #[must_use]
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Returns the directory user definitions are conventionally kept in:
+    /// `<config dir>/harness-locate/harnesses`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined.
+    pub fn default_dir() -> Result<PathBuf> {
+        Ok(platform::config_dir()?
+            .join("harness-locate")
+            .join("harnesses"))
+    }
+
+    /// Registers `definition`, replacing any harness with the same id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if the definition is invalid.
+    pub fn register(&self, definition: HarnessDefinition) -> Result<HarnessKind> {
+        definition.validate()?;
+        let id = intern(&definition.id);
+        let entry = Entry {
+            name: definition.display_name().to_string(),
+            binaries: intern_all(&definition.binaries),
+            definition: Some(Arc::new(definition)),
+            adapter: Arc::new(CustomAdapter::new(id)),
+        };
+        self.write().insert(id, entry);
+        Ok(HarnessKind::Custom(id))
+    }
+
+    /// Registers a harness implemented in code, replacing any harness with
+    /// the same id.
+    ///
+    /// The adapter's [`kind`](HarnessAdapter::kind) must be a
+    /// [`HarnessKind::Custom`] kind; its id follows the rules of
+    /// [`HarnessDefinition::id`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if the adapter has a built-in kind
+    /// or an invalid id.
+    pub fn register_adapter(&self, adapter: Arc<dyn HarnessAdapter>) -> Result<HarnessKind> {
+        let kind = adapter.kind();
+        let HarnessKind::Custom(id) = kind else {
+            return Err(Error::InvalidConfig(format!(
+                "harness adapter for built-in harness '{kind}' cannot be registered"
+            )));
+        };
+        if !is_valid_id(id) {
+            return Err(Error::InvalidConfig(format!(
+                "harness adapter '{id}': id must be non-empty ASCII letters, digits, '-' or '_'"
+            )));
+        }
+        let entry = Entry {
+            name: adapter.name().to_string(),
+            binaries: intern_all(&adapter.binary_names()),
+            definition: None,
+            adapter,
+        };
+        self.write().insert(intern(id), entry);
+        Ok(kind)
+    }
+
+    /// Reads and registers a `.toml` or `.json` definition file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, parsed or validated.
+    pub fn load_file(&self, path: &Path) -> Result<HarnessKind> {
+        self.register(HarnessDefinition::from_file(path)?)
+    }
+
+    /// Registers every `.toml` and `.json` file in `dir`, in file name order.
+    ///
+    /// A missing directory registers nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a definition cannot be read,
+    /// parsed or validated. Definitions loaded before the failing one stay
+    /// registered.
+    pub fn load_dir(&self, dir: &Path) -> Result<Vec<HarnessKind>> {
+        let read_dir = match std::fs::read_dir(dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut paths = Vec::new();
+        for entry in read_dir {
+            let path = entry?.path();
+            if path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext == "toml" || ext == "json")
+            {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        paths.iter().map(|path| self.load_file(path)).collect()
+    }
+
+    /// Removes the harness with the given id, returning its definition.
+    ///
+    /// Harnesses registered with [`register_adapter`](Self::register_adapter)
+    /// are removed too, but have no definition to return.
+    pub fn unregister(&self, id: &str) -> Option<Arc<HarnessDefinition>> {
+        self.write().remove(id).and_then(|entry| entry.definition)
+    }
+
+    /// Returns the definition with the given id.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<Arc<HarnessDefinition>> {
+        self.read()
+            .get(id)
+            .and_then(|entry| entry.definition.clone())
+    }
+
+    /// Returns the adapter of the harness with the given id.
+    #[must_use]
+    pub fn adapter(&self, id: &str) -> Option<Arc<dyn HarnessAdapter>> {
+        self.read().get(id).map(|entry| Arc::clone(&entry.adapter))
+    }
+
+    /// Returns every built-in harness kind followed by the registered
+    /// custom kinds, ordered by id.
+    #[must_use]
+    pub fn kinds(&self) -> Vec<HarnessKind> {
+        HarnessKind::ALL
+            .iter()
+            .copied()
+            .chain(self.read().keys().map(|id| HarnessKind::Custom(id)))
+            .collect()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, BTreeMap<&'static str, Entry>> {
+        self.entries.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, BTreeMap<&'static str, Entry>> {
+        self.entries.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for HarnessRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.read().keys()).finish()
+    }
+}
+
+/// Returns the binary names of the registered harness `id`.
+pub(crate) fn binary_names(id: &str) -> &'static [&'static str] {
+    HarnessRegistry::global()
+        .read()
+        .get(id)
+        .map_or(&[], |entry| entry.binaries)
+}
+
+/// Returns the display name of the registered harness `id`.
+pub(crate) fn display_name(id: &str) -> Option<String> {
+    HarnessRegistry::global()
+        .read()
+        .get(id)
+        .map(|entry| entry.name.clone())
+}
+
+/// Interns every string of `strings` into a leaked slice.
+fn intern_all(strings: &[String]) -> &'static [&'static str] {
+    let interned: Vec<&'static str> = strings.iter().map(|s| intern(s)).collect();
+    Box::leak(interned.into_boxed_slice())
+}
+
+/// Returns a `'static` copy of `s`, leaking each distinct string once.
+fn intern(s: &str) -> &'static str {
+    static STRINGS: LazyLock<Mutex<HashSet<&'static str>>> =
+        LazyLock::new(|| Mutex::new(HashSet::new()));
+    let mut strings = STRINGS.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(interned) = strings.get(s) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(s.to_string().into_boxed_str());
+    strings.insert(interned);
+    interned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACME: &str = r#"
+        id = "registry-acme"
+        name = "Acme"
+        binaries = ["acme", "acme-cli"]
+        global_dir = "{config}/acme"
+        project_dir = ".acme"
+
+        [skills]
+        dir = "skills"
+
+        [mcp]
+        file = "acme.json"
+        key_path = "/servers"
+        env_syntax = "opencode"
+        capabilities = { stdio = true, sse = true }
+
+        [mcp.fields]
+        command = "cmd"
+        transport = "kind"
+    "#;
+
+    #[test]
+    fn parses_toml_with_defaults() {
+        let definition = HarnessDefinition::from_toml(ACME).unwrap();
+        assert_eq!(definition.display_name(), "Acme");
+        assert_eq!(definition.skills.as_ref().unwrap().dir, "skills");
+        assert!(definition.commands.is_none());
+
+        let mcp = definition.mcp.unwrap();
+        assert_eq!(mcp.format, FileFormat::Json);
+        assert_eq!(mcp.env_syntax, EnvSyntax::OpenCode);
+        assert_eq!(mcp.fields.command, "cmd");
+        assert_eq!(mcp.fields.args, "args");
+        assert_eq!(mcp.fields.transport.as_deref(), Some("kind"));
+        assert!(mcp.capabilities.sse);
+        assert!(!mcp.capabilities.http);
+    }
+
+    #[test]
+    fn json_matches_toml() {
+        let toml = HarnessDefinition::from_toml(ACME).unwrap();
+        let json = serde_json::to_string(&toml).unwrap();
+        assert_eq!(HarnessDefinition::from_json(&json).unwrap(), toml);
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let result = HarnessDefinition::from_toml("id = \"x\"\nglobal_dir = \"~/.x\"\nbogus = 1\n");
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn register_validates_definitions() {
+        let mut definition = HarnessDefinition::from_toml(ACME).unwrap();
+        definition.id = "bad id".to_string();
+        assert!(matches!(
+            HarnessRegistry::global().register(definition),
+            Err(Error::InvalidConfig(_))
+        ));
+
+        let mut definition = HarnessDefinition::from_toml(ACME).unwrap();
+        definition.id = "registry-absolute".to_string();
+        definition.skills.as_mut().unwrap().dir = "/skills".to_string();
+        assert!(matches!(
+            HarnessRegistry::global().register(definition),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn register_and_unregister() {
+        let registry = HarnessRegistry::global();
+        let kind = registry
+            .register(HarnessDefinition::from_toml(ACME).unwrap())
+            .unwrap();
+        assert_eq!(kind, HarnessKind::Custom("registry-acme"));
+        assert_eq!(kind.to_string(), "Acme");
+        assert_eq!(kind.as_str(), "registry-acme");
+        assert_eq!(kind.binary_names(), &["acme", "acme-cli"]);
+        assert_eq!(EnvSyntax::of(kind), EnvSyntax::OpenCode);
+        assert!(registry.kinds().contains(&kind));
+        assert!(registry.kinds().starts_with(HarnessKind::ALL));
+
+        assert!(registry.unregister("registry-acme").is_some());
+        assert!(registry.get("registry-acme").is_none());
+        assert_eq!(kind.to_string(), "registry-acme");
+        assert!(kind.binary_names().is_empty());
+    }
+
+    #[test]
+    fn register_adapter_requires_custom_kind() {
+        let registry = HarnessRegistry::global();
+        assert!(matches!(
+            registry.register_adapter(crate::harness::adapter::for_kind(HarnessKind::Goose)),
+            Err(Error::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            registry.register_adapter(Arc::new(CustomAdapter::new("bad id"))),
+            Err(Error::InvalidConfig(_))
+        ));
+
+        let kind = registry
+            .register_adapter(Arc::new(CustomAdapter::new("registry-adapter")))
+            .unwrap();
+        assert_eq!(kind, HarnessKind::Custom("registry-adapter"));
+        assert!(registry.adapter("registry-adapter").is_some());
+        assert!(registry.get("registry-adapter").is_none());
+        assert!(registry.unregister("registry-adapter").is_none());
+        assert!(registry.adapter("registry-adapter").is_none());
+    }
+
+    #[test]
+    fn load_dir_registers_definition_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.toml"),
+            "id = \"registry-dir-a\"\nglobal_dir = \"~/.a\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.json"),
+            r#"{"id": "registry-dir-b", "global_dir": "~/.b"}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let kinds = HarnessRegistry::global().load_dir(dir.path()).unwrap();
+        assert_eq!(
+            kinds,
+            [
+                HarnessKind::Custom("registry-dir-a"),
+                HarnessKind::Custom("registry-dir-b")
+            ]
+        );
+        assert!(
+            HarnessRegistry::global()
+                .load_dir(&dir.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn expand_templates() {
+        assert_eq!(expand("/opt/acme").unwrap(), PathBuf::from("/opt/acme"));
+        if let Ok(home) = platform::home_dir() {
+            assert_eq!(expand("~/.acme").unwrap(), home.join(".acme"));
+            assert_eq!(expand("{home}").unwrap(), home);
+        }
+        if let Ok(config) = platform::config_dir() {
+            assert_eq!(expand("{config}/acme").unwrap(), config.join("acme"));
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! JSON Schemas published for harness configuration files.
+//!
+//! [`ConfigResource::schema_url`](crate::ConfigResource::schema_url) is set
+//! to one of the URLs below for files written against a published schema.
+//!
+//! With the `schemas` feature, snapshots of these schemas are bundled with
+//! the crate, so [`validate_config_file`](crate::validation::validate_config_file)
+//! can check configuration files without network access. The snapshots
+//! cover the settings this crate reads and writes, and accept other keys.
+
+/// Schema of Claude Code's `settings.json`, `settings.local.json` and
+/// `managed-settings.json`.
+pub const CLAUDE_CODE_SETTINGS: &str = "https://json.schemastore.org/claude-code-settings.json";
+
+/// Schema of OpenCode's `opencode.json` and `opencode.jsonc`.
+pub const OPENCODE_CONFIG: &str = "https://opencode.ai/config.json";
+
+/// Schema of Crush's `crush.json` and `.crush.json`.
+pub const CRUSH_CONFIG: &str = "https://charm.land/crush.json";
+
+#[cfg(feature = "schemas")]
+const BUNDLED: &[(&str, &str)] = &[
+    (
+        CLAUDE_CODE_SETTINGS,
+        include_str!("../schemas/claude-code-settings.json"),
+    ),
+    (
+        OPENCODE_CONFIG,
+        include_str!("../schemas/opencode-config.json"),
+    ),
+    (CRUSH_CONFIG, include_str!("../schemas/crush.json")),
+];
+
+/// Returns the bundled snapshot of the schema at `url`, if there is one.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::schema;
+///
+/// assert!(schema::bundled(schema::OPENCODE_CONFIG).is_some());
+/// assert!(schema::bundled("https://example.com/schema.json").is_none());
+/// ```
+#[cfg(feature = "schemas")]
+#[must_use]
+pub fn bundled(url: &str) -> Option<&'static str> {
+    BUNDLED
+        .iter()
+        .find(|(bundled_url, _)| *bundled_url == url)
+        .map(|(_, schema)| *schema)
+}
+
+/// Returns the compiled validator for the bundled schema at `url`.
+#[cfg(feature = "schemas")]
+pub(crate) fn validator(url: &str) -> Option<&'static jsonschema::Validator> {
+    static VALIDATORS: std::sync::OnceLock<Vec<jsonschema::Validator>> = std::sync::OnceLock::new();
+    let validators = VALIDATORS.get_or_init(|| {
+        BUNDLED
+            .iter()
+            .map(|(url, schema)| {
+                let schema = serde_json::from_str(schema)
+                    .unwrap_or_else(|e| panic!("bundled schema {url} is not JSON: {e}"));
+                jsonschema::validator_for(&schema)
+                    .unwrap_or_else(|e| panic!("bundled schema {url} is invalid: {e}"))
+            })
+            .collect()
+    });
+    let index = BUNDLED
+        .iter()
+        .position(|(bundled_url, _)| *bundled_url == url)?;
+    Some(&validators[index])
+}
+
+#[cfg(all(test, feature = "schemas"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_schemas_compile() {
+        for (url, _) in BUNDLED {
+            assert!(validator(url).is_some(), "{url}");
+        }
+    }
+
+    #[test]
+    fn bundled_schema_ids_match_urls() {
+        for (url, schema) in BUNDLED {
+            let schema: serde_json::Value = serde_json::from_str(schema).unwrap();
+            assert_eq!(schema["$id"], *url);
+        }
+    }
+}
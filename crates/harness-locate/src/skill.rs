@@ -40,15 +40,19 @@ pub struct Skill {
 ///
 /// Returns `Error::YamlParse` if frontmatter exists but contains invalid YAML.
 pub fn parse_frontmatter(content: &str) -> Result<Frontmatter<'_>> {
+    let (yaml_content, body) = split_frontmatter(content);
+    let yaml = yaml_content.map(serde_yaml::from_str).transpose()?;
+    Ok(Frontmatter { yaml, body })
+}
+
+/// Splits markdown content into its raw frontmatter text, if any, and body.
+pub(crate) fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
     let (opener, line_ending) = if content.starts_with("---\r\n") {
         ("---\r\n", "\r\n")
     } else if content.starts_with("---\n") {
         ("---\n", "\n")
     } else {
-        return Ok(Frontmatter {
-            yaml: None,
-            body: content,
-        });
+        return (None, content);
     };
 
     let after_opener = &content[opener.len()..];
@@ -56,26 +60,23 @@ pub fn parse_frontmatter(content: &str) -> Result<Frontmatter<'_>> {
     let closer = format!("{line_ending}---{line_ending}");
     let closer_eof = format!("{line_ending}---");
 
-    let (yaml_content, body) = if after_opener.starts_with(&empty_closer) {
-        ("", &after_opener[empty_closer.len()..])
+    if after_opener.starts_with(&empty_closer) {
+        (Some(""), &after_opener[empty_closer.len()..])
     } else if let Some(pos) = after_opener.find(&closer) {
-        (&after_opener[..pos], &after_opener[pos + closer.len()..])
+        (
+            Some(&after_opener[..pos]),
+            &after_opener[pos + closer.len()..],
+        )
     } else if after_opener.ends_with(&closer_eof) {
-        (&after_opener[..after_opener.len() - closer_eof.len()], "")
+        (
+            Some(&after_opener[..after_opener.len() - closer_eof.len()]),
+            "",
+        )
     } else if after_opener == "---" {
-        ("", "")
+        (Some(""), "")
     } else {
-        return Ok(Frontmatter {
-            yaml: None,
-            body: content,
-        });
-    };
-
-    let yaml_value: serde_yaml::Value = serde_yaml::from_str(yaml_content)?;
-    Ok(Frontmatter {
-        yaml: Some(yaml_value),
-        body,
-    })
+        (None, content)
+    }
 }
 
 /// Parse a skill file from markdown content with YAML frontmatter.
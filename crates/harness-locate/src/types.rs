@@ -31,6 +31,28 @@ pub enum HarnessKind {
     Crush,
     /// Factory Droid (Factory's AI coding assistant)
     Droid,
+    /// OpenAI Codex CLI
+    Codex,
+    /// Google Gemini CLI
+    GeminiCli,
+    /// Cursor editor
+    Cursor,
+    /// Visual Studio Code agent mode
+    VsCode,
+    /// Cline VS Code extension
+    Cline,
+    /// Roo Code VS Code extension
+    RooCode,
+    /// Zed editor
+    Zed,
+    /// A harness described by a [`HarnessDefinition`], identified by its id.
+    ///
+    /// Custom harnesses must be registered with the [`HarnessRegistry`]
+    /// before they can be resolved; see [`crate::registry`].
+    ///
+    /// [`HarnessDefinition`]: crate::registry::HarnessDefinition
+    /// [`HarnessRegistry`]: crate::registry::HarnessRegistry
+    Custom(&'static str),
 }
 
 impl fmt::Display for HarnessKind {
@@ -43,11 +65,31 @@ impl fmt::Display for HarnessKind {
             Self::CopilotCli => write!(f, "Copilot CLI"),
             Self::Crush => write!(f, "Crush"),
             Self::Droid => write!(f, "Droid"),
+            Self::Codex => write!(f, "Codex"),
+            Self::GeminiCli => write!(f, "Gemini CLI"),
+            Self::Cursor => write!(f, "Cursor"),
+            Self::VsCode => write!(f, "VS Code"),
+            Self::Cline => write!(f, "Cline"),
+            Self::RooCode => write!(f, "Roo Code"),
+            Self::Zed => write!(f, "Zed"),
+            Self::Custom(id) => match crate::registry::display_name(id) {
+                Some(name) => f.write_str(&name),
+                None => f.write_str(id),
+            },
         }
     }
 }
 
+/// Serializes as [`HarnessKind::as_str`].
+impl Serialize for HarnessKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 impl HarnessKind {
+    /// Returns the display name of a built-in harness, or the id of a
+    /// custom one.
     #[must_use]
     pub const fn as_str(&self) -> &'static str {
         match self {
@@ -58,10 +100,22 @@ impl HarnessKind {
             Self::CopilotCli => "Copilot CLI",
             Self::Crush => "Crush",
             Self::Droid => "Droid",
+            Self::Codex => "Codex",
+            Self::GeminiCli => "Gemini CLI",
+            Self::Cursor => "Cursor",
+            Self::VsCode => "VS Code",
+            Self::Cline => "Cline",
+            Self::RooCode => "Roo Code",
+            Self::Zed => "Zed",
+            Self::Custom(id) => id,
         }
     }
 
-    /// All supported harness kinds.
+    /// All built-in harness kinds.
+    ///
+    /// Custom harnesses are not included; use
+    /// [`HarnessRegistry::kinds`](crate::registry::HarnessRegistry::kinds)
+    /// to list them as well.
     ///
     /// Useful for iterating over all harnesses to check installation status
     /// or enumerate capabilities.
@@ -83,12 +137,20 @@ impl HarnessKind {
         Self::CopilotCli,
         Self::Crush,
         Self::Droid,
+        Self::Codex,
+        Self::GeminiCli,
+        Self::Cursor,
+        Self::VsCode,
+        Self::Cline,
+        Self::RooCode,
+        Self::Zed,
     ];
 
     /// Returns the known CLI binary names for this harness.
     ///
     /// These are the executable names that indicate the harness is installed
-    /// and available in PATH.
+    /// and available in PATH. Custom harnesses return the binaries of their
+    /// registered definition, or nothing if they are not registered.
     ///
     /// # Examples
     ///
@@ -109,6 +171,15 @@ impl HarnessKind {
             Self::CopilotCli => &["copilot"],
             Self::Crush => &["crush"],
             Self::Droid => &["droid"],
+            Self::Codex => &["codex"],
+            Self::GeminiCli => &["gemini"],
+            Self::Cursor => &["cursor"],
+            Self::VsCode => &["code"],
+            Self::Cline => &["cline"],
+            // VS Code extension without a CLI
+            Self::RooCode => &[],
+            Self::Zed => &["zed"],
+            Self::Custom(id) => crate::registry::binary_names(id),
         }
     }
 
@@ -120,6 +191,8 @@ impl HarnessKind {
     ///
     /// Returns `None` if the harness doesn't support that resource type.
     /// When multiple names are returned, index 0 is the canonical name.
+    /// Custom harnesses always return `None`; their layout is described by
+    /// their [`HarnessDefinition`](crate::registry::HarnessDefinition).
     ///
     /// # Examples
     ///
@@ -178,6 +251,30 @@ impl HarnessKind {
             (Self::Droid, ResourceKind::Commands) => Some(&["commands"]),
             (Self::Droid, ResourceKind::Agents) => Some(&["droids"]),
 
+            // Codex - commands are called prompts
+            (Self::Codex, ResourceKind::Skills) => Some(&["skills"]),
+            (Self::Codex, ResourceKind::Commands) => Some(&["prompts"]),
+
+            // Gemini CLI - plural names, TOML commands
+            (Self::GeminiCli, ResourceKind::Skills) => Some(&["skills"]),
+            (Self::GeminiCli, ResourceKind::Commands) => Some(&["commands"]),
+
+            // Cursor - plural names
+            (Self::Cursor, ResourceKind::Skills) => Some(&["skills"]),
+            (Self::Cursor, ResourceKind::Commands) => Some(&["commands"]),
+
+            // VS Code - prompt files are commands
+            (Self::VsCode, ResourceKind::Skills) => Some(&["skills"]),
+            (Self::VsCode, ResourceKind::Commands) => Some(&["prompts"]),
+
+            // Cline - workflows are commands
+            (Self::Cline, ResourceKind::Skills) => Some(&["skills"]),
+            (Self::Cline, ResourceKind::Commands) => Some(&["workflows"]),
+
+            // Roo Code - plural names
+            (Self::RooCode, ResourceKind::Skills) => Some(&["skills"]),
+            (Self::RooCode, ResourceKind::Commands) => Some(&["commands"]),
+
             // Unsupported combinations
             _ => None,
         }
@@ -188,7 +285,17 @@ impl HarnessKind {
 ///
 /// Determines whether to look up global (user-level) or
 /// project-local configuration paths.
-#[derive(Debug, Clone)]
+///
+/// Serializes as `{"type": "project", "path": "/path/to/project"}`, with
+/// no `path` for the global and managed scopes.
+///
+/// # Extensibility
+///
+/// This enum is marked `#[non_exhaustive]` so that scopes can be added in
+/// minor releases. Match on it with a wildcard arm.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "path", rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum Scope {
     /// User-level global configuration (e.g., `~/.config/...`)
     Global,
@@ -196,12 +303,26 @@ pub enum Scope {
     Project(PathBuf),
     /// Custom path for profile-scoped resources (inherits harness directory structure)
     Custom(PathBuf),
+    /// Per-user configuration for a project that is not committed to it
+    /// (e.g., `.claude/settings.local.json` or `CLAUDE.local.md`).
+    ///
+    /// Holds the project root. Harnesses without such a layer return
+    /// [`Error::UnsupportedScope`](crate::Error::UnsupportedScope).
+    Local(PathBuf),
+    /// System-wide managed (enterprise policy) configuration that overrides
+    /// user settings (e.g., `/etc/claude-code/managed-settings.json`).
+    ///
+    /// This scope is read-only: write APIs return
+    /// [`Error::ReadOnlyScope`](crate::Error::ReadOnlyScope).
+    Managed,
 }
 
 /// Installation status of a harness on the current system.
 ///
 /// Represents the different states a harness can be in, from not installed
-/// to fully configured with both binary and configuration present.
+/// to fully configured with both binary and configuration present. Every
+/// state other than [`NotInstalled`](Self::NotInstalled) also reports any
+/// [`ManagedPolicy`] applied to the harness on this system.
 ///
 /// # Extensibility
 ///
@@ -216,11 +337,15 @@ pub enum InstallationStatus {
     ConfigOnly {
         /// Path to the configuration directory.
         config_path: PathBuf,
+        /// Managed policy applied to the harness, if any.
+        managed: Option<ManagedPolicy>,
     },
     /// Only the binary exists in PATH (no configuration found).
     BinaryOnly {
         /// Path to the binary executable.
         binary_path: PathBuf,
+        /// Managed policy applied to the harness, if any.
+        managed: Option<ManagedPolicy>,
     },
     /// Fully installed with both binary and configuration.
     FullyInstalled {
@@ -228,6 +353,14 @@ pub enum InstallationStatus {
         binary_path: PathBuf,
         /// Path to the configuration directory.
         config_path: PathBuf,
+        /// Managed policy applied to the harness, if any.
+        managed: Option<ManagedPolicy>,
+    },
+    /// Neither binary nor user configuration exists, but a managed policy
+    /// has been deployed for the harness.
+    ManagedOnly {
+        /// The managed policy found on this system.
+        managed: ManagedPolicy,
     },
 }
 
@@ -245,6 +378,7 @@ impl InstallationStatus {
     ///
     /// let status = InstallationStatus::BinaryOnly {
     ///     binary_path: PathBuf::from("/usr/bin/claude"),
+    ///     managed: None,
     /// };
     /// assert!(status.is_runnable());
     ///
@@ -267,13 +401,14 @@ impl InstallationStatus {
     /// let status = InstallationStatus::FullyInstalled {
     ///     binary_path: PathBuf::from("/usr/bin/claude"),
     ///     config_path: PathBuf::from("/home/user/.claude"),
+    ///     managed: None,
     /// };
     /// assert_eq!(status.binary_path(), Some(Path::new("/usr/bin/claude")));
     /// ```
     #[must_use]
     pub fn binary_path(&self) -> Option<&Path> {
         match self {
-            Self::BinaryOnly { binary_path } | Self::FullyInstalled { binary_path, .. } => {
+            Self::BinaryOnly { binary_path, .. } | Self::FullyInstalled { binary_path, .. } => {
                 Some(binary_path)
             }
             _ => None,
@@ -290,18 +425,45 @@ impl InstallationStatus {
     ///
     /// let status = InstallationStatus::ConfigOnly {
     ///     config_path: PathBuf::from("/home/user/.claude"),
+    ///     managed: None,
     /// };
     /// assert_eq!(status.config_path(), Some(Path::new("/home/user/.claude")));
     /// ```
     #[must_use]
     pub fn config_path(&self) -> Option<&Path> {
         match self {
-            Self::ConfigOnly { config_path } | Self::FullyInstalled { config_path, .. } => {
+            Self::ConfigOnly { config_path, .. } | Self::FullyInstalled { config_path, .. } => {
                 Some(config_path)
             }
             _ => None,
         }
     }
+
+    /// Returns the managed policy applied to the harness, if any.
+    #[must_use]
+    pub fn managed_policy(&self) -> Option<&ManagedPolicy> {
+        match self {
+            Self::ConfigOnly { managed, .. }
+            | Self::BinaryOnly { managed, .. }
+            | Self::FullyInstalled { managed, .. } => managed.as_ref(),
+            Self::ManagedOnly { managed } => Some(managed),
+            Self::NotInstalled => None,
+        }
+    }
+}
+
+/// Managed (enterprise policy) configuration deployed for a harness.
+///
+/// Managed files are installed system-wide by an administrator and take
+/// precedence over user and project configuration. See [`Scope::Managed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagedPolicy {
+    /// Managed policy files present on this system.
+    pub files: Vec<PathBuf>,
+    /// Contents of the managed settings file, if present and parseable.
+    pub settings: Option<serde_json::Value>,
+    /// Names of the MCP servers the policy defines.
+    pub mcp_servers: Vec<String>,
 }
 
 /// Types of paths a harness may provide.
@@ -371,6 +533,8 @@ pub enum FileFormat {
     Jsonc,
     /// YAML format.
     Yaml,
+    /// TOML format.
+    Toml,
     /// Plain Markdown.
     Markdown,
     /// Markdown with YAML frontmatter.
@@ -439,6 +603,56 @@ pub struct ConfigResource {
     pub schema_url: Option<String>,
 }
 
+/// How a harness writes environment variable references in its config.
+///
+/// # Extensibility
+///
+/// This enum is marked `#[non_exhaustive]` so that syntaxes can be added in
+/// minor releases. Match on it with a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum EnvSyntax {
+    /// Shell-style `${VAR}` (Claude Code, AMP Code, Copilot CLI, Droid,
+    /// Gemini CLI).
+    #[default]
+    Shell,
+    /// `{env:VAR}` (OpenCode, Crush).
+    OpenCode,
+    /// No inline syntax: values are resolved when written (Goose). Goose
+    /// server entries list references in `env_keys` instead.
+    Resolved,
+    /// `${env:VAR}`, with `${input:ID}` for prompted secrets (Cursor,
+    /// VS Code, Roo Code).
+    VsCode,
+    /// No inline syntax, and references are never resolved into the file
+    /// (Codex, Cline, Zed). Codex server entries forward them through `env_vars`
+    /// and `env_http_headers` instead.
+    Unsupported,
+}
+
+impl EnvSyntax {
+    /// Returns the syntax used by `kind`.
+    ///
+    /// Custom harnesses use the syntax of their registered adapter, or
+    /// [`EnvSyntax::Shell`] if they are not registered.
+    #[must_use]
+    pub fn of(kind: HarnessKind) -> Self {
+        match kind {
+            HarnessKind::ClaudeCode
+            | HarnessKind::AmpCode
+            | HarnessKind::CopilotCli
+            | HarnessKind::Droid
+            | HarnessKind::GeminiCli => Self::Shell,
+            HarnessKind::OpenCode | HarnessKind::Crush => Self::OpenCode,
+            HarnessKind::Goose => Self::Resolved,
+            HarnessKind::Codex | HarnessKind::Cline | HarnessKind::Zed => Self::Unsupported,
+            HarnessKind::Cursor | HarnessKind::VsCode | HarnessKind::RooCode => Self::VsCode,
+            HarnessKind::Custom(_) => crate::harness::adapter::for_kind(kind).env_syntax(),
+        }
+    }
+}
+
 /// A value that may be a plain string or a reference to an environment variable.
 ///
 /// This type handles the different syntax each harness uses for environment
@@ -446,6 +660,10 @@ pub struct ConfigResource {
 /// - Claude Code: `${VAR}`
 /// - OpenCode: `{env:VAR}`
 /// - Goose: Uses `env_keys` array, values resolved at runtime
+/// - Codex: Uses `env_vars` and `env_http_headers`, which forward variables
+///   by name
+/// - Cursor, VS Code: `${env:VAR}`; VS Code also prompts for
+///   [`SecretPrompt`] values with `${input:ID}`
 ///
 /// # Serde Behavior
 ///
@@ -453,6 +671,11 @@ pub struct ConfigResource {
 /// - Plain string: `"hello"` deserializes to `Plain("hello")`
 /// - Object with env key: `{"env": "VAR"}` deserializes to `EnvRef { env: "VAR" }`
 ///
+/// # Extensibility
+///
+/// This enum is marked `#[non_exhaustive]` so that kinds of value can be
+/// added in minor releases. Match on it with a wildcard arm.
+///
 /// # Examples
 ///
 /// ```
@@ -469,6 +692,7 @@ pub struct ConfigResource {
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
+#[non_exhaustive]
 pub enum EnvValue {
     /// A plain string value.
     Plain(String),
@@ -477,6 +701,29 @@ pub enum EnvValue {
         /// The name of the environment variable.
         env: String,
     },
+    /// A secret the harness prompts the user for.
+    Prompt {
+        /// The prompt definition.
+        prompt: SecretPrompt,
+    },
+}
+
+/// A value the harness asks the user for when a server starts, such as
+/// VS Code's `promptString` inputs.
+///
+/// Only harnesses with [`McpCapabilities::prompts`] can write prompts.
+///
+/// [`McpCapabilities::prompts`]: crate::mcp::McpCapabilities::prompts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretPrompt {
+    /// Identifier the value is referenced by.
+    pub id: String,
+    /// Text shown when prompting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the input is masked.
+    #[serde(default)]
+    pub password: bool,
 }
 
 impl EnvValue {
@@ -510,6 +757,27 @@ impl EnvValue {
         Self::EnvRef { env: var.into() }
     }
 
+    /// Creates a masked secret prompt with the given id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::types::{EnvValue, HarnessKind};
+    ///
+    /// let value = EnvValue::prompt("api-key");
+    /// assert_eq!(value.to_native(HarnessKind::VsCode), "${input:api-key}");
+    /// ```
+    #[must_use]
+    pub fn prompt(id: impl Into<String>) -> Self {
+        Self::Prompt {
+            prompt: SecretPrompt {
+                id: id.into(),
+                description: None,
+                password: true,
+            },
+        }
+    }
+
     /// Converts to the harness-specific native string format.
     ///
     /// # Arguments
@@ -521,7 +789,13 @@ impl EnvValue {
     /// - For `Plain`: Returns the string as-is
     /// - For `EnvRef` with Claude Code: Returns `${VAR}`
     /// - For `EnvRef` with OpenCode: Returns `{env:VAR}`
-    /// - For `EnvRef` with Goose: Resolves the env var immediately
+    /// - For `EnvRef` with Goose: Resolves the env var immediately; Goose
+    ///   server configs write references to `env_keys` instead
+    /// - For `EnvRef` with Cursor or VS Code: Returns `${env:VAR}`
+    /// - For `EnvRef` with Codex, Cline or Zed: Returns an empty string, as these
+    ///   harnesses have no way to write a reference into a value
+    /// - For `Prompt` with VS Code: Returns `${input:ID}`; harnesses that
+    ///   cannot prompt get an empty string
     ///
     /// # Examples
     ///
@@ -536,33 +810,36 @@ impl EnvValue {
     pub fn to_native(&self, kind: HarnessKind) -> String {
         match self {
             Self::Plain(s) => s.clone(),
-            Self::EnvRef { env } => match kind {
-                HarnessKind::ClaudeCode
-                | HarnessKind::AmpCode
-                | HarnessKind::CopilotCli
-                | HarnessKind::Droid => {
-                    format!("${{{env}}}")
-                }
-                HarnessKind::OpenCode | HarnessKind::Crush => format!("{{env:{env}}}"),
-                HarnessKind::Goose => std::env::var(env).unwrap_or_default(),
+            Self::EnvRef { env } => match EnvSyntax::of(kind) {
+                EnvSyntax::Shell => format!("${{{env}}}"),
+                EnvSyntax::OpenCode => format!("{{env:{env}}}"),
+                EnvSyntax::Resolved => std::env::var(env).unwrap_or_default(),
+                EnvSyntax::VsCode => format!("${{env:{env}}}"),
+                EnvSyntax::Unsupported => String::new(),
             },
+            Self::Prompt { .. } => self.try_to_native(kind).unwrap_or_default(),
         }
     }
 
     /// Fallible version of [`to_native`](Self::to_native) that returns an error
     /// when an environment variable reference cannot be resolved.
     ///
-    /// For Goose harness, this validates that the referenced environment variable
-    /// is actually set, returning `Error::MissingEnvVar` if not.
+    /// Harnesses with [`EnvSyntax::Resolved`] read the variable now, so this
+    /// validates that it is set, returning `Error::MissingEnvVar` if not.
+    ///
+    /// Harnesses with [`EnvSyntax::Unsupported`] (Codex, Cline, Zed) cannot hold a
+    /// reference at all, so an `EnvRef` fails instead of being resolved.
     ///
-    /// For other harnesses that use template syntax (Claude Code, OpenCode, AmpCode),
-    /// this behaves identically to `to_native` since the variable is not resolved
-    /// at conversion time.
+    /// For harnesses that use template syntax (Claude Code, OpenCode, AmpCode,
+    /// VS Code, …), this behaves identically to `to_native` since the variable
+    /// is not resolved at conversion time.
     ///
     /// # Errors
     ///
     /// Returns [`crate::Error::MissingEnvVar`] if the harness is Goose and the
-    /// referenced environment variable is not set.
+    /// referenced environment variable is not set, or
+    /// [`crate::Error::UnsupportedMcpConfig`] for an `EnvRef` or `Prompt` the
+    /// harness cannot express, such as any `EnvRef` for Codex, Cline or Zed.
     ///
     /// # Examples
     ///
@@ -582,15 +859,27 @@ impl EnvValue {
     pub fn try_to_native(&self, kind: HarnessKind) -> crate::Result<String> {
         match self {
             Self::Plain(s) => Ok(s.clone()),
-            Self::EnvRef { env } => match kind {
-                HarnessKind::ClaudeCode
-                | HarnessKind::AmpCode
-                | HarnessKind::CopilotCli
-                | HarnessKind::Droid => Ok(format!("${{{env}}}")),
-                HarnessKind::OpenCode | HarnessKind::Crush => Ok(format!("{{env:{env}}}")),
-                HarnessKind::Goose => std::env::var(env)
+            Self::EnvRef { env } => match EnvSyntax::of(kind) {
+                EnvSyntax::Shell => Ok(format!("${{{env}}}")),
+                EnvSyntax::OpenCode => Ok(format!("{{env:{env}}}")),
+                EnvSyntax::Resolved => std::env::var(env)
                     .map_err(|_| crate::Error::MissingEnvVar { name: env.clone() }),
+                EnvSyntax::VsCode => Ok(format!("${{env:{env}}}")),
+                EnvSyntax::Unsupported => Err(crate::Error::UnsupportedMcpConfig {
+                    harness: kind.to_string(),
+                    reason: format!("environment variable reference ${env} cannot be written"),
+                }),
             },
+            Self::Prompt { prompt } => {
+                if crate::mcp::McpCapabilities::for_kind(kind).prompts {
+                    Ok(format!("${{input:{}}}", prompt.id))
+                } else {
+                    Err(crate::Error::UnsupportedMcpConfig {
+                        harness: kind.to_string(),
+                        reason: format!("secret prompt '{}' not supported", prompt.id),
+                    })
+                }
+            }
         }
     }
 
@@ -624,11 +913,8 @@ impl EnvValue {
     /// ```
     #[must_use]
     pub fn from_native(s: &str, kind: HarnessKind) -> Self {
-        match kind {
-            HarnessKind::ClaudeCode
-            | HarnessKind::AmpCode
-            | HarnessKind::CopilotCli
-            | HarnessKind::Droid => {
+        match EnvSyntax::of(kind) {
+            EnvSyntax::Shell => {
                 if let Some(var) = s.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
                     Self::EnvRef {
                         env: var.to_string(),
@@ -637,7 +923,7 @@ impl EnvValue {
                     Self::Plain(s.to_string())
                 }
             }
-            HarnessKind::OpenCode | HarnessKind::Crush => {
+            EnvSyntax::OpenCode => {
                 if let Some(var) = s.strip_prefix("{env:").and_then(|s| s.strip_suffix('}')) {
                     Self::EnvRef {
                         env: var.to_string(),
@@ -646,7 +932,25 @@ impl EnvValue {
                     Self::Plain(s.to_string())
                 }
             }
-            HarnessKind::Goose => Self::Plain(s.to_string()),
+            EnvSyntax::Resolved | EnvSyntax::Unsupported => Self::Plain(s.to_string()),
+            EnvSyntax::VsCode => {
+                let inner = s.strip_prefix("${").and_then(|s| s.strip_suffix('}'));
+                if let Some(var) = inner.and_then(|s| s.strip_prefix("env:")) {
+                    Self::EnvRef {
+                        env: var.to_string(),
+                    }
+                } else if let Some(id) = inner.and_then(|s| s.strip_prefix("input:")) {
+                    Self::Prompt {
+                        prompt: SecretPrompt {
+                            id: id.to_string(),
+                            description: None,
+                            password: false,
+                        },
+                    }
+                } else {
+                    Self::Plain(s.to_string())
+                }
+            }
         }
     }
 
@@ -656,6 +960,7 @@ impl EnvValue {
     ///
     /// - For `Plain`: Returns `Some(value)`
     /// - For `EnvRef`: Returns `Some(value)` if the env var is set, `None` otherwise
+    /// - For `Prompt`: Returns `None`; only the harness can ask for the value
     ///
     /// # Examples
     ///
@@ -674,6 +979,7 @@ impl EnvValue {
         match self {
             Self::Plain(s) => Some(s.clone()),
             Self::EnvRef { env } => std::env::var(env).ok(),
+            Self::Prompt { .. } => None,
         }
     }
 
@@ -784,6 +1090,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_to_native_never_resolves_unsupported_env_refs() {
+        unsafe { std::env::set_var("TEST_TRY_NATIVE_UNSUPPORTED_VAR", "s3cret") };
+        let value = EnvValue::env("TEST_TRY_NATIVE_UNSUPPORTED_VAR");
+        for kind in [HarnessKind::Codex, HarnessKind::Cline, HarnessKind::Zed] {
+            assert!(matches!(
+                value.try_to_native(kind),
+                Err(crate::Error::UnsupportedMcpConfig { .. })
+            ));
+            assert_eq!(value.to_native(kind), "");
+        }
+        unsafe { std::env::remove_var("TEST_TRY_NATIVE_UNSUPPORTED_VAR") };
+    }
+
     #[test]
     fn try_to_native_goose_succeeds_when_var_set() {
         unsafe { std::env::set_var("TEST_TRY_NATIVE_VAR", "success") };
@@ -905,22 +1225,32 @@ mod tests {
     #[test]
     fn binary_names_returns_static_slice() {
         for kind in HarnessKind::ALL {
+            if *kind == HarnessKind::RooCode {
+                continue;
+            }
             assert_eq!(kind.binary_names().len(), 1);
         }
     }
 
+    #[test]
+    fn binary_names_empty_for_extension_without_cli() {
+        assert!(HarnessKind::RooCode.binary_names().is_empty());
+    }
+
     #[test]
     fn installation_status_is_runnable() {
         assert!(!InstallationStatus::NotInstalled.is_runnable());
         assert!(
             !InstallationStatus::ConfigOnly {
                 config_path: PathBuf::from("/config"),
+                managed: None,
             }
             .is_runnable()
         );
         assert!(
             InstallationStatus::BinaryOnly {
                 binary_path: PathBuf::from("/bin"),
+                managed: None,
             }
             .is_runnable()
         );
@@ -928,6 +1258,7 @@ mod tests {
             InstallationStatus::FullyInstalled {
                 binary_path: PathBuf::from("/bin"),
                 config_path: PathBuf::from("/config"),
+                managed: None,
             }
             .is_runnable()
         );
@@ -938,15 +1269,33 @@ mod tests {
         let status = InstallationStatus::FullyInstalled {
             binary_path: PathBuf::from("/bin/claude"),
             config_path: PathBuf::from("/home/.claude"),
+            managed: None,
         };
         assert_eq!(status.binary_path(), Some(Path::new("/bin/claude")));
         assert_eq!(status.config_path(), Some(Path::new("/home/.claude")));
 
+        assert_eq!(status.managed_policy(), None);
+
         let status = InstallationStatus::NotInstalled;
         assert_eq!(status.binary_path(), None);
         assert_eq!(status.config_path(), None);
     }
 
+    #[test]
+    fn installation_status_managed_only() {
+        let policy = ManagedPolicy {
+            files: vec![PathBuf::from("/etc/claude-code/managed-settings.json")],
+            settings: None,
+            mcp_servers: Vec::new(),
+        };
+        let status = InstallationStatus::ManagedOnly {
+            managed: policy.clone(),
+        };
+        assert!(!status.is_runnable());
+        assert_eq!(status.config_path(), None);
+        assert_eq!(status.managed_policy(), Some(&policy));
+    }
+
     #[test]
     fn directory_names_opencode_singular() {
         assert_eq!(
@@ -994,6 +1343,11 @@ mod tests {
     #[test]
     fn directory_names_all_harnesses_support_skills() {
         for kind in HarnessKind::ALL {
+            // Zed has no skills
+            if *kind == HarnessKind::Zed {
+                assert!(kind.directory_names(ResourceKind::Skills).is_none());
+                continue;
+            }
             assert!(
                 kind.directory_names(ResourceKind::Skills).is_some(),
                 "{kind} should support skills"
@@ -21,6 +21,7 @@
 //!     cwd: None,
 //!     enabled: true,
 //!     timeout_ms: None,
+//!     auto_approve: Vec::new(),
 //! });
 //!
 //! let issues = validate_mcp_server(&server);
@@ -35,8 +36,13 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+#[cfg(feature = "schemas")]
+use crate::document::Document;
 use crate::mcp::{HttpMcpServer, McpCapabilities, McpServer, SseMcpServer, StdioMcpServer};
+#[cfg(feature = "schemas")]
+use crate::types::ConfigResource;
 use crate::types::{EnvValue, HarnessKind};
+use crate::version::{Feature, Version};
 
 static SKILL_NAME_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(SKILL_NAME_REGEX).expect("invalid skill name regex"));
@@ -58,15 +64,25 @@ pub const CODE_TIMEOUT_EXCESSIVE: &str = "timeout.excessive";
 /// Environment variable name suggests sensitive data.
 pub const CODE_SUSPICIOUS_ENV: &str = "env.suspicious_name";
 
+/// Environment variable reference under a different name, which harnesses
+/// that pass variables through by name cannot express.
+pub const CODE_ENV_KEY_RENAMED: &str = "harness.env.renamed";
+
 /// Working directory (cwd) not supported by harness.
 pub const CODE_CWD_UNSUPPORTED: &str = "harness.cwd.unsupported";
 
 /// Toggle (enabled field) not supported by harness.
 pub const CODE_TOGGLE_UNSUPPORTED: &str = "harness.toggle.unsupported";
 
+/// Auto-approved tools not supported by harness.
+pub const CODE_AUTO_APPROVE_UNSUPPORTED: &str = "harness.auto_approve.unsupported";
+
 /// SSE transport deprecated for this harness (prefer HTTP).
 pub const CODE_SSE_DEPRECATED: &str = "harness.transport.sse_deprecated";
 
+/// Feature requires a newer release of the harness.
+pub const CODE_VERSION_UNSUPPORTED: &str = "harness.version.unsupported";
+
 // Agent validation codes.
 
 /// Agent tools field has wrong type for harness.
@@ -84,6 +100,15 @@ pub const CODE_AGENT_UNSUPPORTED: &str = "agent.unsupported";
 /// Agent frontmatter failed to parse.
 pub const CODE_AGENT_PARSE_ERROR: &str = "agent.parse_error";
 
+/// Agent field dropped because the target harness cannot express it.
+pub const CODE_AGENT_FIELD_DROPPED: &str = "agent.field.dropped";
+
+/// Agent has no name for a harness that names agents in the file.
+pub const CODE_AGENT_NAME_MISSING: &str = "agent.name.missing";
+
+/// Agent tool not known to the harness.
+pub const CODE_AGENT_TOOL_UNKNOWN: &str = "agent.tool.unknown";
+
 // Skill validation codes.
 
 /// Skill name has invalid format for harness.
@@ -107,6 +132,23 @@ pub const CODE_SKILL_PARSE_ERROR: &str = "skill.parse_error";
 /// Skill is missing required description field.
 pub const CODE_SKILL_DESCRIPTION_MISSING: &str = "skill.description.missing";
 
+/// Skill name was rewritten to the format the target harness requires.
+pub const CODE_SKILL_NAME_NORMALIZED: &str = "skill.name.normalized";
+
+/// Missing skill description was filled from the skill body.
+pub const CODE_SKILL_DESCRIPTION_DERIVED: &str = "skill.description.derived";
+
+/// Skill `allowed-tools` entry not known to the harness.
+pub const CODE_SKILL_TOOL_UNKNOWN: &str = "skill.tool.unknown";
+
+// Configuration file validation codes.
+
+/// Configuration file could not be read or parsed.
+pub const CODE_CONFIG_PARSE_ERROR: &str = "config.parse_error";
+
+/// Configuration file does not match its JSON Schema.
+pub const CODE_CONFIG_SCHEMA: &str = "config.schema";
+
 /// Skill name validation regex: lowercase alphanumeric with single hyphens.
 pub const SKILL_NAME_REGEX: &str = r"^[a-z0-9]+(-[a-z0-9]+)*$";
 
@@ -180,8 +222,29 @@ impl AgentCapabilities {
                 color_format: ColorFormat::NamedOrHex,
                 supported_modes: &["subagent", "primary"],
             }),
-            HarnessKind::Goose | HarnessKind::Crush => None,
+            HarnessKind::Goose
+            | HarnessKind::Crush
+            | HarnessKind::Codex
+            | HarnessKind::GeminiCli
+            | HarnessKind::Cursor
+            | HarnessKind::VsCode
+            | HarnessKind::Cline
+            | HarnessKind::RooCode
+            | HarnessKind::Zed => None,
+            HarnessKind::Custom(_) => crate::harness::adapter::for_kind(kind).agent_capabilities(),
+        }
+    }
+
+    /// Returns the agent capabilities of a specific release of a harness.
+    ///
+    /// Returns `None` if `version` predates agent support. With `None`,
+    /// the latest known release is assumed, as in [`AgentCapabilities::for_kind`].
+    #[must_use]
+    pub fn for_version(kind: HarnessKind, version: Option<&Version>) -> Option<Self> {
+        if version.is_some_and(|v| !Feature::Agents.is_available(kind, v)) {
+            return None;
         }
+        Self::for_kind(kind)
     }
 }
 
@@ -219,20 +282,45 @@ impl SkillCapabilities {
                 name_must_match_directory: false,
                 description_required: false,
             }),
-            // Copilot CLI follows agentskills.io spec: lowercase hyphenated names,
-            // name must match directory, description required
-            HarnessKind::CopilotCli => Some(Self {
+            // Copilot CLI, Gemini CLI, Cursor, VS Code, Cline and Roo Code follow
+            // agentskills.io spec: lowercase hyphenated names, name must match
+            // directory, description required
+            HarnessKind::CopilotCli
+            | HarnessKind::GeminiCli
+            | HarnessKind::Cursor
+            | HarnessKind::VsCode
+            | HarnessKind::Cline
+            | HarnessKind::RooCode => Some(Self {
                 name_format: NameFormat::LowercaseHyphenated,
                 name_must_match_directory: true,
                 description_required: true,
             }),
-            HarnessKind::Goose => None,
+            HarnessKind::Goose | HarnessKind::Zed => None,
             HarnessKind::Crush => Some(Self {
                 name_format: NameFormat::Any,
                 name_must_match_directory: false,
                 description_required: false,
             }),
+            // Codex lists skills by name and description, so both must be set
+            HarnessKind::Codex => Some(Self {
+                name_format: NameFormat::Any,
+                name_must_match_directory: false,
+                description_required: true,
+            }),
+            HarnessKind::Custom(_) => crate::harness::adapter::for_kind(kind).skill_capabilities(),
+        }
+    }
+
+    /// Returns the skill capabilities of a specific release of a harness.
+    ///
+    /// Returns `None` if `version` predates skill support. With `None`,
+    /// the latest known release is assumed, as in [`SkillCapabilities::for_kind`].
+    #[must_use]
+    pub fn for_version(kind: HarnessKind, version: Option<&Version>) -> Option<Self> {
+        if version.is_some_and(|v| !Feature::Skills.is_available(kind, v)) {
+            return None;
         }
+        Self::for_kind(kind)
     }
 }
 
@@ -263,6 +351,14 @@ pub struct ValidationIssue {
     ///
     /// See the `CODE_*` constants in this module.
     pub code: Option<&'static str>,
+
+    /// 1-based line of the issue in the validated file, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    /// 1-based column of the issue in the validated file, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
 }
 
 impl ValidationIssue {
@@ -284,6 +380,8 @@ impl ValidationIssue {
             field: field.into(),
             message: message.into(),
             code,
+            line: None,
+            column: None,
         }
     }
 
@@ -305,8 +403,18 @@ impl ValidationIssue {
             field: field.into(),
             message: message.into(),
             code,
+            line: None,
+            column: None,
         }
     }
+
+    /// Sets the position of the issue in the validated file.
+    #[must_use]
+    pub fn with_position(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
 }
 
 /// Maximum recommended timeout in milliseconds (5 minutes).
@@ -355,6 +463,7 @@ const SUSPICIOUS_ENV_PATTERNS: &[&str] = &[
 ///     cwd: None,
 ///     enabled: true,
 ///     timeout_ms: None,
+///     auto_approve: Vec::new(),
 /// });
 ///
 /// let issues = validate_mcp_server(&server);
@@ -423,15 +532,110 @@ pub fn validate_for_harness(server: &McpServer, kind: HarnessKind) -> Vec<Valida
         }
     }
 
+    let auto_approve = match server {
+        McpServer::Stdio(s) => &s.auto_approve,
+        McpServer::Sse(s) => &s.auto_approve,
+        McpServer::Http(s) => &s.auto_approve,
+    };
+    if !auto_approve.is_empty() && !caps.auto_approve {
+        issues.push(ValidationIssue::warning(
+            "auto_approve",
+            format!("{harness_name} has no auto-approve list; tools will ask for approval"),
+            Some(CODE_AUTO_APPROVE_UNSUPPORTED),
+        ));
+    }
+
+    issues.extend(crate::harness::adapter::for_kind(kind).validate_mcp_server(server));
     issues
 }
 
+/// Validates an MCP server configuration for a specific release of a harness.
+///
+/// Like [`validate_for_harness`], and additionally reports a
+/// `CODE_VERSION_UNSUPPORTED` error for each feature the server uses that
+/// arrived after `version`. With `None`, no version checks are made.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::HarnessKind;
+/// use harness_locate::mcp::{HttpMcpServer, McpServer};
+/// use harness_locate::validation::{CODE_VERSION_UNSUPPORTED, validate_for_harness_version};
+/// use harness_locate::version::Version;
+///
+/// let server = McpServer::Http(HttpMcpServer {
+///     url: "https://example.com/mcp".to_string(),
+///     headers: Default::default(),
+///     oauth: None,
+///     enabled: true,
+///     timeout_ms: None,
+///     auto_approve: Vec::new(),
+/// });
+/// let old = Version::new(1, 0, 0);
+/// let issues = validate_for_harness_version(&server, HarnessKind::ClaudeCode, Some(&old));
+/// assert!(issues.iter().any(|i| i.code == Some(CODE_VERSION_UNSUPPORTED)));
+/// ```
+#[must_use]
+pub fn validate_for_harness_version(
+    server: &McpServer,
+    kind: HarnessKind,
+    version: Option<&Version>,
+) -> Vec<ValidationIssue> {
+    let mut issues = validate_for_harness(server, kind);
+    let Some(version) = version else {
+        return issues;
+    };
+
+    let features: &[(&str, Feature)] = match server {
+        McpServer::Stdio(_) => &[],
+        McpServer::Sse(_) => &[("transport", Feature::SseTransport)],
+        McpServer::Http(h) if h.oauth.is_some() => &[
+            ("transport", Feature::HttpTransport),
+            ("oauth", Feature::OAuth),
+        ],
+        McpServer::Http(_) => &[("transport", Feature::HttpTransport)],
+    };
+    for (field, feature) in features {
+        if let Some(issue) = version_issue(field, *feature, kind, version) {
+            issues.push(issue);
+        }
+    }
+    issues
+}
+
+/// Returns an error if `feature` arrived after `version` of `kind`.
+fn version_issue(
+    field: &str,
+    feature: Feature,
+    kind: HarnessKind,
+    version: &Version,
+) -> Option<ValidationIssue> {
+    let min = feature.min_version(kind)?;
+    (*version < min).then(|| {
+        ValidationIssue::error(
+            field,
+            format!(
+                "{feature} requires {} ≥ {min} (found {version})",
+                kind.as_str()
+            ),
+            Some(CODE_VERSION_UNSUPPORTED),
+        )
+    })
+}
+
 /// Validates agent frontmatter content for a specific harness.
 ///
 /// Returns an empty vector if valid, or a list of issues found.
 /// Returns a single `CODE_AGENT_UNSUPPORTED` error if harness doesn't support agents.
 #[must_use]
 pub fn validate_agent_for_harness(content: &str, kind: HarnessKind) -> Vec<ValidationIssue> {
+    let mut issues = check_agent(content, kind);
+    issues.extend(crate::harness::adapter::for_kind(kind).validate_agent(content));
+    issues
+}
+
+/// Checks agent frontmatter against the [`AgentCapabilities`] of `kind`.
+fn check_agent(content: &str, kind: HarnessKind) -> Vec<ValidationIssue> {
     let mut issues = Vec::new();
 
     let caps = match AgentCapabilities::for_kind(kind) {
@@ -465,6 +669,12 @@ pub fn validate_agent_for_harness(content: &str, kind: HarnessKind) -> Vec<Valid
 
     if let Some(tools) = yaml.get("tools") {
         issues.extend(validate_tools_format(tools, caps.tools_format, kind));
+        issues.extend(validate_tool_names(
+            tools,
+            "tools",
+            kind,
+            CODE_AGENT_TOOL_UNKNOWN,
+        ));
     }
 
     if let Some(color) = yaml.get("color").and_then(|v| v.as_str()) {
@@ -489,6 +699,22 @@ pub fn validate_agent_for_harness(content: &str, kind: HarnessKind) -> Vec<Valid
     issues
 }
 
+/// Validates agent frontmatter content for a specific release of a harness.
+///
+/// Returns a single `CODE_VERSION_UNSUPPORTED` error if `version` predates
+/// agent support; otherwise behaves like [`validate_agent_for_harness`].
+#[must_use]
+pub fn validate_agent_for_harness_version(
+    content: &str,
+    kind: HarnessKind,
+    version: Option<&Version>,
+) -> Vec<ValidationIssue> {
+    if let Some(issue) = version.and_then(|v| version_issue("agent", Feature::Agents, kind, v)) {
+        return vec![issue];
+    }
+    validate_agent_for_harness(content, kind)
+}
+
 /// Validates skill frontmatter content for a specific harness.
 ///
 /// Returns an empty vector if valid, or a list of issues found.
@@ -499,6 +725,13 @@ pub fn validate_skill_for_harness(
     directory_name: &str,
     kind: HarnessKind,
 ) -> Vec<ValidationIssue> {
+    let mut issues = check_skill(content, directory_name, kind);
+    issues.extend(crate::harness::adapter::for_kind(kind).validate_skill(content, directory_name));
+    issues
+}
+
+/// Checks skill frontmatter against the [`SkillCapabilities`] of `kind`.
+fn check_skill(content: &str, directory_name: &str, kind: HarnessKind) -> Vec<ValidationIssue> {
     let mut issues = Vec::new();
 
     let caps = match SkillCapabilities::for_kind(kind) {
@@ -581,9 +814,98 @@ pub fn validate_skill_for_harness(
         ));
     }
 
+    if let Some(tools) = yaml.get("allowed-tools") {
+        issues.extend(validate_tool_names(
+            tools,
+            "allowed-tools",
+            kind,
+            CODE_SKILL_TOOL_UNKNOWN,
+        ));
+    }
+
     issues
 }
 
+/// Validates skill frontmatter content for a specific release of a harness.
+///
+/// Returns a single `CODE_VERSION_UNSUPPORTED` error if `version` predates
+/// skill support; otherwise behaves like [`validate_skill_for_harness`].
+#[must_use]
+pub fn validate_skill_for_harness_version(
+    content: &str,
+    directory_name: &str,
+    kind: HarnessKind,
+    version: Option<&Version>,
+) -> Vec<ValidationIssue> {
+    if let Some(issue) = version.and_then(|v| version_issue("skill", Feature::Skills, kind, v)) {
+        return vec![issue];
+    }
+    validate_skill_for_harness(content, directory_name, kind)
+}
+
+/// Validates a configuration file against the bundled snapshot of its
+/// JSON Schema.
+///
+/// Each schema violation is reported as an error whose `field` is the
+/// JSON pointer of the offending value (empty for the document root),
+/// with its line and column for JSON and JSONC files. A file that cannot
+/// be read or parsed yields a single `CODE_CONFIG_PARSE_ERROR`.
+///
+/// Returns no issues if `resource` has no
+/// [`schema_url`](ConfigResource::schema_url), the schema is not bundled,
+/// or the file does not exist. Runs offline.
+///
+/// # Example
+///
+/// ```no_run
+/// use harness_locate::validation::validate_config_file;
+/// use harness_locate::{Harness, HarnessKind, Scope};
+///
+/// let harness = Harness::new(HarnessKind::OpenCode);
+/// if let Some(config) = harness.mcp(&Scope::Global)? {
+///     for issue in validate_config_file(&config) {
+///         eprintln!("{}:{}: {}", issue.line.unwrap_or(0), issue.field, issue.message);
+///     }
+/// }
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+#[cfg(feature = "schemas")]
+#[must_use]
+pub fn validate_config_file(resource: &ConfigResource) -> Vec<ValidationIssue> {
+    let Some(validator) = resource
+        .schema_url
+        .as_deref()
+        .and_then(crate::schema::validator)
+    else {
+        return Vec::new();
+    };
+    if !resource.file.is_file() {
+        return Vec::new();
+    }
+    let document = match Document::read(&resource.file, resource.format) {
+        Ok(document) => document,
+        Err(e) => {
+            return vec![ValidationIssue::error(
+                "",
+                e.to_string(),
+                Some(CODE_CONFIG_PARSE_ERROR),
+            )];
+        }
+    };
+    validator
+        .iter_errors(document.value())
+        .map(|error| {
+            let pointer = error.instance_path.as_str();
+            let issue =
+                ValidationIssue::error(pointer, error.to_string(), Some(CODE_CONFIG_SCHEMA));
+            match document.position(pointer) {
+                Some((line, column)) => issue.with_position(line, column),
+                None => issue,
+            }
+        })
+        .collect()
+}
+
 fn validate_tools_format(
     tools: &serde_yaml::Value,
     expected: ToolsFormat,
@@ -623,6 +945,33 @@ fn validate_tools_format(
     issues
 }
 
+/// Warns about each tool in a record, string or list that `kind` is not
+/// known to have. Harnesses without a tool table are not checked.
+fn validate_tool_names(
+    tools: &serde_yaml::Value,
+    field: &str,
+    kind: HarnessKind,
+    code: &'static str,
+) -> Vec<ValidationIssue> {
+    let names: Vec<&str> = match tools {
+        serde_yaml::Value::Mapping(record) => record.keys().filter_map(|k| k.as_str()).collect(),
+        serde_yaml::Value::String(list) => crate::mapping::split_tool_list(list),
+        serde_yaml::Value::Sequence(list) => list.iter().filter_map(|v| v.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    names
+        .into_iter()
+        .filter(|name| crate::mapping::is_known_tool(name, kind) == Some(false))
+        .map(|name| {
+            ValidationIssue::warning(
+                field,
+                format!("tool '{}' is not known to {}", name, kind.as_str()),
+                Some(code),
+            )
+        })
+        .collect()
+}
+
 fn validate_color_format(
     color: &str,
     expected: ColorFormat,
@@ -779,6 +1128,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         })
     }
 
@@ -788,6 +1138,7 @@ mod tests {
             headers: HashMap::new(),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         })
     }
 
@@ -798,6 +1149,7 @@ mod tests {
             oauth: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         })
     }
 
@@ -860,6 +1212,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: Some(600_000),
+            auto_approve: Vec::new(),
         });
         let issues = validate_mcp_server(&server);
 
@@ -878,6 +1231,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: Some(30_000),
+            auto_approve: Vec::new(),
         });
         let issues = validate_mcp_server(&server);
 
@@ -896,6 +1250,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
         let issues = validate_mcp_server(&server);
 
@@ -918,6 +1273,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
         let issues = validate_mcp_server(&server);
 
@@ -936,6 +1292,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: Some(600_000),
+            auto_approve: Vec::new(),
         });
         let issues = validate_mcp_server(&server);
 
@@ -971,6 +1328,7 @@ mod tests {
             cwd: None,
             enabled: true,
             timeout_ms: Some(30_000),
+            auto_approve: Vec::new(),
         });
         let issues = validate_mcp_server(&server);
 
@@ -980,7 +1338,7 @@ mod tests {
     // Harness-specific validation tests
 
     #[test]
-    fn cwd_on_any_harness_returns_error() {
+    fn cwd_on_harness_without_cwd_returns_error() {
         let server = McpServer::Stdio(StdioMcpServer {
             command: "node".to_string(),
             args: vec![],
@@ -988,12 +1346,19 @@ mod tests {
             cwd: Some(std::path::PathBuf::from("/tmp")),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         for kind in HarnessKind::ALL {
             let issues = validate_for_harness(&server, *kind);
-            assert!(issues.iter().any(|i| i.code == Some(CODE_CWD_UNSUPPORTED)));
+            let unsupported = issues.iter().any(|i| i.code == Some(CODE_CWD_UNSUPPORTED));
+            assert_eq!(unsupported, !McpCapabilities::for_kind(*kind).cwd, "{kind}");
         }
+        assert!(
+            validate_for_harness(&server, HarnessKind::Codex)
+                .iter()
+                .all(|i| i.code != Some(CODE_CWD_UNSUPPORTED))
+        );
     }
 
     #[test]
@@ -1005,6 +1370,7 @@ mod tests {
             cwd: None,
             enabled: false,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let issues = validate_for_harness(&server, HarnessKind::ClaudeCode);
@@ -1024,6 +1390,7 @@ mod tests {
             cwd: None,
             enabled: false,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let issues = validate_for_harness(&server, HarnessKind::OpenCode);
@@ -1034,6 +1401,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn auto_approve_warns_only_without_support() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: vec!["read_file".to_string()],
+        });
+
+        let has_warning = |kind| {
+            validate_for_harness(&server, kind)
+                .iter()
+                .any(|i| i.code == Some(CODE_AUTO_APPROVE_UNSUPPORTED))
+        };
+        assert!(has_warning(HarnessKind::ClaudeCode));
+        assert!(!has_warning(HarnessKind::Cline));
+    }
+
     #[test]
     fn sse_on_claude_code_returns_warning() {
         let server = McpServer::Sse(SseMcpServer {
@@ -1041,6 +1429,7 @@ mod tests {
             headers: HashMap::new(),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let issues = validate_for_harness(&server, HarnessKind::ClaudeCode);
@@ -1054,6 +1443,7 @@ mod tests {
             headers: HashMap::new(),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let issues = validate_for_harness(&server, HarnessKind::OpenCode);
@@ -1069,6 +1459,7 @@ mod tests {
             cwd: Some(std::path::PathBuf::from("/tmp")),
             enabled: true,
             timeout_ms: None,
+            auto_approve: Vec::new(),
         });
 
         let issues = validate_for_harness(&server, HarnessKind::ClaudeCode);
@@ -1133,6 +1524,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn agent_tools_unknown_to_harness_warn() {
+        let content = "---\ntools: Read, read, mcp__github__search\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::ClaudeCode);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_AGENT_TOOL_UNKNOWN));
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert!(issues[0].message.contains("'read'"));
+
+        let content = "---\ntools:\n  \"*\": false\n  Bash: true\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::OpenCode);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_AGENT_TOOL_UNKNOWN));
+
+        let content = "---\ntools: anything\n---\nAgent prompt";
+        assert!(validate_agent_for_harness(content, HarnessKind::AmpCode).is_empty());
+    }
+
+    #[test]
+    fn skill_allowed_tools_unknown_to_harness_warn() {
+        let content =
+            "---\nname: git\ndescription: Git\nallowed-tools: Bash(git status:*) Grep\n---\n";
+        assert!(validate_skill_for_harness(content, "git", HarnessKind::ClaudeCode).is_empty());
+
+        let issues = validate_skill_for_harness(content, "git", HarnessKind::OpenCode);
+        assert_eq!(issues.len(), 2);
+        assert!(
+            issues
+                .iter()
+                .all(|i| i.code == Some(CODE_SKILL_TOOL_UNKNOWN))
+        );
+    }
+
     #[test]
     fn claude_code_accepts_named_color() {
         let content = "---\ncolor: red\n---\nAgent prompt";
@@ -1335,4 +1759,154 @@ mod tests {
                 .any(|i| i.code == Some(CODE_SKILL_PARSE_ERROR))
         );
     }
+
+    #[test]
+    fn validate_for_harness_version_flags_newer_features() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::new(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+            auto_approve: Vec::new(),
+        });
+        let old = Version::new(1, 0, 0);
+        let issues = validate_for_harness_version(&server, HarnessKind::Goose, Some(&old));
+        let issue = issues
+            .iter()
+            .find(|i| i.code == Some(CODE_VERSION_UNSUPPORTED))
+            .unwrap();
+        assert_eq!(issue.field, "transport");
+        assert_eq!(
+            issue.message,
+            "HTTP transport requires Goose ≥ 1.1.0 (found 1.0.0)"
+        );
+
+        let new = Version::new(1, 9, 0);
+        assert!(validate_for_harness_version(&server, HarnessKind::Goose, Some(&new)).is_empty());
+        assert!(validate_for_harness_version(&server, HarnessKind::Goose, None).is_empty());
+    }
+
+    #[test]
+    fn skill_validation_respects_version() {
+        let content = "---\nname: test\n---\nSkill content";
+        let old = Version::new(1, 0, 0);
+        let issues = validate_skill_for_harness_version(
+            content,
+            "test",
+            HarnessKind::ClaudeCode,
+            Some(&old),
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_VERSION_UNSUPPORTED));
+        assert!(SkillCapabilities::for_version(HarnessKind::ClaudeCode, Some(&old)).is_none());
+
+        let new = Version::new(2, 0, 20);
+        assert!(
+            validate_skill_for_harness_version(
+                content,
+                "test",
+                HarnessKind::ClaudeCode,
+                Some(&new)
+            )
+            .is_empty()
+        );
+    }
+
+    #[cfg(feature = "schemas")]
+    fn config_resource(dir: &std::path::Path, text: &str, schema_url: &str) -> ConfigResource {
+        let file = dir.join("opencode.json");
+        std::fs::write(&file, text).unwrap();
+        ConfigResource {
+            file,
+            file_exists: true,
+            key_path: "/mcp".to_string(),
+            format: crate::types::FileFormat::Json,
+            schema_url: Some(schema_url.to_string()),
+        }
+    }
+
+    #[cfg(feature = "schemas")]
+    #[test]
+    fn validate_config_file_accepts_valid_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let resource = config_resource(
+            dir.path(),
+            r#"{"mcp": {"dev": {"type": "local", "command": ["node"], "enabled": true}}}"#,
+            crate::schema::OPENCODE_CONFIG,
+        );
+        assert!(validate_config_file(&resource).is_empty());
+    }
+
+    #[cfg(feature = "schemas")]
+    #[test]
+    fn validate_config_file_reports_pointer_and_position() {
+        let dir = tempfile::tempdir().unwrap();
+        let text =
+            "{\n  \"mcp\": {\n    \"dev\": {\"type\": \"local\", \"command\": \"node\"}\n  }\n}\n";
+        let resource = config_resource(dir.path(), text, crate::schema::OPENCODE_CONFIG);
+
+        let issues = validate_config_file(&resource);
+
+        let issue = issues
+            .iter()
+            .find(|i| i.field == "/mcp/dev")
+            .expect("issue for /mcp/dev");
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.code, Some(CODE_CONFIG_SCHEMA));
+        assert_eq!((issue.line, issue.column), (Some(3), Some(5)));
+    }
+
+    #[cfg(feature = "schemas")]
+    #[test]
+    fn validate_config_file_reports_parse_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let resource = config_resource(dir.path(), "{\"mcp\": ", crate::schema::OPENCODE_CONFIG);
+
+        let issues = validate_config_file(&resource);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_CONFIG_PARSE_ERROR));
+    }
+
+    #[cfg(feature = "schemas")]
+    #[test]
+    fn validate_config_file_skips_unknown_schemas() {
+        let dir = tempfile::tempdir().unwrap();
+        let resource = config_resource(dir.path(), "[]", "https://example.com/schema.json");
+        assert!(validate_config_file(&resource).is_empty());
+    }
+
+    #[cfg(feature = "schemas")]
+    #[test]
+    fn validate_config_file_accepts_written_servers() {
+        use crate::harness::Harness;
+        use crate::types::Scope;
+
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec!["server.js".to_string()],
+            env: HashMap::from([("DEBUG".to_string(), EnvValue::plain("1"))]),
+            cwd: None,
+            enabled: false,
+            timeout_ms: Some(5000),
+            auto_approve: Vec::new(),
+        });
+        for kind in [HarnessKind::OpenCode, HarnessKind::Crush] {
+            let dir = tempfile::tempdir().unwrap();
+            let scope = Scope::Project(dir.path().to_path_buf());
+            let harness = Harness::new(kind).without_journal();
+            harness.install_mcp_server(&scope, "dev", &server).unwrap();
+
+            let resource = harness.mcp(&scope).unwrap().unwrap();
+            assert!(resource.schema_url.is_some(), "{kind}");
+            assert_eq!(validate_config_file(&resource), Vec::new(), "{kind}");
+        }
+    }
+
+    #[test]
+    fn with_position_sets_line_and_column() {
+        let issue = ValidationIssue::error("/a", "bad", None).with_position(2, 7);
+        assert_eq!((issue.line, issue.column), (Some(2), Some(7)));
+    }
 }
@@ -0,0 +1,384 @@
+//! Harness version detection and version-gated features.
+//!
+//! [`detect_version`] runs a harness binary with `--version` and parses the
+//! first semantic version in its output. Results are cached per binary path
+//! and modification time, so repeated lookups don't spawn new processes.
+//!
+//! [`Feature::min_version`] records the first release of each harness that
+//! supports a feature, which capability lookups and validation use to
+//! report requirements such as "HTTP transport requires Claude Code ≥ 1.0.27".
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::{Harness, HarnessKind};
+//!
+//! let harness = Harness::new(HarnessKind::Goose);
+//! if let Some(version) = harness.detect_version()? {
+//!     let harness = harness.with_version(version);
+//!     println!("HTTP supported: {}", harness.mcp_capabilities().http);
+//! }
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{LazyLock, Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use regex::Regex;
+pub use semver::Version;
+use wait_timeout::ChildExt;
+
+use crate::error::{Error, Result};
+use crate::types::HarnessKind;
+
+/// How long [`detect_version`] waits for `--version` to exit.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+static VERSION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d+)\.(\d+)(?:\.(\d+))?(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z.-]+)?")
+        .expect("invalid version regex")
+});
+
+type CacheKey = (PathBuf, Option<SystemTime>);
+
+static CACHE: LazyLock<Mutex<HashMap<CacheKey, Option<Version>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A harness feature that only some releases support.
+///
+/// # Extensibility
+///
+/// This enum is marked `#[non_exhaustive]` to allow adding new
+/// features in future versions without breaking changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Feature {
+    /// SSE remote MCP servers.
+    SseTransport,
+    /// HTTP (streamable HTTP) remote MCP servers.
+    HttpTransport,
+    /// OAuth authentication for remote MCP servers.
+    OAuth,
+    /// Skills directories.
+    Skills,
+    /// Agent (subagent) definitions.
+    Agents,
+}
+
+/// The first release of a harness known to support a feature.
+const MIN_VERSIONS: &[(HarnessKind, Feature, (u64, u64, u64))] = &[
+    (HarnessKind::ClaudeCode, Feature::HttpTransport, (1, 0, 27)),
+    (HarnessKind::ClaudeCode, Feature::OAuth, (1, 0, 27)),
+    (HarnessKind::ClaudeCode, Feature::Agents, (1, 0, 60)),
+    (HarnessKind::ClaudeCode, Feature::Skills, (2, 0, 20)),
+    (HarnessKind::Goose, Feature::HttpTransport, (1, 1, 0)),
+];
+
+impl Feature {
+    /// Returns a human-readable name for the feature.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::SseTransport => "SSE transport",
+            Self::HttpTransport => "HTTP transport",
+            Self::OAuth => "OAuth",
+            Self::Skills => "skills",
+            Self::Agents => "agents",
+        }
+    }
+
+    /// Returns the first release of `kind` that supports this feature.
+    ///
+    /// Returns `None` if the feature is not version-gated for `kind`:
+    /// either every known release supports it, or none does (see the
+    /// static capability tables).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::HarnessKind;
+    /// use harness_locate::version::{Feature, Version};
+    ///
+    /// let min = Feature::HttpTransport.min_version(HarnessKind::ClaudeCode);
+    /// assert_eq!(min, Some(Version::new(1, 0, 27)));
+    /// ```
+    #[must_use]
+    pub fn min_version(self, kind: HarnessKind) -> Option<Version> {
+        MIN_VERSIONS
+            .iter()
+            .find(|(k, feature, _)| *k == kind && *feature == self)
+            .map(|(_, _, (major, minor, patch))| Version::new(*major, *minor, *patch))
+    }
+
+    /// Returns `true` if release `version` of `kind` supports this feature.
+    ///
+    /// Only the version gate is checked; use the capability tables to find
+    /// out whether the harness supports the feature at all.
+    #[must_use]
+    pub fn is_available(self, kind: HarnessKind, version: &Version) -> bool {
+        self.min_version(kind).is_none_or(|min| *version >= min)
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses the first semantic version found in `output`.
+///
+/// Accepts the usual `--version` formats such as `1.2.3 (Claude Code)`,
+/// `goose 1.0.24` or `v0.0.339`. A missing patch component is read as 0.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::version::{Version, parse_version};
+///
+/// assert_eq!(parse_version("1.0.27 (Claude Code)"), Some(Version::new(1, 0, 27)));
+/// assert_eq!(parse_version("crush version v0.7"), Some(Version::new(0, 7, 0)));
+/// assert_eq!(parse_version("unknown"), None);
+/// ```
+#[must_use]
+pub fn parse_version(output: &str) -> Option<Version> {
+    let caps = VERSION_RE.captures(output)?;
+    let text = format!(
+        "{}.{}.{}{}{}",
+        &caps[1],
+        &caps[2],
+        caps.get(3).map_or("0", |m| m.as_str()),
+        caps.get(4).map_or("", |m| m.as_str()),
+        caps.get(5).map_or("", |m| m.as_str()),
+    );
+    Version::parse(&text).ok()
+}
+
+/// Runs `binary --version` and parses the reported version.
+///
+/// Waits at most [`DEFAULT_TIMEOUT`]. Returns `Ok(None)` if the output
+/// contains no recognizable version.
+///
+/// # Errors
+///
+/// Returns an error if the binary cannot be run, or
+/// [`Error::BinaryDetection`] if it does not exit in time.
+pub fn detect_version(binary: &Path) -> Result<Option<Version>> {
+    detect_version_with_timeout(binary, DEFAULT_TIMEOUT)
+}
+
+/// Like [`detect_version`], waiting at most `timeout`.
+///
+/// Successful results are cached until the binary's modification time
+/// changes or [`clear_cache`] is called.
+///
+/// # Errors
+///
+/// Returns an error if the binary cannot be run, or
+/// [`Error::BinaryDetection`] if it does not exit within `timeout`.
+pub fn detect_version_with_timeout(binary: &Path, timeout: Duration) -> Result<Option<Version>> {
+    let modified = std::fs::metadata(binary).and_then(|m| m.modified()).ok();
+    let key = (binary.to_path_buf(), modified);
+    if let Some(version) = cache().get(&key) {
+        return Ok(version.clone());
+    }
+
+    let version = run_version(binary, timeout)?;
+    cache().insert(key, version.clone());
+    Ok(version)
+}
+
+/// Forgets every cached [`detect_version`] result.
+pub fn clear_cache() {
+    cache().clear();
+}
+
+fn cache() -> std::sync::MutexGuard<'static, HashMap<CacheKey, Option<Version>>> {
+    CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn run_version(binary: &Path, timeout: Duration) -> Result<Option<Version>> {
+    let started = Instant::now();
+    let mut child = Command::new(binary)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Read both pipes while waiting, so a binary that prints more than a
+    // pipe buffer can exit instead of blocking until the timeout
+    let pipes = [
+        child.stdout.take().map(read_pipe),
+        child.stderr.take().map(read_pipe),
+    ];
+
+    if child.wait_timeout(timeout)?.is_none() {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(Error::BinaryDetection(format!(
+            "'{} --version' did not exit within {timeout:?}",
+            binary.display()
+        )));
+    }
+
+    let mut output = String::new();
+    for pipe in pipes.into_iter().flatten() {
+        // A process the binary left running may hold the pipe open
+        let remaining = timeout.saturating_sub(started.elapsed());
+        let Ok(bytes) = pipe.recv_timeout(remaining.max(Duration::from_millis(100))) else {
+            continue;
+        };
+        output.push_str(&String::from_utf8_lossy(&bytes));
+        if let Some(version) = parse_version(&output) {
+            return Ok(Some(version));
+        }
+    }
+    Ok(None)
+}
+
+/// Reads up to 64 KiB of `pipe` on a thread, discarding the rest so the
+/// writer never blocks.
+fn read_pipe(mut pipe: impl Read + Send + 'static) -> mpsc::Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = (&mut pipe).take(64 * 1024).read_to_end(&mut bytes);
+        let _ = std::io::copy(&mut pipe, &mut std::io::sink());
+        let _ = sender.send(bytes);
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_formats() {
+        assert_eq!(
+            parse_version("2.0.14 (Claude Code)"),
+            Some(Version::new(2, 0, 14))
+        );
+        assert_eq!(parse_version("goose 1.9.3\n"), Some(Version::new(1, 9, 3)));
+        assert_eq!(parse_version("v0.0.339"), Some(Version::new(0, 0, 339)));
+        assert_eq!(parse_version("opencode 1.2"), Some(Version::new(1, 2, 0)));
+        assert_eq!(
+            parse_version("droid 0.22.1-beta.2"),
+            Some(Version::parse("0.22.1-beta.2").unwrap())
+        );
+        assert_eq!(parse_version("no version here"), None);
+    }
+
+    #[test]
+    fn feature_gates() {
+        let old = Version::new(1, 0, 0);
+        let new = Version::new(2, 1, 0);
+        assert!(!Feature::HttpTransport.is_available(HarnessKind::ClaudeCode, &old));
+        assert!(Feature::HttpTransport.is_available(HarnessKind::ClaudeCode, &new));
+        // Not version-gated
+        assert!(Feature::SseTransport.is_available(HarnessKind::ClaudeCode, &old));
+        assert_eq!(Feature::Skills.min_version(HarnessKind::OpenCode), None);
+    }
+
+    #[cfg(unix)]
+    fn fake_binary(dir: &Path, name: &str, script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_version_runs_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_binary(dir.path(), "fake", "echo 'fake 3.4.5'");
+        assert_eq!(
+            detect_version(&binary).unwrap(),
+            Some(Version::new(3, 4, 5))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_version_reads_stderr() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_binary(dir.path(), "fake", "echo 'v1.2.3' >&2");
+        assert_eq!(
+            detect_version(&binary).unwrap(),
+            Some(Version::new(1, 2, 3))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_version_caches_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let counter = dir.path().join("runs");
+        let binary = fake_binary(
+            dir.path(),
+            "fake",
+            &format!("echo run >> '{}'\necho 1.0.0", counter.display()),
+        );
+        detect_version(&binary).unwrap();
+        detect_version(&binary).unwrap();
+        let runs = std::fs::read_to_string(&counter).unwrap();
+        assert_eq!(runs.lines().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_version_reads_output_larger_than_pipe_buffer() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_binary(
+            dir.path(),
+            "chatty",
+            "head -c 262144 /dev/zero | tr '\\0' x\necho 'chatty 2.3.4' >&2",
+        );
+        let result = detect_version_with_timeout(&binary, Duration::from_secs(2));
+        assert_eq!(result.unwrap(), Some(Version::new(2, 3, 4)));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn harness_detects_version_of_binary_on_path() {
+        use crate::Harness;
+        use crate::platform::test_utils::EnvGuard;
+
+        let dir = tempfile::tempdir().unwrap();
+        fake_binary(dir.path(), "claude", "echo '2.0.14 (Claude Code)'");
+        // Put the stub first, keeping the rest of PATH for concurrent tests
+        let path = std::env::join_paths(std::iter::once(dir.path().to_path_buf()).chain(
+            std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()),
+        ))
+        .unwrap();
+        let mut env = EnvGuard::new();
+        env.set("PATH", path.to_str().unwrap());
+
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        assert_eq!(
+            harness.detect_version().unwrap(),
+            Some(Version::new(2, 0, 14))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_version_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_binary(dir.path(), "slow", "exec sleep 10");
+        let result = detect_version_with_timeout(&binary, Duration::from_millis(100));
+        assert!(matches!(result, Err(Error::BinaryDetection(_))));
+    }
+}
@@ -80,6 +80,7 @@ impl ManifestConfig {
                     timeout_ms: None,
                     enabled: true,
                     cwd: None,
+                    auto_approve: Vec::new(),
                 }))
             }
             "streamable-http" | "http" => {
@@ -90,6 +91,7 @@ impl ManifestConfig {
                     timeout_ms: None,
                     enabled: true,
                     oauth: None,
+                    auto_approve: Vec::new(),
                 }))
             }
             _ => None,
@@ -47,6 +47,7 @@ fn entry_to_mcp_server(name: String, entry: McpServerEntry) -> Option<(String, M
                     headers: HashMap::new(),
                     timeout_ms: None,
                     enabled: true,
+                    auto_approve: Vec::new(),
                 }),
             ))
         }
@@ -60,6 +61,7 @@ fn entry_to_mcp_server(name: String, entry: McpServerEntry) -> Option<(String, M
                     timeout_ms: None,
                     enabled: true,
                     oauth: None,
+                    auto_approve: Vec::new(),
                 }),
             ))
         }
@@ -74,6 +76,7 @@ fn entry_to_mcp_server(name: String, entry: McpServerEntry) -> Option<(String, M
                     timeout_ms: None,
                     enabled: true,
                     cwd: None,
+                    auto_approve: Vec::new(),
                 }),
             ))
         }
@@ -48,6 +48,7 @@ pub fn detect_npm_mcp(content: &str) -> Option<(String, McpServer)> {
         cwd: None,
         enabled: true,
         timeout_ms: None,
+        auto_approve: Vec::new(),
     });
 
     Some((name.clone(), server))
@@ -92,6 +92,7 @@ fn create_python_server(name: &str) -> McpServer {
         timeout_ms: None,
         enabled: true,
         cwd: None,
+        auto_approve: Vec::new(),
     })
 }
 
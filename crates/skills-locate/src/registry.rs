@@ -120,6 +120,7 @@ impl PackageEntry {
             timeout_ms: None,
             enabled: true,
             cwd: None,
+            auto_approve: Vec::new(),
         }))
     }
 }
@@ -132,6 +133,7 @@ impl RemoteEntry {
                 headers: HashMap::new(),
                 timeout_ms: None,
                 enabled: true,
+                auto_approve: Vec::new(),
             })),
             "http" | "streamable-http" => Some(McpServer::Http(HttpMcpServer {
                 url: self.url.clone(),
@@ -139,6 +141,7 @@ impl RemoteEntry {
                 timeout_ms: None,
                 enabled: true,
                 oauth: None,
+                auto_approve: Vec::new(),
             })),
             _ => None,
         }